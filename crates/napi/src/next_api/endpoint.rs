@@ -7,7 +7,7 @@ use next_api::{
     route::{Endpoint, WrittenEndpoint},
 };
 use tracing::Instrument;
-use turbo_tasks::{Completion, ReadRef, Vc, VcValueType};
+use turbo_tasks::{Completion, ReadRef, TaskPriority, Vc, VcValueType};
 use turbopack_core::{
     diagnostics::PlainDiagnostic,
     error::PrettyPrintError,
@@ -167,6 +167,27 @@ pub async fn endpoint_write_to_disk(
     })
 }
 
+/// Writes the webpack stats of the endpoint. This is executed in the
+/// background, so it doesn't delay work for requests.
+#[napi]
+#[tracing::instrument(skip_all)]
+pub async fn endpoint_write_webpack_stats_to_disk(
+    #[napi(ts_arg_type = "{ __napiType: \"Endpoint\" }")] endpoint: External<ExternalEndpoint>,
+) -> napi::Result<()> {
+    let turbo_tasks = endpoint.turbo_tasks().clone();
+    let endpoint = ***endpoint;
+    turbo_tasks
+        .run_once_with_priority(TaskPriority::Background, async move {
+            endpoint
+                .write_webpack_stats_to_disk()
+                .strongly_consistent()
+                .await?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))
+}
+
 #[napi(ts_return_type = "{ __napiType: \"RootTask\" }")]
 pub fn endpoint_server_changed_subscribe(
     #[napi(ts_arg_type = "{ __napiType: \"Endpoint\" }")] endpoint: External<ExternalEndpoint>,
@@ -1,4 +1,9 @@
-use std::{path::PathBuf, sync::Arc, thread, time::Duration};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
 
 use anyhow::{anyhow, bail, Context, Result};
 use napi::{
@@ -22,7 +27,7 @@ use rand::Rng;
 use tokio::{io::AsyncWriteExt, time::Instant};
 use tracing::Instrument;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Registry};
-use turbo_tasks::{Completion, RcStr, ReadRef, TransientInstance, UpdateInfo, Vc};
+use turbo_tasks::{Completion, RcStr, ReadRef, TaskPriority, TransientInstance, UpdateInfo, Vc};
 use turbo_tasks_fs::{DiskFileSystem, FileContent, FileSystem, FileSystemPath};
 use turbopack_core::{
     diagnostics::PlainDiagnostic,
@@ -43,8 +48,8 @@ use url::Url;
 use super::{
     endpoint::ExternalEndpoint,
     utils::{
-        create_turbo_tasks, get_diagnostics, get_issues, subscribe, NapiDiagnostic, NapiIssue,
-        NextTurboTasks, RootTask, TurbopackResult, VcArc,
+        create_turbo_tasks, get_diagnostics, get_issues, subscribe, subscribe_with_priority,
+        NapiDiagnostic, NapiIssue, NextTurboTasks, RootTask, TurbopackResult, VcArc,
     },
 };
 use crate::register;
@@ -277,6 +282,9 @@ pub struct ProjectInstance {
     turbo_tasks: NextTurboTasks,
     container: Vc<ProjectContainer>,
     exit_receiver: tokio::sync::Mutex<Option<ExitReceiver>>,
+    /// The number of update info subscriptions that record invalidations.
+    /// Recording is enabled while there is at least one of them.
+    invalidation_recording_subscribers: Arc<Mutex<usize>>,
 }
 
 #[napi(ts_return_type = "Promise<{ __napiType: \"Project\" }>")]
@@ -379,7 +387,7 @@ pub async fn project_new(
         .await
         .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))?;
 
-    turbo_tasks.spawn_once_task(async move {
+    turbo_tasks.spawn_once_task_with_priority(TaskPriority::Background, async move {
         benchmark_file_io(container.project().node_root())
             .await
             .inspect_err(|err| tracing::warn!(%err, "failed to benchmark file IO"))
@@ -389,6 +397,7 @@ pub async fn project_new(
             turbo_tasks,
             container,
             exit_receiver: tokio::sync::Mutex::new(Some(exit_receiver)),
+            invalidation_recording_subscribers: Default::default(),
         },
         100,
     ))
@@ -707,6 +716,58 @@ pub fn project_entrypoints_subscribe(
     )
 }
 
+#[turbo_tasks::value(serialization = "none")]
+struct FeatureTelemetryDiagnostics {
+    diagnostics: Arc<Vec<ReadRef<PlainDiagnostic>>>,
+}
+
+#[turbo_tasks::function]
+async fn get_feature_telemetry_diagnostics(
+    container: Vc<ProjectContainer>,
+) -> Result<Vc<FeatureTelemetryDiagnostics>> {
+    let telemetry_operation = container.project().collect_project_feature_telemetry();
+    telemetry_operation.strongly_consistent().await?;
+    let diagnostics = get_diagnostics(telemetry_operation).await?;
+    Ok(FeatureTelemetryDiagnostics { diagnostics }.cell())
+}
+
+/// Subscribes to the feature telemetry of the project. It isn't needed for
+/// compilation, so it's collected with [TaskPriority::Background] instead of
+/// as part of the entrypoints.
+#[napi(ts_return_type = "{ __napiType: \"RootTask\" }")]
+pub fn project_feature_telemetry_subscribe(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+    func: JsFunction,
+) -> napi::Result<External<RootTask>> {
+    let turbo_tasks = project.turbo_tasks.clone();
+    let container = project.container;
+    subscribe_with_priority(
+        turbo_tasks,
+        TaskPriority::Background,
+        func,
+        move || {
+            async move {
+                get_feature_telemetry_diagnostics(container)
+                    .strongly_consistent()
+                    .await
+            }
+            .instrument(tracing::info_span!("feature telemetry subscription"))
+        },
+        |ctx| {
+            Ok(vec![TurbopackResult {
+                result: (),
+                issues: vec![],
+                diagnostics: ctx
+                    .value
+                    .diagnostics
+                    .iter()
+                    .map(|d| NapiDiagnostic::from(d))
+                    .collect(),
+            }])
+        },
+    )
+}
+
 #[turbo_tasks::value(serialization = "none")]
 struct HmrUpdateWithIssues {
     update: ReadRef<Update>,
@@ -741,8 +802,9 @@ pub fn project_hmr_events(
     let turbo_tasks = project.turbo_tasks.clone();
     let project = project.container;
     let session = TransientInstance::new(());
-    subscribe(
+    subscribe_with_priority(
         turbo_tasks.clone(),
+        TaskPriority::Hmr,
         func,
         {
             let outer_identifier = identifier.clone();
@@ -851,8 +913,9 @@ pub fn project_hmr_identifiers_subscribe(
 ) -> napi::Result<External<RootTask>> {
     let turbo_tasks = project.turbo_tasks.clone();
     let container = project.container;
-    subscribe(
+    subscribe_with_priority(
         turbo_tasks.clone(),
+        TaskPriority::Hmr,
         func,
         move || async move {
             let HmrIdentifiersWithIssues {
@@ -918,6 +981,8 @@ impl From<UpdateMessage> for NapiUpdateMessage {
 struct NapiUpdateInfo {
     pub duration: u32,
     pub tasks: u32,
+    /// Only set when recording invalidations was requested.
+    pub recomputed_tasks: Option<Vec<NapiRecomputedTask>>,
 }
 
 impl From<UpdateInfo> for NapiUpdateInfo {
@@ -925,10 +990,31 @@ impl From<UpdateInfo> for NapiUpdateInfo {
         Self {
             duration: update_info.duration.as_millis() as u32,
             tasks: update_info.tasks as u32,
+            recomputed_tasks: update_info.recomputed_tasks.map(|tasks| {
+                tasks
+                    .into_iter()
+                    .map(|task| NapiRecomputedTask {
+                        description: task.description,
+                        reason: task.reason,
+                        chain: task.chain,
+                    })
+                    .collect()
+            }),
         }
     }
 }
 
+#[napi(object)]
+struct NapiRecomputedTask {
+    pub description: String,
+    /// The reason of the invalidation that caused the re-execution, e. g. a
+    /// changed file.
+    pub reason: Option<String>,
+    /// The tasks the invalidation propagated through, starting with the
+    /// invalidated task and ending with the re-executed task.
+    pub chain: Vec<String>,
+}
+
 /// Subscribes to lifecycle events of the compilation.
 ///
 /// Emits an [UpdateMessage::Start] event when any computation starts.
@@ -939,18 +1025,33 @@ impl From<UpdateInfo> for NapiUpdateInfo {
 /// (excluding the idle time that was spend waiting for `aggregation_ms`), and
 /// the number of tasks that were executed.
 ///
+/// When `record_invalidations` is true, the [UpdateMessage::End] event also
+/// lists the re-executed tasks together with the invalidation reason and the
+/// chain of dependencies that caused the re-execution. Recording is disabled
+/// again when the subscription ends.
+///
 /// The signature of the `func` is `(update_message: UpdateMessage) => void`.
 #[napi]
 pub fn project_update_info_subscribe(
     #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
     aggregation_ms: u32,
     func: JsFunction,
+    record_invalidations: Option<bool>,
 ) -> napi::Result<()> {
     let func: ThreadsafeFunction<UpdateMessage> = func.create_threadsafe_function(0, |ctx| {
         let message = ctx.value;
         Ok(vec![NapiUpdateMessage::from(message)])
     })?;
     let turbo_tasks = project.turbo_tasks.clone();
+    let record_invalidations = record_invalidations.unwrap_or(false);
+    let invalidation_recording_subscribers = project.invalidation_recording_subscribers.clone();
+    if record_invalidations {
+        let mut subscribers = invalidation_recording_subscribers.lock().unwrap();
+        if *subscribers == 0 {
+            turbo_tasks.set_invalidation_recording(true);
+        }
+        *subscribers += 1;
+    }
     tokio::spawn(async move {
         loop {
             let update_info = turbo_tasks
@@ -984,6 +1085,15 @@ pub fn project_update_info_subscribe(
                 break;
             }
         }
+        // Recording adds overhead to every invalidation, so don't keep it
+        // running once nobody listens anymore.
+        if record_invalidations {
+            let mut subscribers = invalidation_recording_subscribers.lock().unwrap();
+            *subscribers -= 1;
+            if *subscribers == 0 {
+                turbo_tasks.set_invalidation_recording(false);
+            }
+        }
     });
     Ok(())
 }
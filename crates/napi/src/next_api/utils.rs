@@ -10,7 +10,7 @@ use napi::{
 };
 use serde::Serialize;
 use turbo_tasks::{
-    trace::TraceRawVcs, ReadRef, TaskId, TryJoinIterExt, TurboTasks, UpdateInfo, Vc,
+    trace::TraceRawVcs, ReadRef, TaskId, TaskPriority, TryJoinIterExt, TurboTasks, UpdateInfo, Vc,
 };
 use turbo_tasks_backend::{default_backing_storage, DefaultBackingStorage};
 use turbo_tasks_fs::FileContent;
@@ -39,36 +39,89 @@ impl NextTurboTasks {
         }
     }
 
+    pub fn cancel_root_task(&self, task: TaskId) {
+        match self {
+            NextTurboTasks::Memory(turbo_tasks) => turbo_tasks.cancel_root_task(task),
+            NextTurboTasks::PersistentCaching(turbo_tasks) => turbo_tasks.cancel_root_task(task),
+        }
+    }
+
     pub fn spawn_root_task<T, F, Fut>(&self, functor: F) -> TaskId
+    where
+        T: Send,
+        F: Fn() -> Fut + Send + Sync + Clone + 'static,
+        Fut: Future<Output = Result<Vc<T>>> + Send,
+    {
+        self.spawn_root_task_with_priority(TaskPriority::default(), functor)
+    }
+
+    pub fn spawn_root_task_with_priority<T, F, Fut>(
+        &self,
+        priority: TaskPriority,
+        functor: F,
+    ) -> TaskId
     where
         T: Send,
         F: Fn() -> Fut + Send + Sync + Clone + 'static,
         Fut: Future<Output = Result<Vc<T>>> + Send,
     {
         match self {
-            NextTurboTasks::Memory(turbo_tasks) => turbo_tasks.spawn_root_task(functor),
-            NextTurboTasks::PersistentCaching(turbo_tasks) => turbo_tasks.spawn_root_task(functor),
+            NextTurboTasks::Memory(turbo_tasks) => {
+                turbo_tasks.spawn_root_task_with_priority(priority, functor)
+            }
+            NextTurboTasks::PersistentCaching(turbo_tasks) => {
+                turbo_tasks.spawn_root_task_with_priority(priority, functor)
+            }
         }
     }
 
     pub async fn run_once<T: TraceRawVcs + Send + 'static>(
         &self,
         future: impl Future<Output = Result<T>> + Send + 'static,
+    ) -> Result<T> {
+        self.run_once_with_priority(TaskPriority::default(), future)
+            .await
+    }
+
+    pub async fn run_once_with_priority<T: TraceRawVcs + Send + 'static>(
+        &self,
+        priority: TaskPriority,
+        future: impl Future<Output = Result<T>> + Send + 'static,
     ) -> Result<T> {
         match self {
-            NextTurboTasks::Memory(turbo_tasks) => turbo_tasks.run_once(future).await,
-            NextTurboTasks::PersistentCaching(turbo_tasks) => turbo_tasks.run_once(future).await,
+            NextTurboTasks::Memory(turbo_tasks) => {
+                turbo_tasks.run_once_with_priority(priority, future).await
+            }
+            NextTurboTasks::PersistentCaching(turbo_tasks) => {
+                turbo_tasks.run_once_with_priority(priority, future).await
+            }
         }
     }
 
     pub fn spawn_once_task<T, Fut>(&self, future: Fut) -> TaskId
+    where
+        T: Send,
+        Fut: Future<Output = Result<Vc<T>>> + Send + 'static,
+    {
+        self.spawn_once_task_with_priority(TaskPriority::default(), future)
+    }
+
+    pub fn spawn_once_task_with_priority<T, Fut>(
+        &self,
+        priority: TaskPriority,
+        future: Fut,
+    ) -> TaskId
     where
         T: Send,
         Fut: Future<Output = Result<Vc<T>>> + Send + 'static,
     {
         match self {
-            NextTurboTasks::Memory(turbo_tasks) => turbo_tasks.spawn_once_task(future),
-            NextTurboTasks::PersistentCaching(turbo_tasks) => turbo_tasks.spawn_once_task(future),
+            NextTurboTasks::Memory(turbo_tasks) => {
+                turbo_tasks.spawn_once_task_with_priority(priority, future)
+            }
+            NextTurboTasks::PersistentCaching(turbo_tasks) => {
+                turbo_tasks.spawn_once_task_with_priority(priority, future)
+            }
         }
     }
 
@@ -106,6 +159,15 @@ impl NextTurboTasks {
         }
     }
 
+    pub fn set_invalidation_recording(&self, enabled: bool) {
+        match self {
+            NextTurboTasks::Memory(turbo_tasks) => turbo_tasks.set_invalidation_recording(enabled),
+            NextTurboTasks::PersistentCaching(turbo_tasks) => {
+                turbo_tasks.set_invalidation_recording(enabled)
+            }
+        }
+    }
+
     pub fn memory_backend(&self) -> Option<&turbo_tasks_memory::MemoryBackend> {
         match self {
             NextTurboTasks::Memory(turbo_tasks) => Some(turbo_tasks.backend()),
@@ -128,9 +190,10 @@ pub fn create_turbo_tasks(
 ) -> Result<NextTurboTasks> {
     Ok(if persistent_caching {
         NextTurboTasks::PersistentCaching(TurboTasks::new(
-            turbo_tasks_backend::TurboTasksBackend::new(default_backing_storage(
-                &output_path.join("cache/turbopack"),
-            )?),
+            turbo_tasks_backend::TurboTasksBackend::new_with_memory_limit(
+                default_backing_storage(&output_path.join("cache/turbopack"))?,
+                memory_limit,
+            ),
         ))
     } else {
         NextTurboTasks::Memory(TurboTasks::new(turbo_tasks_memory::MemoryBackend::new(
@@ -198,6 +261,22 @@ pub fn root_task_dispose(
     Ok(())
 }
 
+/// Disposes a subscription like [root_task_dispose] and also cancels work
+/// that only the subscription still needs, e. g. compiling a route the user
+/// navigated away from. Finding that work is done on a blocking thread.
+#[napi]
+pub async fn root_task_cancel(
+    #[napi(ts_arg_type = "{ __napiType: \"RootTask\" }")] mut root_task: External<RootTask>,
+) -> napi::Result<()> {
+    if let Some(task) = root_task.task_id.take() {
+        let turbo_tasks = root_task.turbo_tasks.clone();
+        tokio::task::spawn_blocking(move || turbo_tasks.cancel_root_task(task))
+            .await
+            .map_err(|e| napi::Error::from_reason(e.to_string()))?;
+    }
+    Ok(())
+}
+
 pub async fn get_issues<T: Send>(source: Vc<T>) -> Result<Arc<Vec<ReadRef<PlainIssue>>>> {
     let issues = source.peek_issues_with_path().await?;
     Ok(Arc::new(issues.get_plain_issues().await?))
@@ -425,9 +504,25 @@ pub fn subscribe<T: 'static + Send + Sync, F: Future<Output = Result<T>> + Send,
     func: JsFunction,
     handler: impl 'static + Sync + Send + Clone + Fn() -> F,
     mapper: impl 'static + Sync + Send + FnMut(ThreadSafeCallContext<T>) -> napi::Result<Vec<V>>,
+) -> napi::Result<External<RootTask>> {
+    subscribe_with_priority(turbo_tasks, TaskPriority::default(), func, handler, mapper)
+}
+
+/// Like [`subscribe`], but all work needed for the subscription is executed
+/// with `priority`.
+pub fn subscribe_with_priority<
+    T: 'static + Send + Sync,
+    F: Future<Output = Result<T>> + Send,
+    V: ToNapiValue,
+>(
+    turbo_tasks: NextTurboTasks,
+    priority: TaskPriority,
+    func: JsFunction,
+    handler: impl 'static + Sync + Send + Clone + Fn() -> F,
+    mapper: impl 'static + Sync + Send + FnMut(ThreadSafeCallContext<T>) -> napi::Result<Vec<V>>,
 ) -> napi::Result<External<RootTask>> {
     let func: ThreadsafeFunction<T> = func.create_threadsafe_function(0, mapper)?;
-    let task_id = turbo_tasks.spawn_root_task(move || {
+    let task_id = turbo_tasks.spawn_root_task_with_priority(priority, move || {
         let handler = handler.clone();
         let func = func.clone();
         Box::pin(async move {
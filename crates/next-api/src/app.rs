@@ -19,7 +19,8 @@ use next_core::{
     },
     next_client_reference::{
         client_reference_graph, find_server_entries, ClientReferenceGraphResult,
-        NextEcmascriptClientReferenceTransition, ServerEntries, VisitedClientReferenceGraphNodes,
+        ClientReferenceType, NextEcmascriptClientReferenceTransition, ServerEntries,
+        VisitedClientReferenceGraphNodes,
     },
     next_config::NextConfig,
     next_dynamic::NextDynamicTransition,
@@ -57,6 +58,7 @@ use turbopack_core::{
     },
     file_source::FileSource,
     ident::AssetIdent,
+    issue::IssueExt,
     module::{Module, Modules},
     output::{OutputAsset, OutputAssets},
     raw_output::RawOutput,
@@ -67,6 +69,7 @@ use turbopack_core::{
 use turbopack_ecmascript::resolve::cjs_resolve;
 
 use crate::{
+    duplicate_packages::{detect_duplicate_packages, DuplicatePackagesIssue},
     dynamic_imports::{
         collect_chunk_group, collect_evaluated_chunk_group, collect_next_dynamic_imports,
         VisitedDynamicImportModules,
@@ -80,6 +83,7 @@ use crate::{
     project::Project,
     route::{AppPageRoute, Endpoint, Route, Routes, WrittenEndpoint},
     server_actions::create_server_actions_manifest,
+    subresource_integrity::create_subresource_integrity_manifest,
     webpack_stats::generate_webpack_stats,
 };
 
@@ -812,6 +816,34 @@ impl AppEndpoint {
         self.output().output_assets()
     }
 
+    #[turbo_tasks::function]
+    async fn webpack_stats(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
+        let this = self.await?;
+        if !matches!(this.ty, AppEndpointType::Page { .. })
+            || !*this
+                .app_project
+                .project()
+                .should_create_webpack_stats()
+                .await?
+        {
+            return Ok(OutputAssets::empty());
+        }
+
+        let app_entry = self.app_endpoint_entry().await?;
+        let client_assets = self.output().client_assets().await?;
+        let webpack_stats =
+            generate_webpack_stats(app_entry.original_name.clone(), client_assets.iter()).await?;
+        let manifest_path_prefix = &app_entry.original_name;
+        let stats_output: Vc<Box<dyn OutputAsset>> = Vc::upcast(VirtualOutputAsset::new(
+            this.app_project
+                .project()
+                .node_root()
+                .join(format!("server/app{manifest_path_prefix}/webpack-stats.json",).into()),
+            AssetContent::file(File::from(serde_json::to_string_pretty(&webpack_stats)?).into()),
+        ));
+        Ok(Vc::cell(vec![stats_output]))
+    }
+
     #[turbo_tasks::function]
     async fn output(self: Vc<Self>) -> Result<Vc<AppEndpointOutput>> {
         let this = self.await?;
@@ -1024,20 +1056,43 @@ impl AppEndpoint {
             if *this
                 .app_project
                 .project()
-                .should_create_webpack_stats()
+                .should_detect_duplicate_packages()
                 .await?
             {
-                let webpack_stats =
-                    generate_webpack_stats(app_entry.original_name.clone(), &client_assets).await?;
-                let stats_output: Vc<Box<dyn OutputAsset>> = Vc::upcast(VirtualOutputAsset::new(
+                let mut entry_modules: Vec<Vc<Box<dyn Module>>> = this
+                    .app_project
+                    .client_runtime_entries()
+                    .await?
+                    .iter()
+                    .map(|&entry| Vc::upcast(entry))
+                    .collect();
+                for client_reference in &client_references.client_references {
+                    entry_modules.push(match client_reference.ty() {
+                        ClientReferenceType::EcmascriptClientReference { module, .. } => {
+                            Vc::upcast(module.await?.client_module)
+                        }
+                        ClientReferenceType::CssClientReference(module) => Vc::upcast(module),
+                    });
+                }
+                let report =
+                    detect_duplicate_packages(app_entry.original_name.clone(), entry_modules)
+                        .await?;
+                let report_output: Vc<Box<dyn OutputAsset>> = Vc::upcast(VirtualOutputAsset::new(
                     node_root.join(
-                        format!("server/app{manifest_path_prefix}/webpack-stats.json",).into(),
-                    ),
-                    AssetContent::file(
-                        File::from(serde_json::to_string_pretty(&webpack_stats)?).into(),
+                        format!("server/app{manifest_path_prefix}/duplicate-packages.json",).into(),
                     ),
+                    AssetContent::file(File::from(serde_json::to_string_pretty(&report)?).into()),
                 ));
-                server_assets.insert(Vc::upcast(stats_output));
+                server_assets.insert(report_output);
+                if !report.duplicates.is_empty() {
+                    DuplicatePackagesIssue {
+                        path: this.app_project.project().project_path(),
+                        entry: report.entry,
+                        duplicates: report.duplicates,
+                    }
+                    .cell()
+                    .emit();
+                }
             }
 
             let build_manifest = BuildManifest {
@@ -1154,6 +1209,20 @@ impl AppEndpoint {
         .await?;
         server_assets.insert(next_font_manifest_output);
 
+        let integrity_algorithm = *client_chunking_context.integrity_algorithm().await?;
+        if let Some(integrity_algorithm) = integrity_algorithm {
+            let manifest_path_prefix = &app_entry.original_name;
+            server_assets.insert(create_subresource_integrity_manifest(
+                client_assets,
+                client_relative_path,
+                integrity_algorithm,
+                node_root.join(
+                    format!("server/app{manifest_path_prefix}/subresource-integrity-manifest.json")
+                        .into(),
+                ),
+            ));
+        }
+
         let endpoint_output = match runtime {
             NextRuntime::Edge => {
                 // the next-edge-ssr-loader templates expect the manifests to be stored in
@@ -1167,6 +1236,9 @@ impl AppEndpoint {
                     "server/next-font-manifest.js".into(),
                     "server/interception-route-rewrite-manifest.js".into(),
                 ];
+                if integrity_algorithm.is_some() {
+                    file_paths_from_root.push("server/subresource-integrity-manifest.js".into());
+                }
                 let mut wasm_paths_from_root = vec![];
 
                 let node_root_value = node_root.await?;
@@ -1591,6 +1663,16 @@ impl Endpoint for AppEndpoint {
         let rsc_entry = self.app_endpoint_entry().await?.rsc_entry;
         Ok(Vc::cell(vec![rsc_entry]))
     }
+
+    #[turbo_tasks::function]
+    async fn write_webpack_stats_to_disk(self: Vc<Self>) -> Result<Vc<Completion>> {
+        self.await?
+            .app_project
+            .project()
+            .emit_all_output_assets(Vc::cell(self.webpack_stats()))
+            .await?;
+        Ok(Completion::new())
+    }
 }
 
 #[turbo_tasks::value]
@@ -0,0 +1,278 @@
+//! Reports packages that are bundled more than once for a client entry, e.g.
+//! two versions of a library or the same version installed at two paths.
+//!
+//! The analysis is opt-in: set `TURBOPACK_DUPLICATE_PACKAGES=1` when running
+//! `next dev --turbo` or `next build --turbo`. Each client entry then writes a
+//! `duplicate-packages.json` report next to its other manifests in
+//! `.next/server`, and emits a warning when duplicates are found.
+
+use std::collections::hash_map::Entry;
+
+use anyhow::Result;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{
+    debug::ValueDebugFormat, trace::TraceRawVcs, FxIndexMap, RcStr, ReadRef, TryFlatJoinIterExt,
+    TryJoinIterExt, ValueToString, Vc,
+};
+use turbo_tasks_fs::{FileJsonContent, FileSystemPath};
+use turbopack_core::{
+    issue::{Issue, IssueSeverity, IssueStage, OptionStyledString, StyledString},
+    module::Module,
+    reference::primary_referenced_modules,
+    resolve::{find_context_file, package_json, FindContextFileResult},
+};
+
+/// The package a module belongs to, described by the nearest `package.json`
+/// that has a `name`.
+#[turbo_tasks::value(shared)]
+struct ModulePackage {
+    name: RcStr,
+    version: Option<RcStr>,
+    path: Vc<FileSystemPath>,
+}
+
+#[turbo_tasks::value(transparent)]
+struct OptionModulePackage(Option<Vc<ModulePackage>>);
+
+#[turbo_tasks::function]
+async fn package_for_path(lookup_path: Vc<FileSystemPath>) -> Result<Vc<OptionModulePackage>> {
+    let &FindContextFileResult::Found(package_json_path, _) =
+        &*find_context_file(lookup_path, package_json()).await?
+    else {
+        return Ok(Vc::cell(None));
+    };
+    let package_dir = package_json_path.parent();
+    if let FileJsonContent::Content(json) = &*package_json_path.read().parse_json().await? {
+        if let Some(name) = json.get("name").and_then(|name| name.as_str()) {
+            return Ok(Vc::cell(Some(
+                ModulePackage {
+                    name: name.into(),
+                    version: json
+                        .get("version")
+                        .and_then(|version| version.as_str())
+                        .map(RcStr::from),
+                    path: package_dir,
+                }
+                .cell(),
+            )));
+        }
+    }
+    // A `package.json` without a name (e.g. `{ "type": "module" }` in a `dist/esm`
+    // folder) doesn't start a new package, so keep looking further up.
+    if package_dir.await?.is_root() {
+        return Ok(Vc::cell(None));
+    }
+    Ok(package_for_path(package_dir.parent()))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ValueDebugFormat, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePackageCopy {
+    pub version: Option<RcStr>,
+    pub path: RcStr,
+    /// The size of the source files of the modules in this copy. This is
+    /// before transforms and minification, so it's an estimate of the size
+    /// in the chunks.
+    pub bytes: u64,
+    pub modules: usize,
+    /// The chain of modules from the entry to the first module of this copy.
+    pub importers: Vec<RcStr>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ValueDebugFormat, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePackage {
+    pub name: RcStr,
+    /// The source size of all copies except the largest one, i. e. the bytes
+    /// that could be saved by deduplicating the package.
+    pub duplicated_bytes: u64,
+    pub copies: Vec<DuplicatePackageCopy>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePackagesReport {
+    pub entry: RcStr,
+    pub duplicates: Vec<DuplicatePackage>,
+}
+
+struct PackageCopy {
+    version: Option<RcStr>,
+    bytes: u64,
+    modules: usize,
+    first_module: Vc<Box<dyn Module>>,
+}
+
+/// Walks the module graph of a client entry and reports every package that is
+/// included from more than one install path.
+pub async fn detect_duplicate_packages<I>(
+    entry_name: RcStr,
+    entry_modules: I,
+) -> Result<DuplicatePackagesReport>
+where
+    I: IntoIterator<Item = Vc<Box<dyn Module>>>,
+{
+    // Breadth-first, so the importer chain recorded for each module is a
+    // shortest one.
+    let mut parents: FxHashMap<Vc<Box<dyn Module>>, Option<Vc<Box<dyn Module>>>> =
+        FxHashMap::default();
+    let mut modules = vec![];
+    let mut queue = vec![];
+    for module in entry_modules {
+        let module = module.resolve().await?;
+        if let Entry::Vacant(entry) = parents.entry(module) {
+            entry.insert(None);
+            queue.push(module);
+        }
+    }
+    while !queue.is_empty() {
+        modules.extend(queue.iter().copied());
+        let edges = queue
+            .iter()
+            .map(|&module| async move { Ok((module, primary_referenced_modules(module).await?)) })
+            .try_join()
+            .await?;
+        queue = vec![];
+        for (parent, referenced_modules) in edges {
+            for &module in referenced_modules.iter() {
+                let module = module.resolve().await?;
+                if let Entry::Vacant(entry) = parents.entry(module) {
+                    entry.insert(Some(parent));
+                    queue.push(module);
+                }
+            }
+        }
+    }
+
+    let module_packages = modules
+        .iter()
+        .map(|&module| async move {
+            let path = module.ident().path();
+            let Some(package) = *package_for_path(path.parent()).await? else {
+                return Ok(None);
+            };
+            let package = package.await?;
+            let package_path = package.path.to_string().await?;
+            let bytes = path.read().len().await?.unwrap_or_default();
+            Ok(Some((module, package, package_path, bytes)))
+        })
+        .try_flat_join()
+        .await?;
+
+    let mut packages: FxIndexMap<RcStr, FxIndexMap<ReadRef<RcStr>, PackageCopy>> =
+        FxIndexMap::default();
+    for (module, package, package_path, bytes) in module_packages {
+        let copy = packages
+            .entry(package.name.clone())
+            .or_default()
+            .entry(package_path)
+            .or_insert_with(|| PackageCopy {
+                version: package.version.clone(),
+                bytes: 0,
+                modules: 0,
+                first_module: module,
+            });
+        copy.bytes += bytes;
+        copy.modules += 1;
+    }
+
+    let mut duplicates = vec![];
+    for (name, copies) in packages {
+        if copies.len() < 2 {
+            continue;
+        }
+        let mut duplicate_copies = vec![];
+        for (path, copy) in copies {
+            let mut chain = vec![];
+            let mut current = Some(copy.first_module);
+            while let Some(module) = current {
+                chain.push(module.ident().path().to_string().await?.clone_value());
+                current = parents.get(&module).copied().flatten();
+            }
+            chain.reverse();
+            duplicate_copies.push(DuplicatePackageCopy {
+                version: copy.version,
+                path: path.clone_value(),
+                bytes: copy.bytes,
+                modules: copy.modules,
+                importers: chain,
+            });
+        }
+        duplicate_copies.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+        let duplicated_bytes = duplicate_copies.iter().skip(1).map(|copy| copy.bytes).sum();
+        duplicates.push(DuplicatePackage {
+            name,
+            duplicated_bytes,
+            copies: duplicate_copies,
+        });
+    }
+    duplicates.sort_by(|a, b| b.duplicated_bytes.cmp(&a.duplicated_bytes));
+
+    Ok(DuplicatePackagesReport {
+        entry: entry_name,
+        duplicates,
+    })
+}
+
+#[turbo_tasks::value(shared)]
+pub struct DuplicatePackagesIssue {
+    pub path: Vc<FileSystemPath>,
+    pub entry: RcStr,
+    pub duplicates: Vec<DuplicatePackage>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for DuplicatePackagesIssue {
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Analysis.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Text(format!("Duplicate packages bundled for {}", self.entry).into()).cell()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        let mut lines = vec![];
+        for duplicate in &self.duplicates {
+            lines.push(StyledString::Line(vec![
+                StyledString::Code(duplicate.name.clone()),
+                StyledString::Text(
+                    format!(
+                        " is bundled {} times ({} duplicated bytes)",
+                        duplicate.copies.len(),
+                        duplicate.duplicated_bytes
+                    )
+                    .into(),
+                ),
+            ]));
+            for copy in &duplicate.copies {
+                lines.push(StyledString::Text(
+                    format!(
+                        "  {}@{} at {} ({} bytes), imported via {}",
+                        duplicate.name,
+                        copy.version.as_deref().unwrap_or("unknown"),
+                        copy.path,
+                        copy.bytes,
+                        copy.importers.join(" -> ")
+                    )
+                    .into(),
+                ));
+            }
+        }
+        Vc::cell(Some(StyledString::Stack(lines).cell()))
+    }
+}
@@ -253,6 +253,11 @@ impl Endpoint for InstrumentationEndpoint {
         Completion::immutable()
     }
 
+    #[turbo_tasks::function]
+    fn write_webpack_stats_to_disk(self: Vc<Self>) -> Vc<Completion> {
+        Completion::immutable()
+    }
+
     #[turbo_tasks::function]
     async fn root_modules(self: Vc<Self>) -> Result<Vc<Modules>> {
         let core_modules = self.core_modules().await?;
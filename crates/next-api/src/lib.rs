@@ -4,6 +4,7 @@
 #![feature(impl_trait_in_assoc_type)]
 
 mod app;
+mod duplicate_packages;
 mod dynamic_imports;
 mod empty;
 pub mod entrypoints;
@@ -17,6 +18,7 @@ pub mod paths;
 pub mod project;
 pub mod route;
 mod server_actions;
+mod subresource_integrity;
 mod versioned_content_map;
 mod webpack_stats;
 
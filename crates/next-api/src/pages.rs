@@ -48,6 +48,7 @@ use turbopack_core::{
     context::AssetContext,
     file_source::FileSource,
     ident::AssetIdent,
+    issue::IssueExt,
     module::{Module, Modules},
     output::{OutputAsset, OutputAssets},
     reference_type::{EcmaScriptModulesReferenceSubType, EntryReferenceSubType, ReferenceType},
@@ -59,6 +60,7 @@ use turbopack_ecmascript::resolve::esm_resolve;
 use turbopack_nodejs::NodeJsChunkingContext;
 
 use crate::{
+    duplicate_packages::{detect_duplicate_packages, DuplicatePackagesIssue},
     dynamic_imports::{
         collect_chunk_group, collect_evaluated_chunk_group, collect_next_dynamic_imports,
         DynamicImportedChunks, VisitedDynamicImportModules,
@@ -1028,6 +1030,33 @@ impl PageEndpoint {
         self.output().output_assets()
     }
 
+    #[turbo_tasks::function]
+    async fn webpack_stats(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
+        let this = self.await?;
+        if !*this
+            .pages_project
+            .project()
+            .should_create_webpack_stats()
+            .await?
+        {
+            return Ok(OutputAssets::empty());
+        }
+
+        let original_name = this.original_name.await?;
+        let client_assets = self.output().client_assets().await?;
+        let webpack_stats =
+            generate_webpack_stats(original_name.clone_value(), client_assets.iter()).await?;
+        let manifest_path_prefix = get_asset_prefix_from_pathname(&this.pathname.await?);
+        let stats_output: Vc<Box<dyn OutputAsset>> = Vc::upcast(VirtualOutputAsset::new(
+            this.pages_project
+                .project()
+                .node_root()
+                .join(format!("server/pages{manifest_path_prefix}/webpack-stats.json",).into()),
+            AssetContent::file(File::from(serde_json::to_string_pretty(&webpack_stats)?).into()),
+        ));
+        Ok(Vc::cell(vec![stats_output]))
+    }
+
     #[turbo_tasks::function]
     async fn output(self: Vc<Self>) -> Result<Vc<PageEndpointOutput>> {
         let this = self.await?;
@@ -1070,22 +1099,39 @@ impl PageEndpoint {
         .await?;
         server_assets.push(next_font_manifest_output);
 
-        if *this
-            .pages_project
-            .project()
-            .should_create_webpack_stats()
-            .await?
+        if matches!(this.ty, PageEndpointType::Html)
+            && *this
+                .pages_project
+                .project()
+                .should_detect_duplicate_packages()
+                .await?
         {
-            let webpack_stats =
-                generate_webpack_stats(original_name.to_owned(), &client_assets.await?).await?;
-            let stats_output: Vc<Box<dyn OutputAsset>> = Vc::upcast(VirtualOutputAsset::new(
-                node_root
-                    .join(format!("server/pages{manifest_path_prefix}/webpack-stats.json",).into()),
-                AssetContent::file(
-                    File::from(serde_json::to_string_pretty(&webpack_stats)?).into(),
+            let mut entry_modules: Vec<Vc<Box<dyn Module>>> = this
+                .pages_project
+                .client_runtime_entries()
+                .await?
+                .iter()
+                .map(|&entry| Vc::upcast(entry))
+                .collect();
+            entry_modules.push(this.pages_project.client_main_module());
+            entry_modules.push(self.client_module());
+            let report = detect_duplicate_packages(original_name.clone(), entry_modules).await?;
+            let report_output: Vc<Box<dyn OutputAsset>> = Vc::upcast(VirtualOutputAsset::new(
+                node_root.join(
+                    format!("server/pages{manifest_path_prefix}/duplicate-packages.json",).into(),
                 ),
+                AssetContent::file(File::from(serde_json::to_string_pretty(&report)?).into()),
             ));
-            server_assets.push(Vc::upcast(stats_output));
+            server_assets.push(report_output);
+            if !report.duplicates.is_empty() {
+                DuplicatePackagesIssue {
+                    path: this.pages_project.project().project_path(),
+                    entry: report.entry,
+                    duplicates: report.duplicates,
+                }
+                .cell()
+                .emit();
+            }
         }
 
         let page_output = match *ssr_chunk.await? {
@@ -1319,6 +1365,16 @@ impl Endpoint for PageEndpoint {
 
         Ok(Vc::cell(modules))
     }
+
+    #[turbo_tasks::function]
+    async fn write_webpack_stats_to_disk(self: Vc<Self>) -> Result<Vc<Completion>> {
+        self.await?
+            .pages_project
+            .project()
+            .emit_all_output_assets(Vc::cell(self.webpack_stats()))
+            .await?;
+        Ok(Completion::new())
+    }
 }
 
 #[turbo_tasks::value]
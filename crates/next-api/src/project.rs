@@ -630,6 +630,18 @@ impl Project {
         ))
     }
 
+    /// Whether to report packages that are bundled more than once, see
+    /// `duplicate_packages.rs`.
+    #[turbo_tasks::function]
+    pub(super) async fn should_detect_duplicate_packages(&self) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.env
+                .read("TURBOPACK_DUPLICATE_PACKAGES".into())
+                .await?
+                .is_some(),
+        ))
+    }
+
     #[turbo_tasks::function]
     pub(super) async fn execution_context(self: Vc<Self>) -> Result<Vc<ExecutionContext>> {
         let node_root = self.node_root();
@@ -699,6 +711,10 @@ impl Project {
             self.client_compile_time_info().environment(),
             self.next_mode(),
             self.module_id_strategy(),
+            self.next_config().subresource_integrity_algorithm(),
+            self.next_config().content_hashing(),
+            self.next_config().module_federation_options(),
+            self.next_config().url_externals_options(),
         )
     }
 
@@ -768,8 +784,11 @@ impl Project {
 
     /// Emit a telemetry event corresponding to [webpack configuration telemetry](https://github.com/vercel/next.js/blob/9da305fe320b89ee2f8c3cfb7ecbf48856368913/packages/next/src/build/webpack-config.ts#L2516)
     /// to detect which feature is enabled.
+    ///
+    /// This is not needed for compilation, so it's collected by a separate
+    /// background subscription instead of by [`Project::entrypoints`].
     #[turbo_tasks::function]
-    async fn collect_project_feature_telemetry(self: Vc<Self>) -> Result<Vc<()>> {
+    pub async fn collect_project_feature_telemetry(self: Vc<Self>) -> Result<Vc<()>> {
         let emit_event = |feature_name: &str, enabled: bool| {
             NextFeatureTelemetry::new(feature_name.into(), enabled)
                 .cell()
@@ -846,8 +865,6 @@ impl Project {
     /// provided page_extensions).
     #[turbo_tasks::function]
     pub async fn entrypoints(self: Vc<Self>) -> Result<Vc<Entrypoints>> {
-        self.collect_project_feature_telemetry().await?;
-
         let mut routes = FxIndexMap::default();
         let app_project = self.app_project();
         let pages_project = self.pages_project();
@@ -76,6 +76,10 @@ pub trait Endpoint {
     fn server_changed(self: Vc<Self>) -> Vc<Completion>;
     fn client_changed(self: Vc<Self>) -> Vc<Completion>;
     fn root_modules(self: Vc<Self>) -> Vc<Modules>;
+    /// Writes `webpack-stats.json` when `TURBOPACK_STATS` is set. This is
+    /// separate from [`Endpoint::write_to_disk`], so it can be executed in the
+    /// background.
+    fn write_webpack_stats_to_disk(self: Vc<Self>) -> Vc<Completion>;
 }
 
 #[turbo_tasks::value(shared)]
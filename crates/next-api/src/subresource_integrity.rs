@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use next_core::next_manifests::SubresourceIntegrityManifest;
+use turbo_tasks::{TryFlatJoinIterExt, Vc};
+use turbo_tasks_fs::{File, FileContent, FileSystemPath};
+use turbopack_core::{
+    asset::AssetContent,
+    chunk::{output_asset_integrity, IntegrityAlgorithm},
+    output::{OutputAsset, OutputAssets},
+    virtual_output::VirtualOutputAsset,
+};
+
+/// Creates the `subresource-integrity-manifest.json` for the `client_assets`
+/// of an entry. It contains the integrity metadata of every asset within
+/// `client_relative_path`.
+#[turbo_tasks::function]
+pub async fn create_subresource_integrity_manifest(
+    client_assets: Vc<OutputAssets>,
+    client_relative_path: Vc<FileSystemPath>,
+    integrity_algorithm: IntegrityAlgorithm,
+    output_path: Vc<FileSystemPath>,
+) -> Result<Vc<Box<dyn OutputAsset>>> {
+    let client_relative_path = client_relative_path.await?;
+    let entries: HashMap<_, _> = client_assets
+        .await?
+        .iter()
+        .map(|&asset| {
+            let client_relative_path = client_relative_path.clone();
+            async move {
+                let path = asset.ident().path().await?;
+                let Some(path) = client_relative_path.get_path_to(&path) else {
+                    return Ok(None);
+                };
+                let Some(integrity) = output_asset_integrity(asset, integrity_algorithm)
+                    .await?
+                    .clone_value()
+                else {
+                    return Ok(None);
+                };
+                Ok(Some((path.into(), integrity)))
+            }
+        })
+        .try_flat_join()
+        .await?
+        .into_iter()
+        .collect();
+
+    Ok(Vc::upcast(VirtualOutputAsset::new(
+        output_path,
+        AssetContent::file(
+            FileContent::Content(File::from(serde_json::to_string_pretty(
+                &SubresourceIntegrityManifest(entries),
+            )?))
+            .cell(),
+        ),
+    )))
+}
@@ -11,9 +11,15 @@ use turbopack::{
     },
     resolve_options_context::ResolveOptionsContext,
 };
-use turbopack_browser::{react_refresh::assert_can_resolve_react_refresh, BrowserChunkingContext};
+use turbopack_browser::{
+    import_map_loader_source, react_refresh::assert_can_resolve_react_refresh,
+    BrowserChunkingContext, OptionModuleFederationOptions, OptionUrlExternalsOptions,
+};
 use turbopack_core::{
-    chunk::{module_id_strategies::ModuleIdStrategy, ChunkingContext},
+    chunk::{
+        module_id_strategies::ModuleIdStrategy, ChunkingContext, OptionContentHashing,
+        OptionIntegrityAlgorithm,
+    },
     compile_time_info::{
         CompileTimeDefineValue, CompileTimeDefines, CompileTimeInfo, DefineableNameSegment,
         FreeVarReference, FreeVarReferences,
@@ -33,7 +39,10 @@ use crate::{
     embed_js::next_js_fs,
     mode::NextMode,
     next_build::get_postcss_package_mapping,
-    next_client::runtime_entry::{RuntimeEntries, RuntimeEntry},
+    next_client::{
+        module_federation::MODULE_FEDERATION_SHARED_DIR,
+        runtime_entry::{RuntimeEntries, RuntimeEntry},
+    },
     next_config::NextConfig,
     next_font::local::NextFontLocalResolvePlugin,
     next_import_map::{
@@ -119,6 +128,7 @@ pub fn get_client_compile_time_info(
             web_worker: false,
             service_worker: false,
             browserslist_query: browserslist_query.to_owned(),
+            module_chunks: false,
         }
         .into(),
     ))))
@@ -150,10 +160,19 @@ pub async fn get_client_resolve_options_context(
     let next_client_resolved_map =
         get_next_client_resolved_map(project_path, project_path, *mode.await?);
     let custom_conditions = vec![mode.await?.condition().into()];
+    let next_client_import_map = match *next_config.url_externals_options().await? {
+        Some(url_externals) => next_client_import_map.extend(url_externals.import_map()),
+        None => next_client_import_map,
+    };
+    let module_federation = *next_config.module_federation_options().await?;
+    let import_map = match module_federation {
+        Some(module_federation) => next_client_import_map.extend(module_federation.import_map()),
+        None => next_client_import_map,
+    };
     let module_options_context = ResolveOptionsContext {
         enable_node_modules: Some(project_path.root().resolve().await?),
         custom_conditions,
-        import_map: Some(next_client_import_map),
+        import_map: Some(import_map),
         fallback_import_map: Some(next_client_fallback_import_map),
         resolved_map: Some(next_client_resolved_map),
         browser: true,
@@ -166,17 +185,30 @@ pub async fn get_client_resolve_options_context(
         after_resolve_plugins: vec![Vc::upcast(NextSharedRuntimeResolvePlugin::new(
             project_path,
         ))],
+        trace_requests: next_config.trace_resolve_requests().await?.clone_value(),
         ..Default::default()
     };
+    let mut rules = vec![];
+    if module_federation.is_some() {
+        rules.push((
+            ContextCondition::InPath(project_path.join(MODULE_FEDERATION_SHARED_DIR.into())),
+            ResolveOptionsContext {
+                import_map: Some(next_client_import_map),
+                ..module_options_context.clone()
+            }
+            .cell(),
+        ));
+    }
+    rules.push((
+        foreign_code_context_condition(next_config, project_path).await?,
+        module_options_context.clone().cell(),
+    ));
     Ok(ResolveOptionsContext {
         enable_typescript: true,
         enable_react: true,
         enable_mjs_extension: true,
         custom_extensions: next_config.resolve_extension().await?.clone_value(),
-        rules: vec![(
-            foreign_code_context_condition(next_config, project_path).await?,
-            module_options_context.clone().cell(),
-        )],
+        rules,
         ..module_options_context
     }
     .cell())
@@ -281,6 +313,7 @@ pub async fn get_client_module_options_context(
     let module_options_context = ModuleOptionsContext {
         ecmascript: EcmascriptOptionsContext {
             enable_typeof_window_inlining: Some(TypeofWindow::Object),
+            import_externals: next_config.url_externals_options().await?.is_some(),
             ..Default::default()
         },
         preset_env_versions: Some(env),
@@ -358,6 +391,10 @@ pub async fn get_client_chunking_context(
     environment: Vc<Environment>,
     mode: Vc<NextMode>,
     module_id_strategy: Vc<Box<dyn ModuleIdStrategy>>,
+    integrity_algorithm: Vc<OptionIntegrityAlgorithm>,
+    content_hashing: Vc<OptionContentHashing>,
+    module_federation: Vc<OptionModuleFederationOptions>,
+    url_externals: Vc<OptionUrlExternalsOptions>,
 ) -> Result<Vc<Box<dyn ChunkingContext>>> {
     let next_mode = mode.await?;
     let mut builder = BrowserChunkingContext::builder(
@@ -374,8 +411,33 @@ pub async fn get_client_chunking_context(
     .asset_base_path(asset_prefix)
     .module_id_strategy(module_id_strategy);
 
+    if let Some(module_federation) = *module_federation.await? {
+        // The chunks of containers loaded into the same page must not register with each
+        // other's runtime.
+        let name = module_federation.await?.name.clone();
+        let chunk_loading_global = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        builder = builder
+            .module_federation()
+            .chunk_loading_global(format!("TURBOPACK_{chunk_loading_global}").into());
+    }
+
+    if url_externals.await?.is_some() {
+        builder = builder.url_externals();
+    }
+
     if next_mode.is_development() {
         builder = builder.hot_module_replacement();
+    } else {
+        if let Some(content_hashing) = *content_hashing.await? {
+            builder = builder.use_content_hashing(content_hashing);
+        }
+        if let Some(integrity_algorithm) = *integrity_algorithm.await? {
+            // Like with webpack, integrity metadata is only computed for production builds.
+            builder = builder.integrity_algorithm(integrity_algorithm);
+        }
     }
 
     Ok(Vc::upcast(builder.build()))
@@ -398,6 +460,26 @@ pub async fn get_client_runtime_entries(
     let resolve_options_context =
         get_client_resolve_options_context(project_root, ty, mode, next_config, execution_context);
 
+    // The import map must be added before any module imports a URL external.
+    if let Some(url_externals) = *next_config.url_externals_options().await? {
+        runtime_entries.push(
+            RuntimeEntry::Source(
+                import_map_loader_source(
+                    project_root.join("_next_url_externals_import_map.js".into()),
+                    url_externals,
+                )
+                .to_resolved()
+                .await?,
+            )
+            .cell(),
+        );
+    }
+
+    // Shared dependencies must be provided before any module imports them.
+    if next_config.module_federation_options().await?.is_some() {
+        runtime_entries.push(RuntimeEntry::ModuleFederation(next_config, project_root).cell());
+    }
+
     if mode.await?.is_development() {
         let enable_react_refresh =
             assert_can_resolve_react_refresh(project_root, resolve_options_context)
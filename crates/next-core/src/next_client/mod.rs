@@ -1,4 +1,5 @@
 pub(crate) mod context;
+pub(crate) mod module_federation;
 pub(crate) mod runtime_entry;
 pub(crate) mod transforms;
 
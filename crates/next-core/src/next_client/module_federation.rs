@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use turbo_tasks::{FxIndexMap, RcStr, Value, ValueToString, Vc};
+use turbo_tasks_fs::FileSystemPath;
+use turbopack_browser::module_federation_entry;
+use turbopack_core::{
+    chunk::{ChunkableModule, EvaluatableAssets},
+    context::AssetContext,
+    ident::AssetIdent,
+    module::Module,
+    resolve::{
+        origin::{PlainResolveOrigin, ResolveOrigin},
+        parse::Request,
+        pattern::Pattern,
+    },
+};
+use turbopack_ecmascript::resolve::cjs_resolve;
+
+use crate::next_config::NextConfig;
+
+/// Shared dependencies are resolved from this (virtual) directory of the
+/// project, which the client resolve options exclude from the module
+/// federation import map. Otherwise they would resolve to themselves.
+pub const MODULE_FEDERATION_SHARED_DIR: &str = "__module_federation_shared__";
+
+/// Returns the runtime entry that registers the remotes, provides the shared
+/// dependencies and exposes the modules configured in
+/// `experimental.turbo.moduleFederation`, or no entries when it isn't
+/// configured.
+#[turbo_tasks::function]
+pub async fn get_module_federation_entries(
+    project_path: Vc<FileSystemPath>,
+    next_config: Vc<NextConfig>,
+    asset_context: Vc<Box<dyn AssetContext>>,
+) -> Result<Vc<EvaluatableAssets>> {
+    let Some(options) = *next_config.module_federation_options().await? else {
+        return Ok(EvaluatableAssets::empty());
+    };
+
+    let exposes_origin = PlainResolveOrigin::new(asset_context, project_path.join("_".into()));
+    let mut exposes = FxIndexMap::default();
+    for (name, request) in next_config.module_federation_exposes().await?.iter() {
+        let module = resolve_chunkable(Vc::upcast(exposes_origin), request.clone())
+            .await
+            .with_context(|| format!("Unable to resolve the exposed module {name}"))?;
+        exposes.insert(name.clone(), module);
+    }
+
+    let shared_origin = PlainResolveOrigin::new(
+        asset_context,
+        project_path.join(format!("{MODULE_FEDERATION_SHARED_DIR}/_").into()),
+    );
+    let mut shared = FxIndexMap::default();
+    for package in options.await?.shared.keys() {
+        let module = resolve_chunkable(Vc::upcast(shared_origin), package.clone())
+            .await
+            .with_context(|| format!("Unable to resolve the shared module {package}"))?;
+        shared.insert(package.clone(), module);
+    }
+
+    let entry = module_federation_entry(
+        AssetIdent::from_path(project_path),
+        options,
+        Vc::cell(exposes),
+        Vc::cell(shared),
+    );
+    Ok(EvaluatableAssets::one(Vc::upcast(entry)))
+}
+
+async fn resolve_chunkable(
+    origin: Vc<Box<dyn ResolveOrigin>>,
+    request: RcStr,
+) -> Result<Vc<Box<dyn ChunkableModule>>> {
+    let result = cjs_resolve(
+        origin,
+        Request::parse(Value::new(Pattern::Constant(request))),
+        None,
+        false,
+    );
+    let module = (*result.first_module().await?).context("no module found")?;
+    Vc::try_resolve_downcast::<Box<dyn ChunkableModule>>(module)
+        .await?
+        .with_context(|| format!("{} is not chunkable", module.ident().to_string().await?))
+}
@@ -10,11 +10,17 @@ use turbopack_core::{
 };
 use turbopack_ecmascript::resolve::cjs_resolve;
 
+use super::module_federation::get_module_federation_entries;
+use crate::next_config::NextConfig;
+
 #[turbo_tasks::value(shared)]
 pub enum RuntimeEntry {
     Request(Vc<Request>, Vc<FileSystemPath>),
     Evaluatable(Vc<Box<dyn EvaluatableAsset>>),
     Source(ResolvedVc<Box<dyn Source>>),
+    /// The module federation entry of the project, see
+    /// [get_module_federation_entries].
+    ModuleFederation(Vc<NextConfig>, Vc<FileSystemPath>),
 }
 
 #[turbo_tasks::value_impl]
@@ -29,6 +35,13 @@ impl RuntimeEntry {
             RuntimeEntry::Source(source) => {
                 return Ok(EvaluatableAssets::one(source.to_evaluatable(asset_context)));
             }
+            RuntimeEntry::ModuleFederation(next_config, project_path) => {
+                return Ok(get_module_federation_entries(
+                    project_path,
+                    next_config,
+                    asset_context,
+                ));
+            }
             RuntimeEntry::Request(r, path) => (r, path),
         };
 
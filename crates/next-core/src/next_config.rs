@@ -9,7 +9,12 @@ use turbo_tasks_fs::FileSystemPath;
 use turbopack::module_options::{
     module_options_context::MdxTransformOptions, LoaderRuleItem, OptionWebpackRules,
 };
+use turbopack_browser::{
+    ModuleFederationOptions, OptionModuleFederationOptions, OptionUrlExternalsOptions,
+    SharedDependency, UrlExternal, UrlExternalsOptions,
+};
 use turbopack_core::{
+    chunk::{ContentHashing, IntegrityAlgorithm, OptionContentHashing, OptionIntegrityAlgorithm},
     issue::{Issue, IssueSeverity, IssueStage, OptionStyledString, StyledString},
     resolve::ResolveAliasMap,
 };
@@ -403,8 +408,44 @@ pub struct ExperimentalTurboConfig {
     pub use_swc_css: Option<bool>,
     pub tree_shaking: Option<bool>,
     pub module_id_strategy: Option<ModuleIdStrategy>,
+    pub loader_timeout_ms: Option<u64>,
+    pub module_federation: Option<ModuleFederationConfig>,
+    /// Requests to report how they are resolved for, as an informational issue
+    /// listing each decision, e. g. the import map entries or `exports`
+    /// conditions that matched.
+    pub trace_resolve: Option<Vec<RcStr>>,
+    /// Includes a hash of their content in the filenames of production client
+    /// chunks, so they can be cached immutably. The hash excludes the chunk's
+    /// references to its own filename, see [ContentHashing::Direct].
+    pub content_hashing: Option<bool>,
+    /// Map from a specifier to the URL the package is loaded from in the
+    /// browser instead of being bundled into client chunks.
+    pub url_externals: Option<FxIndexMap<RcStr, UrlExternal>>,
+}
+
+/// Module federation options of the client build, similar to the options of
+/// webpack's `ModuleFederationPlugin`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleFederationConfig {
+    /// The name of the container, which other builds load it by.
+    pub name: RcStr,
+    /// Map from the alias remotes are imported by to the remote, given as
+    /// `name@url`.
+    #[serde(default)]
+    pub remotes: FxIndexMap<RcStr, RcStr>,
+    /// Map from an exposed name like `./Button` to the request of the exposed
+    /// module, relative to the project.
+    #[serde(default)]
+    pub exposes: FxIndexMap<RcStr, RcStr>,
+    /// Map from a package name to how it's shared with other containers.
+    #[serde(default)]
+    pub shared: FxIndexMap<RcStr, SharedDependency>,
 }
 
+#[turbo_tasks::value(transparent)]
+pub struct ModuleFederationExposes(FxIndexMap<RcStr, RcStr>);
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct RuleConfigItemOptions {
@@ -680,6 +721,76 @@ fn test_parse_experimental_partial_prerendering() {
     assert!(config.is_err());
 }
 
+#[test]
+fn test_parse_module_federation() {
+    let json = serde_json::json!({
+        "turbo": {
+            "moduleFederation": {
+                "name": "shop",
+                "remotes": {
+                    "checkout": "checkout@https://checkout.example.com/remoteEntry.js"
+                },
+                "exposes": {
+                    "./Button": "./components/Button"
+                },
+                "shared": {
+                    "react": { "singleton": true, "requiredVersion": "^18.2.0" },
+                    "lodash": {}
+                }
+            }
+        }
+    });
+    let config: ExperimentalConfig = serde_json::from_value(json).unwrap();
+    let module_federation = config.turbo.unwrap().module_federation.unwrap();
+    assert_eq!(module_federation.name, "shop");
+    assert_eq!(
+        module_federation.remotes["checkout"],
+        "checkout@https://checkout.example.com/remoteEntry.js"
+    );
+    assert_eq!(module_federation.exposes["./Button"], "./components/Button");
+    assert_eq!(
+        module_federation.shared["react"],
+        SharedDependency {
+            singleton: true,
+            required_version: Some("^18.2.0".into()),
+            version: None,
+        }
+    );
+    assert_eq!(
+        module_federation.shared["lodash"],
+        SharedDependency::default()
+    );
+
+    // The container name is required.
+    let json = serde_json::json!({
+        "turbo": { "moduleFederation": { "shared": { "react": {} } } }
+    });
+    assert!(serde_json::from_value::<ExperimentalConfig>(json).is_err());
+}
+
+#[test]
+fn test_parse_url_externals() {
+    let json = serde_json::json!({
+        "turbo": {
+            "urlExternals": {
+                "react": { "url": "https://esm.sh/react@18", "integrity": "sha384-abc" },
+                "lodash/": { "url": "https://esm.sh/lodash@4/" }
+            }
+        }
+    });
+    let config: ExperimentalConfig = serde_json::from_value(json).unwrap();
+    let url_externals = config.turbo.unwrap().url_externals.unwrap();
+    assert_eq!(
+        url_externals["react"],
+        UrlExternal {
+            url: "https://esm.sh/react@18".into(),
+            integrity: Some("sha384-abc".into()),
+        }
+    );
+    assert_eq!(url_externals["lodash/"].url, "https://esm.sh/lodash@4/");
+    assert_eq!(url_externals["lodash/"].integrity, None);
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct SubResourceIntegrity {
@@ -1148,6 +1259,30 @@ impl NextConfig {
         Vc::cell(self.experimental.taint.unwrap_or(false))
     }
 
+    /// The time a single run of the webpack loaders of a module may take.
+    /// `None` uses the default of the loader transform.
+    #[turbo_tasks::function]
+    pub fn loader_timeout_ms(&self) -> Vc<Option<u64>> {
+        Vc::cell(
+            self.experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.loader_timeout_ms),
+        )
+    }
+
+    /// The requests configured in `experimental.turbo.traceResolve`.
+    #[turbo_tasks::function]
+    pub fn trace_resolve_requests(&self) -> Vc<Vec<RcStr>> {
+        Vc::cell(
+            self.experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.trace_resolve.clone())
+                .unwrap_or_default(),
+        )
+    }
+
     #[turbo_tasks::function]
     pub fn use_swc_css(&self) -> Vc<bool> {
         Vc::cell(
@@ -1228,6 +1363,89 @@ impl NextConfig {
         };
         Vc::cell(Some(module_id_strategy.clone()))
     }
+
+    /// The content hashing of client chunk filenames, enabled with
+    /// `experimental.turbo.contentHashing`.
+    #[turbo_tasks::function]
+    pub fn content_hashing(&self) -> Vc<OptionContentHashing> {
+        let enabled = self
+            .experimental
+            .turbo
+            .as_ref()
+            .and_then(|t| t.content_hashing)
+            .unwrap_or(false);
+        Vc::cell(enabled.then_some(ContentHashing::Direct { length: 16 }))
+    }
+
+    /// The module federation options configured in
+    /// `experimental.turbo.moduleFederation`, without the exposed modules.
+    #[turbo_tasks::function]
+    pub fn module_federation_options(&self) -> Vc<OptionModuleFederationOptions> {
+        let Some(module_federation) = self
+            .experimental
+            .turbo
+            .as_ref()
+            .and_then(|t| t.module_federation.as_ref())
+        else {
+            return Vc::cell(None);
+        };
+        Vc::cell(Some(
+            ModuleFederationOptions {
+                name: module_federation.name.clone(),
+                remotes: module_federation.remotes.clone(),
+                shared: module_federation.shared.clone(),
+            }
+            .cell(),
+        ))
+    }
+
+    /// The URL externals of the client build, configured in
+    /// `experimental.turbo.urlExternals`.
+    #[turbo_tasks::function]
+    pub fn url_externals_options(&self) -> Vc<OptionUrlExternalsOptions> {
+        let Some(url_externals) = self
+            .experimental
+            .turbo
+            .as_ref()
+            .and_then(|t| t.url_externals.as_ref())
+            .filter(|url_externals| !url_externals.is_empty())
+        else {
+            return Vc::cell(None);
+        };
+        Vc::cell(Some(
+            UrlExternalsOptions {
+                externals: url_externals.clone(),
+            }
+            .cell(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub fn module_federation_exposes(&self) -> Vc<ModuleFederationExposes> {
+        Vc::cell(
+            self.experimental
+                .turbo
+                .as_ref()
+                .and_then(|t| t.module_federation.as_ref())
+                .map(|module_federation| module_federation.exposes.clone())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// The algorithm configured in `experimental.sri.algorithm`, used for the
+    /// subresource integrity metadata of client chunks.
+    #[turbo_tasks::function]
+    pub fn subresource_integrity_algorithm(&self) -> Result<Vc<OptionIntegrityAlgorithm>> {
+        let Some(algorithm) = self
+            .experimental
+            .sri
+            .as_ref()
+            .and_then(|sri| sri.algorithm.as_deref())
+        else {
+            return Ok(Vc::cell(None));
+        };
+        Ok(Vc::cell(Some(algorithm.parse::<IntegrityAlgorithm>()?)))
+    }
 }
 
 /// A subset of ts/jsconfig that next.js implicitly
@@ -155,6 +155,7 @@ pub async fn get_edge_resolve_options_context(
         browser: true,
         after_resolve_plugins,
         before_resolve_plugins,
+        trace_requests: next_config.trace_resolve_requests().await?.clone_value(),
         ..Default::default()
     };
 
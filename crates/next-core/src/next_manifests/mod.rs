@@ -301,6 +301,11 @@ pub struct AppBuildManifest {
     pub pages: HashMap<RcStr, Vec<RcStr>>,
 }
 
+/// Maps client asset paths, relative to the client root, to their
+/// subresource integrity metadata.
+#[derive(Serialize, Default, Debug)]
+pub struct SubresourceIntegrityManifest(pub HashMap<RcStr, RcStr>);
+
 // TODO(alexkirsz) Unify with the one for dev.
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
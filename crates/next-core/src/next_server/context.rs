@@ -294,6 +294,7 @@ pub async fn get_server_resolve_options_context(
         import_map: Some(next_server_import_map),
         before_resolve_plugins,
         after_resolve_plugins,
+        trace_requests: next_config.trace_resolve_requests().await?.clone_value(),
         ..Default::default()
     };
 
@@ -4,6 +4,8 @@ use turbo_tasks::RcStr;
 use turbo_tasks::Vc;
 use turbo_tasks_fs::FileSystemPath;
 use turbopack::module_options::ModuleRule;
+#[cfg(feature = "plugin")]
+use turbopack_ecmascript_plugins::transform::swc_ecma_transform_plugins::SwcPluginModule;
 
 use crate::next_config::NextConfig;
 
@@ -35,6 +37,35 @@ pub async fn get_swc_ecma_transform_rule_impl(
     plugin_configs: &[(RcStr, serde_json::Value)],
     enable_mdx_rs: bool,
 ) -> Result<Option<ModuleRule>> {
+    use turbopack_ecmascript_plugins::transform::swc_ecma_transform_plugins::SwcEcmaTransformPluginsTransformer;
+
+    use crate::next_shared::transforms::get_ecma_transform_rule;
+
+    let mut plugins = vec![];
+    for (name, config) in plugin_configs.iter() {
+        plugins.push((
+            swc_plugin_module(project_path, name.clone())
+                .to_resolved()
+                .await?,
+            config.clone(),
+        ));
+    }
+
+    Ok(Some(get_ecma_transform_rule(
+        Box::new(SwcEcmaTransformPluginsTransformer::new(plugins)),
+        enable_mdx_rs,
+        true,
+    )))
+}
+
+/// Resolves and compiles the swc plugin `name` from the project. The compiled
+/// module is cached, so it's only compiled once for all files it transforms.
+#[cfg(feature = "plugin")]
+#[turbo_tasks::function]
+pub async fn swc_plugin_module(
+    project_path: Vc<FileSystemPath>,
+    name: RcStr,
+) -> Result<Vc<SwcPluginModule>> {
     use anyhow::{bail, Context};
     use turbo_tasks::Value;
     use turbo_tasks_fs::FileContent;
@@ -44,66 +75,48 @@ pub async fn get_swc_ecma_transform_rule_impl(
         reference_type::{CommonJsReferenceSubType, ReferenceType},
         resolve::{handle_resolve_error, parse::Request, pattern::Pattern, resolve},
     };
-    use turbopack_ecmascript_plugins::transform::swc_ecma_transform_plugins::{
-        SwcEcmaTransformPluginsTransformer, SwcPluginModule,
-    };
-
-    use crate::next_shared::transforms::get_ecma_transform_rule;
+    // [TODO]: SWC's current experimental config supports
+    // two forms of plugin path,
+    // one for implicit package name resolves to node_modules,
+    // and one for explicit path to a .wasm binary.
+    // Current resolve will fail with latter.
+    let request = Request::parse(Value::new(Pattern::Constant(name.as_str().into())));
+    let resolve_options = resolve_options(
+        project_path,
+        ResolveOptionsContext {
+            enable_node_modules: Some(project_path.root().resolve().await?),
+            enable_node_native_modules: true,
+            ..Default::default()
+        }
+        .cell(),
+    );
 
-    let mut plugins = vec![];
-    for (name, config) in plugin_configs.iter() {
-        // [TODO]: SWC's current experimental config supports
-        // two forms of plugin path,
-        // one for implicit package name resolves to node_modules,
-        // and one for explicit path to a .wasm binary.
-        // Current resolve will fail with latter.
-        let request = Request::parse(Value::new(Pattern::Constant(name.as_str().into())));
-        let resolve_options = resolve_options(
+    let plugin_wasm_module_resolve_result = handle_resolve_error(
+        resolve(
             project_path,
-            ResolveOptionsContext {
-                enable_node_modules: Some(project_path.root().resolve().await?),
-                enable_node_native_modules: true,
-                ..Default::default()
-            }
-            .cell(),
-        );
-
-        let plugin_wasm_module_resolve_result = handle_resolve_error(
-            resolve(
-                project_path,
-                Value::new(ReferenceType::CommonJs(CommonJsReferenceSubType::Undefined)),
-                request,
-                resolve_options,
-            )
-            .as_raw_module_result(),
             Value::new(ReferenceType::CommonJs(CommonJsReferenceSubType::Undefined)),
-            project_path,
             request,
             resolve_options,
-            false,
-            None,
         )
-        .await?;
-        let plugin_module = plugin_wasm_module_resolve_result
-            .first_module()
-            .await?
-            .context("Expected to find module")?;
-
-        let content = &*plugin_module.content().file_content().await?;
+        .as_raw_module_result(),
+        Value::new(ReferenceType::CommonJs(CommonJsReferenceSubType::Undefined)),
+        project_path,
+        request,
+        resolve_options,
+        false,
+        None,
+    )
+    .await?;
+    let plugin_module = plugin_wasm_module_resolve_result
+        .first_module()
+        .await?
+        .context("Expected to find module")?;
 
-        let FileContent::Content(file) = content else {
-            bail!("Expected file content for plugin module");
-        };
+    let content = &*plugin_module.content().file_content().await?;
 
-        plugins.push((
-            SwcPluginModule::new(name, file.content().to_bytes()?.to_vec()).resolved_cell(),
-            config.clone(),
-        ));
-    }
+    let FileContent::Content(file) = content else {
+        bail!("Expected file content for plugin module");
+    };
 
-    Ok(Some(get_ecma_transform_rule(
-        Box::new(SwcEcmaTransformPluginsTransformer::new(plugins)),
-        enable_mdx_rs,
-        true,
-    )))
+    Ok(SwcPluginModule::new(&name, file.content().to_bytes()?.to_vec()).cell())
 }
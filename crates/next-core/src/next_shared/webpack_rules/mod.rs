@@ -4,10 +4,15 @@ use turbo_tasks_fs::FileSystemPath;
 use turbopack::module_options::WebpackLoadersOptions;
 use turbopack_core::resolve::options::ImportMapping;
 
-use self::{babel::maybe_add_babel_loader, sass::maybe_add_sass_loader};
+use self::{
+    babel::maybe_add_babel_loader,
+    native::{native_loaders, remove_unavailable_native_loaders},
+    sass::maybe_add_sass_loader,
+};
 use crate::{next_build::get_external_next_compiled_package_mapping, next_config::NextConfig};
 
 pub(crate) mod babel;
+pub(crate) mod native;
 pub(crate) mod sass;
 
 pub async fn webpack_loader_options(
@@ -23,10 +28,14 @@ pub async fn webpack_loader_options(
     } else {
         *maybe_add_babel_loader(project_path, rules).await?
     };
+    let rules = *remove_unavailable_native_loaders(project_path, rules).await?;
+    let loader_timeout_ms = *next_config.loader_timeout_ms().await?;
     Ok(rules.map(|rules| {
         WebpackLoadersOptions {
             rules,
             loader_runner_package: Some(loader_runner_package_mapping()),
+            native_loaders: Some(native_loaders(project_path)),
+            loader_timeout_ms,
         }
         .cell()
     }))
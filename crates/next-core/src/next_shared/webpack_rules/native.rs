@@ -0,0 +1,320 @@
+use anyhow::{bail, Result};
+use serde_json::Value as JsonValue;
+use turbo_tasks::{FxIndexMap, RcStr, Vc};
+use turbo_tasks_fs::{File, FileContent, FileSystemPath};
+use turbopack::module_options::{LoaderRuleItem, OptionWebpackRules, WebpackRules};
+use turbopack_core::{
+    asset::AssetContent,
+    issue::{Issue, IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString},
+    native_loader::{NativeLoader, NativeLoaderResult, NativeLoaders},
+    source::Source,
+};
+use turbopack_ecmascript::utils::StringifyJs;
+
+/// The prefix of loaders that are built into Next.js.
+const NATIVE_LOADER_PREFIX: &str = "next:";
+
+/// Loaders that are built into Next.js. Rules in `experimental.turbo.rules`
+/// reference them with the `next:` prefix, so they don't replace npm packages
+/// of the same name.
+///
+/// Custom native loaders can't be registered. Wasm SWC plugins can be run as a
+/// loader with `next:swc-plugin-loader` instead.
+#[turbo_tasks::function]
+pub fn native_loaders(project_path: Vc<FileSystemPath>) -> Vc<NativeLoaders> {
+    let mut loaders = FxIndexMap::default();
+    loaders.insert(
+        "next:raw-loader".into(),
+        Vc::upcast::<Box<dyn NativeLoader>>(RawLoader::new()),
+    );
+    #[cfg(feature = "plugin")]
+    loaders.insert(
+        "next:swc-plugin-loader".into(),
+        Vc::upcast::<Box<dyn NativeLoader>>(swc_plugin::SwcPluginLoader::new(project_path)),
+    );
+    #[cfg(not(feature = "plugin"))]
+    let _ = project_path;
+    Vc::cell(loaders)
+}
+
+/// Removes loaders with the `next:` prefix that aren't [native_loaders] of this
+/// build from `webpack_rules` and emits an issue for each of them, e.g. for
+/// `next:swc-plugin-loader` when Next.js was built without SWC plugin support.
+/// Otherwise they would be resolved as npm packages, which fails with a less
+/// helpful error.
+#[turbo_tasks::function]
+pub async fn remove_unavailable_native_loaders(
+    project_path: Vc<FileSystemPath>,
+    webpack_rules: Option<Vc<WebpackRules>>,
+) -> Result<Vc<OptionWebpackRules>> {
+    let Some(webpack_rules) = webpack_rules else {
+        return Ok(Vc::cell(None));
+    };
+    let native_loaders = native_loaders(project_path).await?;
+    let mut rules = FxIndexMap::default();
+    let mut has_changed = false;
+    for (glob, rule) in webpack_rules.await?.iter() {
+        let loaders = rule.loaders.await?;
+        let (available, unavailable): (Vec<_>, Vec<_>) =
+            loaders.iter().cloned().partition(|loader| {
+                !loader.loader.starts_with(NATIVE_LOADER_PREFIX)
+                    || native_loaders.contains_key(&loader.loader)
+            });
+        if unavailable.is_empty() {
+            rules.insert(glob.clone(), rule.clone());
+            continue;
+        }
+        for loader in unavailable {
+            UnavailableNativeLoaderIssue {
+                path: project_path,
+                glob: glob.clone(),
+                loader: loader.loader,
+                available: native_loaders.keys().cloned().collect(),
+            }
+            .cell()
+            .emit();
+        }
+        if !available.is_empty() {
+            rules.insert(
+                glob.clone(),
+                LoaderRuleItem {
+                    loaders: Vc::cell(available),
+                    rename_as: rule.rename_as.clone(),
+                },
+            );
+        }
+        has_changed = true;
+    }
+    if has_changed {
+        return Ok(Vc::cell(Some(Vc::cell(rules))));
+    }
+    Ok(Vc::cell(Some(webpack_rules)))
+}
+
+#[turbo_tasks::value]
+struct UnavailableNativeLoaderIssue {
+    path: Vc<FileSystemPath>,
+    glob: RcStr,
+    loader: RcStr,
+    available: Vec<RcStr>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for UnavailableNativeLoaderIssue {
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Config.into()
+    }
+
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Text(format!("Unknown built-in loader {}", self.loader).into()).cell()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        Vc::cell(Some(
+            StyledString::Text(
+                format!(
+                    "The rule for \"{}\" uses the loader \"{}\", which isn't built into this \
+                     version of Next.js, so it was skipped. Built-in loaders are: {}.",
+                    self.glob,
+                    self.loader,
+                    self.available.join(", ")
+                )
+                .into(),
+            )
+            .cell(),
+        ))
+    }
+}
+
+/// A native implementation of `raw-loader`, which exports the content of a
+/// file as a string.
+#[turbo_tasks::value]
+struct RawLoader;
+
+#[turbo_tasks::value_impl]
+impl RawLoader {
+    #[turbo_tasks::function]
+    fn new() -> Vc<Self> {
+        RawLoader.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl NativeLoader for RawLoader {
+    #[turbo_tasks::function]
+    async fn process(
+        &self,
+        source: Vc<Box<dyn Source>>,
+        options: Vc<JsonValue>,
+    ) -> Result<Vc<NativeLoaderResult>> {
+        let AssetContent::File(file) = *source.content().await? else {
+            bail!("raw-loader only supports files");
+        };
+        let content = match &*file.await? {
+            FileContent::Content(file) => {
+                // Like `raw-loader`, invalid UTF-8 sequences are replaced instead
+                // of failing on binary files.
+                let bytes = file.content().to_bytes()?;
+                let content = String::from_utf8_lossy(&bytes);
+                let es_module = options
+                    .await?
+                    .get("esModule")
+                    .and_then(JsonValue::as_bool)
+                    .unwrap_or(true);
+                let code = if es_module {
+                    format!("export default {};\n", StringifyJs(&content))
+                } else {
+                    format!("module.exports = {};\n", StringifyJs(&content))
+                };
+                FileContent::Content(File::from(code))
+            }
+            FileContent::NotFound => FileContent::NotFound,
+        };
+        Ok(NativeLoaderResult {
+            content: AssetContent::File(content.cell()).cell(),
+            source_map: None,
+            file_dependencies: vec![],
+        }
+        .cell())
+    }
+}
+
+#[cfg(feature = "plugin")]
+mod swc_plugin {
+    use anyhow::{bail, Context, Result};
+    use serde::Deserialize;
+    use serde_json::{json, Value as JsonValue};
+    use swc_core::ecma::codegen::{text_writer::JsWriter, Emitter};
+    use turbo_tasks::{RcStr, Value, ValueToString, Vc};
+    use turbo_tasks_fs::{File, FileContent, FileSystemPath};
+    use turbopack_core::{
+        asset::AssetContent,
+        native_loader::{NativeLoader, NativeLoaderResult},
+        source::Source,
+        source_map::{GenerateSourceMap, OptionSourceMap},
+    };
+    use turbopack_ecmascript::{
+        parse::{parse, ParseResult},
+        EcmascriptInputTransform, EcmascriptModuleAssetType, ParseResultSourceMap, TransformPlugin,
+    };
+    use turbopack_ecmascript_plugins::transform::swc_ecma_transform_plugins::SwcEcmaTransformPluginsTransformer;
+
+    use crate::next_shared::transforms::swc_ecma_transform_plugins::swc_plugin_module;
+
+    #[derive(Deserialize)]
+    struct SwcPluginLoaderOptions {
+        /// The package name of the plugin, like in `experimental.swcPlugins`.
+        plugin: RcStr,
+        /// The config passed to the plugin.
+        options: Option<JsonValue>,
+    }
+
+    /// Runs an swc transform plugin on a file, using the same wasm plugin
+    /// runtime as `experimental.swcPlugins`, but limited to the files of a
+    /// rule:
+    ///
+    /// ```js
+    /// loaders: [{
+    ///   loader: 'next:swc-plugin-loader',
+    ///   options: { plugin: 'my-swc-plugin', options: { ... } },
+    /// }]
+    /// ```
+    #[turbo_tasks::value]
+    pub(super) struct SwcPluginLoader {
+        project_path: Vc<FileSystemPath>,
+    }
+
+    #[turbo_tasks::value_impl]
+    impl SwcPluginLoader {
+        #[turbo_tasks::function]
+        pub(super) fn new(project_path: Vc<FileSystemPath>) -> Vc<Self> {
+            SwcPluginLoader { project_path }.cell()
+        }
+    }
+
+    #[turbo_tasks::value_impl]
+    impl NativeLoader for SwcPluginLoader {
+        #[turbo_tasks::function]
+        async fn process(
+            &self,
+            source: Vc<Box<dyn Source>>,
+            options: Vc<JsonValue>,
+        ) -> Result<Vc<NativeLoaderResult>> {
+            let SwcPluginLoaderOptions { plugin, options } =
+                serde_json::from_value(options.await?.clone_value())
+                    .context("next:swc-plugin-loader requires the `plugin` option")?;
+            let plugin_module = swc_plugin_module(self.project_path, plugin)
+                .to_resolved()
+                .await?;
+            let transforms = Vc::cell(vec![EcmascriptInputTransform::Plugin(
+                TransformPlugin::cell(Box::new(SwcEcmaTransformPluginsTransformer::new(vec![(
+                    plugin_module,
+                    options.unwrap_or_else(|| json!({})),
+                )]))),
+            )]);
+
+            let ty = match source.ident().path().await?.extension_ref() {
+                Some("ts" | "mts" | "cts") => EcmascriptModuleAssetType::Typescript {
+                    tsx: false,
+                    analyze_types: false,
+                },
+                Some("tsx") => EcmascriptModuleAssetType::Typescript {
+                    tsx: true,
+                    analyze_types: false,
+                },
+                _ => EcmascriptModuleAssetType::Ecmascript,
+            };
+            let parsed = parse(source, Value::new(ty), transforms).await?;
+            let ParseResult::Ok {
+                program,
+                comments,
+                source_map,
+                ..
+            } = &*parsed
+            else {
+                bail!(
+                    "next:swc-plugin-loader is unable to parse {}",
+                    source.ident().path().to_string().await?
+                );
+            };
+
+            let mut bytes = vec![];
+            let mut mappings = vec![];
+            let comments = comments.consumable();
+            let mut emitter = Emitter {
+                cfg: swc_core::ecma::codegen::Config::default(),
+                cm: source_map.clone(),
+                comments: Some(&comments),
+                wr: JsWriter::new(source_map.clone(), "\n", &mut bytes, Some(&mut mappings)),
+            };
+            emitter.emit_program(program)?;
+
+            // The source map of the source is chained by the native loader
+            // transform.
+            let source_map =
+                ParseResultSourceMap::new(source_map.clone(), mappings, OptionSourceMap::none())
+                    .cell()
+                    .generate_source_map()
+                    .await?;
+            Ok(NativeLoaderResult {
+                content: AssetContent::File(FileContent::Content(File::from(bytes)).cell()).cell(),
+                source_map: *source_map,
+                file_dependencies: vec![],
+            }
+            .cell())
+        }
+    }
+}
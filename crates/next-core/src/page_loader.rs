@@ -117,7 +117,11 @@ impl PageLoaderAsset {
             chunks = Vc::cell(rebased);
         };
 
-        Ok(ChunkData::from_assets(self.server_root, chunks))
+        Ok(ChunkData::from_assets(
+            self.server_root,
+            chunks,
+            Vc::cell(None),
+        ))
     }
 }
 
@@ -65,6 +65,9 @@ export interface NapiWrittenEndpoint {
 export function endpointWriteToDisk(endpoint: {
   __napiType: 'Endpoint'
 }): Promise<TurbopackResult>
+export function endpointWriteWebpackStatsToDisk(endpoint: {
+  __napiType: 'Endpoint'
+}): Promise<void>
 export function endpointServerChangedSubscribe(
   endpoint: { __napiType: 'Endpoint' },
   issues: boolean,
@@ -227,6 +230,15 @@ export function projectEntrypointsSubscribe(
   project: { __napiType: 'Project' },
   func: (...args: any[]) => any
 ): { __napiType: 'RootTask' }
+/**
+ * Subscribes to the feature telemetry of the project. It isn't needed for
+ * compilation, so it's collected with [TaskPriority::Background] instead of
+ * as part of the entrypoints.
+ */
+export function projectFeatureTelemetrySubscribe(
+  project: { __napiType: 'Project' },
+  func: (...args: any[]) => any
+): { __napiType: 'RootTask' }
 export function projectHmrEvents(
   project: { __napiType: 'Project' },
   identifier: string,
@@ -246,6 +258,21 @@ export interface NapiUpdateMessage {
 export interface NapiUpdateInfo {
   duration: number
   tasks: number
+  /** Only set when recording invalidations was requested. */
+  recomputedTasks?: Array<NapiRecomputedTask>
+}
+export interface NapiRecomputedTask {
+  description: string
+  /**
+   * The reason of the invalidation that caused the re-execution, e. g. a
+   * changed file.
+   */
+  reason?: string
+  /**
+   * The tasks the invalidation propagated through, starting with the
+   * invalidated task and ending with the re-executed task.
+   */
+  chain: Array<string>
 }
 /**
  * Subscribes to lifecycle events of the compilation.
@@ -258,12 +285,17 @@ export interface NapiUpdateInfo {
  * (excluding the idle time that was spend waiting for `aggregation_ms`), and
  * the number of tasks that were executed.
  *
+ * When `record_invalidations` is true, the [UpdateMessage::End] event also
+ * lists the re-executed tasks together with the invalidation reason and the
+ * chain of dependencies that caused the re-execution.
+ *
  * The signature of the `func` is `(update_message: UpdateMessage) => void`.
  */
 export function projectUpdateInfoSubscribe(
   project: { __napiType: 'Project' },
   aggregationMs: number,
-  func: (...args: any[]) => any
+  func: (...args: any[]) => any,
+  recordInvalidations?: boolean | undefined | null
 ): void
 export interface StackFrame {
   isServer: boolean
@@ -288,6 +320,14 @@ export function projectGetSourceMap(
 /** Runs exit handlers for the project registered using the [`ExitHandler`] API. */
 export function projectOnExit(project: { __napiType: 'Project' }): Promise<void>
 export function rootTaskDispose(rootTask: { __napiType: 'RootTask' }): void
+/**
+ * Disposes a subscription like [root_task_dispose] and also cancels work
+ * that only the subscription still needs, e. g. compiling a route the user
+ * navigated away from. Finding that work is done on a blocking thread.
+ */
+export function rootTaskCancel(rootTask: {
+  __napiType: 'RootTask'
+}): Promise<void>
 export interface NapiIssue {
   severity: string
   stage: string
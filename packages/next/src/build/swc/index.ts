@@ -31,6 +31,7 @@ import type {
 import type {
   Binding,
   DefineEnv,
+  Diagnostics,
   Endpoint,
   HmrIdentifiers,
   Project,
@@ -647,7 +648,34 @@ function bindingToApi(
         async (callback) =>
           binding.projectEntrypointsSubscribe(this._nativeProject, callback)
       )
+      // Feature telemetry isn't needed for compilation, so it's collected in
+      // the background. Entrypoints yielded before it's available don't
+      // include it in their diagnostics.
+      const telemetrySubscription = subscribe<TurbopackResult>(
+        false,
+        async (callback) =>
+          binding.projectFeatureTelemetrySubscribe(
+            this._nativeProject,
+            callback
+          )
+      )
+      let telemetryDiagnostics: Diagnostics[] = []
+      ;(async () => {
+        for await (const telemetry of telemetrySubscription) {
+          telemetryDiagnostics = telemetry.diagnostics
+        }
+      })().catch(() => {
+        // Telemetry is best effort.
+      })
       return (async function* () {
+        try {
+          yield* entrypointsFromSubscription()
+        } finally {
+          telemetrySubscription.return?.()
+        }
+      })()
+
+      async function* entrypointsFromSubscription() {
         for await (const entrypoints of subscription) {
           const routes = new Map()
           for (const { pathname, ...nativeRoute } of entrypoints.routes) {
@@ -727,10 +755,10 @@ function bindingToApi(
               entrypoints.pagesErrorEndpoint
             ),
             issues: entrypoints.issues,
-            diagnostics: entrypoints.diagnostics,
+            diagnostics: [...entrypoints.diagnostics, ...telemetryDiagnostics],
           }
         }
-      })()
+      }
     }
 
     hmrEvents(identifier: string) {
@@ -761,12 +789,13 @@ function bindingToApi(
       return binding.projectGetSourceMap(this._nativeProject, filePath)
     }
 
-    updateInfoSubscribe(aggregationMs: number) {
+    updateInfoSubscribe(aggregationMs: number, recordInvalidations?: boolean) {
       return subscribe<TurbopackResult<UpdateMessage>>(true, async (callback) =>
         binding.projectUpdateInfoSubscribe(
           this._nativeProject,
           aggregationMs,
-          callback
+          callback,
+          recordInvalidations
         )
       )
     }
@@ -796,6 +825,12 @@ function bindingToApi(
       )
     }
 
+    async writeWebpackStatsToDisk(): Promise<void> {
+      await withErrorCause(() =>
+        binding.endpointWriteWebpackStatsToDisk(this._nativeEndpoint)
+      )
+    }
+
     async clientChanged(): Promise<AsyncIterableIterator<TurbopackResult<{}>>> {
       const clientSubscription = subscribe<TurbopackResult>(
         false,
@@ -198,6 +198,14 @@ export type UpdateMessage =
 export interface UpdateInfo {
   duration: number
   tasks: number
+  /** Only set when `recordInvalidations` was passed to `updateInfoSubscribe` */
+  recomputedTasks?: RecomputedTask[]
+}
+
+export interface RecomputedTask {
+  description: string
+  reason?: string
+  chain: string[]
 }
 
 export interface Project {
@@ -220,7 +228,8 @@ export interface Project {
   ): Promise<TurbopackStackFrame | null>
 
   updateInfoSubscribe(
-    aggregationMs: number
+    aggregationMs: number,
+    recordInvalidations?: boolean
   ): AsyncIterableIterator<TurbopackResult<UpdateMessage>>
 
   shutdown(): Promise<void>
@@ -259,6 +268,12 @@ export interface Endpoint {
   /** Write files for the endpoint to disk. */
   writeToDisk(): Promise<TurbopackResult<WrittenEndpoint>>
 
+  /**
+   * Write `webpack-stats.json` for the endpoint to disk. This is executed with
+   * background priority.
+   */
+  writeWebpackStatsToDisk(): Promise<void>
+
   /**
    * Listen to client-side changes to the endpoint.
    * After clientChanged() has been awaited it will listen to changes.
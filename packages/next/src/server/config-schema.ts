@@ -373,6 +373,19 @@ export const configSchema: zod.ZodType<NextConfig> = z.lazy(() =>
               jsxImportSource: z.string().optional(),
               providerImportSource: z.string().optional(),
               mdxType: z.enum(['gfm', 'commonmark']).optional(),
+              plugins: z
+                .array(
+                  z.union([
+                    z.object({ name: z.literal('headingSlugs') }),
+                    z.object({ name: z.literal('frontmatter') }),
+                    z.object({ name: z.literal('toc') }),
+                    z.object({
+                      name: z.literal('syntaxHighlightClasses'),
+                      classPrefix: z.string().optional(),
+                    }),
+                  ])
+                )
+                .optional(),
             }),
           ])
           .optional(),
@@ -406,6 +419,35 @@ export const configSchema: zod.ZodType<NextConfig> = z.lazy(() =>
               .optional(),
             memoryLimit: z.number().optional(),
             moduleIdStrategy: z.enum(['named', 'deterministic']).optional(),
+            loaderTimeoutMs: z.number().int().positive().optional(),
+            moduleFederation: z
+              .object({
+                name: z.string(),
+                remotes: z.record(z.string(), z.string()).optional(),
+                exposes: z.record(z.string(), z.string()).optional(),
+                shared: z
+                  .record(
+                    z.string(),
+                    z.object({
+                      singleton: z.boolean().optional(),
+                      requiredVersion: z.string().optional(),
+                      version: z.string().optional(),
+                    })
+                  )
+                  .optional(),
+              })
+              .optional(),
+            traceResolve: z.array(z.string()).optional(),
+            contentHashing: z.boolean().optional(),
+            urlExternals: z
+              .record(
+                z.string(),
+                z.object({
+                  url: z.string(),
+                  integrity: z.string().optional(),
+                })
+              )
+              .optional(),
           })
           .optional(),
         optimizePackageImports: z.array(z.string()).optional(),
@@ -174,6 +174,50 @@ export interface ExperimentalTurboOptions {
    */
   moduleIdStrategy?: 'named' | 'deterministic'
 
+  /**
+   * The time in milliseconds the webpack loaders of a single module may run
+   * before the module fails to compile. Defaults to 5 minutes.
+   */
+  loaderTimeoutMs?: number
+
+  /**
+   * Builds the client as a module federation container: `exposes` modules to
+   * other builds, loads modules of `remotes` given as `name@url`, and
+   * negotiates `shared` dependencies like `react` with them at runtime.
+   */
+  moduleFederation?: {
+    name: string
+    remotes?: Record<string, string>
+    exposes?: Record<string, string>
+    shared?: Record<
+      string,
+      { singleton?: boolean; requiredVersion?: string; version?: string }
+    >
+  }
+
+  /**
+   * Requests like `react` or `@/components/button` to log how they are
+   * resolved for in development, listing each decision like the import map
+   * entries, tsconfig `paths` or `exports` conditions that matched.
+   */
+  traceResolve?: string[]
+
+  /**
+   * Includes a hash of their content in the filenames of production client
+   * chunks, e.g. `static/chunks/main.1a2b3c4d5e6f7a8b.js`, so they can be
+   * cached immutably. As chunks refer to their own filename, the hash is
+   * computed with a placeholder in its place, so it doesn't match a hash of the
+   * emitted file. Defaults to `false`.
+   */
+  contentHashing?: boolean
+
+  /**
+   * Packages to load from a URL in the browser instead of bundling them into
+   * client chunks, e.g. `{ react: { url: 'https://esm.sh/react@18' } }`.
+   * Specifiers ending with `/` map all subpaths and can't have `integrity`.
+   */
+  urlExternals?: Record<string, { url: string; integrity?: string }>
+
   /**
    * This is the repo root usually and only files above this
    * directory can be resolved by turbopack.
@@ -383,6 +427,15 @@ export interface ExperimentalConfig {
         jsxImportSource?: string
         providerImportSource?: string
         mdxType?: 'gfm' | 'commonmark'
+        /**
+         * Built-in Rust plugins run while compiling the document.
+         */
+        plugins?: Array<
+          | { name: 'headingSlugs' }
+          | { name: 'frontmatter' }
+          | { name: 'toc' }
+          | { name: 'syntaxHighlightClasses'; classPrefix?: string }
+        >
       }
 
   /**
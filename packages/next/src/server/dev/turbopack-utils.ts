@@ -113,9 +113,18 @@ function shouldEmitOnceWarning(issue: Issue): boolean {
 export function printNonFatalIssue(issue: Issue) {
   if (isRelevantWarning(issue) && shouldEmitOnceWarning(issue)) {
     Log.warn(formatIssue(issue))
+  } else if (isResolveTrace(issue)) {
+    Log.info(formatIssue(issue))
   }
 }
 
+/**
+ * Resolve traces are requested with `experimental.turbo.traceResolve`.
+ */
+function isResolveTrace(issue: Issue): boolean {
+  return issue.severity === 'info' && issue.stage === 'resolve'
+}
+
 function isNodeModulesIssue(issue: Issue): boolean {
   if (issue.severity === 'warning' && issue.stage === 'config') {
     // Override for the externalize issue
@@ -360,6 +369,26 @@ type HandleRouteTypeHooks = {
   subscribeToChanges: StartChangeSubscription
 }
 
+/**
+ * Writes the webpack stats of an endpoint and merges them into the combined
+ * stats file. This isn't awaited to keep it off the request path, so failures
+ * are only logged.
+ */
+function writeWebpackStatsInBackground(
+  endpoint: Endpoint,
+  manifestLoader: TurbopackManifestLoader,
+  page: string,
+  type: 'app' | 'pages'
+) {
+  endpoint
+    .writeWebpackStatsToDisk()
+    .then(() => manifestLoader.loadWebpackStats(page, type))
+    .then(() => manifestLoader.writeWebpackStats())
+    .catch((err) => {
+      Log.error(`Failed to write webpack stats for ${page}: ${err}`)
+    })
+}
+
 export async function handleRouteType({
   dev,
   page,
@@ -446,16 +475,21 @@ export async function handleRouteType({
         await manifestLoader.loadFontManifest(page, 'pages')
         await manifestLoader.loadLoadableManifest(page, 'pages')
 
-        if (shouldCreateWebpackStats) {
-          await manifestLoader.loadWebpackStats(page, 'pages')
-        }
-
         await manifestLoader.writeManifests({
           devRewrites,
           productionRewrites,
           entrypoints,
         })
 
+        if (shouldCreateWebpackStats) {
+          writeWebpackStatsInBackground(
+            route.htmlEndpoint,
+            manifestLoader,
+            page,
+            'pages'
+          )
+        }
+
         processIssues(
           currentEntryIssues,
           serverKey,
@@ -587,10 +621,7 @@ export async function handleRouteType({
       await manifestLoader.loadActionManifest(page)
       await manifestLoader.loadLoadableManifest(page, 'app')
       await manifestLoader.loadFontManifest(page, 'app')
-
-      if (shouldCreateWebpackStats) {
-        await manifestLoader.loadWebpackStats(page, 'app')
-      }
+      await manifestLoader.loadSubresourceIntegrityManifest(page)
 
       await manifestLoader.writeManifests({
         devRewrites,
@@ -598,6 +629,15 @@ export async function handleRouteType({
         entrypoints,
       })
 
+      if (shouldCreateWebpackStats) {
+        writeWebpackStatsInBackground(
+          route.htmlEndpoint,
+          manifestLoader,
+          page,
+          'app'
+        )
+      }
+
       processIssues(currentEntryIssues, key, writtenEndpoint, dev, logErrors)
 
       break
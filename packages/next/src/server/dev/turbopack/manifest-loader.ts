@@ -22,6 +22,7 @@ import {
   PAGES_MANIFEST,
   REACT_LOADABLE_MANIFEST,
   SERVER_REFERENCE_MANIFEST,
+  SUBRESOURCE_INTEGRITY_MANIFEST,
   TURBOPACK_CLIENT_MIDDLEWARE_MANIFEST,
   WEBPACK_STATS,
 } from '../../../shared/lib/constants'
@@ -51,6 +52,11 @@ interface InstrumentationDefinition {
   name: 'instrumentation'
 }
 
+/**
+ * Maps client asset paths to their subresource integrity metadata.
+ */
+type SubresourceIntegrityManifest = Record<string, string>
+
 type TurbopackMiddlewareManifest = MiddlewareManifest & {
   instrumentation?: InstrumentationDefinition
 }
@@ -81,6 +87,7 @@ async function readPartialManifest<T>(
     | typeof APP_PATHS_MANIFEST
     | `${typeof SERVER_REFERENCE_MANIFEST}.json`
     | `${typeof NEXT_FONT_MANIFEST}.json`
+    | `${typeof SUBRESOURCE_INTEGRITY_MANIFEST}.json`
     | typeof REACT_LOADABLE_MANIFEST,
   pageName: string,
   type: 'pages' | 'app' | 'middleware' | 'instrumentation' = 'pages'
@@ -112,6 +119,10 @@ export class TurbopackManifestLoader {
   private middlewareManifests: Map<EntryKey, TurbopackMiddlewareManifest> =
     new Map()
   private pagesManifests: Map<string, PagesManifest> = new Map()
+  private subresourceIntegrityManifests: Map<
+    EntryKey,
+    SubresourceIntegrityManifest
+  > = new Map()
   private webpackStats: Map<EntryKey, WebpackStats> = new Map()
   private encryptionKey: string
 
@@ -141,6 +152,7 @@ export class TurbopackManifestLoader {
     this.loadableManifests.delete(key)
     this.middlewareManifests.delete(key)
     this.pagesManifests.delete(key)
+    this.subresourceIntegrityManifests.delete(key)
     this.webpackStats.delete(key)
   }
 
@@ -272,7 +284,7 @@ export class TurbopackManifestLoader {
     )
   }
 
-  private async writeWebpackStats(): Promise<void> {
+  async writeWebpackStats(): Promise<void> {
     const webpackStats = this.mergeWebpackStats(
       this.webpackStats.values()
     )
@@ -567,6 +579,52 @@ export class TurbopackManifestLoader {
     )
   }
 
+  /**
+   * Subresource integrity manifests are only emitted when
+   * `experimental.sri.algorithm` is configured, so they are optional.
+   */
+  async loadSubresourceIntegrityManifest(pageName: string): Promise<void> {
+    const key = getEntryKey('app', 'server', pageName)
+    const name = `${SUBRESOURCE_INTEGRITY_MANIFEST}.json` as const
+    if (!existsSync(getManifestPath(pageName, this.distDir, name, 'app'))) {
+      this.subresourceIntegrityManifests.delete(key)
+      return
+    }
+    this.subresourceIntegrityManifests.set(
+      key,
+      await readPartialManifest(this.distDir, name, pageName, 'app')
+    )
+  }
+
+  private async writeSubresourceIntegrityManifest(): Promise<void> {
+    if (this.subresourceIntegrityManifests.size === 0) {
+      return
+    }
+    const manifest: SubresourceIntegrityManifest = {}
+    for (const m of this.subresourceIntegrityManifests.values()) {
+      Object.assign(manifest, m)
+    }
+    const json = JSON.stringify(manifest, null, 2)
+
+    const manifestJsonPath = join(
+      this.distDir,
+      'server',
+      `${SUBRESOURCE_INTEGRITY_MANIFEST}.json`
+    )
+    const manifestJsPath = join(
+      this.distDir,
+      'server',
+      `${SUBRESOURCE_INTEGRITY_MANIFEST}.js`
+    )
+    deleteCache(manifestJsonPath)
+    deleteCache(manifestJsPath)
+    await writeFileAtomic(manifestJsonPath, json)
+    await writeFileAtomic(
+      manifestJsPath,
+      `self.__SUBRESOURCE_INTEGRITY_MANIFEST=${JSON.stringify(json)}`
+    )
+  }
+
   async loadLoadableManifest(
     pageName: string,
     type: 'app' | 'pages' = 'pages'
@@ -764,6 +822,7 @@ export class TurbopackManifestLoader {
     await this.writeClientMiddlewareManifest()
     await this.writeNextFontManifest()
     await this.writePagesManifest()
+    await this.writeSubresourceIntegrityManifest()
 
     if (process.env.TURBOPACK_STATS != null) {
       await this.writeWebpackStats()
@@ -0,0 +1,5 @@
+import { Versions } from './versions'
+
+export default function Page() {
+  return <Versions />
+}
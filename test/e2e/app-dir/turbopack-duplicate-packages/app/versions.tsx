@@ -0,0 +1,12 @@
+'use client'
+
+import { version } from 'shared'
+import { wrappedVersion } from 'wrapper'
+
+export function Versions() {
+  return (
+    <p>
+      {version} {wrappedVersion}
+    </p>
+  )
+}
@@ -0,0 +1,91 @@
+import { nextTestSetup } from 'e2e-utils'
+import { retry } from 'next-test-utils'
+;(process.env.TURBOPACK ? describe : describe.skip)(
+  'turbopack-duplicate-packages',
+  () => {
+    const { next, isNextDev } = nextTestSetup({
+      files: __dirname,
+      env: {
+        TURBOPACK_DUPLICATE_PACKAGES: '1',
+      },
+    })
+
+    async function fileSize(file: string) {
+      return Buffer.byteLength(await next.readFile(file))
+    }
+
+    async function readReport() {
+      return JSON.parse(
+        await next.readFile('.next/server/app/page/duplicate-packages.json')
+      )
+    }
+
+    it('should bundle both copies of the package', async () => {
+      const $ = await next.render$('/')
+
+      expect($('p').text()).toBe('shared@1.0.0 shared@2.0.0')
+    })
+
+    it('should report both copies of the package', async () => {
+      await next.render$('/')
+
+      const report = await retry(readReport)
+      expect(report.entry).toBe('/page')
+
+      const shared = report.duplicates.find(
+        (duplicate) => duplicate.name === 'shared'
+      )
+      const nestedSize = await fileSize(
+        'node_modules/wrapper/node_modules/shared/index.js'
+      )
+      const topLevelSize = await fileSize('node_modules/shared/index.js')
+      // Copies are sorted by size, the largest one first
+      expect(shared).toEqual({
+        name: 'shared',
+        duplicatedBytes: topLevelSize,
+        copies: [
+          {
+            version: '2.0.0',
+            path: expect.stringMatching(
+              /node_modules\/wrapper\/node_modules\/shared$/
+            ),
+            bytes: nestedSize,
+            modules: 1,
+            importers: [
+              expect.stringMatching(/app\/versions\.tsx$/),
+              expect.stringMatching(/node_modules\/wrapper\/index\.js$/),
+              expect.stringMatching(
+                /node_modules\/wrapper\/node_modules\/shared\/index\.js$/
+              ),
+            ],
+          },
+          {
+            version: '1.0.0',
+            path: expect.stringMatching(/\]\/node_modules\/shared$/),
+            bytes: topLevelSize,
+            modules: 1,
+            importers: [
+              expect.stringMatching(/app\/versions\.tsx$/),
+              expect.stringMatching(/\]\/node_modules\/shared\/index\.js$/),
+            ],
+          },
+        ],
+      })
+      expect(
+        report.duplicates.find((duplicate) => duplicate.name === 'wrapper')
+      ).toBeUndefined()
+    })
+
+    if (isNextDev) {
+      it('should warn about the duplicated package', async () => {
+        await next.render$('/')
+
+        await retry(() => {
+          expect(next.cliOutput).toContain(
+            'Duplicate packages bundled for /page'
+          )
+        })
+      })
+    }
+  }
+)
@@ -0,0 +1,6 @@
+// @ts-expect-error -- ignore
+import greeting from './world.greeting'
+
+export default function Page() {
+  return <p id="greeting">{greeting}</p>
+}
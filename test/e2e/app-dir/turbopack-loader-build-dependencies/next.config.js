@@ -0,0 +1,15 @@
+/** @type {import('next').NextConfig} */
+const nextConfig = {
+  experimental: {
+    turbo: {
+      rules: {
+        '*.greeting': {
+          loaders: ['greeting-loader'],
+          as: '*.js',
+        },
+      },
+    },
+  },
+}
+
+module.exports = nextConfig
@@ -0,0 +1,44 @@
+import { nextTestSetup } from 'e2e-utils'
+import { retry } from 'next-test-utils'
+;(process.env.TURBOPACK ? describe : describe.skip)(
+  'turbopack-loader-build-dependencies',
+  () => {
+    const { next, isNextDev } = nextTestSetup({
+      files: __dirname,
+    })
+
+    it('should run the loader', async () => {
+      const $ = await next.render$('/')
+
+      expect($('#greeting').text()).toBe('Hello world!')
+    })
+
+    if (isNextDev) {
+      it('should run the loader again when a module required by the loader changes', async () => {
+        await next.patchFile(
+          'node_modules/greeting-loader/punctuation.js',
+          "exports.punctuation = '?'",
+          async () => {
+            await retry(async () => {
+              const $ = await next.render$('/')
+              expect($('#greeting').text()).toBe('Hello world?')
+            })
+          }
+        )
+      })
+
+      it('should run the loader again when a file added with addBuildDependency changes', async () => {
+        await next.patchFile(
+          'greeting.config.json',
+          '{ "salutation": "Goodbye" }',
+          async () => {
+            await retry(async () => {
+              const $ = await next.render$('/')
+              expect($('#greeting').text()).toBe('Goodbye world!')
+            })
+          }
+        )
+      })
+    }
+  }
+)
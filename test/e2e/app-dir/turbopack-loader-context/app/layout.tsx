@@ -0,0 +1,9 @@
+import { ReactNode } from 'react'
+import './theme.style'
+export default function Root({ children }: { children: ReactNode }) {
+  return (
+    <html>
+      <body>{children}</body>
+    </html>
+  )
+}
@@ -0,0 +1,14 @@
+// @ts-expect-error -- ignore
+import { resource, loaded } from './data.bin'
+
+export default function Page() {
+  return (
+    <>
+      <p id="resource">{JSON.stringify(resource)}</p>
+      <p id="loaded">{JSON.stringify(loaded)}</p>
+      <p id="styled" className="from-loader">
+        styled
+      </p>
+    </>
+  )
+}
@@ -0,0 +1,19 @@
+/** @type {import('next').NextConfig} */
+const nextConfig = {
+  experimental: {
+    turbo: {
+      rules: {
+        '*.bin': {
+          loaders: ['binary-loader.js'],
+          as: '*.js',
+        },
+        '*.style': {
+          loaders: ['css-emit-loader.js'],
+          as: '*.css',
+        },
+      },
+    },
+  },
+}
+
+module.exports = nextConfig
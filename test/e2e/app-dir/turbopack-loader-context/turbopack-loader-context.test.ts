@@ -0,0 +1,61 @@
+import { nextTestSetup } from 'e2e-utils'
+import fs from 'fs'
+import path from 'path'
+;(process.env.TURBOPACK ? describe : describe.skip)(
+  'turbopack-loader-context',
+  () => {
+    const { next } = nextTestSetup({
+      files: __dirname,
+    })
+
+    it('should pass binary resources to raw loaders as buffers', async () => {
+      const $ = await next.render$('/')
+
+      expect(JSON.parse($('#resource').text())).toEqual({
+        isBuffer: true,
+        bytes: [0x00, 0xff, 0xfe, 0x80],
+      })
+    })
+
+    it('should pass binary modules loaded with loadModule as buffers', async () => {
+      const $ = await next.render$('/')
+
+      expect(JSON.parse($('#loaded').text())).toEqual({
+        isBuffer: true,
+        bytes: [0x89, 0x50, 0x4e, 0x47, 0xff, 0x00],
+      })
+    })
+
+    it('should normalize emitted file paths', async () => {
+      await next.render$('/')
+
+      const distDir = path.join(next.testDir, '.next')
+      const emitted = fs
+        .readdirSync(distDir, { recursive: true, encoding: 'utf8' })
+        .filter((file) => path.basename(file) === 'emitted-by-loader.txt')
+      expect(emitted).toHaveLength(1)
+      expect(emitted[0]).not.toContain('assets')
+      expect(
+        await fs.promises.readFile(path.join(distDir, emitted[0]), 'utf8')
+      ).toBe('emitted')
+    })
+
+    it('should emit files from loaders producing CSS', async () => {
+      const browser = await next.browser('/')
+      expect(
+        await browser.eval(
+          `getComputedStyle(document.querySelector('#styled')).color`
+        )
+      ).toBe('rgb(255, 0, 0)')
+
+      const distDir = path.join(next.testDir, '.next')
+      const emitted = fs
+        .readdirSync(distDir, { recursive: true, encoding: 'utf8' })
+        .filter((file) => path.basename(file) === 'emitted-by-css-loader.txt')
+      expect(emitted).toHaveLength(1)
+      expect(
+        await fs.promises.readFile(path.join(distDir, emitted[0]), 'utf8')
+      ).toBe('emitted from css')
+    })
+  }
+)
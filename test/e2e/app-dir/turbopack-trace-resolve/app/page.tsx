@@ -0,0 +1,10 @@
+import { greeting } from '@lib/greeting'
+import { name } from 'traced-pkg'
+
+export default function Page() {
+  return (
+    <p>
+      {greeting} {name}
+    </p>
+  )
+}
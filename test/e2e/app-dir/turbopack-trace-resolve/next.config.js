@@ -0,0 +1,10 @@
+/** @type {import('next').NextConfig} */
+const nextConfig = {
+  experimental: {
+    turbo: {
+      traceResolve: ['traced-pkg', '@lib/greeting'],
+    },
+  },
+}
+
+module.exports = nextConfig
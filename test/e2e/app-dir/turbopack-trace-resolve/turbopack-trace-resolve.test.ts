@@ -0,0 +1,43 @@
+import { nextTestSetup } from 'e2e-utils'
+import { retry } from 'next-test-utils'
+;(process.env.TURBOPACK ? describe : describe.skip)(
+  'turbopack-trace-resolve',
+  () => {
+    const { next, isNextDev } = nextTestSetup({
+      files: __dirname,
+    })
+
+    it('should resolve the traced requests', async () => {
+      const $ = await next.render$('/')
+
+      expect($('p').text()).toBe('hello esm')
+    })
+
+    if (isNextDev) {
+      it('should log the steps of resolving a package in order', async () => {
+        await next.render$('/')
+
+        await retry(() => {
+          const findPackage = next.cliOutput.indexOf(
+            'package traced-pkg found in'
+          )
+          const exportsField = next.cliOutput.indexOf(
+            '"exports" field in [project]/node_modules/traced-pkg/package.json'
+          )
+          expect(findPackage).toBeGreaterThan(-1)
+          expect(exportsField).toBeGreaterThan(findPackage)
+        })
+      })
+
+      it('should label tsconfig paths', async () => {
+        await next.render$('/')
+
+        await retry(() => {
+          expect(next.cliOutput).toContain(
+            'module "@lib/greeting": tsconfigPaths entry'
+          )
+        })
+      })
+    }
+  }
+)
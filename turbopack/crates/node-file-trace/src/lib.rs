@@ -3,6 +3,8 @@
 #![feature(arbitrary_self_types_pointers)]
 
 mod nft_json;
+mod trace;
+mod why;
 
 use std::{
     collections::BTreeSet,
@@ -30,7 +32,7 @@ use turbo_tasks_fs::{
     glob::Glob, DirectoryEntry, DiskFileSystem, FileSystem, FileSystemPath, ReadGlobResult,
 };
 use turbopack::{
-    emit_asset, emit_with_completion, module_options::ModuleOptionsContext, rebase::RebasedAsset,
+    emit_asset, emit_asset_into_dir, module_options::ModuleOptionsContext, rebase::RebasedAsset,
     ModuleAssetContext,
 };
 use turbopack_cli_utils::issue::{ConsoleUi, IssueSeverityCliOption, LogOptions};
@@ -42,12 +44,15 @@ use turbopack_core::{
     issue::{IssueDescriptionExt, IssueReporter, IssueSeverity},
     module::{Module, Modules},
     output::OutputAsset,
-    reference::all_modules_and_affecting_sources,
     resolve::options::{ImportMapping, ResolvedMap},
 };
 use turbopack_resolve::resolve_options_context::ResolveOptionsContext;
 
-use crate::nft_json::NftJsonAsset;
+use crate::{
+    nft_json::NftJsonAsset,
+    trace::{all_traced_modules, check_size_budget, size_breakdown, TraceOptions},
+    why::reference_chains,
+};
 
 #[cfg(feature = "persistent_cache")]
 #[cfg_attr(feature = "cli", derive(clap::Args))]
@@ -122,11 +127,37 @@ pub struct CommonArgs {
     #[cfg_attr(feature = "node-api", serde(default))]
     exact: bool,
 
+    /// Exclude files matching this glob (relative to the context directory)
+    /// from the trace. Ignored files are not followed either. Can be
+    /// repeated.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "node-api", serde(default))]
+    ignore: Vec<String>,
+
+    /// Always include files matching this glob (relative to the context
+    /// directory) in the trace, e.g. files loaded by dynamic code. Can be
+    /// repeated.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "node-api", serde(default))]
+    include: Vec<String>,
+
+    /// Fail with a breakdown by package when the traced files exceed this
+    /// many bytes.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "node-api", serde(default))]
+    max_size: Option<u64>,
+
     /// Enable experimental garbage collection with the provided memory limit in
     /// MB.
     #[cfg_attr(feature = "cli", clap(long))]
     #[cfg_attr(feature = "serializable", serde(default))]
     pub memory_limit: Option<usize>,
+
+    /// Resolve dependencies with Yarn Plug'n'Play, using the `.pnp.cjs` or
+    /// `.pnp.data.json` in the context directory.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "node-api", serde(default))]
+    pnp: bool,
 }
 
 #[cfg_attr(feature = "cli", derive(Parser))]
@@ -169,6 +200,21 @@ pub enum Args {
         #[cfg_attr(feature = "node-api", serde(flatten))]
         common: CommonArgs,
     },
+
+    // Print every reference chain from the input files to the given file
+    Why {
+        /// The traced file to explain, relative to the current directory.
+        file: String,
+
+        #[cfg_attr(feature = "cli", clap(flatten))]
+        #[cfg_attr(feature = "node-api", serde(flatten))]
+        common: CommonArgs,
+
+        /// Stop after printing this many chains.
+        #[cfg_attr(feature = "cli", clap(long, default_value_t = 100))]
+        #[cfg_attr(feature = "node-api", serde(default = "default_max_chains"))]
+        max_chains: usize,
+    },
 }
 
 #[cfg(feature = "node-api")]
@@ -176,13 +222,19 @@ fn default_output_directory() -> String {
     "dist".to_string()
 }
 
+#[cfg(feature = "node-api")]
+fn default_max_chains() -> usize {
+    100
+}
+
 impl Args {
     pub fn common(&self) -> &CommonArgs {
         match self {
             Args::Print { common, .. }
             | Args::Annotate { common, .. }
             | Args::Build { common, .. }
-            | Args::Size { common, .. } => common,
+            | Args::Size { common, .. }
+            | Args::Why { common, .. } => common,
         }
     }
 }
@@ -236,6 +288,7 @@ async fn input_to_modules(
     exact: bool,
     process_cwd: Option<RcStr>,
     context_directory: RcStr,
+    ignore: Option<Vc<Glob>>,
     module_options: TransientInstance<ModuleOptionsContext>,
     resolve_options: TransientInstance<ResolveOptionsContext>,
 ) -> Result<Vc<Modules>> {
@@ -247,6 +300,7 @@ async fn input_to_modules(
     let asset_context: Vc<Box<dyn AssetContext>> = Vc::upcast(create_module_asset(
         root,
         process_cwd,
+        ignore,
         module_options,
         resolve_options,
     ));
@@ -393,8 +447,10 @@ async fn run<B: Backend + 'static, F: Future<Output = ()>>(
             result
         }
     };
-    let has_return_value =
-        matches!(&*args, Args::Annotate { .. }) || matches!(&*args, Args::Print { .. });
+    let has_return_value = matches!(
+        &*args,
+        Args::Annotate { .. } | Args::Print { .. } | Args::Size { .. } | Args::Why { .. }
+    );
     let (sender, mut receiver) = channel(1);
     let dir = current_dir().unwrap();
     let module_options = TransientInstance::new(module_options.unwrap_or_default());
@@ -467,6 +523,10 @@ async fn main_operation(
         exact,
         ref context_directory,
         ref process_cwd,
+        ref ignore,
+        ref include,
+        max_size,
+        pnp,
         ..
     } = args.common();
     let context_directory: RcStr = process_context(&dir, context_directory.as_ref())
@@ -474,50 +534,84 @@ async fn main_operation(
         .into();
     let fs = create_fs("context directory", &context_directory, watch).await?;
     let process_cwd = process_cwd.clone().map(RcStr::from);
+    let resolve_options = if pnp {
+        TransientInstance::new(ResolveOptionsContext {
+            enable_pnp: Some(fs.root()),
+            ..ResolveOptionsContext::clone(&*resolve_options)
+        })
+    } else {
+        resolve_options
+    };
+    let ignore = if ignore.is_empty() {
+        None
+    } else {
+        Some(Glob::alternatives(
+            ignore
+                .iter()
+                .map(|glob| Glob::new(glob.as_str().into()))
+                .collect(),
+        ))
+    };
+    let include = if include.is_empty() {
+        Vc::cell(Vec::new())
+    } else {
+        input_to_modules(
+            fs,
+            include.iter().map(|glob| glob.as_str().into()).collect(),
+            false,
+            process_cwd.clone(),
+            context_directory.clone(),
+            ignore,
+            module_options.clone(),
+            resolve_options.clone(),
+        )
+    };
+    let trace_options = TraceOptions { ignore, include }.cell();
+
+    let input = process_input(&dir, &context_directory, input).unwrap();
+    let modules = input_to_modules(
+        fs,
+        input,
+        exact,
+        process_cwd,
+        context_directory.clone(),
+        ignore,
+        module_options,
+        resolve_options,
+    );
+
+    let traced = all_traced_modules(modules, trace_options);
+    if let Some(max_size) = max_size {
+        // The budget is not enforced when explaining why files are included,
+        // as that is what helps to get below it.
+        if !matches!(**args, Args::Why { .. }) {
+            check_size_budget(traced, max_size).await?;
+        }
+    }
 
     match **args {
+        Args::Why {
+            ref file,
+            max_chains,
+            common: _,
+        } => {
+            let file = make_relative_path(&dir, &context_directory, file)?;
+            return Ok(reference_chains(modules, trace_options, file, max_chains));
+        }
         Args::Print { common: _ } => {
-            let input = process_input(&dir, &context_directory, input).unwrap();
             let mut result = BTreeSet::new();
-            let modules = input_to_modules(
-                fs,
-                input,
-                exact,
-                process_cwd.clone(),
-                context_directory,
-                module_options,
-                resolve_options,
-            )
-            .await?;
-            for module in modules.iter() {
-                let set = all_modules_and_affecting_sources(*module)
-                    .issue_file_path(module.ident().path(), "gathering list of assets")
-                    .await?;
-                for asset in set.await?.iter() {
-                    let path = asset.ident().path().await?;
-                    result.insert(RcStr::from(&*path.path));
-                }
+            for asset in traced.await?.iter() {
+                let path = asset.ident().path().await?;
+                result.insert(RcStr::from(&*path.path));
             }
 
             return Ok(Vc::cell(result.into_iter().collect::<Vec<_>>()));
         }
         Args::Annotate { common: _ } => {
-            let input = process_input(&dir, &context_directory, input).unwrap();
             let mut output_nft_assets = Vec::new();
             let mut emits = Vec::new();
-            for module in input_to_modules(
-                fs,
-                input,
-                exact,
-                process_cwd.clone(),
-                context_directory,
-                module_options,
-                resolve_options,
-            )
-            .await?
-            .iter()
-            {
-                let nft_asset = NftJsonAsset::new(*module);
+            for module in modules.await?.iter() {
+                let nft_asset = NftJsonAsset::new(*module, trace_options);
                 let path = nft_asset.ident().path().await?.path.clone();
                 output_nft_assets.push(path);
                 emits.push(emit_asset(Vc::upcast(nft_asset)));
@@ -533,32 +627,24 @@ async fn main_operation(
             common: _,
         } => {
             let output = process_context(&dir, Some(output_directory)).unwrap();
-            let input = process_input(&dir, &context_directory, input).unwrap();
             let out_fs = create_fs("output directory", &output, watch).await?;
             let input_dir = fs.root();
             let output_dir = out_fs.root();
             let mut emits = Vec::new();
-            for module in input_to_modules(
-                fs,
-                input,
-                exact,
-                process_cwd.clone(),
-                context_directory,
-                module_options,
-                resolve_options,
-            )
-            .await?
-            .iter()
-            {
+            // Emit the traced modules one by one instead of following the
+            // references of a rebased entry, so ignored files are skipped.
+            for module in traced.await?.iter() {
                 let rebased = Vc::upcast(RebasedAsset::new(*module, input_dir, output_dir));
-                emits.push(emit_with_completion(rebased, output_dir));
+                emits.push(emit_asset_into_dir(rebased, output_dir));
             }
             // Wait for all files to be emitted
             for emit in emits {
                 emit.await?;
             }
         }
-        Args::Size { common: _ } => todo!(),
+        Args::Size { common: _ } => {
+            return Ok(Vc::cell(size_breakdown(traced).await?.lines()));
+        }
     }
     Ok(Vc::cell(Vec::new()))
 }
@@ -567,6 +653,7 @@ async fn main_operation(
 async fn create_module_asset(
     root: Vc<FileSystemPath>,
     process_cwd: Option<RcStr>,
+    ignore: Option<Vc<Glob>>,
     module_options: TransientInstance<ModuleOptionsContext>,
     resolve_options: TransientInstance<ResolveOptionsContext>,
 ) -> Vc<ModuleAssetContext> {
@@ -578,7 +665,7 @@ async fn create_module_asset(
         .into(),
     )));
     let compile_time_info = CompileTimeInfo::builder(env).cell();
-    let glob_mappings = vec![
+    let mut glob_mappings = vec![
         (
             root,
             Glob::new("**/*/next/dist/server/next.js".into()),
@@ -590,6 +677,9 @@ async fn create_module_asset(
             ImportMapping::Ignore.into(),
         ),
     ];
+    if let Some(ignore) = ignore {
+        glob_mappings.push((root, ignore, ImportMapping::Ignore.into()));
+    }
     let mut resolve_options = ResolveOptionsContext::clone(&*resolve_options);
     if resolve_options.emulate_environment.is_none() {
         resolve_options.emulate_environment = Some(env);
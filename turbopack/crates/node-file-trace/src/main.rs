@@ -17,7 +17,10 @@ async fn main() -> Result<()> {
     #[cfg(feature = "tokio_console")]
     console_subscriber::init();
     let args = Arc::new(Args::parse());
-    let should_print = matches!(&*args, Args::Print { .. });
+    let should_print = matches!(
+        &*args,
+        Args::Print { .. } | Args::Size { .. } | Args::Why { .. }
+    );
     let turbo_tasks = TurboTasks::new(MemoryBackend::new(
         args.common().memory_limit.unwrap_or(usize::MAX),
     ));
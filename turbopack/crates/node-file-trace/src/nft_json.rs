@@ -7,19 +7,21 @@ use turbopack_core::{
     ident::AssetIdent,
     module::Module,
     output::OutputAsset,
-    reference::all_modules_and_affecting_sources,
 };
 
+use crate::trace::{traced_modules, TraceOptions};
+
 #[turbo_tasks::value(shared)]
 pub struct NftJsonAsset {
     entry: Vc<Box<dyn Module>>,
+    options: Vc<TraceOptions>,
 }
 
 #[turbo_tasks::value_impl]
 impl NftJsonAsset {
     #[turbo_tasks::function]
-    pub fn new(entry: Vc<Box<dyn Module>>) -> Vc<Self> {
-        NftJsonAsset { entry }.cell()
+    pub fn new(entry: Vc<Box<dyn Module>>, options: Vc<TraceOptions>) -> Vc<Self> {
+        NftJsonAsset { entry, options }.cell()
     }
 }
 
@@ -45,7 +47,7 @@ impl Asset for NftJsonAsset {
         let entry_path = &*self.entry.ident().path().await?;
         let mut result = Vec::new();
         if let Some(self_path) = parent_dir.get_relative_path_to(entry_path) {
-            let set = all_modules_and_affecting_sources(self.entry);
+            let set = traced_modules(self.entry, self.options);
             for asset in set.await?.iter() {
                 let path = asset.ident().path().await?;
                 if let Some(rel_path) = parent_dir.get_relative_path_to(&path) {
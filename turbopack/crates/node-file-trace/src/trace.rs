@@ -0,0 +1,260 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fmt::Write,
+};
+
+use anyhow::{bail, Result};
+use turbo_tasks::{RcStr, ReadRef, ValueToString, Vc};
+use turbo_tasks_fs::{glob::Glob, FileContent, FileSystemPath, ZipFileSystem};
+use turbopack_core::{
+    asset::{Asset, AssetContent},
+    file_source::FileSource,
+    issue::IssueDescriptionExt,
+    module::{Module, Modules},
+    raw_module::RawModule,
+    reference::ModuleReference,
+};
+
+/// Options that shape the traced module graph.
+#[turbo_tasks::value(shared)]
+pub struct TraceOptions {
+    /// Files matching this glob are neither included in the trace nor
+    /// followed.
+    pub ignore: Option<Vc<Glob>>,
+    /// Modules which are always traced, in addition to the entries.
+    pub include: Vc<Modules>,
+}
+
+/// The references of a module, labeled with the description of the reference
+/// that created them (e.g. `require 'foo'`). Affecting sources are included as
+/// raw modules.
+#[turbo_tasks::value(transparent)]
+pub struct TracedReferences(Vec<(RcStr, Vc<Box<dyn Module>>)>);
+
+#[turbo_tasks::function]
+pub async fn traced_references(module: Vc<Box<dyn Module>>) -> Result<Vc<TracedReferences>> {
+    let mut result = Vec::new();
+    for reference in module.references().await?.iter() {
+        let description = reference.to_string().await?.clone_value();
+        let resolve_result = reference.resolve_reference().await?;
+        for module in resolve_result.primary_modules_iter() {
+            result.push((description.clone(), module.resolve().await?));
+        }
+        for source in resolve_result.affecting_sources_iter() {
+            let module: Vc<Box<dyn Module>> = Vc::upcast(RawModule::new(source));
+            result.push((
+                format!("{description} (affecting source)").into(),
+                module.resolve().await?,
+            ));
+        }
+    }
+    Ok(Vc::cell(result))
+}
+
+/// The module graph below some roots, respecting the [TraceOptions]. Both the
+/// traced files and the reference chains of `why` are computed from it.
+pub struct TracedGraph {
+    /// The modules in breadth-first order. Ignored modules are neither part of
+    /// the graph nor followed.
+    pub modules: Vec<Vc<Box<dyn Module>>>,
+    pub references: HashMap<Vc<Box<dyn Module>>, ReadRef<TracedReferences>>,
+}
+
+impl TracedGraph {
+    pub async fn walk(roots: Vec<Vc<Box<dyn Module>>>, options: &TraceOptions) -> Result<Self> {
+        let ignore = match options.ignore {
+            Some(ignore) => Some(ignore.await?),
+            None => None,
+        };
+
+        let mut queue = VecDeque::new();
+        let mut visited = HashSet::new();
+        for module in roots {
+            let module = module.resolve().await?;
+            if visited.insert(module) {
+                queue.push_back(module);
+            }
+        }
+        let mut graph = TracedGraph {
+            modules: Vec::new(),
+            references: HashMap::new(),
+        };
+        while let Some(module) = queue.pop_front() {
+            let path = module.ident().path().await?;
+            if ignore
+                .as_ref()
+                .is_some_and(|ignore| ignore.execute(&path.path))
+            {
+                continue;
+            }
+            graph.modules.push(module);
+            let references = traced_references(module)
+                .issue_file_path(module.ident().path(), "expanding references of asset")
+                .await?
+                .await?;
+            for &(_, module) in references.iter() {
+                if visited.insert(module) {
+                    queue.push_back(module);
+                }
+            }
+            graph.references.insert(module, references);
+        }
+        Ok(graph)
+    }
+}
+
+/// Like [turbopack_core::reference::all_modules_and_affecting_sources], but
+/// respecting the [TraceOptions]. Modules inside of zip archives, e. g. of
+/// packages in the cache of Yarn Plug'n'Play, are replaced by the archive.
+#[turbo_tasks::function]
+pub async fn traced_modules(
+    entry: Vc<Box<dyn Module>>,
+    options: Vc<TraceOptions>,
+) -> Result<Vc<Modules>> {
+    let options = options.await?;
+    let include = options.include.await?;
+    let roots = [entry].into_iter().chain(include.iter().copied()).collect();
+    let graph = TracedGraph::walk(roots, &options).await?;
+
+    let mut result = Vec::new();
+    let mut archives = HashSet::new();
+    for module in graph.modules {
+        match zip_archive(module.ident().path()).await? {
+            Some(archive) => {
+                if archives.insert(archive) {
+                    result.push(Vc::upcast(RawModule::new(Vc::upcast(FileSource::new(
+                        archive,
+                    )))));
+                }
+            }
+            None => result.push(module),
+        }
+    }
+    Ok(Vc::cell(result))
+}
+
+/// Returns the archive when `path` is inside of a zip archive.
+async fn zip_archive(path: Vc<FileSystemPath>) -> Result<Option<Vc<FileSystemPath>>> {
+    Ok(
+        match Vc::try_resolve_downcast_type::<ZipFileSystem>(path.fs()).await? {
+            Some(zip_fs) => Some(zip_fs.archive().resolve().await?),
+            None => None,
+        },
+    )
+}
+
+/// The union of the traced modules of all `entries`.
+#[turbo_tasks::function]
+pub async fn all_traced_modules(
+    entries: Vc<Modules>,
+    options: Vc<TraceOptions>,
+) -> Result<Vc<Modules>> {
+    let mut modules = HashSet::new();
+    let mut result = Vec::new();
+    for &entry in entries.await?.iter() {
+        for &module in traced_modules(entry, options).await?.iter() {
+            if modules.insert(module) {
+                result.push(module);
+            }
+        }
+    }
+    Ok(Vc::cell(result))
+}
+
+#[turbo_tasks::function]
+async fn module_size(module: Vc<Box<dyn Module>>) -> Result<Vc<u64>> {
+    let size = match &*module.content().await? {
+        AssetContent::File(file) => match &*file.await? {
+            FileContent::Content(file) => file.content().len() as u64,
+            FileContent::NotFound => 0,
+        },
+        AssetContent::Redirect { .. } => 0,
+    };
+    Ok(Vc::cell(size))
+}
+
+/// Extracts the package name from a path like
+/// `node_modules/@scope/pkg/lib/index.js`, using the innermost
+/// `node_modules` directory.
+fn package_name(path: &str) -> &str {
+    let Some(index) = path.rfind("node_modules/") else {
+        return "(app)";
+    };
+    let rest = &path[index + "node_modules/".len()..];
+    let segments = if rest.starts_with('@') { 2 } else { 1 };
+    let end = rest
+        .match_indices('/')
+        .nth(segments - 1)
+        .map_or(rest.len(), |(i, _)| i);
+    &rest[..end]
+}
+
+/// Total size of traced files, grouped by the package they belong to.
+#[turbo_tasks::value(shared)]
+pub struct SizeBreakdown {
+    pub total: u64,
+    /// Packages sorted by size, largest first.
+    pub packages: Vec<(RcStr, u64)>,
+}
+
+#[turbo_tasks::function]
+pub async fn size_breakdown(modules: Vc<Modules>) -> Result<Vc<SizeBreakdown>> {
+    let mut packages = BTreeMap::<RcStr, u64>::new();
+    let mut total = 0;
+    for &module in modules.await?.iter() {
+        let size = *module_size(module).await?;
+        let path = module.ident().path().await?;
+        *packages.entry(package_name(&path.path).into()).or_default() += size;
+        total += size;
+    }
+    let mut packages = packages.into_iter().collect::<Vec<_>>();
+    packages.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then_with(|| a_name.cmp(b_name)));
+    Ok(SizeBreakdown { total, packages }.cell())
+}
+
+impl SizeBreakdown {
+    pub fn lines(&self) -> Vec<RcStr> {
+        let mut lines = vec![format!("total: {} bytes", self.total).into()];
+        for (package, size) in &self.packages {
+            lines.push(format!("  {size:>12} {package}").into());
+        }
+        lines
+    }
+}
+
+/// Fails with a breakdown by package when the traced `modules` exceed
+/// `max_size` bytes.
+pub async fn check_size_budget(modules: Vc<Modules>, max_size: u64) -> Result<()> {
+    let breakdown = size_breakdown(modules).await?;
+    if breakdown.total > max_size {
+        let mut message = format!(
+            "traced files are {} bytes, exceeding the budget of {} bytes:",
+            breakdown.total, max_size
+        );
+        for line in breakdown.lines().iter().skip(1) {
+            write!(message, "\n{line}")?;
+        }
+        bail!(message);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::package_name;
+
+    #[test]
+    fn package_names() {
+        assert_eq!(package_name("src/index.js"), "(app)");
+        assert_eq!(package_name("node_modules/sharp/lib/index.js"), "sharp");
+        assert_eq!(
+            package_name("node_modules/@img/sharp/build/a.node"),
+            "@img/sharp"
+        );
+        assert_eq!(
+            package_name("node_modules/a/node_modules/b/package.json"),
+            "b"
+        );
+        assert_eq!(package_name("node_modules/c"), "c");
+    }
+}
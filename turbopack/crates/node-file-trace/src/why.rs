@@ -0,0 +1,238 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use anyhow::Result;
+use turbo_tasks::{RcStr, Vc};
+use turbopack_core::module::{Module, Modules};
+
+use crate::trace::{TraceOptions, TracedGraph};
+
+/// Edges of a module graph, labeled with the description of the reference
+/// (e.g. `require 'foo'`, `raw asset ...`) that created them.
+type Edges<N> = HashMap<N, Vec<(RcStr, N)>>;
+
+/// A chain of references, starting with a root, which has no description.
+type Chain<N> = Vec<(Option<RcStr>, N)>;
+
+/// All nodes from which one of the `targets` can be reached, including the
+/// targets themselves.
+fn reaching<N: Copy + Eq + Hash>(edges: &Edges<N>, targets: &[N]) -> HashSet<N> {
+    let mut reverse: HashMap<_, Vec<_>> = HashMap::new();
+    for (&from, edges) in edges {
+        for &(_, to) in edges {
+            reverse.entry(to).or_default().push(from);
+        }
+    }
+    let mut reaching: HashSet<_> = targets.iter().copied().collect();
+    let mut queue: VecDeque<_> = targets.iter().copied().collect();
+    while let Some(node) = queue.pop_front() {
+        for &parent in reverse.get(&node).into_iter().flatten() {
+            if reaching.insert(parent) {
+                queue.push_back(parent);
+            }
+        }
+    }
+    reaching
+}
+
+struct ChainSearch<'a, N> {
+    edges: &'a Edges<N>,
+    reaching: HashSet<N>,
+    targets: HashSet<N>,
+    max_chains: usize,
+    chains: Vec<Chain<N>>,
+    truncated: bool,
+}
+
+impl<'a, N: Copy + Eq + Hash> ChainSearch<'a, N> {
+    fn new(edges: &'a Edges<N>, targets: &[N], max_chains: usize) -> Self {
+        ChainSearch {
+            edges,
+            reaching: reaching(edges, targets),
+            targets: targets.iter().copied().collect(),
+            max_chains,
+            chains: Vec::new(),
+            truncated: false,
+        }
+    }
+
+    /// Enumerates all simple paths from the `roots` which end in a target.
+    fn search(&mut self, roots: &[N]) {
+        for &root in roots {
+            if self.reaching.contains(&root) && !self.truncated {
+                self.visit(&mut vec![(None, root)]);
+            }
+        }
+    }
+
+    /// Depth-first enumeration of all simple paths which end in a target.
+    fn visit(&mut self, stack: &mut Chain<N>) {
+        let (_, node) = *stack.last().unwrap();
+        if self.targets.contains(&node) {
+            if self.chains.len() < self.max_chains {
+                self.chains.push(stack.clone());
+            } else {
+                self.truncated = true;
+            }
+            return;
+        }
+        let edges = self.edges;
+        for (description, to) in edges.get(&node).into_iter().flatten() {
+            if self.truncated {
+                return;
+            }
+            if !self.reaching.contains(to) || stack.iter().any(|(_, n)| n == to) {
+                continue;
+            }
+            stack.push((Some(description.clone()), *to));
+            self.visit(stack);
+            stack.pop();
+        }
+    }
+}
+
+/// Explains why `target` is part of the trace, by listing every reference
+/// chain from one of the `entries` to it. Each chain is printed as the entry
+/// followed by one line per reference, chains are separated by empty lines.
+///
+/// The chains are searched in the same graph the trace is computed from, so
+/// ignored files are never explained and files of `--include` are roots.
+#[turbo_tasks::function]
+pub async fn reference_chains(
+    entries: Vc<Modules>,
+    options: Vc<TraceOptions>,
+    target: RcStr,
+    max_chains: usize,
+) -> Result<Vc<Vec<RcStr>>> {
+    let options = options.await?;
+    let entries = entries.await?;
+    let include = options.include.await?;
+    let roots = entries.iter().chain(include.iter()).copied().collect();
+    let graph = TracedGraph::walk(roots, &options).await?;
+
+    let mut paths = HashMap::new();
+    for &module in &graph.modules {
+        paths.insert(module, module.ident().path().await?.path.clone());
+    }
+    let edges = graph
+        .references
+        .iter()
+        .map(|(&module, references)| (module, references.to_vec()))
+        .collect::<Edges<_>>();
+
+    let targets = graph
+        .modules
+        .iter()
+        .filter(|module| paths[*module] == target)
+        .copied()
+        .collect::<Vec<_>>();
+    if targets.is_empty() {
+        return Ok(Vc::cell(vec![format!(
+            "{target} is not referenced from the input files"
+        )
+        .into()]));
+    }
+
+    let mut roots = Vec::new();
+    for &module in entries.iter() {
+        roots.push(module.resolve().await?);
+    }
+    let mut included = HashSet::new();
+    for &module in include.iter() {
+        let module = module.resolve().await?;
+        included.insert(module);
+        roots.push(module);
+    }
+
+    let mut search = ChainSearch::new(&edges, &targets, max_chains);
+    search.search(&roots);
+
+    let mut lines = Vec::new();
+    for chain in &search.chains {
+        if !lines.is_empty() {
+            lines.push(RcStr::default());
+        }
+        for (description, module) in chain {
+            let path = &paths[module];
+            lines.push(match description {
+                Some(description) => format!("  -> {path} ({description})").into(),
+                None if included.contains(module) => format!("{path} (--include)").into(),
+                None => path.clone(),
+            });
+        }
+    }
+    if search.truncated {
+        lines.push(RcStr::default());
+        lines.push(format!("... stopped after {max_chains} chains").into());
+    }
+    Ok(Vc::cell(lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use turbo_tasks::RcStr;
+
+    use super::{ChainSearch, Edges};
+
+    fn edges(list: &[(u32, &str, u32)]) -> Edges<u32> {
+        let mut edges: Edges<u32> = HashMap::new();
+        for &(from, description, to) in list {
+            edges
+                .entry(from)
+                .or_default()
+                .push((RcStr::from(description), to));
+        }
+        edges
+    }
+
+    fn chains(search: &ChainSearch<'_, u32>) -> Vec<Vec<u32>> {
+        search
+            .chains
+            .iter()
+            .map(|chain| chain.iter().map(|&(_, node)| node).collect())
+            .collect()
+    }
+
+    #[test]
+    fn finds_all_simple_chains() {
+        // 1 -> 2 -> 4, 1 -> 3 -> 4, 3 -> 1 (cycle), 3 -> 5 (dead end)
+        let edges = edges(&[
+            (1, "require './2'", 2),
+            (1, "require './3'", 3),
+            (2, "require './4'", 4),
+            (3, "require './4'", 4),
+            (3, "require './1'", 1),
+            (3, "require './5'", 5),
+        ]);
+        let mut search = ChainSearch::new(&edges, &[4], 10);
+        search.search(&[1]);
+
+        assert_eq!(chains(&search), vec![vec![1, 2, 4], vec![1, 3, 4]]);
+        assert!(!search.truncated);
+        assert_eq!(search.chains[0][0].0, None);
+        assert_eq!(search.chains[0][1].0.as_deref(), Some("require './2'"));
+    }
+
+    #[test]
+    fn stops_after_max_chains() {
+        let edges = edges(&[(1, "a", 2), (1, "b", 3), (2, "c", 4), (3, "d", 4)]);
+        let mut search = ChainSearch::new(&edges, &[4], 1);
+        search.search(&[1]);
+
+        assert_eq!(chains(&search), vec![vec![1, 2, 4]]);
+        assert!(search.truncated);
+    }
+
+    #[test]
+    fn skips_unreachable_roots() {
+        let edges = edges(&[(1, "a", 2), (3, "b", 1)]);
+        let mut search = ChainSearch::new(&edges, &[2], 10);
+        search.search(&[4, 3]);
+
+        assert_eq!(chains(&search), vec![vec![3, 1, 2]]);
+    }
+}
@@ -0,0 +1,214 @@
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+#![cfg(feature = "cli")]
+
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::Result;
+use clap::Parser;
+use node_file_trace::{start, Args};
+use turbo_tasks::{RcStr, TurboTasks};
+use turbo_tasks_memory::MemoryBackend;
+
+fn fixture(name: &str) -> String {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Runs the cli with `args`, in the context directory of the fixture `name`.
+async fn run(name: &str, args: &[&str]) -> Result<Vec<RcStr>> {
+    let dir = fixture(name);
+    let args = args
+        .iter()
+        .map(|arg| arg.replace("$DIR", &dir))
+        .chain(["--context-directory".to_string(), dir.clone()]);
+    let args = Args::try_parse_from(["node-file-trace".to_string()].into_iter().chain(args))?;
+    start(
+        Arc::new(args),
+        TurboTasks::new(MemoryBackend::default()),
+        None,
+        None,
+    )
+    .await
+}
+
+/// Strips the reference descriptions, which depend on the analysis.
+fn without_descriptions(lines: Vec<RcStr>) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(|line| match line.find(" (") {
+            Some(index) if line.starts_with("  -> ") => line[..index].to_string(),
+            _ => line.to_string(),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn why_lists_every_chain() {
+    let output = run(
+        "why",
+        &["why", "$DIR/shared.js", "--exact", "$DIR/index.js"],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        without_descriptions(output),
+        [
+            "index.js",
+            "  -> a.js",
+            "  -> shared.js",
+            "",
+            "index.js",
+            "  -> b.js",
+            "  -> shared.js",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn why_respects_max_chains_and_ignore() {
+    let output = run(
+        "why",
+        &[
+            "why",
+            "$DIR/shared.js",
+            "--exact",
+            "$DIR/index.js",
+            "--max-chains",
+            "1",
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        without_descriptions(output),
+        [
+            "index.js",
+            "  -> a.js",
+            "  -> shared.js",
+            "",
+            "... stopped after 1 chains",
+        ]
+    );
+
+    // Ignored files are not followed, so `shared.js` is only reached via `b.js`.
+    let output = run(
+        "why",
+        &[
+            "why",
+            "$DIR/shared.js",
+            "--exact",
+            "$DIR/index.js",
+            "--ignore",
+            "a.js",
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        without_descriptions(output),
+        ["index.js", "  -> b.js", "  -> shared.js"]
+    );
+}
+
+#[tokio::test]
+async fn print_traces_all_files() {
+    let output = run("trace", &["print", "--exact", "$DIR/index.js"])
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        [
+            "ignored/skip.js",
+            "index.js",
+            "lib/only-via-ignored.js",
+            "lib/used.js",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn ignored_files_are_not_followed() {
+    let output = run(
+        "trace",
+        &[
+            "print",
+            "--exact",
+            "$DIR/index.js",
+            "--ignore",
+            "ignored/**",
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(output, ["index.js", "lib/used.js"]);
+}
+
+#[tokio::test]
+async fn included_files_are_traced() {
+    let output = run(
+        "trace",
+        &[
+            "print",
+            "--exact",
+            "$DIR/index.js",
+            "--ignore",
+            "ignored/**",
+            "--include",
+            "dynamic/*.js",
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(output, ["dynamic/plugin.js", "index.js", "lib/used.js"]);
+}
+
+#[tokio::test]
+async fn size_budget_fails_with_a_breakdown() {
+    let err = run(
+        "trace",
+        &["print", "--exact", "$DIR/index.js", "--max-size", "10"],
+    )
+    .await
+    .unwrap_err();
+    let message = format!("{err:?}");
+    assert!(
+        message.contains("exceeding the budget of 10 bytes"),
+        "{message}"
+    );
+    assert!(message.contains("(app)"), "{message}");
+
+    let output = run(
+        "trace",
+        &[
+            "print",
+            "--exact",
+            "$DIR/index.js",
+            "--ignore",
+            "ignored/**",
+            "--max-size",
+            "1000",
+        ],
+    )
+    .await
+    .unwrap();
+    assert_eq!(output, ["index.js", "lib/used.js"]);
+}
+
+#[tokio::test]
+async fn pnp_resolves_packages_in_zip_archives() {
+    let output = run("pnp", &["print", "--pnp", "--exact", "$DIR/index.js"])
+        .await
+        .unwrap();
+    assert_eq!(
+        output,
+        [
+            ".pnp.data.json",
+            ".yarn/cache/dep-npm-1.0.0-0123456789-abcdef0123.zip",
+            "index.js",
+        ]
+    );
+}
@@ -0,0 +1,2 @@
+// Loaded by dynamic code, which the analysis cannot follow.
+module.exports = "plugin";
@@ -0,0 +1 @@
+module.exports = require("../lib/only-via-ignored");
@@ -0,0 +1,2 @@
+require("./lib/used");
+require("./ignored/skip");
@@ -0,0 +1 @@
+module.exports = "only via ignored";
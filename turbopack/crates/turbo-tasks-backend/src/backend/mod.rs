@@ -8,9 +8,10 @@ use std::{
     future::Future,
     hash::BuildHasherDefault,
     mem::take,
+    ops::ControlFlow,
     pin::Pin,
     sync::{
-        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
     thread::available_parallelism,
@@ -20,30 +21,32 @@ use anyhow::{bail, Result};
 use auto_hash_map::{AutoMap, AutoSet};
 use dashmap::DashMap;
 use parking_lot::{Condvar, Mutex};
-use rustc_hash::FxHasher;
+use rustc_hash::{FxHashMap, FxHasher};
 use smallvec::smallvec;
 use tokio::time::{Duration, Instant};
 use turbo_tasks::{
     backend::{
-        Backend, BackendJobId, CachedTaskType, CellContent, TaskExecutionSpec, TransientTaskRoot,
-        TransientTaskType, TypedCellContent,
+        Backend, BackendJobId, CachedTaskType, CellContent, TaskExecutionSpec, TaskMetaSnapshot,
+        TaskSnapshot, TransientTaskRoot, TransientTaskType, TypedCellContent,
     },
     event::{Event, EventListener},
     registry,
     util::IdFactoryWithReuse,
-    CellId, FunctionId, RawVc, ReadConsistency, SessionId, TaskId, TraitTypeId,
+    CellId, FunctionId, RawVc, ReadConsistency, SessionId, TaskId, TaskPriority, TraitTypeId,
     TurboTasksBackendApi, ValueTypeId, TRANSIENT_TASK_BIT,
 };
+use turbo_tasks_malloc::TurboMalloc;
 
 pub use self::{operation::AnyOperation, storage::TaskDataCategory};
 use crate::{
     backend::{
         operation::{
-            get_aggregation_number, is_root_node, AggregatedDataUpdate, AggregationUpdateJob,
-            AggregationUpdateQueue, CleanupOldEdgesOperation, ConnectChildOperation,
-            ExecuteContext, ExecuteContextImpl, Operation, OutdatedEdge, TaskGuard,
+            get_aggregation_number, get_uppers, is_root_node, AggregatedDataUpdate,
+            AggregationUpdateJob, AggregationUpdateQueue, CleanupOldEdgesOperation,
+            ConnectChildOperation, ExecuteContext, ExecuteContextImpl, Operation, OutdatedEdge,
+            TaskGuard,
         },
-        storage::{get, get_many, get_mut, iter_many, remove, Storage},
+        storage::{get, get_many, get_mut, iter_many, remove, InnerStorage, Storage},
     },
     backing_storage::BackingStorage,
     data::{
@@ -59,6 +62,12 @@ const BACKEND_JOB_FOLLOW_UP_SNAPSHOT: BackendJobId = unsafe { BackendJobId::new_
 
 const SNAPSHOT_REQUESTED_BIT: usize = 1 << (usize::BITS - 1);
 
+/// Eviction stops when the memory usage drops below this percentage of the
+/// memory limit.
+const PERCENTAGE_EVICTION_TARGET_MEMORY: usize = 80;
+/// Number of evicted tasks between memory usage checks.
+const EVICTION_BATCH_SIZE: usize = 1000;
+
 struct SnapshotRequest {
     snapshot_requested: bool,
     suspended_operations: HashSet<PtrEqArc<AnyOperation>>,
@@ -82,7 +91,7 @@ pub enum TransientTask {
     /// execution.
     ///
     /// Always active. Automatically scheduled.
-    Root(TransientTaskRoot),
+    Root(TransientTaskRoot, TaskPriority),
 
     // TODO implement these strongly consistency
     /// A single root task execution. It won't track dependencies.
@@ -92,7 +101,7 @@ pub enum TransientTask {
     /// applied.
     ///
     /// Active until done. Automatically scheduled.
-    Once(TransientTaskOnce),
+    Once(TransientTaskOnce, TaskPriority),
 }
 
 pub struct TurboTasksBackend<B: BackingStorage>(Arc<TurboTasksBackendInner<B>>);
@@ -130,6 +139,15 @@ struct TurboTasksBackendInner<B: BackingStorage> {
     /// The timestamp of the last started snapshot since [`Self::start_time`].
     last_snapshot: AtomicU64,
 
+    /// When the memory usage exceeds this limit, data of cold tasks is evicted
+    /// after a snapshot. `usize::MAX` disables eviction.
+    memory_limit: usize,
+    /// Incremented by every eviction. Tasks that were not accessed in the
+    /// current generation are considered cold.
+    eviction_generation: AtomicU32,
+    /// The number of evicted tasks since startup.
+    evicted_tasks: AtomicU64,
+
     stopping: AtomicBool,
     stopping_event: Event,
     idle_start_event: Event,
@@ -140,12 +158,28 @@ struct TurboTasksBackendInner<B: BackingStorage> {
 
 impl<B: BackingStorage> TurboTasksBackend<B> {
     pub fn new(backing_storage: B) -> Self {
-        Self(Arc::new(TurboTasksBackendInner::new(backing_storage)))
+        Self::new_with_memory_limit(backing_storage, usize::MAX)
+    }
+
+    /// Creates a backend that evicts persisted data of cold tasks from memory
+    /// when the memory usage exceeds `memory_limit`. Evicted data is restored
+    /// from the `backing_storage` on the next access.
+    pub fn new_with_memory_limit(backing_storage: B, memory_limit: usize) -> Self {
+        Self(Arc::new(TurboTasksBackendInner::new(
+            backing_storage,
+            memory_limit,
+        )))
+    }
+
+    /// The number of times data of a task was evicted from memory since the
+    /// backend was created.
+    pub fn evicted_task_count(&self) -> u64 {
+        self.0.evicted_tasks.load(Ordering::Relaxed)
     }
 }
 
 impl<B: BackingStorage> TurboTasksBackendInner<B> {
-    pub fn new(backing_storage: B) -> Self {
+    pub fn new(backing_storage: B, memory_limit: usize) -> Self {
         let shard_amount =
             (available_parallelism().map_or(4, |v| v.get()) * 64).next_power_of_two();
         Self {
@@ -170,6 +204,9 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
             operations_suspended: Condvar::new(),
             snapshot_completed: Condvar::new(),
             last_snapshot: AtomicU64::new(0),
+            memory_limit,
+            eviction_generation: AtomicU32::new(0),
+            evicted_tasks: AtomicU64::new(0),
             stopping: AtomicBool::new(false),
             stopping_event: Event::new(|| "TurboTasksBackend::stopping_event".to_string()),
             idle_start_event: Event::new(|| "TurboTasksBackend::idle_start_event".to_string()),
@@ -271,6 +308,10 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
             TaskDataCategory::All => unreachable!(),
         }
     }
+
+    fn eviction_generation(&self) -> u32 {
+        self.eviction_generation.load(Ordering::Relaxed)
+    }
 }
 
 pub(crate) struct OperationGuard<'a, B: BackingStorage> {
@@ -613,18 +654,17 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
         let snapshot_time = Instant::now();
         drop(snapshot_request);
 
-        // TODO track which items are persisting
         // TODO This is very inefficient, maybe the BackingStorage could compute that since it need
         // to iterate items anyway.
-        // let mut counts: FxHashMap<TaskId, u32> =
-        // FxHashMap::with_capacity_and_hasher(); for log in persisted_storage_meta_log
-        //     .iter()
-        //     .chain(persisted_storage_data_log.iter())
-        // {
-        //     for CachedDataUpdate { task, .. } in log.iter() {
-        //         *counts.entry(*task).or_default() += 1;
-        //     }
-        // }
+        let mut counts: FxHashMap<TaskId, u32> = FxHashMap::default();
+        for log in persisted_storage_meta_log
+            .iter()
+            .chain(persisted_storage_data_log.iter())
+        {
+            for CachedDataUpdate { task, .. } in log.iter() {
+                *counts.entry(*task).or_default() += 1;
+            }
+        }
 
         let mut new_items = false;
 
@@ -649,17 +689,55 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
             }
         }
 
-        // TODO add when we need to track persisted items
-        // for (task_id, count) in counts {
-        //     self.storage
-        //         .access_mut(task_id)
-        //         .persistance_state_mut()
-        //         .finish_persisting_items(count);
-        // }
+        for (task_id, count) in counts {
+            self.storage
+                .access_mut(task_id)
+                .persistance_state_mut()
+                .finish_persisting_items(count);
+        }
 
         Some((snapshot_time, new_items))
     }
 
+    /// Evicts the data of tasks that were not accessed since the previous
+    /// eviction until the memory usage is below the target. Only data that is
+    /// completely persisted is evicted. It's restored from the backing storage
+    /// when the task is accessed again.
+    fn evict_cold_tasks(&self) {
+        let usage = TurboMalloc::memory_usage();
+        if usage < self.memory_limit {
+            return;
+        }
+        let target = self.memory_limit / 100 * PERCENTAGE_EVICTION_TARGET_MEMORY;
+        let generation = self.eviction_generation.fetch_add(1, Ordering::Relaxed);
+        let _span = tracing::trace_span!("evict cold tasks", usage).entered();
+
+        let mut evicted = 0;
+        self.storage.for_each_mut(|task_id, task| {
+            let state = task.persistance_state();
+            if task_id.is_transient()
+                || state.last_access() >= generation
+                || !state.is_fully_persisted()
+            {
+                return ControlFlow::Continue(());
+            }
+            let data_evicted = evict_task_data(task, TaskDataCategory::Data);
+            // Meta data is accessed more often, so only evict it when the task is
+            // completely cold.
+            let meta_evicted = !task.persistance_state().is_restored(TaskDataCategory::Data)
+                && evict_task_data(task, TaskDataCategory::Meta);
+            if data_evicted || meta_evicted {
+                evicted += 1;
+                if evicted % EVICTION_BATCH_SIZE == 0 && TurboMalloc::memory_usage() < target {
+                    return ControlFlow::Break(());
+                }
+            }
+            ControlFlow::Continue(())
+        });
+        self.evicted_tasks
+            .fetch_add(evicted as u64, Ordering::Relaxed);
+    }
+
     fn startup(&self, turbo_tasks: &dyn TurboTasksBackendApi<TurboTasksBackend<B>>) {
         // Continue all uncompleted operations
         // They can't be interrupted by a snapshot since the snapshotting job has not been scheduled
@@ -779,7 +857,11 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
         task_id: TaskId,
         turbo_tasks: &dyn TurboTasksBackendApi<TurboTasksBackend<B>>,
     ) {
-        operation::InvalidateOperation::run(smallvec![task_id], self.execute_context(turbo_tasks));
+        operation::InvalidateOperation::run(
+            smallvec![task_id],
+            None,
+            self.execute_context(turbo_tasks),
+        );
     }
 
     fn invalidate_tasks(
@@ -789,6 +871,7 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
     ) {
         operation::InvalidateOperation::run(
             tasks.iter().copied().collect(),
+            None,
             self.execute_context(turbo_tasks),
         );
     }
@@ -800,6 +883,7 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
     ) {
         operation::InvalidateOperation::run(
             tasks.iter().copied().collect(),
+            None,
             self.execute_context(turbo_tasks),
         );
     }
@@ -822,6 +906,48 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
         task_type.to_string()
     }
 
+    fn task_snapshot(
+        &self,
+        task_id: TaskId,
+        turbo_tasks: &dyn TurboTasksBackendApi<TurboTasksBackend<B>>,
+    ) -> TaskSnapshot {
+        let mut ctx = self.execute_context(turbo_tasks);
+        let task = ctx.task(task_id, TaskDataCategory::All);
+        TaskSnapshot {
+            children: get_many!(task, Child { task } => *task),
+            output_dependencies: get_many!(task, OutputDependency { target } => *target),
+            cell_dependencies: get_many!(
+                task,
+                CellDependency { target } => (target.task, target.cell)
+            ),
+            collectibles_dependencies: get_many!(
+                task,
+                CollectiblesDependency { target } => (target.task, target.collectible_type)
+            ),
+            cells: get_many!(task, CellData { cell } => *cell),
+            collectibles: get_many!(
+                task,
+                Collectible { collectible } count => (
+                    collectible.collectible_type,
+                    RawVc::TaskCell(collectible.cell.task, collectible.cell.cell),
+                    *count
+                )
+            ),
+        }
+    }
+
+    fn task_meta_snapshot(
+        &self,
+        task_id: TaskId,
+        turbo_tasks: &dyn TurboTasksBackendApi<TurboTasksBackend<B>>,
+    ) -> TaskMetaSnapshot {
+        let mut ctx = self.execute_context(turbo_tasks);
+        let task = ctx.task(task_id, TaskDataCategory::Meta);
+        TaskMetaSnapshot {
+            uppers: get_uppers(&task),
+        }
+    }
+
     fn try_get_function_id(&self, task_id: TaskId) -> Option<FunctionId> {
         self.lookup_task_type(task_id)
             .and_then(|task_type| match &*task_type {
@@ -844,7 +970,7 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
         } else if let Some(task_type) = self.transient_tasks.get(&task_id) {
             (
                 TaskType::Transient(task_type.clone()),
-                matches!(**task_type, TransientTask::Once(_)),
+                matches!(**task_type, TransientTask::Once(..)),
             )
         } else {
             return None;
@@ -1045,8 +1171,8 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
                 let task_type = task_type.clone();
                 let span = tracing::trace_span!("turbo_tasks::root_task");
                 let future = match &*task_type {
-                    TransientTask::Root(f) => f(),
-                    TransientTask::Once(future_mutex) => take(&mut *future_mutex.lock())?,
+                    TransientTask::Root(f, _) => f(),
+                    TransientTask::Once(future_mutex, _) => take(&mut *future_mutex.lock())?,
                 };
                 (span, future)
             }
@@ -1362,6 +1488,10 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
                     let snapshot = turbo_tasks::spawn_blocking(move || this.snapshot()).await;
                     if let Some((snapshot_start, new_data)) = snapshot {
                         last_snapshot = snapshot_start;
+                        if self.memory_limit != usize::MAX {
+                            let this = self.clone();
+                            turbo_tasks::spawn_blocking(move || this.evict_cold_tasks()).await;
+                        }
                         if new_data {
                             continue;
                         }
@@ -1555,7 +1685,11 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
         ConnectChildOperation::run(parent_task, task, self.execute_context(turbo_tasks));
     }
 
-    fn create_transient_task(&self, task_type: TransientTaskType) -> TaskId {
+    fn create_transient_task(
+        &self,
+        task_type: TransientTaskType,
+        priority: TaskPriority,
+    ) -> TaskId {
         let task_id = self.transient_task_id_factory.get();
         let root_type = match task_type {
             TransientTaskType::Root(_) => ActiveType::RootTask,
@@ -1564,8 +1698,8 @@ impl<B: BackingStorage> TurboTasksBackendInner<B> {
         self.transient_tasks.insert(
             task_id,
             Arc::new(match task_type {
-                TransientTaskType::Root(f) => TransientTask::Root(f),
-                TransientTaskType::Once(f) => TransientTask::Once(Mutex::new(Some(f))),
+                TransientTaskType::Root(f) => TransientTask::Root(f, priority),
+                TransientTaskType::Once(f) => TransientTask::Once(Mutex::new(Some(f)), priority),
             }),
         );
         {
@@ -1655,6 +1789,22 @@ impl<B: BackingStorage> Backend for TurboTasksBackend<B> {
         self.0.get_task_description(task)
     }
 
+    fn task_snapshot(
+        &self,
+        task_id: TaskId,
+        turbo_tasks: &dyn TurboTasksBackendApi<Self>,
+    ) -> Option<TaskSnapshot> {
+        Some(self.0.task_snapshot(task_id, turbo_tasks))
+    }
+
+    fn task_meta_snapshot(
+        &self,
+        task_id: TaskId,
+        turbo_tasks: &dyn TurboTasksBackendApi<Self>,
+    ) -> Option<TaskMetaSnapshot> {
+        Some(self.0.task_meta_snapshot(task_id, turbo_tasks))
+    }
+
     fn try_get_function_id(&self, task_id: TaskId) -> Option<FunctionId> {
         self.0.try_get_function_id(task_id)
     }
@@ -1821,9 +1971,19 @@ impl<B: BackingStorage> Backend for TurboTasksBackend<B> {
     fn create_transient_task(
         &self,
         task_type: TransientTaskType,
+        priority: TaskPriority,
         _turbo_tasks: &dyn TurboTasksBackendApi<Self>,
     ) -> TaskId {
-        self.0.create_transient_task(task_type)
+        self.0.create_transient_task(task_type, priority)
+    }
+
+    fn transient_task_priority(&self, task: TaskId) -> Option<TaskPriority> {
+        if !task.is_transient() {
+            return None;
+        }
+        match &**self.0.transient_tasks.get(&task)? {
+            TransientTask::Root(_, priority) | TransientTask::Once(_, priority) => Some(*priority),
+        }
     }
 
     fn dispose_root_task(&self, _: TaskId, _: &dyn TurboTasksBackendApi<Self>) {
@@ -1839,3 +1999,21 @@ fn far_future() -> Instant {
     // 1000 years overflows on macOS, 100 years overflows on FreeBSD.
     Instant::now() + Duration::from_secs(86400 * 365 * 30)
 }
+
+/// Removes all items of the `category` from the task, when all of them are
+/// persisted. Returns true when the data was evicted.
+fn evict_task_data(task: &mut InnerStorage<CachedDataItem>, category: TaskDataCategory) -> bool {
+    if !task.persistance_state().is_restored(category) {
+        return false;
+    }
+    let evictable = task
+        .iter_all()
+        .filter(|(key, _)| key.category() == category)
+        .all(|(key, value)| key.is_persistent() && value.is_persistent());
+    if !evictable {
+        return false;
+    }
+    task.retain(|key, _| key.category() != category);
+    task.persistance_state_mut().set_unrestored(category);
+    true
+}
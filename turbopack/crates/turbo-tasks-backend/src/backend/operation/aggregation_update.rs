@@ -103,6 +103,8 @@ pub enum AggregationUpdateJob {
     },
     Invalidate {
         task_ids: SmallVec<[TaskId; 4]>,
+        /// The task whose collectibles changed.
+        cause: TaskId,
     },
     BalanceEdge {
         upper_id: TaskId,
@@ -286,6 +288,7 @@ impl AggregatedDataUpdate {
                 if !dependent.is_empty() {
                     queue.push(AggregationUpdateJob::Invalidate {
                         task_ids: dependent,
+                        cause: task.id(),
                     })
                 }
             }
@@ -556,9 +559,9 @@ impl AggregationUpdateQueue {
                 AggregationUpdateJob::AggregatedDataUpdate { upper_ids, update } => {
                     self.aggregated_data_update(upper_ids, ctx, update);
                 }
-                AggregationUpdateJob::Invalidate { task_ids } => {
+                AggregationUpdateJob::Invalidate { task_ids, cause } => {
                     for task_id in task_ids {
-                        make_task_dirty(task_id, self, ctx);
+                        make_task_dirty(task_id, Some(cause), self, ctx);
                     }
                 }
             }
@@ -177,8 +177,8 @@ impl Operation for CleanupOldEdgesOperation {
                                     });
                                 }
                             }
-                            OutdatedEdge::RemovedCellDependent(task_id) => {
-                                make_task_dirty(task_id, queue, ctx);
+                            OutdatedEdge::RemovedCellDependent(dependent_task_id) => {
+                                make_task_dirty(dependent_task_id, Some(task_id), queue, ctx);
                             }
                         }
                     }
@@ -22,6 +22,8 @@ pub enum InvalidateOperation {
     // TODO DetermineActiveness
     MakeDirty {
         task_ids: SmallVec<[TaskId; 4]>,
+        /// The task whose change caused the invalidation, if any.
+        cause: Option<TaskId>,
     },
     AggregationUpdate {
         queue: AggregationUpdateQueue,
@@ -31,8 +33,12 @@ pub enum InvalidateOperation {
 }
 
 impl InvalidateOperation {
-    pub fn run(task_ids: SmallVec<[TaskId; 4]>, mut ctx: impl ExecuteContext) {
-        InvalidateOperation::MakeDirty { task_ids }.execute(&mut ctx)
+    pub fn run(
+        task_ids: SmallVec<[TaskId; 4]>,
+        cause: Option<TaskId>,
+        mut ctx: impl ExecuteContext,
+    ) {
+        InvalidateOperation::MakeDirty { task_ids, cause }.execute(&mut ctx)
     }
 }
 
@@ -41,10 +47,10 @@ impl Operation for InvalidateOperation {
         loop {
             ctx.operation_suspend_point(&self);
             match self {
-                InvalidateOperation::MakeDirty { task_ids } => {
+                InvalidateOperation::MakeDirty { task_ids, cause } => {
                     let mut queue = AggregationUpdateQueue::new();
                     for task_id in task_ids {
-                        make_task_dirty(task_id, &mut queue, ctx);
+                        make_task_dirty(task_id, cause, &mut queue, ctx);
                     }
                     if queue.is_empty() {
                         self = InvalidateOperation::Done
@@ -66,8 +72,11 @@ impl Operation for InvalidateOperation {
     }
 }
 
+/// Marks `task_id` as dirty. `cause` is the task whose change made it dirty,
+/// it's reported to the invalidation recorder of turbo-tasks.
 pub fn make_task_dirty(
     task_id: TaskId,
+    cause: Option<TaskId>,
     queue: &mut AggregationUpdateQueue,
     ctx: &mut impl ExecuteContext,
 ) {
@@ -75,6 +84,10 @@ pub fn make_task_dirty(
         return;
     }
 
+    if let Some(cause) = cause {
+        ctx.record_invalidation(cause, task_id);
+    }
+
     let mut task = ctx.task(task_id, TaskDataCategory::All);
 
     make_task_dirty_internal(&mut task, task_id, true, queue, ctx);
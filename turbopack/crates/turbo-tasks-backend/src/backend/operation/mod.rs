@@ -73,6 +73,8 @@ pub trait ExecuteContext<'e>: Sized {
         category: TaskDataCategory,
     ) -> (impl TaskGuard + 'e, impl TaskGuard + 'e);
     fn schedule(&self, task_id: TaskId);
+    /// Reports that `task_id` was made dirty because of a change in `cause`.
+    fn record_invalidation(&self, cause: TaskId, task_id: TaskId);
     fn operation_suspend_point<T>(&mut self, op: &T)
     where
         T: Clone + Into<AnyOperation>;
@@ -199,6 +201,8 @@ where
                 }
             }
         }
+        task.persistance_state_mut()
+            .set_last_access(self.backend.eviction_generation());
         TaskGuardImpl {
             task,
             task_id,
@@ -211,7 +215,7 @@ where
             return false;
         }
         if let Some(ty) = self.backend.transient_tasks.get(&task_id) {
-            matches!(**ty, TransientTask::Once(_))
+            matches!(**ty, TransientTask::Once(..))
         } else {
             false
         }
@@ -252,6 +256,9 @@ where
                 }
             }
         }
+        let generation = self.backend.eviction_generation();
+        task1.persistance_state_mut().set_last_access(generation);
+        task2.persistance_state_mut().set_last_access(generation);
         (
             TaskGuardImpl {
                 task: task1,
@@ -270,6 +277,11 @@ where
         self.turbo_tasks.schedule(task_id);
     }
 
+    fn record_invalidation(&self, cause: TaskId, task_id: TaskId) {
+        self.turbo_tasks
+            .record_dependent_invalidation(cause, &[task_id]);
+    }
+
     fn operation_suspend_point<T: Clone + Into<AnyOperation>>(&mut self, op: &T) {
         if self.parent.is_some() {
             self.backend.operation_suspend_point(|| {
@@ -650,7 +662,8 @@ impl_operation!(AggregationUpdate aggregation_update::AggregationUpdateQueue);
 
 pub use self::{
     aggregation_update::{
-        get_aggregation_number, is_root_node, AggregatedDataUpdate, AggregationUpdateJob,
+        get_aggregation_number, get_uppers, is_root_node, AggregatedDataUpdate,
+        AggregationUpdateJob,
     },
     cleanup_old_edges::OutdatedEdge,
     update_cell::UpdateCellOperation,
@@ -48,6 +48,6 @@ impl UpdateCellOperation {
         drop(task);
         drop(old_content);
 
-        InvalidateOperation::run(dependent, ctx);
+        InvalidateOperation::run(dependent, Some(task_id), ctx);
     }
 }
@@ -22,6 +22,7 @@ use crate::{
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub enum UpdateOutputOperation {
     MakeDependentTasksDirty {
+        task_id: TaskId,
         dependent_tasks: Vec<TaskId>,
         children: Vec<TaskId>,
         queue: AggregationUpdateQueue,
@@ -115,6 +116,7 @@ impl UpdateOutputOperation {
         drop(old_error);
 
         UpdateOutputOperation::MakeDependentTasksDirty {
+            task_id,
             dependent_tasks,
             children,
             queue,
@@ -129,12 +131,13 @@ impl Operation for UpdateOutputOperation {
             ctx.operation_suspend_point(&self);
             match self {
                 UpdateOutputOperation::MakeDependentTasksDirty {
+                    task_id,
                     ref mut dependent_tasks,
                     ref mut children,
                     ref mut queue,
                 } => {
                     if let Some(dependent_task_id) = dependent_tasks.pop() {
-                        make_task_dirty(dependent_task_id, queue, ctx);
+                        make_task_dirty(dependent_task_id, Some(task_id), queue, ctx);
                     }
                     if dependent_tasks.is_empty() {
                         self = UpdateOutputOperation::EnsureUnfinishedChildrenDirty {
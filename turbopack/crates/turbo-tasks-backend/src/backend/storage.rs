@@ -1,7 +1,7 @@
 use std::{
     hash::{BuildHasherDefault, Hash},
     mem::take,
-    ops::{Deref, DerefMut},
+    ops::{ControlFlow, Deref, DerefMut},
     thread::available_parallelism,
 };
 
@@ -18,6 +18,7 @@ use crate::{
 
 const META_UNRESTORED: u32 = 1 << 31;
 const DATA_UNRESTORED: u32 = 1 << 30;
+const PERSISTING_ITEMS_MASK: u32 = DATA_UNRESTORED - 1;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TaskDataCategory {
@@ -80,13 +81,18 @@ impl Iterator for TaskDataCategoryIterator {
 }
 
 pub struct PersistanceState {
+    /// The unrestored flags and the number of items that are not persisted
+    /// yet in the lower bits.
     value: u32,
+    /// The eviction generation in which the task was accessed the last time.
+    last_access: u32,
 }
 
 impl Default for PersistanceState {
     fn default() -> Self {
         Self {
             value: META_UNRESTORED | DATA_UNRESTORED,
+            last_access: 0,
         }
     }
 }
@@ -96,26 +102,65 @@ impl PersistanceState {
         self.value &= !category.flag();
     }
 
+    /// Marks the data as evicted. It will be restored from the backing
+    /// storage on the next access.
+    pub fn set_unrestored(&mut self, category: TaskDataCategory) {
+        self.value |= category.flag();
+    }
+
     pub fn add_persisting_item(&mut self) {
-        // TODO add when we need to track unpersisted items
-        // self.value += 1;
+        self.add_persisting_items(1);
     }
 
-    pub fn add_persisting_items(&mut self, _count: u32) {
-        // TODO add when we need to track unpersisted items
-        // self.value += count;
+    pub fn add_persisting_items(&mut self, count: u32) {
+        let persisting = self.value & PERSISTING_ITEMS_MASK;
+        // The counter must never overflow into the unrestored flags. A
+        // saturated counter keeps the task from being evicted, which is safe.
+        self.set_persisting_items(persisting.saturating_add(count).min(PERSISTING_ITEMS_MASK));
     }
 
-    // TODO remove when we need to track unpersisted items
-    #[allow(dead_code)]
-    pub fn finish_persisting_items(&mut self, _count: u32) {
-        // TODO add when we need to track unpersisted items
-        // self.value -= count;
+    pub fn finish_persisting_items(&mut self, count: u32) {
+        let persisting = self.value & PERSISTING_ITEMS_MASK;
+        if persisting == PERSISTING_ITEMS_MASK {
+            // The counter saturated, so it doesn't know how many items are
+            // still unpersisted.
+            return;
+        }
+        debug_assert!(
+            persisting >= count,
+            "more items were persisted ({count}) than tracked ({persisting})"
+        );
+        // When more items were persisted than tracked, the counter is off and
+        // it's unknown whether all items were persisted. Saturating it keeps
+        // the task from being evicted instead of dropping data that might not
+        // be stored yet, and it must not underflow into the unrestored flags.
+        let persisting = persisting
+            .checked_sub(count)
+            .unwrap_or(PERSISTING_ITEMS_MASK);
+        self.set_persisting_items(persisting);
+    }
+
+    fn set_persisting_items(&mut self, count: u32) {
+        self.value = (self.value & !PERSISTING_ITEMS_MASK) | count;
     }
 
     pub fn is_restored(&self, category: TaskDataCategory) -> bool {
         (self.value & category.flag()) == 0
     }
+
+    /// Returns true when all persistent items of the task are stored in the
+    /// backing storage.
+    pub fn is_fully_persisted(&self) -> bool {
+        (self.value & PERSISTING_ITEMS_MASK) == 0
+    }
+
+    pub fn set_last_access(&mut self, generation: u32) {
+        self.last_access = generation;
+    }
+
+    pub fn last_access(&self) -> u32 {
+        self.last_access
+    }
 }
 
 const INDEX_THRESHOLD: usize = 1024;
@@ -279,6 +324,25 @@ where
             }
         }
     }
+
+    /// Removes all items for which `f` returns false and releases the memory
+    /// of emptied maps.
+    pub fn retain(&mut self, mut f: impl FnMut(&T::Key, &T::Value) -> bool) {
+        match self {
+            InnerStorage::Plain { map, .. } => {
+                map.retain(|key, value| f(key, value));
+                map.shrink_to_fit();
+            }
+            InnerStorage::Indexed { map, .. } => {
+                map.retain(|_, inner| {
+                    inner.retain(|key, value| f(key, value));
+                    inner.shrink_to_fit();
+                    !inner.is_empty()
+                });
+                map.shrink_to_fit();
+            }
+        }
+    }
 }
 
 impl<T: KeyValuePair> InnerStorage<T>
@@ -341,6 +405,27 @@ where
         }
     }
 
+    /// Calls `f` for every stored task until it returns
+    /// [`ControlFlow::Break`]. Only the task passed to `f` is locked while `f`
+    /// is running, so other tasks can be accessed during the walk. Tasks
+    /// added during the walk are skipped.
+    pub fn for_each_mut(&self, mut f: impl FnMut(&K, &mut InnerStorage<T>) -> ControlFlow<()>) {
+        let keys = self
+            .map
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect::<Vec<_>>();
+        for key in keys {
+            let Some(mut entry) = self.map.get_mut(&key) else {
+                continue;
+            };
+            let (key, inner) = entry.pair_mut();
+            if f(key, inner).is_break() {
+                return;
+            }
+        }
+    }
+
     pub fn access_pair_mut(
         &self,
         key1: K,
@@ -617,6 +617,7 @@ pub mod indicies {
     pub const OutputDependency: CachedDataItemIndex = CachedDataItemIndex::Dependencies;
     pub const CellDependency: CachedDataItemIndex = CachedDataItemIndex::Dependencies;
     pub const CollectibleDependency: CachedDataItemIndex = CachedDataItemIndex::Dependencies;
+    pub const CollectiblesDependency: CachedDataItemIndex = CachedDataItemIndex::Dependencies;
     pub const OutdatedOutputDependency: CachedDataItemIndex = CachedDataItemIndex::Dependencies;
     pub const OutdatedCellDependency: CachedDataItemIndex = CachedDataItemIndex::Dependencies;
     pub const OutdatedCollectiblesDependency: CachedDataItemIndex =
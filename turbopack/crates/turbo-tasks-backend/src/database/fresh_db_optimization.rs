@@ -56,8 +56,8 @@ impl<T: KeyValueDatabase> KeyValueDatabase for FreshDbOptimization<T> {
     ) -> anyhow::Result<Option<Self::ValueBuffer<'l>>> {
         if self.fresh_db.load(Ordering::Acquire) {
             // Performance optimization when the database was empty
-            // It's assumed that no cache entries are removed from the memory cache, but we
-            // might change that in future.
+            // Cache entries are only removed from the memory cache after they have been
+            // persisted, which clears this flag.
             return Ok(None);
         }
         self.database.get(transaction, key_space, key)
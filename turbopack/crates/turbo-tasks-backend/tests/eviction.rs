@@ -0,0 +1,71 @@
+#![feature(arbitrary_self_types)]
+#![feature(arbitrary_self_types_pointers)]
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+use anyhow::Result;
+use turbo_tasks::{TurboTasks, Vc};
+use turbo_tasks_backend::{default_backing_storage, TurboTasksBackend};
+use turbo_tasks_testing::{register, Registration};
+
+static REGISTRATION: Registration = register!();
+
+static EXECUTIONS: AtomicU32 = AtomicU32::new(0);
+
+#[tokio::test]
+async fn evicted_tasks_are_restored() {
+    REGISTRATION.ensure_registered();
+    let path = PathBuf::from(concat!(
+        env!("OUT_DIR"),
+        "/.cache/evicted_tasks_are_restored"
+    ));
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).unwrap();
+    // With a memory limit of 0 every snapshot evicts all cold tasks.
+    let tt = TurboTasks::new(TurboTasksBackend::new_with_memory_limit(
+        default_backing_storage(&path).unwrap(),
+        0,
+    ));
+
+    let sum = tt.run_once(async { Ok(*sum_of_squares(10).await?) });
+    assert_eq!(sum.await.unwrap(), 285);
+    assert_eq!(EXECUTIONS.load(Ordering::SeqCst), 10);
+
+    // A task is evicted when it wasn't accessed since the previous eviction,
+    // so this takes two snapshots.
+    tokio::time::timeout(Duration::from_secs(120), async {
+        while tt.backend().evicted_task_count() == 0 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("tasks should be evicted");
+
+    // The evicted tasks are restored from the backing storage instead of
+    // being executed again.
+    let sum = tt.run_once(async { Ok(*sum_of_squares(10).await?) });
+    assert_eq!(sum.await.unwrap(), 285);
+    assert_eq!(EXECUTIONS.load(Ordering::SeqCst), 10);
+
+    tt.stop_and_wait().await;
+}
+
+#[turbo_tasks::function]
+async fn sum_of_squares(n: u32) -> Result<Vc<u32>> {
+    let mut sum = 0;
+    for i in 0..n {
+        sum += *square(i).await?;
+    }
+    Ok(Vc::cell(sum))
+}
+
+#[turbo_tasks::function]
+fn square(n: u32) -> Vc<u32> {
+    EXECUTIONS.fetch_add(1, Ordering::SeqCst);
+    Vc::cell(n * n)
+}
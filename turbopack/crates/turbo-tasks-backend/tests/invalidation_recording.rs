@@ -0,0 +1 @@
+../../turbo-tasks-testing/tests/invalidation_recording.rs
\ No newline at end of file
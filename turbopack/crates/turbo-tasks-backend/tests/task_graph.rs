@@ -0,0 +1 @@
+../../turbo-tasks-testing/tests/task_graph.rs
\ No newline at end of file
@@ -15,6 +15,7 @@ pub mod glob;
 mod invalidation;
 mod invalidator_map;
 pub mod json;
+mod memory_fs;
 mod mutex_map;
 mod read_glob;
 mod retry;
@@ -23,6 +24,7 @@ pub mod source_context;
 pub mod util;
 pub(crate) mod virtual_fs;
 mod watcher;
+mod zip_fs;
 
 use std::{
     borrow::Cow,
@@ -45,6 +47,7 @@ use glob::Glob;
 use invalidation::InvalidateFilesystem;
 use invalidator_map::InvalidatorMap;
 use jsonc_parser::{parse_to_serde_value, ParseOptions};
+pub use memory_fs::{MemoryFileSystem, MemoryFiles};
 use mime::Mime;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use read_glob::read_glob;
@@ -67,6 +70,7 @@ use turbo_tasks_hash::{
 use util::{extract_disk_access, join_path, normalize_path, sys_to_unix, unix_to_sys};
 pub use virtual_fs::VirtualFileSystem;
 use watcher::DiskWatcher;
+pub use zip_fs::ZipFileSystem;
 
 use self::{invalidation::Write, json::UnparseableJson, mutex_map::MutexMap};
 use crate::{
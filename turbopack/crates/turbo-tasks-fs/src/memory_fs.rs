@@ -0,0 +1,254 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{bail, Result};
+use auto_hash_map::AutoMap;
+use turbo_tasks::{mark_session_dependent, Completion, Invalidator, RcStr, ValueToString, Vc};
+
+use crate::{
+    invalidation::Write, invalidator_map::InvalidatorMap, DirectoryContent, DirectoryEntry, File,
+    FileContent, FileMeta, FileSystem, FileSystemPath, LinkContent,
+};
+
+/// The contents of a [MemoryFileSystem].
+///
+/// This is a cheap to clone handle that can be edited from outside of
+/// turbo-tasks, e. g. by a test harness. Edits invalidate exactly the tasks
+/// that read the edited file or list the affected directories.
+#[derive(Clone, Default)]
+pub struct MemoryFiles {
+    inner: Arc<MemoryFilesInner>,
+}
+
+#[derive(Default)]
+struct MemoryFilesInner {
+    files: Mutex<BTreeMap<RcStr, File>>,
+    invalidator_map: InvalidatorMap,
+    dir_invalidator_map: InvalidatorMap,
+}
+
+impl MemoryFiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates or replaces the file at `path`. Paths are relative to the root
+    /// of the filesystem and use `/` as separator.
+    pub fn write(&self, path: &str, content: impl Into<File>) {
+        let content = content.into();
+        let created = {
+            let mut files = self.inner.files.lock().unwrap();
+            if files
+                .get(path)
+                .is_some_and(|old| old.content() == content.content())
+            {
+                return;
+            }
+            files.insert(path.into(), content).is_none()
+        };
+        self.invalidate(path, created);
+    }
+
+    /// Removes the file at `path`. Does nothing when it doesn't exist.
+    pub fn remove(&self, path: &str) {
+        let removed = self.inner.files.lock().unwrap().remove(path).is_some();
+        if removed {
+            self.invalidate(path, true);
+        }
+    }
+
+    /// Invalidates the readers of `path` and, when a file was created or
+    /// removed, the readers of all its parent directories.
+    fn invalidate(&self, path: &str, structural: bool) {
+        let mut invalidators = self
+            .inner
+            .invalidator_map
+            .lock()
+            .unwrap()
+            .remove(path)
+            .unwrap_or_default();
+        if structural {
+            let mut dir_invalidator_map = self.inner.dir_invalidator_map.lock().unwrap();
+            for dir in parent_dirs(path) {
+                invalidators.extend(dir_invalidator_map.remove(dir).unwrap_or_default());
+            }
+        }
+        invalidate_all(path, invalidators);
+    }
+
+    fn read(&self, path: &str) -> FileContent {
+        self.inner
+            .invalidator_map
+            .insert(path.to_string(), current_invalidator());
+        match self.inner.files.lock().unwrap().get(path) {
+            Some(file) => FileContent::Content(file.clone()),
+            None => FileContent::NotFound,
+        }
+    }
+
+    /// Returns the names of the direct children of the directory at `path`
+    /// and whether they are directories, or `None` when the directory
+    /// doesn't exist.
+    fn read_dir(&self, path: &str) -> Option<BTreeMap<RcStr, bool>> {
+        self.inner
+            .dir_invalidator_map
+            .insert(path.to_string(), current_invalidator());
+        let files = self.inner.files.lock().unwrap();
+        let mut entries = BTreeMap::new();
+        for file in files.keys() {
+            let rest = if path.is_empty() {
+                Some(&**file)
+            } else {
+                file.strip_prefix(path)
+                    .and_then(|rest| rest.strip_prefix('/'))
+            };
+            let Some(rest) = rest else {
+                continue;
+            };
+            match rest.split_once('/') {
+                Some((dir, _)) => entries.insert(dir.into(), true),
+                None => entries.insert(rest.into(), false),
+            };
+        }
+        if entries.is_empty() && !path.is_empty() {
+            return None;
+        }
+        Some(entries)
+    }
+}
+
+fn current_invalidator() -> Invalidator {
+    mark_session_dependent();
+    turbo_tasks::get_invalidator()
+}
+
+fn parent_dirs(path: &str) -> impl Iterator<Item = &str> {
+    path.rmatch_indices('/')
+        .map(|(i, _)| &path[..i])
+        .chain(std::iter::once(""))
+}
+
+fn invalidate_all(path: &str, invalidators: HashSet<Invalidator>) {
+    for invalidator in invalidators {
+        invalidator.invalidate_with_reason(Write {
+            path: path.to_string(),
+        });
+    }
+}
+
+/// A filesystem that keeps all files in memory.
+///
+/// Its contents are shared with a [MemoryFiles] handle, which allows to edit
+/// files between updates without touching the disk, e. g. to test incremental
+/// behavior.
+#[turbo_tasks::value(serialization = "none", cell = "new", eq = "manual")]
+pub struct MemoryFileSystem {
+    name: RcStr,
+    #[turbo_tasks(debug_ignore, trace_ignore)]
+    files: MemoryFiles,
+}
+
+impl MemoryFileSystem {
+    /// Creates a new [`Vc<MemoryFileSystem>`] backed by `files`.
+    ///
+    /// NOTE: Like [`VirtualFileSystem::new`][crate::VirtualFileSystem::new]
+    /// this is not a `turbo_tasks::function`, so every call creates a
+    /// distinct filesystem.
+    pub fn new(name: RcStr, files: MemoryFiles) -> Vc<Self> {
+        MemoryFileSystem { name, files }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl FileSystem for MemoryFileSystem {
+    #[turbo_tasks::function]
+    async fn read(&self, fs_path: Vc<FileSystemPath>) -> Result<Vc<FileContent>> {
+        Ok(self.files.read(&fs_path.await?.path).cell())
+    }
+
+    #[turbo_tasks::function]
+    fn read_link(&self, _fs_path: Vc<FileSystemPath>) -> Vc<LinkContent> {
+        LinkContent::NotFound.cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn read_dir(&self, fs_path: Vc<FileSystemPath>) -> Result<Vc<DirectoryContent>> {
+        let Some(entries) = self.files.read_dir(&fs_path.await?.path) else {
+            return Ok(DirectoryContent::not_found());
+        };
+        let mut converted_entries = AutoMap::new();
+        for (name, is_dir) in entries {
+            let entry_path = fs_path.join(name.clone()).to_resolved().await?;
+            converted_entries.insert(
+                name,
+                if is_dir {
+                    DirectoryEntry::Directory(entry_path)
+                } else {
+                    DirectoryEntry::File(entry_path)
+                },
+            );
+        }
+        Ok(DirectoryContent::new(converted_entries))
+    }
+
+    #[turbo_tasks::function]
+    async fn track(&self, fs_path: Vc<FileSystemPath>) -> Result<Vc<Completion>> {
+        self.files.read(&fs_path.await?.path);
+        Ok(Completion::new())
+    }
+
+    #[turbo_tasks::function]
+    async fn write(
+        &self,
+        fs_path: Vc<FileSystemPath>,
+        content: Vc<FileContent>,
+    ) -> Result<Vc<Completion>> {
+        let path = &fs_path.await?.path;
+        match &*content.await? {
+            FileContent::Content(file) => self.files.write(path, file.clone()),
+            FileContent::NotFound => self.files.remove(path),
+        }
+        Ok(Completion::new())
+    }
+
+    #[turbo_tasks::function]
+    fn write_link(
+        &self,
+        _fs_path: Vc<FileSystemPath>,
+        _target: Vc<LinkContent>,
+    ) -> Result<Vc<Completion>> {
+        bail!("Links are not supported by the memory filesystem")
+    }
+
+    #[turbo_tasks::function]
+    async fn metadata(&self, fs_path: Vc<FileSystemPath>) -> Result<Vc<FileMeta>> {
+        match self.files.read(&fs_path.await?.path) {
+            FileContent::Content(file) => Ok(file.meta().clone().cell()),
+            FileContent::NotFound => bail!("path not found, can't read metadata"),
+        }
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ValueToString for MemoryFileSystem {
+    #[turbo_tasks::function]
+    fn to_string(&self) -> Vc<RcStr> {
+        Vc::cell(self.name.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parent_dirs;
+
+    #[test]
+    fn parent_dirs_of_nested_path() {
+        assert_eq!(
+            parent_dirs("a/b/c.js").collect::<Vec<_>>(),
+            vec!["a/b", "a", ""]
+        );
+        assert_eq!(parent_dirs("c.js").collect::<Vec<_>>(), vec![""]);
+    }
+}
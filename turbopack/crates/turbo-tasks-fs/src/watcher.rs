@@ -18,7 +18,7 @@ use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tracing::instrument;
-use turbo_tasks::{spawn_thread, Invalidator, RcStr};
+use turbo_tasks::{spawn_thread, with_task_priority, Invalidator, RcStr, TaskPriority};
 
 use crate::{
     format_absolute_fs_path,
@@ -417,32 +417,37 @@ impl DiskWatcher {
             }
 
             let _lock = invalidation_lock.blocking_write();
-            {
-                let mut invalidator_map = invalidator_map.lock().unwrap();
-                invalidate_path(
-                    &report_invalidation_reason,
-                    &mut invalidator_map,
-                    batched_invalidate_path.drain(),
-                );
-                invalidate_path_and_children_execute(
-                    &report_invalidation_reason,
-                    &mut invalidator_map,
-                    batched_invalidate_path_and_children.drain(),
-                );
-            }
-            {
-                let mut dir_invalidator_map = dir_invalidator_map.lock().unwrap();
-                invalidate_path(
-                    &report_invalidation_reason,
-                    &mut dir_invalidator_map,
-                    batched_invalidate_path_dir.drain(),
-                );
-                invalidate_path_and_children_execute(
-                    &report_invalidation_reason,
-                    &mut dir_invalidator_map,
-                    batched_invalidate_path_and_children_dir.drain(),
-                );
-            }
+            // Recomputations caused by file changes are only needed for HMR
+            // updates. They are executed with a higher priority when a request
+            // reads them.
+            with_task_priority(TaskPriority::Hmr, || {
+                {
+                    let mut invalidator_map = invalidator_map.lock().unwrap();
+                    invalidate_path(
+                        &report_invalidation_reason,
+                        &mut invalidator_map,
+                        batched_invalidate_path.drain(),
+                    );
+                    invalidate_path_and_children_execute(
+                        &report_invalidation_reason,
+                        &mut invalidator_map,
+                        batched_invalidate_path_and_children.drain(),
+                    );
+                }
+                {
+                    let mut dir_invalidator_map = dir_invalidator_map.lock().unwrap();
+                    invalidate_path(
+                        &report_invalidation_reason,
+                        &mut dir_invalidator_map,
+                        batched_invalidate_path_dir.drain(),
+                    );
+                    invalidate_path_and_children_execute(
+                        &report_invalidation_reason,
+                        &mut dir_invalidator_map,
+                        batched_invalidate_path_and_children_dir.drain(),
+                    );
+                }
+            });
         }
     }
 }
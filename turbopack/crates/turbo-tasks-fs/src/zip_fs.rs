@@ -0,0 +1,425 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Read,
+    sync::Arc,
+};
+
+use anyhow::{bail, Context, Result};
+use auto_hash_map::AutoMap;
+use flate2::read::DeflateDecoder;
+use turbo_tasks::{Completion, RcStr, ValueToString, Vc};
+
+use crate::{
+    DirectoryContent, DirectoryEntry, File, FileContent, FileMeta, FileSystem, FileSystemPath,
+    LinkContent,
+};
+
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+const CENTRAL_DIRECTORY_HEADER_SIGNATURE: u32 = 0x02014b50;
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x04034b50;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+
+/// A read-only filesystem with the contents of a zip archive, e. g. a package
+/// in the cache of Yarn Plug'n'Play.
+///
+/// Only stored and deflated entries are supported. Zip64 archives and symlinks
+/// in archives aren't supported.
+#[turbo_tasks::value]
+pub struct ZipFileSystem {
+    /// The zip archive.
+    archive: Vc<FileSystemPath>,
+    /// The path the contents of the archive are available at. This is usually
+    /// the archive itself, but can differ when the same archive is mounted at
+    /// multiple paths, e. g. for virtual packages of Yarn Plug'n'Play.
+    mount_path: Vc<FileSystemPath>,
+}
+
+#[turbo_tasks::value_impl]
+impl ZipFileSystem {
+    /// Creates a filesystem with the contents of `archive`, mounted at the
+    /// archive itself.
+    #[turbo_tasks::function]
+    pub fn new(archive: Vc<FileSystemPath>) -> Vc<Self> {
+        Self::new_mounted(archive, archive)
+    }
+
+    /// Creates a filesystem with the contents of `archive`, mounted at
+    /// `mount_path`.
+    #[turbo_tasks::function]
+    pub fn new_mounted(archive: Vc<FileSystemPath>, mount_path: Vc<FileSystemPath>) -> Vc<Self> {
+        ZipFileSystem {
+            archive,
+            mount_path,
+        }
+        .cell()
+    }
+
+    /// The zip archive.
+    #[turbo_tasks::function]
+    pub fn archive(&self) -> Vc<FileSystemPath> {
+        self.archive
+    }
+
+    /// The path the contents of the archive are available at.
+    #[turbo_tasks::function]
+    pub fn mount_path(&self) -> Vc<FileSystemPath> {
+        self.mount_path
+    }
+
+    #[turbo_tasks::function]
+    async fn index(&self) -> Result<Vc<ZipIndex>> {
+        let archive = match &*self.archive.read().await? {
+            FileContent::Content(file) => {
+                let data = file.content().to_bytes()?.into_owned();
+                let path = self.archive.to_string().await?;
+                ZipArchive::parse(data)
+                    .with_context(|| format!("failed to read zip archive {path}"))?
+            }
+            FileContent::NotFound => ZipArchive::default(),
+        };
+        Ok(ZipIndex {
+            archive: Arc::new(archive),
+        }
+        .cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl FileSystem for ZipFileSystem {
+    #[turbo_tasks::function]
+    async fn read(self: Vc<Self>, fs_path: Vc<FileSystemPath>) -> Result<Vc<FileContent>> {
+        let index = self.index().await?;
+        let path = &fs_path.await?.path;
+        Ok(match index.archive.read(path)? {
+            Some(content) => FileContent::Content(File::from(content)).cell(),
+            None => FileContent::NotFound.cell(),
+        })
+    }
+
+    #[turbo_tasks::function]
+    fn read_link(&self, _fs_path: Vc<FileSystemPath>) -> Vc<LinkContent> {
+        LinkContent::NotFound.cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn read_dir(self: Vc<Self>, fs_path: Vc<FileSystemPath>) -> Result<Vc<DirectoryContent>> {
+        let index = self.index().await?;
+        let Some(entries) = index.archive.dirs.get(&fs_path.await?.path) else {
+            return Ok(DirectoryContent::not_found());
+        };
+        let mut converted_entries = AutoMap::new();
+        for (name, &is_dir) in entries {
+            let entry_path = fs_path.join(name.clone()).to_resolved().await?;
+            converted_entries.insert(
+                name.clone(),
+                if is_dir {
+                    DirectoryEntry::Directory(entry_path)
+                } else {
+                    DirectoryEntry::File(entry_path)
+                },
+            );
+        }
+        Ok(DirectoryContent::new(converted_entries))
+    }
+
+    #[turbo_tasks::function]
+    async fn track(self: Vc<Self>, _fs_path: Vc<FileSystemPath>) -> Result<Vc<Completion>> {
+        // All paths change when the archive changes
+        self.index().await?;
+        Ok(Completion::new())
+    }
+
+    #[turbo_tasks::function]
+    fn write(
+        &self,
+        _fs_path: Vc<FileSystemPath>,
+        _content: Vc<FileContent>,
+    ) -> Result<Vc<Completion>> {
+        bail!("Writing is not possible on a zip file system")
+    }
+
+    #[turbo_tasks::function]
+    fn write_link(
+        &self,
+        _fs_path: Vc<FileSystemPath>,
+        _target: Vc<LinkContent>,
+    ) -> Result<Vc<Completion>> {
+        bail!("Writing is not possible on a zip file system")
+    }
+
+    #[turbo_tasks::function]
+    async fn metadata(self: Vc<Self>, fs_path: Vc<FileSystemPath>) -> Result<Vc<FileMeta>> {
+        let index = self.index().await?;
+        if !index.archive.files.contains_key(&fs_path.await?.path) {
+            bail!("path not found, can't read metadata");
+        }
+        Ok(FileMeta::default().cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ValueToString for ZipFileSystem {
+    #[turbo_tasks::function]
+    fn to_string(&self) -> Vc<RcStr> {
+        self.mount_path.to_string()
+    }
+}
+
+#[turbo_tasks::value(serialization = "none", eq = "manual", cell = "new")]
+struct ZipIndex {
+    #[turbo_tasks(debug_ignore, trace_ignore)]
+    archive: Arc<ZipArchive>,
+}
+
+#[derive(Clone, Copy)]
+struct ZipEntry {
+    method: u16,
+    compressed_size: usize,
+    size: usize,
+    local_header_offset: usize,
+}
+
+/// A parsed zip archive. Entry data is decompressed when it's read.
+#[derive(Default)]
+struct ZipArchive {
+    data: Vec<u8>,
+    files: HashMap<RcStr, ZipEntry>,
+    /// The children of each directory and whether they are directories.
+    /// Directories that are only implied by the paths of files are included.
+    dirs: HashMap<RcStr, BTreeMap<RcStr, bool>>,
+}
+
+impl ZipArchive {
+    fn parse(data: Vec<u8>) -> Result<Self> {
+        let eocd = find_end_of_central_directory(&data)?;
+        let entry_count = read_u16(&data, eocd + 10)? as usize;
+        let central_directory_offset = read_u32(&data, eocd + 16)?;
+        if central_directory_offset == u32::MAX {
+            bail!("zip64 archives are not supported");
+        }
+
+        let mut archive = ZipArchive::default();
+        archive.dirs.insert(RcStr::default(), BTreeMap::new());
+        let mut offset = central_directory_offset as usize;
+        for _ in 0..entry_count {
+            if read_u32(&data, offset)? != CENTRAL_DIRECTORY_HEADER_SIGNATURE {
+                bail!("invalid central directory header at {offset}");
+            }
+            let method = read_u16(&data, offset + 10)?;
+            let compressed_size = read_u32(&data, offset + 20)? as usize;
+            let size = read_u32(&data, offset + 24)? as usize;
+            let name_length = read_u16(&data, offset + 28)? as usize;
+            let extra_length = read_u16(&data, offset + 30)? as usize;
+            let comment_length = read_u16(&data, offset + 32)? as usize;
+            let local_header_offset = read_u32(&data, offset + 42)? as usize;
+            let name = data
+                .get(offset + 46..offset + 46 + name_length)
+                .context("truncated central directory")?;
+            let name = std::str::from_utf8(name).context("entry name is not valid utf-8")?;
+            offset += 46 + name_length + extra_length + comment_length;
+
+            if let Some(dir) = name.strip_suffix('/') {
+                archive.add_dir(dir);
+            } else {
+                archive.add_parent_dirs(name, false);
+                archive.files.insert(
+                    name.into(),
+                    ZipEntry {
+                        method,
+                        compressed_size,
+                        size,
+                        local_header_offset,
+                    },
+                );
+            }
+        }
+        archive.data = data;
+        Ok(archive)
+    }
+
+    fn add_dir(&mut self, path: &str) {
+        if path.is_empty() || self.dirs.contains_key(path) {
+            return;
+        }
+        self.dirs.insert(path.into(), BTreeMap::new());
+        self.add_parent_dirs(path, true);
+    }
+
+    /// Adds `path` as child of its parent directory and creates the parent
+    /// directories as needed.
+    fn add_parent_dirs(&mut self, path: &str, is_dir: bool) {
+        let (parent, name) = path.rsplit_once('/').unwrap_or(("", path));
+        self.add_dir(parent);
+        self.dirs
+            .get_mut(parent)
+            .expect("parent directory has been added")
+            .insert(name.into(), is_dir);
+    }
+
+    fn read(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        let Some(entry) = self.files.get(path) else {
+            return Ok(None);
+        };
+        let offset = entry.local_header_offset;
+        if read_u32(&self.data, offset)? != LOCAL_FILE_HEADER_SIGNATURE {
+            bail!("invalid local file header for {path}");
+        }
+        // The lengths in the local header can differ from the central directory
+        let name_length = read_u16(&self.data, offset + 26)? as usize;
+        let extra_length = read_u16(&self.data, offset + 28)? as usize;
+        let start = offset + 30 + name_length + extra_length;
+        let compressed = self
+            .data
+            .get(start..start + entry.compressed_size)
+            .with_context(|| format!("truncated data for {path}"))?;
+        Ok(Some(match entry.method {
+            METHOD_STORED => compressed.to_vec(),
+            METHOD_DEFLATED => {
+                let mut content = Vec::with_capacity(entry.size);
+                DeflateDecoder::new(compressed)
+                    .read_to_end(&mut content)
+                    .with_context(|| format!("failed to inflate {path}"))?;
+                content
+            }
+            method => bail!("unsupported compression method {method} for {path}"),
+        }))
+    }
+}
+
+fn find_end_of_central_directory(data: &[u8]) -> Result<usize> {
+    // The record is 22 bytes, followed by a comment of at most 65535 bytes
+    let min = data.len().saturating_sub(22 + u16::MAX as usize);
+    let mut offset = data.len().checked_sub(22).context("archive is too small")?;
+    loop {
+        if read_u32(data, offset)? == END_OF_CENTRAL_DIRECTORY_SIGNATURE {
+            return Ok(offset);
+        }
+        if offset == min {
+            bail!("end of central directory not found");
+        }
+        offset -= 1;
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .context("unexpected end of archive")?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    let bytes = data
+        .get(offset..offset + 4)
+        .context("unexpected end of archive")?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::DeflateEncoder, Compression};
+
+    use super::{ZipArchive, METHOD_DEFLATED, METHOD_STORED};
+
+    /// Builds an archive with entries compressed with `method`.
+    fn build_archive(method: u16, entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut central_directory = Vec::new();
+        for (name, content) in entries {
+            let compressed = if method == METHOD_DEFLATED {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(content).unwrap();
+                encoder.finish().unwrap()
+            } else {
+                content.to_vec()
+            };
+
+            let offset = data.len() as u32;
+            data.extend(0x04034b50u32.to_le_bytes());
+            data.extend([0; 4]);
+            data.extend(method.to_le_bytes());
+            data.extend([0; 16]);
+            data.extend((name.len() as u16).to_le_bytes());
+            data.extend(0u16.to_le_bytes());
+            data.extend(name.as_bytes());
+            data.extend(&compressed);
+
+            central_directory.extend(0x02014b50u32.to_le_bytes());
+            central_directory.extend([0; 6]);
+            central_directory.extend(method.to_le_bytes());
+            central_directory.extend([0; 8]);
+            central_directory.extend((compressed.len() as u32).to_le_bytes());
+            central_directory.extend((content.len() as u32).to_le_bytes());
+            central_directory.extend((name.len() as u16).to_le_bytes());
+            central_directory.extend([0; 12]);
+            central_directory.extend(offset.to_le_bytes());
+            central_directory.extend(name.as_bytes());
+        }
+        let central_directory_offset = data.len() as u32;
+        let central_directory_size = central_directory.len() as u32;
+        data.extend(central_directory);
+        data.extend(0x06054b50u32.to_le_bytes());
+        data.extend([0; 6]);
+        data.extend((entries.len() as u16).to_le_bytes());
+        data.extend(central_directory_size.to_le_bytes());
+        data.extend(central_directory_offset.to_le_bytes());
+        data.extend(0u16.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn read_stored_entries() {
+        let archive = ZipArchive::parse(build_archive(
+            METHOD_STORED,
+            &[
+                ("node_modules/", b""),
+                ("node_modules/pkg/package.json", b"{}"),
+                ("node_modules/pkg/lib/index.js", b"module.exports = 1;"),
+            ],
+        ))
+        .unwrap();
+
+        assert_eq!(
+            archive.read("node_modules/pkg/lib/index.js").unwrap(),
+            Some(b"module.exports = 1;".to_vec())
+        );
+        assert_eq!(archive.read("node_modules/pkg/missing.js").unwrap(), None);
+        assert_eq!(
+            archive.dirs["node_modules/pkg"]
+                .iter()
+                .map(|(name, is_dir)| (name.as_str(), *is_dir))
+                .collect::<Vec<_>>(),
+            vec![("lib", true), ("package.json", false)]
+        );
+        assert_eq!(archive.dirs[""].len(), 1);
+    }
+
+    #[test]
+    fn read_deflated_entries() {
+        let content = "module.exports = 'turbopack';\n".repeat(64);
+        let archive = ZipArchive::parse(build_archive(
+            METHOD_DEFLATED,
+            &[
+                ("node_modules/pkg/index.js", content.as_bytes()),
+                ("node_modules/pkg/empty.js", b""),
+            ],
+        ))
+        .unwrap();
+
+        let entry = &archive.files["node_modules/pkg/index.js"];
+        assert_eq!(entry.method, METHOD_DEFLATED);
+        assert!(entry.compressed_size < entry.size);
+        assert_eq!(
+            archive.read("node_modules/pkg/index.js").unwrap(),
+            Some(content.into_bytes())
+        );
+        assert_eq!(
+            archive.read("node_modules/pkg/empty.js").unwrap(),
+            Some(vec![])
+        );
+    }
+}
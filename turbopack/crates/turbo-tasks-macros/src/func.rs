@@ -10,7 +10,7 @@ use syn::{
     spanned::Spanned,
     token::Paren,
     visit_mut::VisitMut,
-    AngleBracketedGenericArguments, Block, Expr, ExprBlock, ExprPath, FnArg, GenericArgument,
+    AngleBracketedGenericArguments, Block, Expr, ExprBlock, ExprPath, FnArg, GenericArgument, Lit,
     Local, Meta, Pat, PatIdent, PatType, Path, PathArguments, PathSegment, Receiver, ReturnType,
     Signature, Stmt, Token, Type, TypeGroup, TypePath, TypeTuple,
 };
@@ -662,6 +662,9 @@ pub struct FunctionArguments {
     ///
     /// Setting this option will also set [`Self::resolved`] to the same span.
     pub local_cells: Option<Span>,
+    /// Fails the task with a `TaskTimeoutError` when a single execution takes longer than this
+    /// many milliseconds.
+    pub timeout_ms: Option<u64>,
 }
 
 impl Parse for FunctionArguments {
@@ -691,11 +694,20 @@ impl Parse for FunctionArguments {
                     parsed_args.local_cells = span;
                     parsed_args.resolved = span;
                 }
+                ("timeout_ms", Meta::NameValue(name_value)) => {
+                    let Lit::Int(value) = &name_value.lit else {
+                        return Err(syn::Error::new_spanned(
+                            &name_value.lit,
+                            "expected an integer number of milliseconds",
+                        ));
+                    };
+                    parsed_args.timeout_ms = Some(value.base10_parse()?);
+                }
                 (_, meta) => {
                     return Err(syn::Error::new_spanned(
                         meta,
                         "unexpected token, expected one of: \"fs\", \"network\", \"resolved\", \
-                         \"local_cells\"",
+                         \"local_cells\", \"timeout_ms\"",
                     ))
                 }
             }
@@ -961,6 +973,7 @@ pub struct NativeFn {
     function_path: ExprPath,
     is_method: bool,
     local_cells: bool,
+    timeout_ms: Option<u64>,
 }
 
 impl NativeFn {
@@ -969,12 +982,14 @@ impl NativeFn {
         function_path: &ExprPath,
         is_method: bool,
         local_cells: bool,
+        timeout_ms: Option<u64>,
     ) -> NativeFn {
         NativeFn {
             function_path_string: function_path_string.to_owned(),
             function_path: function_path.clone(),
             is_method,
             local_cells,
+            timeout_ms,
         }
     }
 
@@ -988,8 +1003,16 @@ impl NativeFn {
             function_path,
             is_method,
             local_cells,
+            timeout_ms,
         } = self;
 
+        let timeout = match timeout_ms {
+            Some(timeout_ms) => quote! {
+                Some(std::time::Duration::from_millis(#timeout_ms))
+            },
+            None => quote! { None },
+        };
+
         let constructor = if *is_method {
             quote! { new_method }
         } else {
@@ -1003,6 +1026,7 @@ impl NativeFn {
                     #function_path_string.to_owned(),
                     turbo_tasks::FunctionMeta {
                         local_cells: #local_cells,
+                        timeout: #timeout,
                     },
                     #function_path,
                 )
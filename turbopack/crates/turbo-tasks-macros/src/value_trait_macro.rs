@@ -122,6 +122,7 @@ pub fn value_trait(args: TokenStream, input: TokenStream) -> TokenStream {
                 //   argument.
                 // - This only makes sense when a default implementation is present.
                 false,
+                None,
             );
 
             let native_function_ident = get_trait_default_impl_function_ident(trait_ident, ident);
@@ -89,7 +89,7 @@ impl<I, A> AggregationNode<I, A> {
         matches!(self, AggregationNode::Leaf { .. })
     }
 
-    fn uppers(&self) -> &CountHashSet<I> {
+    pub fn uppers(&self) -> &CountHashSet<I> {
         match self {
             AggregationNode::Leaf { uppers, .. } => uppers,
             AggregationNode::Aggegating(aggregating) => &aggregating.uppers,
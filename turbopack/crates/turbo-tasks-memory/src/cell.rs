@@ -55,6 +55,11 @@ pub enum ReadContentError {
 }
 
 impl Cell {
+    /// Returns true if the cell currently holds a value.
+    pub fn has_value(&self) -> bool {
+        matches!(self.state, CellState::Value { .. })
+    }
+
     /// Removes a task from the list of dependent tasks.
     pub fn remove_dependent_task(&mut self, task: TaskId) {
         self.dependent_tasks.remove(&task);
@@ -509,6 +509,12 @@ impl TaskEdgesSet {
         }
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = TaskEdge> + '_ {
+        self.edges
+            .iter()
+            .flat_map(|(task, entry)| entry.iter().map(move |e| e.into_dependency(*task)))
+    }
+
     pub fn children(&self) -> impl Iterator<Item = TaskId> + '_ {
         self.edges.iter().filter_map(|(task, entry)| match entry {
             EdgesDataEntry::Child => Some(*task),
@@ -554,6 +560,12 @@ impl TaskEdgesList {
         self.edges.is_empty()
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = TaskEdge> + '_ {
+        self.edges
+            .iter()
+            .flat_map(|(task, entry)| entry.iter().map(move |e| e.into_dependency(*task)))
+    }
+
     pub fn children(&self) -> impl Iterator<Item = TaskId> + '_ {
         self.edges.iter().filter_map(|(task, entry)| match entry {
             EdgesDataEntry::Child => Some(*task),
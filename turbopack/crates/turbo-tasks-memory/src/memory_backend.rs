@@ -20,11 +20,11 @@ use turbo_prehash::{BuildHasherExt, PassThroughHash, PreHashed};
 use turbo_tasks::{
     backend::{
         Backend, BackendJobId, CachedTaskType, CellContent, TaskCollectiblesMap, TaskExecutionSpec,
-        TransientTaskType, TypedCellContent,
+        TaskMetaSnapshot, TaskSnapshot, TransientTaskType, TypedCellContent,
     },
     event::EventListener,
     util::{IdFactoryWithReuse, NoMoveVec},
-    CellId, FunctionId, RawVc, ReadConsistency, TaskId, TaskIdSet, TraitTypeId,
+    CellId, FunctionId, RawVc, ReadConsistency, TaskId, TaskIdSet, TaskPriority, TraitTypeId,
     TurboTasksBackendApi, Unused, ValueTypeId, TRANSIENT_TASK_BIT,
 };
 
@@ -440,6 +440,22 @@ impl Backend for MemoryBackend {
         self.with_task(task, |task| task.get_description())
     }
 
+    fn task_snapshot(
+        &self,
+        task: TaskId,
+        _turbo_tasks: &dyn TurboTasksBackendApi<MemoryBackend>,
+    ) -> Option<TaskSnapshot> {
+        Some(self.with_task(task, |task| task.snapshot()))
+    }
+
+    fn task_meta_snapshot(
+        &self,
+        task: TaskId,
+        _turbo_tasks: &dyn TurboTasksBackendApi<MemoryBackend>,
+    ) -> Option<TaskMetaSnapshot> {
+        Some(self.with_task(task, |task| task.meta_snapshot()))
+    }
+
     type TaskState = TaskState;
     fn new_task_state(&self, _task: TaskId) -> Self::TaskState {
         TaskState {
@@ -800,6 +816,7 @@ impl Backend for MemoryBackend {
     fn create_transient_task(
         &self,
         task_type: TransientTaskType,
+        priority: TaskPriority,
         turbo_tasks: &dyn TurboTasksBackendApi<MemoryBackend>,
     ) -> TaskId {
         let id = turbo_tasks.get_fresh_transient_task_id();
@@ -807,13 +824,13 @@ impl Backend for MemoryBackend {
         let index = (*id - TRANSIENT_TASK_BIT) as usize;
         match task_type {
             TransientTaskType::Root(f) => {
-                let task = Task::new_root(id, move || f() as _);
+                let task = Task::new_root(id, priority, move || f() as _);
                 // SAFETY: We have a fresh task id where nobody knows about yet
                 unsafe { self.transient_tasks.insert(index, task) };
                 Task::set_root(id, self, turbo_tasks);
             }
             TransientTaskType::Once(f) => {
-                let task = Task::new_once(id, f);
+                let task = Task::new_once(id, priority, f);
                 // SAFETY: We have a fresh task id where nobody knows about yet
                 unsafe { self.transient_tasks.insert(index, task) };
                 Task::set_once(id, self, turbo_tasks);
@@ -822,6 +839,13 @@ impl Backend for MemoryBackend {
         id
     }
 
+    fn transient_task_priority(&self, task: TaskId) -> Option<TaskPriority> {
+        if !task.is_transient() {
+            return None;
+        }
+        self.with_task(task, |task| task.priority())
+    }
+
     fn dispose_root_task(&self, task: TaskId, turbo_tasks: &dyn TurboTasksBackendApi<Self>) {
         Task::unset_root(task, self, turbo_tasks);
     }
@@ -19,10 +19,13 @@ use smallvec::SmallVec;
 use tracing::Span;
 use turbo_prehash::PreHashed;
 use turbo_tasks::{
-    backend::{CachedTaskType, CellContent, TaskCollectiblesMap, TaskExecutionSpec},
+    backend::{
+        CachedTaskType, CellContent, TaskCollectiblesMap, TaskExecutionSpec, TaskMetaSnapshot,
+        TaskSnapshot,
+    },
     event::{Event, EventListener},
     get_invalidator, registry, CellId, Invalidator, RawVc, ReadConsistency, TaskId, TaskIdSet,
-    TraitTypeId, TurboTasksBackendApi, TurboTasksBackendApiExt, ValueTypeId,
+    TaskPriority, TraitTypeId, TurboTasksBackendApi, TurboTasksBackendApiExt, ValueTypeId,
 };
 
 use crate::{
@@ -51,7 +54,7 @@ pub enum TaskType {
     /// A root task that will track dependencies and re-execute when
     /// dependencies change. Task will eventually settle to the correct
     /// execution.
-    Root(Box<NativeTaskFn>),
+    Root(Box<NativeTaskFn>, TaskPriority),
 
     // Note: double boxed to reduce TaskType size
     /// A single root task execution. It won't track dependencies.
@@ -59,7 +62,7 @@ pub enum TaskType {
     /// start of the task. It may or may not include invalidations that
     /// happened after that. It may see these invalidations partially
     /// applied.
-    Once(Box<OnceTaskFn>),
+    Once(Box<OnceTaskFn>, TaskPriority),
 
     /// A normal persistent task
     Persistent { ty: Arc<PreHashed<CachedTaskType>> },
@@ -417,6 +420,27 @@ impl TaskStateType {
         }
     }
 
+    fn edges(&self) -> impl Iterator<Item = TaskEdge> + '_ {
+        match self {
+            TaskStateType::Done { edges, .. } => Either::Left(edges.iter()),
+            TaskStateType::InProgress(box InProgressState {
+                outdated_edges,
+                new_children,
+                ..
+            }) => Either::Right(Either::Left(
+                outdated_edges
+                    .iter()
+                    .chain(new_children.iter().copied().map(TaskEdge::Child)),
+            )),
+            TaskStateType::Dirty { outdated_edges, .. } => {
+                Either::Right(Either::Right(outdated_edges.iter()))
+            }
+            TaskStateType::Scheduled(box ScheduledState { outdated_edges, .. }) => {
+                Either::Right(Either::Right(outdated_edges.iter()))
+            }
+        }
+    }
+
     fn into_dependencies_and_children(self) -> (TaskEdgesSet, SmallVec<[TaskId; 6]>) {
         match self {
             TaskStateType::Done { edges, .. } => {
@@ -497,9 +521,10 @@ impl Task {
 
     pub(crate) fn new_root(
         id: TaskId,
+        priority: TaskPriority,
         functor: impl Fn() -> NativeTaskFuture + Sync + Send + 'static,
     ) -> Self {
-        let ty = TaskType::Root(Box::new(Box::new(functor)));
+        let ty = TaskType::Root(Box::new(Box::new(functor)), priority);
         let description = Self::get_event_description_static(id, &ty);
         Self {
             id,
@@ -513,9 +538,10 @@ impl Task {
 
     pub(crate) fn new_once(
         id: TaskId,
+        priority: TaskPriority,
         functor: impl Future<Output = Result<RawVc>> + Send + 'static,
     ) -> Self {
-        let ty = TaskType::Once(Box::new(Mutex::new(Some(Box::pin(functor)))));
+        let ty = TaskType::Once(Box::new(Mutex::new(Some(Box::pin(functor)))), priority);
         let description = Self::get_event_description_static(id, &ty);
         Self {
             id,
@@ -531,8 +557,8 @@ impl Task {
         match &self.ty {
             TaskType::Persistent { .. } => true,
             TaskType::Transient { .. } => true,
-            TaskType::Root(_) => false,
-            TaskType::Once(_) => false,
+            TaskType::Root(..) => false,
+            TaskType::Once(..) => false,
         }
     }
 
@@ -540,8 +566,16 @@ impl Task {
         match &self.ty {
             TaskType::Persistent { .. } => false,
             TaskType::Transient { .. } => false,
-            TaskType::Root(_) => false,
-            TaskType::Once(_) => true,
+            TaskType::Root(..) => false,
+            TaskType::Once(..) => true,
+        }
+    }
+
+    /// The priority of a root or once task.
+    pub(crate) fn priority(&self) -> Option<TaskPriority> {
+        match &self.ty {
+            TaskType::Root(_, priority) | TaskType::Once(_, priority) => Some(*priority),
+            TaskType::Persistent { .. } | TaskType::Transient { .. } => None,
         }
     }
 
@@ -759,10 +793,10 @@ impl Task {
         Span,
     ) {
         match &self.ty {
-            TaskType::Root(bound_fn) => {
+            TaskType::Root(bound_fn, _) => {
                 (bound_fn(), tracing::trace_span!("turbo_tasks::root_task"))
             }
-            TaskType::Once(mutex) => (
+            TaskType::Once(mutex, _) => (
                 mutex.lock().take().expect("Task can only be executed once"),
                 tracing::trace_span!("turbo_tasks::once_task"),
             ),
@@ -1083,7 +1117,7 @@ impl Task {
             remove_job.apply(&aggregation_context);
             change_job.apply(&aggregation_context);
         }
-        if let TaskType::Once(_) = self.ty {
+        if let TaskType::Once(..) = self.ty {
             // unset the root type, so tasks below are no longer active
             aggregation_context.aggregation_data(self.id).root_type = None;
         }
@@ -1097,7 +1131,7 @@ impl Task {
         backend: &MemoryBackend,
         turbo_tasks: &dyn TurboTasksBackendApi<MemoryBackend>,
     ) {
-        if let TaskType::Once(_) = self.ty {
+        if let TaskType::Once(..) = self.ty {
             // once task won't become dirty
             return;
         }
@@ -1822,6 +1856,53 @@ impl Task {
         }
     }
 
+    pub(crate) fn snapshot(&self) -> TaskSnapshot {
+        let mut snapshot = TaskSnapshot::default();
+        let TaskMetaStateReadGuard::Full(state) = self.state() else {
+            // Partial and unloaded tasks have no edges or cells.
+            return snapshot;
+        };
+        for edge in state.state_type.edges() {
+            match edge {
+                TaskEdge::Child(task) => snapshot.children.push(task),
+                TaskEdge::Output(task) => snapshot.output_dependencies.push(task),
+                TaskEdge::Cell(task, cell) => snapshot.cell_dependencies.push((task, cell)),
+                TaskEdge::Collectibles(task, trait_type) => {
+                    snapshot.collectibles_dependencies.push((task, trait_type))
+                }
+            }
+        }
+        for (&type_id, cells) in state.cells.iter() {
+            for (index, cell) in cells.iter().enumerate() {
+                if cell.has_value() {
+                    snapshot.cells.push(CellId {
+                        type_id,
+                        index: index as u32,
+                    });
+                }
+            }
+        }
+        if let Some(collectibles) = state.collectibles.as_ref() {
+            for (&(trait_type, collectible), &count) in collectibles.iter() {
+                snapshot.collectibles.push((trait_type, collectible, count));
+            }
+        }
+        snapshot
+    }
+
+    pub(crate) fn meta_snapshot(&self) -> TaskMetaSnapshot {
+        let state = self.state();
+        let aggregation_node = match &state {
+            TaskMetaStateReadGuard::Full(state) => &state.aggregation_node,
+            TaskMetaStateReadGuard::Partial(state) => &state.aggregation_node,
+            // Unloaded tasks aren't aggregated.
+            TaskMetaStateReadGuard::Unloaded => return TaskMetaSnapshot::default(),
+        };
+        TaskMetaSnapshot {
+            uppers: aggregation_node.uppers().iter().copied().collect(),
+        }
+    }
+
     pub(crate) fn gc_state(&self) -> Option<GcTaskState> {
         if let TaskMetaStateReadGuard::Full(state) = self.state() {
             Some(state.gc)
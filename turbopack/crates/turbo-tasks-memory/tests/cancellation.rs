@@ -0,0 +1 @@
+../../turbo-tasks-testing/tests/cancellation.rs
\ No newline at end of file
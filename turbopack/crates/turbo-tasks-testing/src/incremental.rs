@@ -0,0 +1,199 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Write},
+    future::Future,
+    sync::Arc,
+};
+
+use anyhow::{Context, Result};
+use turbo_tasks::{backend::Backend, ReadConsistency, TaskId, TurboTasks, Vc};
+
+/// A harness for testing incremental behavior. It runs a build as root task,
+/// applies a scripted sequence of edits and records which functions were
+/// re-executed by each of them.
+///
+/// Edits are expected to invalidate tasks, e. g. by writing to a
+/// `MemoryFileSystem` from `turbo-tasks-fs`. The recorded steps are available
+/// as text via [IncrementalTest::report], which is stable across runs and can
+/// be compared against a snapshot.
+pub struct IncrementalTest<B: Backend + 'static> {
+    tt: Arc<TurboTasks<B>>,
+    root: Option<TaskId>,
+    report: String,
+}
+
+impl<B: Backend + 'static> IncrementalTest<B> {
+    /// Creates a new harness. This enables invalidation recording on `tt`.
+    pub fn new(tt: Arc<TurboTasks<B>>) -> Self {
+        tt.set_invalidation_recording(true);
+        Self {
+            tt,
+            root: None,
+            report: String::new(),
+        }
+    }
+
+    /// Runs the initial build. `build` becomes a root task, so it's updated
+    /// automatically whenever a later step invalidates something it depends
+    /// on. Executions of the initial build are not reported.
+    pub async fn build<T, F, Fut>(&mut self, build: F) -> Result<()>
+    where
+        T: Send,
+        F: Fn() -> Fut + Send + Sync + Clone + 'static,
+        Fut: Future<Output = Result<Vc<T>>> + Send,
+    {
+        if let Some(root) = self.root.take() {
+            self.tt.dispose_root_task(root);
+        }
+        let root = self.tt.spawn_root_task(build);
+        self.root = Some(root);
+        self.settle(root).await?;
+        self.tt.take_recomputed_tasks();
+        Ok(())
+    }
+
+    /// Applies `edit` and waits until the build has been updated. Returns the
+    /// functions that were re-executed because of the edit and appends them
+    /// to the report.
+    pub async fn step(&mut self, name: &str, edit: impl FnOnce()) -> Result<Step> {
+        let root = self
+            .root
+            .context("IncrementalTest::build must be called before IncrementalTest::step")?;
+        edit();
+        self.settle(root)
+            .await
+            .with_context(|| format!("updating the build after step \"{name}\""))?;
+
+        let mut executed = BTreeMap::new();
+        for task in self.tt.take_recomputed_tasks().unwrap_or_default() {
+            if let Some(function) = function_name(&task.description) {
+                *executed.entry(function.to_string()).or_default() += 1;
+            }
+        }
+        let step = Step {
+            name: name.to_string(),
+            executed,
+        };
+        writeln!(self.report, "{step}")?;
+        Ok(step)
+    }
+
+    /// The executed functions of all steps so far, in a format suited for
+    /// snapshots.
+    pub fn report(&self) -> &str {
+        &self.report
+    }
+
+    async fn settle(&self, root: TaskId) -> Result<()> {
+        self.tt
+            .wait_task_completion(root, ReadConsistency::Strong)
+            .await?;
+        self.tt.wait_foreground_done().await;
+        Ok(())
+    }
+}
+
+impl<B: Backend + 'static> Drop for IncrementalTest<B> {
+    fn drop(&mut self) {
+        if let Some(root) = self.root.take() {
+            self.tt.dispose_root_task(root);
+        }
+    }
+}
+
+/// Strips the task id from a task description and skips root, once and
+/// resolve tasks. These re-execute whenever the functions they call do, so
+/// they don't say anything about invalidation precision.
+fn function_name(description: &str) -> Option<&str> {
+    let name = match description.strip_prefix('[') {
+        Some(rest) => rest.split_once("] ").map_or(rest, |(_, name)| name),
+        None => description,
+    };
+    if name.starts_with('*') || name == "root" || name == "once" {
+        return None;
+    }
+    Some(name)
+}
+
+/// The functions re-executed in a step of an [IncrementalTest].
+#[derive(Debug, Clone)]
+pub struct Step {
+    name: String,
+    /// Function names with the number of re-executed tasks of that function.
+    executed: BTreeMap<String, usize>,
+}
+
+impl Step {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether any task of `function` was re-executed. `function` is matched
+    /// against the function name as registered, e. g. `FileSystemPath::read`
+    /// or just `read`.
+    pub fn was_executed(&self, function: &str) -> bool {
+        self.executed.keys().any(|name| {
+            name == function
+                || name
+                    .strip_suffix(function)
+                    .is_some_and(|prefix| prefix.ends_with("::"))
+        })
+    }
+
+    /// Panics unless all of `functions` were re-executed.
+    #[track_caller]
+    pub fn assert_executed(&self, functions: &[&str]) {
+        for function in functions {
+            assert!(
+                self.was_executed(function),
+                "expected {function} to be re-executed in step \"{}\"\n{self}",
+                self.name
+            );
+        }
+    }
+
+    /// Panics if any of `functions` was re-executed.
+    #[track_caller]
+    pub fn assert_not_executed(&self, functions: &[&str]) {
+        for function in functions {
+            assert!(
+                !self.was_executed(function),
+                "expected {function} not to be re-executed in step \"{}\"\n{self}",
+                self.name
+            );
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "## {}", self.name)?;
+        if self.executed.is_empty() {
+            writeln!(f, "(nothing re-executed)")?;
+        }
+        for (function, count) in &self.executed {
+            if *count == 1 {
+                writeln!(f, "- {function}")?;
+            } else {
+                writeln!(f, "- {function} (x{count})")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::function_name;
+
+    #[test]
+    fn strips_task_ids() {
+        assert_eq!(
+            function_name("[TaskId 12] FileSystemPath::read"),
+            Some("FileSystemPath::read")
+        );
+        assert_eq!(function_name("module_graph"), Some("module_graph"));
+        assert_eq!(function_name("[TaskId 1] root"), None);
+        assert_eq!(function_name("*FileSystem::read"), None);
+    }
+}
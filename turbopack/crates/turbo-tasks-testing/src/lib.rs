@@ -1,5 +1,6 @@
 //! Testing utilities and macros for turbo-tasks and applications based on it.
 
+mod incremental;
 pub mod retry;
 mod run;
 
@@ -24,7 +25,10 @@ use turbo_tasks::{
     TaskPersistence, TraitTypeId, TurboTasksApi, TurboTasksCallApi,
 };
 
-pub use crate::run::{run, run_with_tt, run_without_cache_check, Registration};
+pub use crate::{
+    incremental::{IncrementalTest, Step},
+    run::{run, run_with_tt, run_without_cache_check, Registration},
+};
 
 enum Task {
     Spawned(Event),
@@ -185,6 +189,22 @@ impl TurboTasksApi for VcStorage {
         // ignore
     }
 
+    fn set_invalidation_recording(&self, _enabled: bool) {
+        // ignore
+    }
+
+    fn take_recomputed_tasks(&self) -> Option<Vec<turbo_tasks::RecomputedTask>> {
+        None
+    }
+
+    fn export_task_graph(
+        &self,
+        _root: TaskId,
+        _options: &turbo_tasks::task_graph::TaskGraphOptions,
+    ) -> Option<turbo_tasks::task_graph::TaskGraph> {
+        None
+    }
+
     fn try_read_task_output(
         &self,
         id: TaskId,
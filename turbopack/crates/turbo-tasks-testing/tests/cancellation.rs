@@ -0,0 +1,256 @@
+#![feature(arbitrary_self_types)]
+#![feature(arbitrary_self_types_pointers)]
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+use std::{
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+    time::Duration,
+};
+
+use anyhow::Result;
+use turbo_tasks::{set_task_timeout, ReadConsistency, Vc};
+use turbo_tasks_testing::{register, run, Registration};
+
+static REGISTRATION: Registration = register!();
+
+#[tokio::test]
+async fn times_out() {
+    run(&REGISTRATION, || async {
+        let error = sleep_with_timeout(2000).await.unwrap_err();
+        assert!(
+            format!("{error:?}").contains("timed out after 50ms"),
+            "{error:?}"
+        );
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn waiting_for_dependencies_does_not_count() {
+    run(&REGISTRATION, || async {
+        assert_eq!(*wait_for_dependency_with_timeout(300).await?, 300);
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn timeout_can_be_changed() {
+    run(&REGISTRATION, || async {
+        let error = sleep_with_changed_timeout(2000).await.unwrap_err();
+        assert!(
+            format!("{error:?}").contains("timed out after 50ms"),
+            "{error:?}"
+        );
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+static HANG: AtomicBool = AtomicBool::new(true);
+static EXECUTIONS: AtomicU32 = AtomicU32::new(0);
+
+#[tokio::test]
+async fn cancel_root_task() {
+    REGISTRATION.ensure_registered();
+    let create_turbo_tasks = include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/test_config.trs"
+    ));
+    let tt = create_turbo_tasks("cancel_root_task", true);
+
+    let root = tt.spawn_root_task(|| async {
+        hang().await?;
+        Ok(Vc::<()>::default())
+    });
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while EXECUTIONS.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("hang should be executed");
+
+    tt.cancel_root_task(root);
+    HANG.store(false, Ordering::SeqCst);
+
+    // The cancelled execution is invalidated, so reading the task executes it
+    // again. A read racing with the cancellation might still see the error.
+    let value = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            match tt.run_once(async { Ok(*hang().await?) }).await {
+                Ok(value) => return value,
+                Err(error) => assert!(format!("{error:?}").contains("was cancelled"), "{error:?}"),
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("the cancelled task should be executed again");
+    assert_eq!(value, 2);
+
+    tt.stop_and_wait().await;
+}
+
+static SHARED_HANG: AtomicBool = AtomicBool::new(true);
+static SHARED_EXECUTIONS: AtomicU32 = AtomicU32::new(0);
+static ROOTS_STARTED: AtomicU32 = AtomicU32::new(0);
+
+#[tokio::test]
+async fn cancel_root_task_keeps_shared_tasks() {
+    REGISTRATION.ensure_registered();
+    let create_turbo_tasks = include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/test_config.trs"
+    ));
+    let tt = create_turbo_tasks("cancel_root_task_keeps_shared_tasks", true);
+
+    let spawn_root = || {
+        tt.spawn_root_task(|| async {
+            let value = shared_hang();
+            ROOTS_STARTED.fetch_add(1, Ordering::SeqCst);
+            value.await?;
+            Ok(Vc::<()>::default())
+        })
+    };
+    let cancelled_root = spawn_root();
+    let root = spawn_root();
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while ROOTS_STARTED.load(Ordering::SeqCst) < 2
+            || SHARED_EXECUTIONS.load(Ordering::SeqCst) == 0
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("both roots should read shared_hang");
+
+    tt.cancel_root_task(cancelled_root);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    SHARED_HANG.store(false, Ordering::SeqCst);
+
+    // The other root still reads shared_hang, so its execution isn't aborted
+    // and it isn't executed again.
+    tokio::time::timeout(
+        Duration::from_secs(10),
+        tt.wait_task_completion(root, ReadConsistency::Strong),
+    )
+    .await
+    .expect("the other root should complete")
+    .unwrap();
+    assert_eq!(SHARED_EXECUTIONS.load(Ordering::SeqCst), 1);
+
+    tt.stop_and_wait().await;
+}
+
+static ONCE_HANG: AtomicBool = AtomicBool::new(true);
+static ONCE_EXECUTIONS: AtomicU32 = AtomicU32::new(0);
+static ONCE_READERS_STARTED: AtomicU32 = AtomicU32::new(0);
+
+#[tokio::test]
+async fn cancel_root_task_keeps_tasks_of_once_tasks() {
+    REGISTRATION.ensure_registered();
+    let create_turbo_tasks = include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/test_config.trs"
+    ));
+    let tt = create_turbo_tasks("cancel_root_task_keeps_tasks_of_once_tasks", true);
+
+    let cancelled_root = tt.spawn_root_task(|| async {
+        let value = once_hang();
+        ONCE_READERS_STARTED.fetch_add(1, Ordering::SeqCst);
+        value.await?;
+        Ok(Vc::<()>::default())
+    });
+    let once = tokio::spawn({
+        let tt = tt.clone();
+        async move {
+            tt.run_once(async {
+                let value = once_hang();
+                ONCE_READERS_STARTED.fetch_add(1, Ordering::SeqCst);
+                Ok(*value.await?)
+            })
+            .await
+        }
+    });
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while ONCE_READERS_STARTED.load(Ordering::SeqCst) < 2
+            || ONCE_EXECUTIONS.load(Ordering::SeqCst) == 0
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("the root and the once task should read once_hang");
+
+    tt.cancel_root_task(cancelled_root);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    ONCE_HANG.store(false, Ordering::SeqCst);
+
+    // The once task is still executing, so the execution of once_hang it
+    // reads isn't aborted.
+    let value = tokio::time::timeout(Duration::from_secs(10), once)
+        .await
+        .expect("the once task should complete")
+        .unwrap()
+        .unwrap();
+    assert_eq!(value, 1);
+    assert_eq!(ONCE_EXECUTIONS.load(Ordering::SeqCst), 1);
+
+    tt.stop_and_wait().await;
+}
+
+#[turbo_tasks::function(timeout_ms = 50)]
+async fn sleep_with_timeout(ms: u64) -> Result<Vc<()>> {
+    tokio::time::sleep(Duration::from_millis(ms)).await;
+    Ok(Vc::cell(()))
+}
+
+#[turbo_tasks::function(timeout_ms = 10_000)]
+async fn sleep_with_changed_timeout(ms: u64) -> Result<Vc<()>> {
+    set_task_timeout(Duration::from_millis(50));
+    tokio::time::sleep(Duration::from_millis(ms)).await;
+    Ok(Vc::cell(()))
+}
+
+#[turbo_tasks::function(timeout_ms = 100)]
+async fn wait_for_dependency_with_timeout(ms: u64) -> Result<Vc<u64>> {
+    Ok(Vc::cell(*slow_dependency(ms).await?))
+}
+
+#[turbo_tasks::function]
+async fn slow_dependency(ms: u64) -> Result<Vc<u64>> {
+    tokio::time::sleep(Duration::from_millis(ms)).await;
+    Ok(Vc::cell(ms))
+}
+
+#[turbo_tasks::function]
+async fn shared_hang() -> Result<Vc<u32>> {
+    let execution = SHARED_EXECUTIONS.fetch_add(1, Ordering::SeqCst) + 1;
+    while SHARED_HANG.load(Ordering::SeqCst) {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    Ok(Vc::cell(execution))
+}
+
+#[turbo_tasks::function]
+async fn once_hang() -> Result<Vc<u32>> {
+    let execution = ONCE_EXECUTIONS.fetch_add(1, Ordering::SeqCst) + 1;
+    while ONCE_HANG.load(Ordering::SeqCst) {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    Ok(Vc::cell(execution))
+}
+
+#[turbo_tasks::function]
+async fn hang() -> Result<Vc<u32>> {
+    let execution = EXECUTIONS.fetch_add(1, Ordering::SeqCst) + 1;
+    if HANG.load(Ordering::SeqCst) {
+        tokio::time::sleep(Duration::from_secs(3600)).await;
+    }
+    Ok(Vc::cell(execution))
+}
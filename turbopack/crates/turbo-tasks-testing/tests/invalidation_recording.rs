@@ -0,0 +1,115 @@
+#![feature(arbitrary_self_types)]
+#![feature(arbitrary_self_types_pointers)]
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+use anyhow::Result;
+use turbo_tasks::{State, Vc};
+use turbo_tasks_testing::{register, run, Registration};
+
+static REGISTRATION: Registration = register!();
+
+#[tokio::test]
+async fn records_dependency_chain() {
+    run(&REGISTRATION, || async {
+        let tt = turbo_tasks::turbo_tasks();
+        tt.set_invalidation_recording(true);
+
+        let input = ChangingInput {
+            state: State::new(1),
+        }
+        .cell();
+        let output = outer(input);
+        assert_eq!(*output.strongly_consistent().await?, 2);
+        tt.take_recomputed_tasks();
+
+        input.await?.state.set(2);
+        assert_eq!(*output.strongly_consistent().await?, 3);
+
+        let recomputed = tt.take_recomputed_tasks().unwrap();
+        let inner_task = recomputed
+            .iter()
+            .find(|task| task.description.contains("inner"))
+            .expect("inner should be recomputed");
+        assert_eq!(inner_task.chain.len(), 1);
+        let outer_task = recomputed
+            .iter()
+            .find(|task| task.description.contains("outer"))
+            .expect("outer should be recomputed");
+        assert_eq!(outer_task.chain.len(), 2);
+        assert_eq!(outer_task.chain[0], inner_task.description);
+        assert_eq!(outer_task.chain[1], outer_task.description);
+
+        // Nothing is reported twice.
+        assert!(tt.take_recomputed_tasks().unwrap().is_empty());
+
+        tt.set_invalidation_recording(false);
+        input.await?.state.set(3);
+        assert_eq!(*output.strongly_consistent().await?, 4);
+        assert!(tt.take_recomputed_tasks().is_none());
+
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn records_readers_of_updated_cells() {
+    run(&REGISTRATION, || async {
+        let tt = turbo_tasks::turbo_tasks();
+        tt.set_invalidation_recording(true);
+
+        let input = ChangingInput {
+            state: State::new(1),
+        }
+        .cell();
+        let output = read_through(input);
+        assert_eq!(*output.strongly_consistent().await?, 2);
+        tt.take_recomputed_tasks();
+
+        // `read_through` reads the cell of `inner` without calling it itself,
+        // so it's only notified by the cell update.
+        input.await?.state.set(2);
+        assert_eq!(*output.strongly_consistent().await?, 3);
+
+        let recomputed = tt.take_recomputed_tasks().unwrap();
+        let reader = recomputed
+            .iter()
+            .find(|task| task.description.contains("read_through"))
+            .expect("read_through should be recomputed");
+        assert_eq!(reader.chain.len(), 2);
+        assert!(reader.chain[0].contains("inner"));
+
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[turbo_tasks::value]
+struct ChangingInput {
+    state: State<u32>,
+}
+
+#[turbo_tasks::function]
+async fn inner(input: Vc<ChangingInput>) -> Result<Vc<u32>> {
+    let value = *input.await?.state.get();
+    Ok(Vc::cell(value))
+}
+
+#[turbo_tasks::function]
+async fn outer(input: Vc<ChangingInput>) -> Result<Vc<u32>> {
+    let value = *inner(input).await?;
+    Ok(Vc::cell(value + 1))
+}
+
+#[turbo_tasks::function]
+fn pass_through(input: Vc<ChangingInput>) -> Vc<u32> {
+    inner(input)
+}
+
+#[turbo_tasks::function]
+async fn read_through(input: Vc<ChangingInput>) -> Result<Vc<u32>> {
+    let value = *pass_through(input).await?;
+    Ok(Vc::cell(value + 1))
+}
@@ -0,0 +1,80 @@
+#![feature(arbitrary_self_types)]
+#![feature(arbitrary_self_types_pointers)]
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+use anyhow::Result;
+use turbo_tasks::{
+    task_graph::{TaskGraphEdgeKind, TaskGraphOptions},
+    Vc,
+};
+use turbo_tasks_testing::{register, run, Registration};
+
+static REGISTRATION: Registration = register!();
+
+#[tokio::test]
+async fn exports_task_graph() {
+    run(&REGISTRATION, || async {
+        let output = graph_root();
+        assert_eq!(*output.strongly_consistent().await?, 3);
+        let root = Vc::into_raw(output).get_task_id();
+        let tt = turbo_tasks::turbo_tasks();
+
+        let graph = tt
+            .export_task_graph(root, &TaskGraphOptions::default())
+            .unwrap();
+        let id_of = |name: &str| {
+            graph
+                .nodes
+                .iter()
+                .find(|node| node.description.contains(name))
+                .unwrap_or_else(|| panic!("{name} should be exported"))
+                .id
+        };
+        let (middle, leaf) = (id_of("graph_middle"), id_of("graph_leaf"));
+        assert_eq!(graph.nodes[0].id, root);
+        for (from, to) in [(root, middle), (middle, leaf)] {
+            assert!(
+                graph.edges.iter().any(|edge| edge.from == from
+                    && edge.to == to
+                    && matches!(edge.kind, TaskGraphEdgeKind::Child)),
+                "{from} -> {to} should be a child edge"
+            );
+        }
+
+        // The filtered out middle task is collapsed into an indirect edge
+        let options = TaskGraphOptions {
+            max_depth: None,
+            function_names: vec!["graph_leaf".to_string()],
+        };
+        let graph = tt.export_task_graph(root, &options).unwrap();
+        let nodes = graph.nodes.iter().map(|node| node.id).collect::<Vec<_>>();
+        assert_eq!(nodes, vec![root, leaf]);
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!((graph.edges[0].from, graph.edges[0].to), (root, leaf));
+        assert!(matches!(
+            graph.edges[0].kind,
+            TaskGraphEdgeKind::Indirect { hidden_tasks: 1 }
+        ));
+
+        anyhow::Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[turbo_tasks::function]
+fn graph_leaf() -> Vc<u32> {
+    Vc::cell(1)
+}
+
+#[turbo_tasks::function]
+async fn graph_middle() -> Result<Vc<u32>> {
+    let value = *graph_leaf().await?;
+    Ok(Vc::cell(value + 1))
+}
+
+#[turbo_tasks::function]
+async fn graph_root() -> Result<Vc<u32>> {
+    let value = *graph_middle().await?;
+    Ok(Vc::cell(value + 1))
+}
@@ -23,8 +23,8 @@ use crate::{
     task::shared_reference::TypedSharedReference,
     trait_helpers::{get_trait_method, has_trait, traits},
     triomphe_utils::unchecked_sidecast_triomphe_arc,
-    FunctionId, RawVc, ReadRef, SharedReference, TaskId, TaskIdSet, TaskPersistence, TraitRef,
-    TraitTypeId, ValueTypeId, VcRead, VcValueTrait, VcValueType,
+    FunctionId, RawVc, ReadRef, SharedReference, TaskId, TaskIdSet, TaskPersistence, TaskPriority,
+    TraitRef, TraitTypeId, ValueTypeId, VcRead, VcValueTrait, VcValueType,
 };
 
 pub type TransientTaskRoot =
@@ -523,6 +523,32 @@ impl TryFrom<CellContent> for SharedReference {
 
 pub type TaskCollectiblesMap = AutoMap<RawVc, i32, BuildHasherDefault<FxHasher>, 1>;
 
+/// The edges, cells and collectibles of a single task as currently known to
+/// the backend. Used to export the task graph for debugging.
+#[derive(Debug, Default)]
+pub struct TaskSnapshot {
+    pub children: Vec<TaskId>,
+    /// Tasks whose output was read by this task.
+    pub output_dependencies: Vec<TaskId>,
+    /// Cells of other tasks that were read by this task.
+    pub cell_dependencies: Vec<(TaskId, CellId)>,
+    /// Tasks whose collectibles were read by this task.
+    pub collectibles_dependencies: Vec<(TaskId, TraitTypeId)>,
+    /// Cells of this task which currently hold a value.
+    pub cells: Vec<CellId>,
+    /// Collectibles emitted by this task, with their emit count.
+    pub collectibles: Vec<(TraitTypeId, RawVc, i32)>,
+}
+
+/// The aggregation edges of a single task. Unlike [`TaskSnapshot`], reading
+/// them doesn't require restoring the data of the task.
+#[derive(Debug, Default)]
+pub struct TaskMetaSnapshot {
+    /// Tasks that aggregate this task. Every root task this task is below is
+    /// reachable along them.
+    pub uppers: Vec<TaskId>,
+}
+
 pub trait Backend: Sync + Send {
     #[allow(unused_variables)]
     fn startup(&self, turbo_tasks: &dyn TurboTasksBackendApi<Self>) {}
@@ -551,6 +577,18 @@ pub trait Backend: Sync + Send {
 
     fn get_task_description(&self, task: TaskId) -> String;
 
+    /// Returns the edges, cells and collectibles of `task`. Backends that
+    /// can't inspect their tasks return `None`, which disables
+    /// [`TurboTasks::export_task_graph`][crate::TurboTasks::export_task_graph].
+    #[allow(unused_variables)]
+    fn task_snapshot(
+        &self,
+        task: TaskId,
+        turbo_tasks: &dyn TurboTasksBackendApi<Self>,
+    ) -> Option<TaskSnapshot> {
+        None
+    }
+
     /// Task-local state that stored inside of [`TurboTasksBackendApi`]. Constructed with
     /// [`Self::new_task_state`].
     ///
@@ -562,6 +600,19 @@ pub trait Backend: Sync + Send {
     /// detached futures ([`crate::TurboTasksApi::detached_for_testing`]) and [pseudo-tasks using
     /// `local_cells`][crate::function]. A [`RwLock`][std::sync::RwLock] is used to provide
     /// concurrent access.
+    /// Returns the aggregation edges of `task`. Backends that can't inspect
+    /// their tasks return `None`, which limits
+    /// [`TurboTasks::cancel_root_task`][crate::TurboTasks::cancel_root_task] to
+    /// the root task itself.
+    #[allow(unused_variables)]
+    fn task_meta_snapshot(
+        &self,
+        task: TaskId,
+        turbo_tasks: &dyn TurboTasksBackendApi<Self>,
+    ) -> Option<TaskMetaSnapshot> {
+        None
+    }
+
     type TaskState: Send + Sync + 'static;
 
     /// Constructs a new task-local [`Self::TaskState`] for the given `task_id`.
@@ -725,12 +776,20 @@ pub trait Backend: Sync + Send {
         // Do nothing by default
     }
 
+    /// Creates a root or once task. The task and all tasks scheduled by it
+    /// are executed with `priority`.
     fn create_transient_task(
         &self,
         task_type: TransientTaskType,
+        priority: TaskPriority,
         turbo_tasks: &dyn TurboTasksBackendApi<Self>,
     ) -> TaskId;
 
+    /// The priority a root or once task was created with. Returns `None` for
+    /// all other tasks, which inherit the priority of the execution that
+    /// schedules them.
+    fn transient_task_priority(&self, task: TaskId) -> Option<TaskPriority>;
+
     fn dispose_root_task(&self, task: TaskId, turbo_tasks: &dyn TurboTasksBackendApi<Self>);
 }
 
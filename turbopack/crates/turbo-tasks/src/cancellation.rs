@@ -0,0 +1,156 @@
+use std::{
+    fmt::Display,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use futures::pin_mut;
+use parking_lot::Mutex;
+use tokio::{select, sync::Notify};
+
+/// The error a task fails with when its execution took longer than the
+/// timeout of its function.
+///
+/// The timeout is set with `#[turbo_tasks::function(timeout_ms = ...)]`.
+/// Time spent waiting for other tasks doesn't count against the timeout.
+/// The task is not retried automatically, but is recomputed when it is
+/// invalidated.
+#[derive(Debug, Clone)]
+pub struct TaskTimeoutError {
+    pub task: String,
+    pub timeout: Duration,
+}
+
+impl Display for TaskTimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} timed out after {}ms",
+            self.task,
+            self.timeout.as_millis()
+        )
+    }
+}
+
+impl std::error::Error for TaskTimeoutError {}
+
+/// The error a task fails with when it was aborted by
+/// [`TurboTasks::cancel_root_task`][crate::TurboTasks::cancel_root_task]. The
+/// task is invalidated, so it's executed again when it's read the next time.
+#[derive(Debug, Clone)]
+pub struct TaskCancelledError {
+    pub task: String,
+}
+
+impl Display for TaskCancelledError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} was cancelled", self.task)
+    }
+}
+
+impl std::error::Error for TaskCancelledError {}
+
+/// Measures how long an execution of a task with a timeout has been running,
+/// excluding the time it spent waiting for other tasks. Otherwise a task
+/// would time out because its dependencies are slow, e. g. because they are
+/// queued behind other work.
+pub(crate) struct ExecutionTimer {
+    state: Mutex<ExecutionTimerState>,
+    /// Wakes up [ExecutionTimer::run] when the timeout is changed.
+    timeout_changed: Notify,
+}
+
+struct ExecutionTimerState {
+    timeout: Duration,
+    started: Instant,
+    /// The number of reads of other tasks the execution is waiting for.
+    waiting: usize,
+    /// When `waiting` became non-zero.
+    waiting_since: Instant,
+    /// The total time spent waiting, excluding the current wait.
+    waited: Duration,
+}
+
+impl ExecutionTimer {
+    pub fn new(timeout: Duration) -> Arc<Self> {
+        let now = Instant::now();
+        Arc::new(ExecutionTimer {
+            state: Mutex::new(ExecutionTimerState {
+                timeout,
+                started: now,
+                waiting: 0,
+                waiting_since: now,
+                waited: Duration::ZERO,
+            }),
+            timeout_changed: Notify::new(),
+        })
+    }
+
+    pub fn set_timeout(&self, timeout: Duration) {
+        self.state.lock().timeout = timeout;
+        // Stores a permit when `run` isn't waiting yet, e. g. because the
+        // timeout is changed while the future is polled.
+        self.timeout_changed.notify_one();
+    }
+
+    /// Pauses the timer until the returned guard is dropped.
+    pub fn wait(self: &Arc<Self>) -> DependencyWait {
+        let mut state = self.state.lock();
+        if state.waiting == 0 {
+            state.waiting_since = Instant::now();
+        }
+        state.waiting += 1;
+        DependencyWait(self.clone())
+    }
+
+    /// The time left until the timeout is reached.
+    fn remaining(&self) -> Duration {
+        let state = self.state.lock();
+        let now = Instant::now();
+        let mut waited = state.waited;
+        if state.waiting > 0 {
+            waited += now - state.waiting_since;
+        }
+        let active = (now - state.started).saturating_sub(waited);
+        state.timeout.saturating_sub(active)
+    }
+
+    /// Runs `future` until it completes or the timeout is reached, in which
+    /// case the result of `on_timeout` is returned.
+    pub async fn run<T>(
+        &self,
+        future: impl Future<Output = T>,
+        on_timeout: impl FnOnce(Duration) -> T,
+    ) -> T {
+        pin_mut!(future);
+        loop {
+            let remaining = self.remaining();
+            if remaining.is_zero() {
+                return on_timeout(self.state.lock().timeout);
+            }
+            // While the task waits for other tasks the timer doesn't advance,
+            // so the sleep might end before the timeout is reached.
+            select! {
+                result = &mut future => return result,
+                _ = tokio::time::sleep(remaining) => {}
+                _ = self.timeout_changed.notified() => {}
+            }
+        }
+    }
+}
+
+/// Keeps the [ExecutionTimer] of a task paused while the task waits for
+/// another task.
+pub(crate) struct DependencyWait(Arc<ExecutionTimer>);
+
+impl Drop for DependencyWait {
+    fn drop(&mut self) {
+        let mut state = self.0.state.lock();
+        state.waiting -= 1;
+        if state.waiting == 0 {
+            let waited = state.waiting_since.elapsed();
+            state.waited += waited;
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::mem::take;
+
+use indexmap::map::Entry;
+
+use crate::{util::StaticOrArc, FxIndexMap, FxIndexSet, InvalidationReason, TaskId};
+
+enum Cause {
+    /// The task was invalidated directly, e. g. by a file watcher.
+    Invalidated(Option<StaticOrArc<dyn InvalidationReason>>),
+    /// A cell or the output the task depends on was updated by another task.
+    Dependency(TaskId),
+}
+
+/// Records why tasks are re-executed during an update. Only the first cause
+/// is kept per task, which is usually the one that scheduled the task.
+#[derive(Default)]
+pub(crate) struct InvalidationRecorder {
+    causes: FxIndexMap<TaskId, Cause>,
+    executed: FxIndexSet<TaskId>,
+}
+
+impl InvalidationRecorder {
+    pub fn invalidated(
+        &mut self,
+        task: TaskId,
+        reason: Option<StaticOrArc<dyn InvalidationReason>>,
+    ) {
+        self.causes.entry(task).or_insert(Cause::Invalidated(reason));
+    }
+
+    pub fn notified(&mut self, source: TaskId, tasks: &[TaskId]) {
+        for &task in tasks {
+            if task == source {
+                continue;
+            }
+            if let Entry::Vacant(entry) = self.causes.entry(task) {
+                entry.insert(Cause::Dependency(source));
+            }
+        }
+    }
+
+    pub fn executed(&mut self, task: TaskId) {
+        self.executed.insert(task);
+    }
+
+    /// Returns all re-executed tasks since the last call and resets the
+    /// recorder. Tasks which were executed without being invalidated (e. g.
+    /// new tasks) are not included.
+    pub fn take(&mut self, describe: impl Fn(TaskId) -> String) -> Vec<RecomputedTask> {
+        let causes = take(&mut self.causes);
+        let executed = take(&mut self.executed);
+        let mut descriptions = FxIndexMap::default();
+        let mut describe = |task: TaskId| {
+            descriptions
+                .entry(task)
+                .or_insert_with(|| describe(task))
+                .clone()
+        };
+
+        let mut result = Vec::new();
+        for task in executed {
+            if !causes.contains_key(&task) {
+                continue;
+            }
+            let mut chain = vec![task];
+            let mut reason = None;
+            let mut current = task;
+            while let Some(cause) = causes.get(&current) {
+                match cause {
+                    Cause::Invalidated(r) => {
+                        reason = r.as_ref().map(|r| r.to_string());
+                        break;
+                    }
+                    Cause::Dependency(source) => {
+                        // Dependencies can be cyclic when a task updates a
+                        // cell it reads itself.
+                        if chain.contains(source) {
+                            break;
+                        }
+                        chain.push(*source);
+                        current = *source;
+                    }
+                }
+            }
+            chain.reverse();
+            result.push(RecomputedTask {
+                task,
+                description: describe(task),
+                reason,
+                chain: chain.into_iter().map(&mut describe).collect(),
+            });
+        }
+        result
+    }
+}
+
+/// A task that was re-executed during an update, as reported in
+/// [UpdateInfo::recomputed_tasks][crate::UpdateInfo::recomputed_tasks].
+#[derive(Debug, Clone)]
+pub struct RecomputedTask {
+    pub task: TaskId,
+    pub description: String,
+    /// The reason given for the invalidation that started the chain, if any.
+    pub reason: Option<String>,
+    /// Descriptions of the tasks the invalidation propagated through. Starts
+    /// with the directly invalidated task and ends with this task.
+    pub chain: Vec<String>,
+}
@@ -37,6 +37,7 @@
 #![feature(impl_trait_in_assoc_type)]
 
 pub mod backend;
+mod cancellation;
 mod capture_future;
 mod collectibles;
 mod completion;
@@ -48,6 +49,7 @@ pub mod graph;
 mod id;
 mod id_factory;
 mod invalidation;
+mod invalidation_recorder;
 mod join_iter_ext;
 mod key_value_pair;
 #[doc(hidden)]
@@ -60,6 +62,7 @@ mod once_map;
 mod output;
 pub mod persisted_graph;
 pub mod primitives;
+mod priority;
 mod raw_vc;
 mod rcstr;
 mod read_ref;
@@ -69,6 +72,7 @@ mod serialization_invalidation;
 pub mod small_duration;
 mod state;
 pub mod task;
+pub mod task_graph;
 pub mod trace;
 mod trait_helpers;
 mod trait_ref;
@@ -82,6 +86,7 @@ use std::hash::BuildHasherDefault;
 
 pub use anyhow::{Error, Result};
 use auto_hash_map::AutoSet;
+pub use cancellation::{TaskCancelledError, TaskTimeoutError};
 pub use collectibles::CollectiblesSource;
 pub use completion::{Completion, Completions};
 pub use display::ValueToString;
@@ -93,18 +98,20 @@ pub use invalidation::{
     get_invalidator, DynamicEqHash, InvalidationReason, InvalidationReasonKind,
     InvalidationReasonSet, Invalidator,
 };
+pub use invalidation_recorder::RecomputedTask;
 pub use join_iter_ext::{JoinIterExt, TryFlatJoinIterExt, TryJoinIterExt};
 pub use key_value_pair::KeyValuePair;
 pub use magic_any::MagicAny;
 pub use manager::{
     dynamic_call, dynamic_this_call, emit, mark_finished, mark_session_dependent, mark_stateful,
-    prevent_gc, run_once, run_once_with_reason, spawn_blocking, spawn_thread, trait_call,
-    turbo_tasks, turbo_tasks_scope, CurrentCellRef, ReadConsistency, TaskPersistence, TurboTasks,
-    TurboTasksApi, TurboTasksBackendApi, TurboTasksBackendApiExt, TurboTasksCallApi, Unused,
-    UpdateInfo,
+    prevent_gc, run_once, run_once_with_reason, set_task_timeout, spawn_blocking, spawn_thread,
+    trait_call, turbo_tasks, turbo_tasks_scope, with_task_priority, CurrentCellRef,
+    ReadConsistency, TaskPersistence, TurboTasks, TurboTasksApi, TurboTasksBackendApi,
+    TurboTasksBackendApiExt, TurboTasksCallApi, Unused, UpdateInfo,
 };
 pub use native_function::{FunctionMeta, NativeFunction};
 pub use output::OutputContent;
+pub use priority::TaskPriority;
 pub use raw_vc::{CellId, RawVc, ReadRawVcFuture, ResolveTypeError};
 pub use rcstr::RcStr;
 pub use read_ref::ReadRef;
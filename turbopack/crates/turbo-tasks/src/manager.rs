@@ -1,6 +1,7 @@
 use std::{
     any::Any,
     borrow::Cow,
+    cell::Cell,
     future::Future,
     hash::BuildHasherDefault,
     mem::take,
@@ -16,11 +17,12 @@ use std::{
 
 use anyhow::{anyhow, Result};
 use auto_hash_map::AutoMap;
+use dashmap::DashMap;
 use futures::FutureExt;
 use rustc_hash::FxHasher;
 use serde::{Deserialize, Serialize};
 use tokio::{runtime::Handle, select, task_local};
-use tokio_util::task::TaskTracker;
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
 use tracing::{info_span, instrument, trace_span, Instrument, Level};
 use turbo_tasks_malloc::TurboMalloc;
 
@@ -29,6 +31,7 @@ use crate::{
         Backend, CachedTaskType, CellContent, TaskCollectiblesMap, TaskExecutionSpec,
         TransientTaskType, TypedCellContent,
     },
+    cancellation::{DependencyWait, ExecutionTimer},
     capture_future::{self, CaptureFuture},
     event::{Event, EventListener},
     id::{
@@ -36,17 +39,21 @@ use crate::{
         TRANSIENT_TASK_BIT,
     },
     id_factory::{IdFactory, IdFactoryWithReuse},
+    invalidation_recorder::{InvalidationRecorder, RecomputedTask},
     magic_any::MagicAny,
+    priority::{PriorityGate, TaskPriority},
     raw_vc::{CellId, RawVc},
     registry::{self, get_function},
     serialization_invalidation::SerializationInvalidator,
     task::shared_reference::TypedSharedReference,
+    task_graph::{TaskGraph, TaskGraphOptions},
     trace::TraceRawVcs,
     trait_helpers::get_trait_method,
     util::StaticOrArc,
     vc::ReadVcFuture,
-    Completion, FunctionMeta, InvalidationReason, InvalidationReasonSet, SharedReference, TaskId,
-    TaskIdSet, ValueTypeId, Vc, VcRead, VcValueTrait, VcValueType,
+    Completion, FunctionMeta, FxIndexSet, InvalidationReason, InvalidationReasonSet,
+    SharedReference, TaskCancelledError, TaskId, TaskIdSet, TaskTimeoutError, ValueTypeId, Vc,
+    VcRead, VcValueTrait, VcValueType,
 };
 
 pub trait TurboTasksCallApi: Sync + Send {
@@ -122,6 +129,15 @@ pub trait TurboTasksApi: TurboTasksCallApi + Sync + Send {
     /// `schedule_notify_tasks_set()`
     fn notify_scheduled_tasks(&self);
 
+    /// See [TurboTasks::set_invalidation_recording].
+    fn set_invalidation_recording(&self, enabled: bool);
+
+    /// See [TurboTasks::take_recomputed_tasks].
+    fn take_recomputed_tasks(&self) -> Option<Vec<RecomputedTask>>;
+
+    /// See [TurboTasks::export_task_graph].
+    fn export_task_graph(&self, root: TaskId, options: &TaskGraphOptions) -> Option<TaskGraph>;
+
     fn try_read_task_output(
         &self,
         task: TaskId,
@@ -260,6 +276,12 @@ pub trait TurboTasksBackendApi<B: Backend + 'static>: TurboTasksCallApi + Sync +
     /// eventually call `invalidate_tasks()` on all tasks.
     fn schedule_notify_tasks_set(&self, tasks: &TaskIdSet);
 
+    /// Reports that `tasks` were invalidated because `source` changed. Backends
+    /// which mark dependent tasks dirty without going through
+    /// [TurboTasksBackendApi::schedule_notify_tasks] call this so the cause shows
+    /// up in [UpdateInfo::recomputed_tasks].
+    fn record_dependent_invalidation(&self, source: TaskId, tasks: &[TaskId]);
+
     /// Returns the duration from the start of the program to the given instant.
     fn program_duration_until(&self, instant: Instant) -> Duration;
 
@@ -316,6 +338,10 @@ pub struct UpdateInfo {
     pub duration: Duration,
     pub tasks: usize,
     pub reasons: InvalidationReasonSet,
+    /// The re-executed tasks with the reason and dependency chain that
+    /// caused the re-execution. Only available when enabled via
+    /// [TurboTasks::set_invalidation_recording].
+    pub recomputed_tasks: Option<Vec<RecomputedTask>>,
     #[allow(dead_code)]
     placeholder_for_future_fields: (),
 }
@@ -373,6 +399,16 @@ pub struct TurboTasks<B: Backend + 'static> {
     scheduled_tasks: AtomicUsize,
     start: Mutex<Option<Instant>>,
     aggregated_update: Mutex<(Option<(Duration, usize)>, InvalidationReasonSet)>,
+    record_invalidations: AtomicBool,
+    invalidation_recorder: Mutex<InvalidationRecorder>,
+    /// Cancellation tokens of all tasks that are currently executing. This is
+    /// sharded as it's updated at the start and the end of every execution.
+    running_tasks: DashMap<TaskId, CancellationToken, BuildHasherDefault<FxHasher>>,
+    /// Root tasks that were spawned and not disposed yet, and once tasks that
+    /// are still executing. [TurboTasks::cancel_root_task] keeps executions
+    /// alive that they still need.
+    root_tasks: Mutex<FxIndexSet<TaskId>>,
+    priority_gate: PriorityGate,
     event: Event,
     event_start: Event,
     event_foreground: Event,
@@ -413,11 +449,18 @@ struct CurrentGlobalTaskState {
     /// complete.
     local_task_tracker: TaskTracker,
 
+    /// The priority of this execution. Tasks scheduled by it inherit it.
+    priority: TaskPriority,
+
     backend_state: Box<dyn Any + Send + Sync>,
 }
 
 impl CurrentGlobalTaskState {
-    fn new(task_id: TaskId, backend_state: Box<dyn Any + Send + Sync>) -> Self {
+    fn new(
+        task_id: TaskId,
+        priority: TaskPriority,
+        backend_state: Box<dyn Any + Send + Sync>,
+    ) -> Self {
         Self {
             task_id,
             tasks_to_notify: Vec::new(),
@@ -425,6 +468,7 @@ impl CurrentGlobalTaskState {
             cell_counters: Some(AutoMap::default()),
             local_cells: Vec::new(),
             local_task_tracker: TaskTracker::new(),
+            priority,
             backend_state,
         }
     }
@@ -444,6 +488,9 @@ struct CurrentLocalTaskState {
     /// The function's metadata if this is a persistent task. Contains information about arguments
     /// passed to the `#[turbo_tasks::function(...)]` macro.
     function_meta: Option<&'static FunctionMeta>,
+
+    /// Measures the execution time if the function has a timeout.
+    timer: Option<Arc<ExecutionTimer>>,
 }
 
 impl CurrentLocalTaskState {
@@ -451,6 +498,27 @@ impl CurrentLocalTaskState {
         Self {
             execution_id,
             function_meta,
+            timer: function_meta
+                .and_then(|meta| meta.timeout)
+                .map(ExecutionTimer::new),
+        }
+    }
+}
+
+/// Removes a once task from [TurboTasks::root_tasks] when its execution ends.
+struct OnceTaskGuard<B: Backend + 'static> {
+    turbo_tasks: Weak<TurboTasks<B>>,
+    task_id: TaskId,
+}
+
+impl<B: Backend + 'static> Drop for OnceTaskGuard<B> {
+    fn drop(&mut self) {
+        if let Some(turbo_tasks) = self.turbo_tasks.upgrade() {
+            turbo_tasks
+                .root_tasks
+                .lock()
+                .unwrap()
+                .shift_remove(&self.task_id);
         }
     }
 }
@@ -464,6 +532,12 @@ task_local! {
     static CURRENT_LOCAL_TASK_STATE: CurrentLocalTaskState;
 }
 
+thread_local! {
+    /// The priority of tasks that are scheduled outside of a task execution on
+    /// this thread, see [`with_task_priority`].
+    static SCHEDULING_PRIORITY: Cell<Option<TaskPriority>> = const { Cell::new(None) };
+}
+
 impl<B: Backend + 'static> TurboTasks<B> {
     // TODO better lifetime management for turbo tasks
     // consider using unsafe for the task_local turbo tasks
@@ -487,6 +561,11 @@ impl<B: Backend + 'static> TurboTasks<B> {
             scheduled_tasks: AtomicUsize::new(0),
             start: Default::default(),
             aggregated_update: Default::default(),
+            record_invalidations: AtomicBool::new(false),
+            invalidation_recorder: Default::default(),
+            running_tasks: Default::default(),
+            root_tasks: Default::default(),
+            priority_gate: Default::default(),
             event: Event::new(|| "TurboTasks::event".to_string()),
             event_start: Event::new(|| "TurboTasks::event_start".to_string()),
             event_foreground: Event::new(|| "TurboTasks::event_foreground".to_string()),
@@ -503,6 +582,22 @@ impl<B: Backend + 'static> TurboTasks<B> {
 
     /// Creates a new root task
     pub fn spawn_root_task<T, F, Fut>(&self, functor: F) -> TaskId
+    where
+        T: Send,
+        F: Fn() -> Fut + Send + Sync + Clone + 'static,
+        Fut: Future<Output = Result<Vc<T>>> + Send,
+    {
+        self.spawn_root_task_with_priority(TaskPriority::default(), functor)
+    }
+
+    /// Creates a new root task that is executed with `priority`. All tasks
+    /// scheduled by it inherit the priority, unless they are read by work of
+    /// higher priority.
+    pub fn spawn_root_task_with_priority<T, F, Fut>(
+        &self,
+        priority: TaskPriority,
+        functor: F,
+    ) -> TaskId
     where
         T: Send,
         F: Fn() -> Fut + Send + Sync + Clone + 'static,
@@ -513,16 +608,81 @@ impl<B: Backend + 'static> TurboTasks<B> {
                 let functor = functor.clone();
                 Box::pin(async move { Ok(functor().await?.node) })
             })),
+            priority,
             self,
         );
+        self.root_tasks.lock().unwrap().insert(id);
         self.schedule(id);
         id
     }
 
     pub fn dispose_root_task(&self, task_id: TaskId) {
+        self.root_tasks.lock().unwrap().shift_remove(&task_id);
         self.backend.dispose_root_task(task_id, self);
     }
 
+    /// Disposes a root task and aborts all executions of tasks below it at
+    /// their next await point. Tasks that are also below another root task or
+    /// a once task that is still executing keep executing. Aborted tasks fail
+    /// with a [`TaskCancelledError`] and are invalidated, so they are executed
+    /// again when they are read the next time.
+    ///
+    /// Only the tasks that are currently executing are inspected. The root
+    /// tasks they are below are found by walking up the aggregation graph,
+    /// which doesn't restore the data of evicted tasks. This still might take
+    /// a while for large graphs, so it shouldn't be called from a thread that
+    /// needs to stay responsive.
+    ///
+    /// When the backend doesn't support inspecting tasks, only the root task
+    /// itself is aborted.
+    pub fn cancel_root_task(&self, task_id: TaskId) {
+        let other_roots = {
+            let mut root_tasks = self.root_tasks.lock().unwrap();
+            root_tasks.shift_remove(&task_id);
+            root_tasks.clone()
+        };
+        let running_tasks = self
+            .running_tasks
+            .iter()
+            .map(|entry| *entry.key())
+            .collect::<Vec<_>>();
+        let tasks = running_tasks
+            .into_iter()
+            .filter(|&task| task == task_id || self.is_only_below(task, task_id, &other_roots))
+            .collect::<Vec<_>>();
+
+        self.dispose_root_task(task_id);
+
+        for task in tasks {
+            if let Some(token) = self.running_tasks.get(&task) {
+                token.cancel();
+            }
+        }
+    }
+
+    /// Returns whether `task` is below `root`, but not below any of
+    /// `other_roots`.
+    fn is_only_below(&self, task: TaskId, root: TaskId, other_roots: &FxIndexSet<TaskId>) -> bool {
+        let mut below_root = false;
+        let mut visited = FxIndexSet::default();
+        let mut stack = vec![task];
+        while let Some(task) = stack.pop() {
+            let Some(snapshot) = self.backend.task_meta_snapshot(task, self) else {
+                return false;
+            };
+            for upper in snapshot.uppers {
+                if other_roots.contains(&upper) {
+                    return false;
+                }
+                below_root |= upper == root;
+                if visited.insert(upper) {
+                    stack.push(upper);
+                }
+            }
+        }
+        below_root
+    }
+
     // TODO make sure that all dependencies settle before reading them
     /// Creates a new root task, that is only executed once.
     /// Dependencies will not invalidate the task.
@@ -532,10 +692,36 @@ impl<B: Backend + 'static> TurboTasks<B> {
         T: Send,
         Fut: Future<Output = Result<Vc<T>>> + Send + 'static,
     {
+        self.spawn_once_task_with_priority(TaskPriority::default(), future)
+    }
+
+    /// Like [`TurboTasks::spawn_once_task`], but executes the task and all
+    /// tasks scheduled by it with `priority`.
+    pub fn spawn_once_task_with_priority<T, Fut>(
+        &self,
+        priority: TaskPriority,
+        future: Fut,
+    ) -> TaskId
+    where
+        T: Send,
+        Fut: Future<Output = Result<Vc<T>>> + Send + 'static,
+    {
+        let this = self.this.clone();
         let id = self.backend.create_transient_task(
-            TransientTaskType::Once(Box::pin(async move { Ok(future.await?.node) })),
+            TransientTaskType::Once(Box::pin(async move {
+                // Also removes the task when its execution is aborted.
+                let _guard = OnceTaskGuard {
+                    turbo_tasks: this,
+                    task_id: current_task("once tasks"),
+                };
+                Ok(future.await?.node)
+            })),
+            priority,
             self,
         );
+        // Like root tasks, once tasks keep the tasks they read from being
+        // cancelled while they are executing.
+        self.root_tasks.lock().unwrap().insert(id);
         self.schedule(id);
         id
     }
@@ -543,9 +729,19 @@ impl<B: Backend + 'static> TurboTasks<B> {
     pub async fn run_once<T: TraceRawVcs + Send + 'static>(
         &self,
         future: impl Future<Output = Result<T>> + Send + 'static,
+    ) -> Result<T> {
+        self.run_once_with_priority(TaskPriority::default(), future)
+            .await
+    }
+
+    /// Like [`TurboTasks::run_once`], but executes the work with `priority`.
+    pub async fn run_once_with_priority<T: TraceRawVcs + Send + 'static>(
+        &self,
+        priority: TaskPriority,
+        future: impl Future<Output = Result<T>> + Send + 'static,
     ) -> Result<T> {
         let (tx, rx) = tokio::sync::oneshot::channel();
-        let task_id = self.spawn_once_task(async move {
+        let task_id = self.spawn_once_task_with_priority(priority, async move {
             let result = future.await?;
             tx.send(result)
                 .map_err(|_| anyhow!("unable to send result"))?;
@@ -755,25 +951,34 @@ impl<B: Backend + 'static> TurboTasks<B> {
     pub(crate) fn schedule(&self, task_id: TaskId) {
         self.begin_primary_job();
         self.scheduled_tasks.fetch_add(1, Ordering::AcqRel);
+        self.record_invalidation(|recorder| recorder.executed(task_id));
 
         #[cfg(feature = "tokio_tracing")]
         let description = self.backend.get_task_description(task_id);
 
+        let priority = self
+            .backend
+            .transient_task_priority(task_id)
+            .unwrap_or_else(current_task_priority);
+
         let this = self.pin();
         let future = async move {
+            let priority = this.priority_gate.enter(task_id, priority).await;
             let mut schedule_again = true;
             while schedule_again {
                 let backend_state = this.backend.new_task_state(task_id);
                 let global_task_state = Arc::new(RwLock::new(CurrentGlobalTaskState::new(
                     task_id,
+                    priority,
                     Box::new(backend_state),
                 )));
-                let local_task_state = CurrentLocalTaskState::new(
-                    this.execution_id_factory.get(),
-                    this.backend
-                        .try_get_function_id(task_id)
-                        .map(|func_id| &get_function(func_id).function_meta),
-                );
+                let function_meta = this
+                    .backend
+                    .try_get_function_id(task_id)
+                    .map(|func_id| &get_function(func_id).function_meta);
+                let local_task_state =
+                    CurrentLocalTaskState::new(this.execution_id_factory.get(), function_meta);
+                let timer = local_task_state.timer.clone();
                 let single_execution_future = async {
                     if this.stopped.load(Ordering::Acquire) {
                         return false;
@@ -785,9 +990,38 @@ impl<B: Backend + 'static> TurboTasks<B> {
                         return false;
                     };
 
+                    let cancellation = CancellationToken::new();
+                    this.running_tasks.insert(task_id, cancellation.clone());
+                    let future = async {
+                        let future = async {
+                            select! {
+                                result = future => result,
+                                _ = cancellation.cancelled() => Err(TaskCancelledError {
+                                    task: this.backend.get_task_description(task_id),
+                                }
+                                .into()),
+                            }
+                        };
+                        match &timer {
+                            Some(timer) => {
+                                timer
+                                    .run(future, |timeout| {
+                                        Err(TaskTimeoutError {
+                                            task: this.backend.get_task_description(task_id),
+                                            timeout,
+                                        }
+                                        .into())
+                                    })
+                                    .await
+                            }
+                            None => future.await,
+                        }
+                    };
+
                     async {
                         let (result, duration, memory_usage) =
                             CaptureFuture::new(AssertUnwindSafe(future).catch_unwind()).await;
+                        this.running_tasks.remove(&task_id);
 
                         // wait for all spawned local tasks using `local_cells` to finish
                         let ltt = CURRENT_GLOBAL_TASK_STATE
@@ -816,6 +1050,12 @@ impl<B: Backend + 'static> TurboTasks<B> {
                         );
                         // task_execution_completed might need to notify tasks
                         this.notify_scheduled_tasks();
+                        if cancellation.is_cancelled() {
+                            // The result of a cancelled execution must not be
+                            // cached, so the task is computed again when it's
+                            // read the next time.
+                            this.backend.invalidate_task(task_id, &*this);
+                        }
                         schedule_again
                     }
                     .instrument(span)
@@ -828,6 +1068,7 @@ impl<B: Backend + 'static> TurboTasks<B> {
                     )
                     .await;
             }
+            this.priority_gate.leave(priority);
             this.finish_primary_job();
             anyhow::Ok(())
         };
@@ -917,6 +1158,45 @@ impl<B: Backend + 'static> TurboTasks<B> {
             .await;
     }
 
+    /// Enables or disables recording which tasks are re-executed and why.
+    /// The result is reported in [UpdateInfo::recomputed_tasks]. Recording
+    /// adds overhead to every invalidation, so it should only be enabled for
+    /// debugging.
+    pub fn set_invalidation_recording(&self, enabled: bool) {
+        self.record_invalidations.store(enabled, Ordering::Release);
+        if !enabled {
+            *self.invalidation_recorder.lock().unwrap() = Default::default();
+        }
+    }
+
+    fn record_invalidation(&self, f: impl FnOnce(&mut InvalidationRecorder)) {
+        if self.record_invalidations.load(Ordering::Acquire) {
+            f(&mut self.invalidation_recorder.lock().unwrap());
+        }
+    }
+
+    /// Returns the tasks re-executed since the last call without waiting for
+    /// an update to finish. Returns `None` when recording is disabled. Note
+    /// that this also clears what [UpdateInfo::recomputed_tasks] reports.
+    pub fn take_recomputed_tasks(&self) -> Option<Vec<RecomputedTask>> {
+        if !self.record_invalidations.load(Ordering::Acquire) {
+            return None;
+        }
+        let mut recorder = self.invalidation_recorder.lock().unwrap();
+        Some(recorder.take(|task| self.backend.get_task_description(task)))
+    }
+
+    /// Exports the subgraph reachable from `root` along child and dependency
+    /// edges. Returns `None` when the backend doesn't support inspecting
+    /// tasks.
+    pub fn export_task_graph(&self, root: TaskId, options: &TaskGraphOptions) -> Option<TaskGraph> {
+        self.backend.task_snapshot(root, self)?;
+        Some(TaskGraph::build(root, options, |task| {
+            let snapshot = self.backend.task_snapshot(task, self)?;
+            Some((self.backend.get_task_description(task), snapshot))
+        }))
+    }
+
     pub fn get_in_progress_count(&self) -> usize {
         self.currently_scheduled_tasks.load(Ordering::Acquire)
     }
@@ -990,6 +1270,7 @@ impl<B: Backend + 'static> TurboTasks<B> {
                         duration,
                         tasks,
                         reasons: take(reason_set),
+                        recomputed_tasks: self.take_recomputed_tasks(),
                         placeholder_for_future_fields: (),
                     });
                 } else {
@@ -1037,6 +1318,7 @@ impl<B: Backend + 'static> TurboTasks<B> {
                 duration,
                 tasks,
                 reasons: take(reason_set),
+                recomputed_tasks: self.take_recomputed_tasks(),
                 placeholder_for_future_fields: (),
             })
         } else {
@@ -1138,16 +1420,18 @@ impl<B: Backend + 'static> TurboTasks<B> {
     }
 
     fn finish_current_task_state(&self) -> bool {
-        let (stateful, tasks) = CURRENT_GLOBAL_TASK_STATE.with(|cell| {
+        let (task_id, stateful, tasks) = CURRENT_GLOBAL_TASK_STATE.with(|cell| {
             let CurrentGlobalTaskState {
+                task_id,
                 tasks_to_notify,
                 stateful,
                 ..
             } = &mut *cell.write().unwrap();
-            (*stateful, take(tasks_to_notify))
+            (*task_id, *stateful, take(tasks_to_notify))
         });
 
         if !tasks.is_empty() {
+            self.record_invalidation(|recorder| recorder.notified(task_id, &tasks));
             self.backend.invalidate_tasks(&tasks, self);
         }
         stateful
@@ -1223,12 +1507,14 @@ impl<B: Backend + 'static> TurboTasksCallApi for TurboTasks<B> {
     ) -> TaskId {
         {
             let (_, reason_set) = &mut *self.aggregated_update.lock().unwrap();
-            reason_set.insert(reason);
+            reason_set.insert(reason.clone());
         }
-        self.spawn_once_task(async move {
+        let task = self.spawn_once_task(async move {
             future.await?;
             Ok(Completion::new())
-        })
+        });
+        self.record_invalidation(|recorder| recorder.invalidated(task, Some(reason)));
+        task
     }
 
     #[track_caller]
@@ -1253,6 +1539,7 @@ impl<B: Backend + 'static> TurboTasksApi for TurboTasks<B> {
 
     #[instrument(level = Level::INFO, skip_all, name = "invalidate")]
     fn invalidate(&self, task: TaskId) {
+        self.record_invalidation(|recorder| recorder.invalidated(task, None));
         self.backend.invalidate_task(task, self);
     }
 
@@ -1260,8 +1547,9 @@ impl<B: Backend + 'static> TurboTasksApi for TurboTasks<B> {
     fn invalidate_with_reason(&self, task: TaskId, reason: StaticOrArc<dyn InvalidationReason>) {
         {
             let (_, reason_set) = &mut *self.aggregated_update.lock().unwrap();
-            reason_set.insert(reason);
+            reason_set.insert(reason.clone());
         }
+        self.record_invalidation(|recorder| recorder.invalidated(task, Some(reason)));
         self.backend.invalidate_task(task, self);
     }
 
@@ -1271,26 +1559,46 @@ impl<B: Backend + 'static> TurboTasksApi for TurboTasks<B> {
 
     fn notify_scheduled_tasks(&self) {
         let _ = CURRENT_GLOBAL_TASK_STATE.try_with(|cell| {
-            let tasks = {
+            let (task_id, tasks) = {
                 let CurrentGlobalTaskState {
-                    tasks_to_notify, ..
+                    task_id,
+                    tasks_to_notify,
+                    ..
                 } = &mut *cell.write().unwrap();
-                take(tasks_to_notify)
+                (*task_id, take(tasks_to_notify))
             };
             if tasks.is_empty() {
                 return;
             }
+            self.record_invalidation(|recorder| recorder.notified(task_id, &tasks));
             self.backend.invalidate_tasks(&tasks, self);
         });
     }
 
+    fn set_invalidation_recording(&self, enabled: bool) {
+        self.set_invalidation_recording(enabled)
+    }
+
+    fn take_recomputed_tasks(&self) -> Option<Vec<RecomputedTask>> {
+        self.take_recomputed_tasks()
+    }
+
+    fn export_task_graph(&self, root: TaskId, options: &TaskGraphOptions) -> Option<TaskGraph> {
+        self.export_task_graph(root, options)
+    }
+
     fn try_read_task_output(
         &self,
         task: TaskId,
         consistency: ReadConsistency,
     ) -> Result<Result<RawVc, EventListener>> {
-        self.backend
-            .try_read_task_output(task, current_task("reading Vcs"), consistency, self)
+        let result =
+            self.backend
+                .try_read_task_output(task, current_task("reading Vcs"), consistency, self);
+        if matches!(result, Ok(Err(_))) {
+            self.priority_gate.boost(task, current_task_priority());
+        }
+        result
     }
 
     fn try_read_task_output_untracked(
@@ -1307,8 +1615,13 @@ impl<B: Backend + 'static> TurboTasksApi for TurboTasks<B> {
         task: TaskId,
         index: CellId,
     ) -> Result<Result<TypedCellContent, EventListener>> {
-        self.backend
-            .try_read_task_cell(task, index, current_task("reading Vcs"), self)
+        let result =
+            self.backend
+                .try_read_task_cell(task, index, current_task("reading Vcs"), self);
+        if matches!(result, Ok(Err(_))) {
+            self.priority_gate.boost(task, current_task_priority());
+        }
+        result
     }
 
     fn try_read_task_cell_untracked(
@@ -1531,6 +1844,10 @@ impl<B: Backend + 'static> TurboTasksBackendApi<B> for TurboTasks<B> {
         };
     }
 
+    fn record_dependent_invalidation(&self, source: TaskId, tasks: &[TaskId]) {
+        self.record_invalidation(|recorder| recorder.notified(source, tasks));
+    }
+
     #[track_caller]
     fn schedule(&self, task: TaskId) {
         self.schedule(task)
@@ -1573,6 +1890,33 @@ impl<B: Backend + 'static> TurboTasksBackendApi<B> for TurboTasks<B> {
     }
 }
 
+/// Executes `func` with tasks scheduled by it using `priority`.
+///
+/// This applies to tasks scheduled outside of a task execution, e. g. because
+/// an invalidation from a file watcher made them dirty. They are executed with
+/// `priority` instead of the default priority.
+pub fn with_task_priority<T>(priority: TaskPriority, func: impl FnOnce() -> T) -> T {
+    /// Restores the previous priority, also when `func` panics.
+    struct RestorePriority(Option<TaskPriority>);
+
+    impl Drop for RestorePriority {
+        fn drop(&mut self) {
+            SCHEDULING_PRIORITY.set(self.0);
+        }
+    }
+
+    let _restore = RestorePriority(SCHEDULING_PRIORITY.replace(Some(priority)));
+    func()
+}
+
+/// The priority of the current execution. Outside of a task it's the priority
+/// set by [`with_task_priority`] or the default priority.
+fn current_task_priority() -> TaskPriority {
+    CURRENT_GLOBAL_TASK_STATE
+        .try_with(|ts| ts.read().unwrap().priority)
+        .unwrap_or_else(|_| SCHEDULING_PRIORITY.get().unwrap_or_default())
+}
+
 pub(crate) fn current_task(from: &str) -> TaskId {
     match CURRENT_GLOBAL_TASK_STATE.try_with(|ts| ts.read().unwrap().task_id) {
         Ok(id) => id,
@@ -1683,6 +2027,7 @@ pub fn with_turbo_tasks_for_testing<T>(
         CURRENT_GLOBAL_TASK_STATE.scope(
             Arc::new(RwLock::new(CurrentGlobalTaskState::new(
                 current_task,
+                TaskPriority::default(),
                 Box::new(()),
             ))),
             CURRENT_LOCAL_TASK_STATE.scope(CurrentLocalTaskState::new(execution_id, None), f),
@@ -1782,7 +2127,10 @@ pub(crate) async fn read_task_output(
     loop {
         match this.try_read_task_output(id, consistency)? {
             Ok(result) => return Ok(result),
-            Err(listener) => listener.await,
+            Err(listener) => {
+                let _wait = dependency_wait();
+                listener.await
+            }
         }
     }
 }
@@ -1797,7 +2145,10 @@ pub(crate) async fn read_task_output_untracked(
     loop {
         match this.try_read_task_output_untracked(id, consistency)? {
             Ok(result) => return Ok(result),
-            Err(listener) => listener.await,
+            Err(listener) => {
+                let _wait = dependency_wait();
+                listener.await
+            }
         }
     }
 }
@@ -1810,7 +2161,10 @@ pub(crate) async fn read_task_cell(
     loop {
         match this.try_read_task_cell(id, index)? {
             Ok(result) => return Ok(result),
-            Err(listener) => listener.await,
+            Err(listener) => {
+                let _wait = dependency_wait();
+                listener.await
+            }
         }
     }
 }
@@ -1998,6 +2352,28 @@ pub fn find_cell_by_type(ty: ValueTypeId) -> CurrentCellRef {
     })
 }
 
+/// Changes the timeout of the current execution of a function.
+///
+/// The function must be declared with
+/// `#[turbo_tasks::function(timeout_ms = ...)]`, e. g. to make the timeout
+/// configurable. Has no effect in functions without a timeout.
+pub fn set_task_timeout(timeout: Duration) {
+    CURRENT_LOCAL_TASK_STATE.with(|ts| {
+        if let Some(timer) = &ts.timer {
+            timer.set_timeout(timeout);
+        }
+    })
+}
+
+/// Pauses the timeout of the current task while the returned guard is alive.
+/// Returns `None` outside of tasks and for functions without a timeout.
+pub(crate) fn dependency_wait() -> Option<DependencyWait> {
+    CURRENT_LOCAL_TASK_STATE
+        .try_with(|ts| ts.timer.as_ref().map(|timer| timer.wait()))
+        .ok()
+        .flatten()
+}
+
 pub(crate) fn try_get_function_meta() -> Option<&'static FunctionMeta> {
     CURRENT_LOCAL_TASK_STATE.with(|ts| ts.function_meta)
 }
@@ -2064,3 +2440,23 @@ pub(crate) fn assert_execution_id(execution_id: ExecutionId) {
         );
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::panic::catch_unwind;
+
+    use super::{current_task_priority, with_task_priority};
+    use crate::TaskPriority;
+
+    #[test]
+    fn task_priority_is_restored_after_panic() {
+        let result = catch_unwind(|| {
+            with_task_priority(TaskPriority::Hmr, || {
+                assert_eq!(current_task_priority(), TaskPriority::Hmr);
+                panic!("watcher callback failed");
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(current_task_priority(), TaskPriority::default());
+    }
+}
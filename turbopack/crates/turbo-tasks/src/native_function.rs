@@ -1,4 +1,4 @@
-use std::{fmt::Debug, hash::Hash, pin::Pin};
+use std::{fmt::Debug, hash::Hash, pin::Pin, time::Duration};
 
 use anyhow::{Context, Result};
 use futures::Future;
@@ -90,6 +90,9 @@ pub struct FunctionMeta {
     /// cached across task executions. Cells can be converted to their non-local
     /// versions by calling `Vc::resolve`.
     pub local_cells: bool,
+    /// Aborts an execution of the function after this duration. The task
+    /// then fails with a [`TaskTimeoutError`][crate::TaskTimeoutError].
+    pub timeout: Option<Duration>,
 }
 
 /// A native (rust) turbo-tasks function. It's used internally by
@@ -0,0 +1,166 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+use crate::{event::Event, TaskId};
+
+/// The priority of a task execution. Root tasks are marked with a priority
+/// (see [`TurboTasks::spawn_root_task_with_priority`]) and tasks scheduled by
+/// an execution inherit the priority of that execution.
+///
+/// Executions of lower priority are delayed while executions of higher
+/// priority are running.
+///
+/// [`TurboTasks::spawn_root_task_with_priority`]: crate::TurboTasks::spawn_root_task_with_priority
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum TaskPriority {
+    /// Work that is not needed right now, e. g. telemetry or eager
+    /// compilation.
+    Background,
+    /// Updates for connected clients, e. g. HMR.
+    Hmr,
+    /// Work a user is actively waiting for, e. g. a page request.
+    #[default]
+    Interactive,
+}
+
+impl TaskPriority {
+    const ALL: [TaskPriority; 3] = [
+        TaskPriority::Background,
+        TaskPriority::Hmr,
+        TaskPriority::Interactive,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// An execution is never delayed longer than this, so work of higher
+    /// priority that (indirectly) waits for it can't deadlock.
+    fn max_delay(self) -> Duration {
+        match self {
+            TaskPriority::Background => Duration::from_secs(1),
+            TaskPriority::Hmr => Duration::from_millis(200),
+            TaskPriority::Interactive => Duration::ZERO,
+        }
+    }
+}
+
+/// Delays executions while executions of higher priority are running.
+pub(crate) struct PriorityGate {
+    running: [AtomicUsize; 3],
+    /// Executions waiting in [`PriorityGate::enter`] with their current
+    /// priority. The priority is raised when a task of higher priority reads
+    /// them.
+    waiting: Mutex<FxHashMap<TaskId, TaskPriority>>,
+    waiting_count: AtomicUsize,
+    event: Event,
+}
+
+impl Default for PriorityGate {
+    fn default() -> Self {
+        Self {
+            running: Default::default(),
+            waiting: Default::default(),
+            waiting_count: AtomicUsize::new(0),
+            event: Event::new(|| "PriorityGate::event".to_string()),
+        }
+    }
+}
+
+impl PriorityGate {
+    fn is_blocked(&self, priority: TaskPriority) -> bool {
+        TaskPriority::ALL
+            .iter()
+            .filter(|&&other| other > priority)
+            .any(|other| self.running[other.index()].load(Ordering::Acquire) > 0)
+    }
+
+    /// Waits until no execution of higher priority is running and returns the
+    /// priority the execution has to run with, which might have been raised
+    /// in the meantime. Every call must be followed by a call to
+    /// [`PriorityGate::leave`].
+    pub async fn enter(&self, task: TaskId, mut priority: TaskPriority) -> TaskPriority {
+        if self.is_blocked(priority) {
+            let deadline = Instant::now() + priority.max_delay();
+            self.waiting.lock().unwrap().insert(task, priority);
+            self.waiting_count.fetch_add(1, Ordering::AcqRel);
+            loop {
+                let listener = self.event.listen();
+                priority = self.waiting.lock().unwrap()[&task];
+                if !self.is_blocked(priority)
+                    || tokio::time::timeout_at(deadline, listener).await.is_err()
+                {
+                    break;
+                }
+            }
+            priority = self.waiting.lock().unwrap().remove(&task).unwrap();
+            self.waiting_count.fetch_sub(1, Ordering::AcqRel);
+        }
+        self.running[priority.index()].fetch_add(1, Ordering::AcqRel);
+        priority
+    }
+
+    pub fn leave(&self, priority: TaskPriority) {
+        if self.running[priority.index()].fetch_sub(1, Ordering::AcqRel) == 1
+            && self.waiting_count.load(Ordering::Acquire) > 0
+        {
+            self.event.notify(usize::MAX);
+        }
+    }
+
+    /// Raises the priority of `task` when it's waiting for executions of
+    /// higher priority, because an execution of `priority` depends on it.
+    pub fn boost(&self, task: TaskId, priority: TaskPriority) {
+        if self.waiting_count.load(Ordering::Acquire) == 0 {
+            return;
+        }
+        let mut waiting = self.waiting.lock().unwrap();
+        if let Some(current) = waiting.get_mut(&task) {
+            if *current < priority {
+                *current = priority;
+                drop(waiting);
+                self.event.notify(usize::MAX);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use super::{PriorityGate, TaskPriority};
+    use crate::TaskId;
+
+    #[allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+    #[tokio::test]
+    async fn boost_releases_waiting_task() {
+        let gate = Arc::new(PriorityGate::default());
+        let interactive = gate.enter(TaskId::from(1), TaskPriority::Interactive).await;
+
+        let background = tokio::spawn({
+            let gate = gate.clone();
+            async move { gate.enter(TaskId::from(2), TaskPriority::Background).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!background.is_finished());
+
+        gate.boost(TaskId::from(2), TaskPriority::Interactive);
+        assert_eq!(background.await.unwrap(), TaskPriority::Interactive);
+
+        gate.leave(interactive);
+        gate.leave(TaskPriority::Interactive);
+        assert!(!gate.is_blocked(TaskPriority::Background));
+    }
+}
@@ -14,11 +14,12 @@ use thiserror::Error;
 
 use crate::{
     backend::{CellContent, TypedCellContent},
+    cancellation::DependencyWait,
     event::EventListener,
     id::{ExecutionId, LocalCellId, LocalTaskId},
     manager::{
-        assert_execution_id, current_task, read_local_cell, read_local_output, read_task_cell,
-        read_task_output, TurboTasksApi,
+        assert_execution_id, current_task, dependency_wait, read_local_cell, read_local_output,
+        read_task_cell, read_task_output, TurboTasksApi,
     },
     registry::{self, get_value_type},
     turbo_tasks, CollectiblesSource, ReadConsistency, TaskId, TraitTypeId, ValueType, ValueTypeId,
@@ -278,6 +279,8 @@ pub struct ReadRawVcFuture {
     current: RawVc,
     untracked: bool,
     listener: Option<EventListener>,
+    /// Pauses the timeout of the current task while waiting for `listener`.
+    wait: Option<DependencyWait>,
 }
 
 impl ReadRawVcFuture {
@@ -289,6 +292,7 @@ impl ReadRawVcFuture {
             current: vc,
             untracked: false,
             listener: None,
+            wait: None,
         }
     }
 
@@ -300,6 +304,7 @@ impl ReadRawVcFuture {
             current: vc,
             untracked: true,
             listener: None,
+            wait: None,
         }
     }
 
@@ -311,6 +316,7 @@ impl ReadRawVcFuture {
             current: vc,
             untracked: true,
             listener: None,
+            wait: None,
         }
     }
 
@@ -322,6 +328,7 @@ impl ReadRawVcFuture {
             current: vc,
             untracked: false,
             listener: None,
+            wait: None,
         }
     }
 
@@ -333,6 +340,7 @@ impl ReadRawVcFuture {
             current: vc,
             untracked: true,
             listener: None,
+            wait: None,
         }
     }
 }
@@ -352,6 +360,7 @@ impl Future for ReadRawVcFuture {
                     return Poll::Pending;
                 }
                 this.listener = None;
+                this.wait = None;
             }
             let mut listener = match this.current {
                 RawVc::TaskOutput(task) => {
@@ -423,6 +432,7 @@ impl Future for ReadRawVcFuture {
                 Poll::Ready(_) => continue,
                 Poll::Pending => {
                     this.listener = Some(listener);
+                    this.wait = dependency_wait();
                     return Poll::Pending;
                 }
             };
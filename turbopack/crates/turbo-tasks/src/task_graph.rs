@@ -0,0 +1,378 @@
+//! Export of the live task graph for debugging, e. g. to find out why too
+//! many tasks are invalidated or which tasks keep a lot of cells alive.
+
+use std::{collections::VecDeque, fmt::Write};
+
+use serde::Serialize;
+
+use crate::{registry, CellId, FxIndexMap, FxIndexSet, RawVc, TaskId};
+
+/// Limits the exported part of the task graph.
+#[derive(Debug, Default, Clone)]
+pub struct TaskGraphOptions {
+    /// Maximum number of edges between the root task and an exported task.
+    pub max_depth: Option<usize>,
+    /// When not empty, only tasks whose description contains one of these
+    /// strings are exported (in addition to the root task). The graph is
+    /// still traversed through the other tasks, and paths through them are
+    /// exported as [TaskGraphEdgeKind::Indirect] edges.
+    pub function_names: Vec<String>,
+}
+
+impl TaskGraphOptions {
+    fn matches(&self, description: &str) -> bool {
+        self.function_names.is_empty()
+            || self
+                .function_names
+                .iter()
+                .any(|name| description.contains(name.as_str()))
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskGraphCollectible {
+    pub trait_name: String,
+    pub collectible: String,
+    pub count: i32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskGraphNode {
+    pub id: TaskId,
+    pub description: String,
+    /// Distance from the root task.
+    pub depth: usize,
+    pub cells: Vec<String>,
+    pub collectibles: Vec<TaskGraphCollectible>,
+    /// The edges of this task were not followed because of
+    /// [TaskGraphOptions::max_depth].
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum TaskGraphEdgeKind {
+    Child,
+    /// `from` read the output of `to`.
+    Output,
+    /// `from` read a cell of `to`.
+    Cell {
+        cell: String,
+    },
+    /// `from` read the collectibles of `to`.
+    #[serde(rename_all = "camelCase")]
+    Collectibles {
+        trait_name: String,
+    },
+    /// `from` reaches `to` only through tasks that were filtered out by
+    /// [TaskGraphOptions::function_names].
+    #[serde(rename_all = "camelCase")]
+    Indirect {
+        hidden_tasks: usize,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskGraphEdge {
+    pub from: TaskId,
+    pub to: TaskId,
+    #[serde(flatten)]
+    pub kind: TaskGraphEdgeKind,
+}
+
+/// A subgraph of the task graph, as returned by
+/// [`TurboTasks::export_task_graph`][crate::TurboTasks::export_task_graph].
+#[derive(Debug, Serialize)]
+pub struct TaskGraph {
+    pub root: TaskId,
+    pub nodes: Vec<TaskGraphNode>,
+    pub edges: Vec<TaskGraphEdge>,
+}
+
+fn cell_name(cell: CellId) -> String {
+    format!(
+        "{}#{}",
+        registry::get_value_type(cell.type_id).name,
+        cell.index
+    )
+}
+
+fn raw_vc_name(vc: RawVc) -> String {
+    match vc {
+        RawVc::TaskOutput(task) => format!("output of {task}"),
+        RawVc::TaskCell(task, cell) => format!("{} of {task}", cell_name(cell)),
+        RawVc::LocalOutput(task, local_task) => format!("{local_task} of {task}"),
+        RawVc::LocalCell(execution, cell) => format!("{cell} of {execution}"),
+    }
+}
+
+/// Keeps the edges between `exported` tasks and replaces each path through
+/// tasks that are not exported by an [TaskGraphEdgeKind::Indirect] edge,
+/// unless the ends are connected directly anyway.
+fn collapse_hidden_tasks(
+    exported: &FxIndexSet<TaskId>,
+    edges: Vec<TaskGraphEdge>,
+) -> Vec<TaskGraphEdge> {
+    let mut outgoing: FxIndexMap<TaskId, Vec<TaskGraphEdge>> = FxIndexMap::default();
+    for edge in edges {
+        outgoing.entry(edge.from).or_default().push(edge);
+    }
+
+    let mut edges = Vec::new();
+    for &from in exported {
+        let Some(direct_edges) = outgoing.get(&from) else {
+            continue;
+        };
+        let mut reached = FxIndexSet::default();
+        reached.insert(from);
+        let mut queue = VecDeque::new();
+        for edge in direct_edges {
+            if exported.contains(&edge.to) {
+                reached.insert(edge.to);
+                edges.push(edge.clone());
+            } else if reached.insert(edge.to) {
+                queue.push_back((edge.to, 1));
+            }
+        }
+        while let Some((task, hidden_tasks)) = queue.pop_front() {
+            for edge in outgoing.get(&task).into_iter().flatten() {
+                if !reached.insert(edge.to) {
+                    continue;
+                }
+                if exported.contains(&edge.to) {
+                    edges.push(TaskGraphEdge {
+                        from,
+                        to: edge.to,
+                        kind: TaskGraphEdgeKind::Indirect { hidden_tasks },
+                    });
+                } else {
+                    queue.push_back((edge.to, hidden_tasks + 1));
+                }
+            }
+        }
+    }
+    edges
+}
+
+impl TaskGraph {
+    /// Traverses the graph from `root` along child and dependency edges.
+    /// `snapshot` returns the description and edges of a task.
+    pub(crate) fn build(
+        root: TaskId,
+        options: &TaskGraphOptions,
+        mut snapshot: impl FnMut(TaskId) -> Option<(String, crate::backend::TaskSnapshot)>,
+    ) -> Self {
+        let mut depths = FxIndexMap::default();
+        let mut queue = VecDeque::new();
+        depths.insert(root, 0);
+        queue.push_back(root);
+
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        while let Some(task) = queue.pop_front() {
+            let depth = depths[&task];
+            let Some((description, snapshot)) = snapshot(task) else {
+                continue;
+            };
+            let truncated = options
+                .max_depth
+                .is_some_and(|max_depth| depth >= max_depth);
+
+            let task_edges = snapshot
+                .children
+                .iter()
+                .map(|&to| (to, TaskGraphEdgeKind::Child))
+                .chain(
+                    snapshot
+                        .output_dependencies
+                        .iter()
+                        .map(|&to| (to, TaskGraphEdgeKind::Output)),
+                )
+                .chain(snapshot.cell_dependencies.iter().map(|&(to, cell)| {
+                    (
+                        to,
+                        TaskGraphEdgeKind::Cell {
+                            cell: cell_name(cell),
+                        },
+                    )
+                }))
+                .chain(
+                    snapshot
+                        .collectibles_dependencies
+                        .iter()
+                        .map(|&(to, trait_type)| {
+                            (
+                                to,
+                                TaskGraphEdgeKind::Collectibles {
+                                    trait_name: registry::get_trait(trait_type).name.clone(),
+                                },
+                            )
+                        }),
+                );
+            for (to, kind) in task_edges {
+                if !truncated && !depths.contains_key(&to) {
+                    depths.insert(to, depth + 1);
+                    queue.push_back(to);
+                }
+                edges.push(TaskGraphEdge {
+                    from: task,
+                    to,
+                    kind,
+                });
+            }
+
+            if task == root || options.matches(&description) {
+                nodes.push(TaskGraphNode {
+                    id: task,
+                    description,
+                    depth,
+                    cells: snapshot.cells.into_iter().map(cell_name).collect(),
+                    collectibles: snapshot
+                        .collectibles
+                        .into_iter()
+                        .map(|(trait_type, collectible, count)| TaskGraphCollectible {
+                            trait_name: registry::get_trait(trait_type).name.clone(),
+                            collectible: raw_vc_name(collectible),
+                            count,
+                        })
+                        .collect(),
+                    truncated,
+                });
+            }
+        }
+
+        let exported = nodes.iter().map(|node| node.id).collect::<FxIndexSet<_>>();
+        let edges = collapse_hidden_tasks(&exported, edges);
+        TaskGraph { root, nodes, edges }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Renders the graph in the Graphviz DOT language. Child edges are solid,
+    /// dependency edges are dashed and point from the reading task to the
+    /// task that was read.
+    pub fn to_dot(&self) -> String {
+        fn escape(value: &str) -> String {
+            value.replace('\\', "\\\\").replace('"', "\\\"")
+        }
+
+        let mut dot = String::new();
+        dot.push_str("digraph tasks {\n");
+        dot.push_str("  node [shape=box, fontname=\"monospace\"];\n");
+        for node in &self.nodes {
+            let mut label = escape(&node.description);
+            if !node.cells.is_empty() {
+                write!(label, "\\ncells: {}", node.cells.len()).unwrap();
+            }
+            if !node.collectibles.is_empty() {
+                write!(label, "\\ncollectibles: {}", node.collectibles.len()).unwrap();
+            }
+            let style = if node.id == self.root {
+                ", style=bold"
+            } else if node.truncated {
+                ", style=dotted"
+            } else {
+                ""
+            };
+            writeln!(dot, "  t{} [label=\"{label}\"{style}];", *node.id).unwrap();
+        }
+        for edge in &self.edges {
+            let attributes = match &edge.kind {
+                TaskGraphEdgeKind::Child => String::new(),
+                TaskGraphEdgeKind::Output => " [style=dashed, label=\"output\"]".to_string(),
+                TaskGraphEdgeKind::Cell { cell } => {
+                    format!(" [style=dashed, label=\"{}\"]", escape(cell))
+                }
+                TaskGraphEdgeKind::Collectibles { trait_name } => {
+                    format!(" [style=dotted, label=\"{}\"]", escape(trait_name))
+                }
+                TaskGraphEdgeKind::Indirect { hidden_tasks } => {
+                    format!(" [color=gray, label=\"via {hidden_tasks}\"]")
+                }
+            };
+            writeln!(dot, "  t{} -> t{}{attributes};", *edge.from, *edge.to).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TaskGraph, TaskGraphEdgeKind, TaskGraphOptions};
+    use crate::{backend::TaskSnapshot, TaskId};
+
+    fn id(id: u32) -> TaskId {
+        TaskId::from(id)
+    }
+
+    #[test]
+    fn filters_by_depth_and_name() {
+        // 1 -> 2 -> 3 -> 4, 1 reads the output of 3
+        let snapshot = |task: TaskId| {
+            let children = match *task {
+                1 => vec![id(2)],
+                2 => vec![id(3)],
+                3 => vec![id(4)],
+                _ => vec![],
+            };
+            let output_dependencies = if *task == 1 { vec![id(3)] } else { vec![] };
+            Some((
+                format!("task_{}", *task),
+                TaskSnapshot {
+                    children,
+                    output_dependencies,
+                    ..Default::default()
+                },
+            ))
+        };
+
+        let options = TaskGraphOptions {
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        let graph = TaskGraph::build(id(1), &options, snapshot);
+        let nodes = graph.nodes.iter().map(|n| *n.id).collect::<Vec<_>>();
+        assert_eq!(nodes, vec![1, 2, 3]);
+        assert!(!graph.nodes[0].truncated);
+        assert!(graph.nodes[1].truncated);
+        // 1 -> 2, 1 -> 3 and 2 -> 3, but not 3 -> 4
+        assert_eq!(graph.edges.len(), 3);
+
+        let options = TaskGraphOptions {
+            max_depth: None,
+            function_names: vec!["task_4".to_string()],
+        };
+        let graph = TaskGraph::build(id(1), &options, snapshot);
+        let nodes = graph.nodes.iter().map(|n| *n.id).collect::<Vec<_>>();
+        assert_eq!(nodes, vec![1, 4]);
+        // 1 -> 3 -> 4 is the shortest path, 1 -> 2 -> 3 -> 4 isn't exported
+        // separately
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!((*graph.edges[0].from, *graph.edges[0].to), (1, 4));
+        assert!(matches!(
+            graph.edges[0].kind,
+            TaskGraphEdgeKind::Indirect { hidden_tasks: 1 }
+        ));
+        let dot = graph.to_dot();
+        assert!(dot.contains("t4 [label=\"task_4\"];"));
+        assert!(dot.contains("t1 -> t4 [color=gray, label=\"via 1\"];"));
+
+        // A direct edge between exported tasks is kept as is
+        let options = TaskGraphOptions {
+            max_depth: None,
+            function_names: vec!["task_3".to_string()],
+        };
+        let graph = TaskGraph::build(id(1), &options, snapshot);
+        let edges = graph
+            .edges
+            .iter()
+            .map(|edge| (*edge.from, *edge.to, &edge.kind))
+            .collect::<Vec<_>>();
+        assert!(matches!(edges[..], [(1, 3, TaskGraphEdgeKind::Output)]));
+    }
+}
@@ -7,8 +7,9 @@ use turbopack_core::{
         availability_info::AvailabilityInfo,
         chunk_group::{make_chunk_group, MakeChunkGroupResult},
         module_id_strategies::{DevModuleIdStrategy, ModuleIdStrategy},
-        Chunk, ChunkGroupResult, ChunkItem, ChunkableModule, ChunkingContext,
-        EntryChunkGroupResult, EvaluatableAssets, MinifyType, ModuleId,
+        Chunk, ChunkGroupResult, ChunkItem, ChunkableModule, ChunkingContext, ContentHashing,
+        EntryChunkGroupResult, EvaluatableAssets, IntegrityAlgorithm, MinifyType, ModuleId,
+        OptionContentHashing, OptionIntegrityAlgorithm,
     },
     environment::Environment,
     ident::AssetIdent,
@@ -83,6 +84,51 @@ impl BrowserChunkingContextBuilder {
         self
     }
 
+    /// Includes a hash of the content in the filenames of chunks, so they can
+    /// be cached immutably. Chunk lists, which are only emitted for HMR, keep
+    /// their stable names.
+    ///
+    /// As a chunk refers to its own filename, the hash is computed from the
+    /// content with a placeholder in place of the hash. The emitted bytes
+    /// differ from the hashed ones in the chunk path and the
+    /// `sourceMappingURL` comment, so the hash can't be verified by hashing the
+    /// emitted file. It still changes whenever anything else in the chunk
+    /// does.
+    pub fn use_content_hashing(mut self, content_hashing: ContentHashing) -> Self {
+        self.chunking_context.content_hashing = Some(content_hashing);
+        self
+    }
+
+    /// Computes subresource integrity metadata for chunks, so the runtime
+    /// sets the `integrity` attribute on the script and link tags it creates.
+    pub fn integrity_algorithm(mut self, integrity_algorithm: IntegrityAlgorithm) -> Self {
+        self.chunking_context.integrity_algorithm = Some(integrity_algorithm);
+        self
+    }
+
+    /// Sets the name of the global variable chunks register themselves with.
+    /// Builds that are loaded into the same page, e. g. module federation
+    /// remotes, need distinct names. Defaults to `TURBOPACK`.
+    pub fn chunk_loading_global(mut self, chunk_loading_global: RcStr) -> Self {
+        self.chunking_context.chunk_loading_global = chunk_loading_global;
+        self
+    }
+
+    /// Includes the module federation runtime, which loads remote containers
+    /// and negotiates shared dependencies. See
+    /// [module_federation_entry][crate::module_federation_entry].
+    pub fn module_federation(mut self) -> Self {
+        self.chunking_context.module_federation = true;
+        self
+    }
+
+    /// Includes the runtime code that loads ESM externals with a native
+    /// `import()`, which is needed for [URL externals][crate::UrlExternalsOptions].
+    pub fn url_externals(mut self) -> Self {
+        self.chunking_context.url_externals = true;
+        self
+    }
+
     pub fn build(self) -> Vc<BrowserChunkingContext> {
         BrowserChunkingContext::new(Value::new(self.chunking_context))
     }
@@ -131,6 +177,16 @@ pub struct BrowserChunkingContext {
     manifest_chunks: bool,
     /// The module id strategy to use
     module_id_strategy: Vc<Box<dyn ModuleIdStrategy>>,
+    /// Whether chunk filenames include a hash of their content
+    content_hashing: Option<ContentHashing>,
+    /// The algorithm used for subresource integrity metadata of chunks
+    integrity_algorithm: Option<IntegrityAlgorithm>,
+    /// The global variable chunks register themselves with
+    chunk_loading_global: RcStr,
+    /// Whether to include the module federation runtime
+    module_federation: bool,
+    /// Whether to include the runtime code for URL externals
+    url_externals: bool,
 }
 
 impl BrowserChunkingContext {
@@ -161,6 +217,11 @@ impl BrowserChunkingContext {
                 minify_type: MinifyType::NoMinify,
                 manifest_chunks: false,
                 module_id_strategy: Vc::upcast(DevModuleIdStrategy::new()),
+                content_hashing: None,
+                integrity_algorithm: None,
+                chunk_loading_global: "TURBOPACK".into(),
+                module_federation: false,
+                url_externals: false,
             },
         }
     }
@@ -184,6 +245,21 @@ impl BrowserChunkingContext {
     pub fn minify_type(&self) -> MinifyType {
         self.minify_type
     }
+
+    /// Returns the global variable chunks register themselves with.
+    pub fn chunk_loading_global(&self) -> &RcStr {
+        &self.chunk_loading_global
+    }
+
+    /// Returns whether the module federation runtime is included.
+    pub fn module_federation(&self) -> bool {
+        self.module_federation
+    }
+
+    /// Returns whether the runtime code for URL externals is included.
+    pub fn url_externals(&self) -> bool {
+        self.url_externals
+    }
 }
 
 #[turbo_tasks::value_impl]
@@ -345,6 +421,16 @@ impl ChunkingContext for BrowserChunkingContext {
         Ok(self.asset_root_path.join(asset_path.into()))
     }
 
+    #[turbo_tasks::function]
+    fn content_hashing(&self) -> Vc<OptionContentHashing> {
+        Vc::cell(self.content_hashing)
+    }
+
+    #[turbo_tasks::function]
+    fn integrity_algorithm(&self) -> Vc<OptionIntegrityAlgorithm> {
+        Vc::cell(self.integrity_algorithm)
+    }
+
     #[turbo_tasks::function]
     fn is_hot_module_replacement_enabled(&self) -> Vc<bool> {
         Vc::cell(self.enable_hot_module_replacement)
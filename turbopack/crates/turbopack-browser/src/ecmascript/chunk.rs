@@ -1,8 +1,12 @@
 use anyhow::Result;
 use turbo_tasks::{FxIndexSet, RcStr, ValueToString, Vc};
+use turbo_tasks_fs::FileSystemPath;
 use turbopack_core::{
     asset::{Asset, AssetContent},
-    chunk::{Chunk, ChunkingContext, OutputChunk, OutputChunkRuntimeInfo},
+    chunk::{
+        content_hash_placeholder_path, content_hashed_path, Chunk, ChunkingContext, OutputChunk,
+        OutputChunkRuntimeInfo,
+    },
     ident::AssetIdent,
     introspect::{Introspectable, IntrospectableChildren},
     output::{OutputAsset, OutputAssets},
@@ -77,14 +81,42 @@ impl EcmascriptDevChunk {
     pub fn chunk(&self) -> Result<Vc<Box<dyn Chunk>>> {
         Ok(Vc::upcast(self.chunk))
     }
+
+    /// The path of the chunk without a content hash.
+    #[turbo_tasks::function]
+    fn unhashed_path(&self) -> Vc<FileSystemPath> {
+        let ident = self.chunk.ident().with_modifier(modifier());
+        self.chunking_context.chunk_path(ident, ".js".into())
+    }
+
+    /// The path the chunk refers to itself by in its content. With content
+    /// hashing, this contains a placeholder for the hash.
+    #[turbo_tasks::function]
+    pub(super) async fn content_path(self: Vc<Self>) -> Result<Vc<FileSystemPath>> {
+        let this = self.await?;
+        Ok(match *this.chunking_context.content_hashing().await? {
+            Some(content_hashing) => {
+                content_hash_placeholder_path(self.unhashed_path(), content_hashing)
+            }
+            None => self.unhashed_path(),
+        })
+    }
 }
 
 #[turbo_tasks::value_impl]
 impl OutputAsset for EcmascriptDevChunk {
     #[turbo_tasks::function]
-    fn ident(&self) -> Vc<AssetIdent> {
-        let ident = self.chunk.ident().with_modifier(modifier());
-        AssetIdent::from_path(self.chunking_context.chunk_path(ident, ".js".into()))
+    async fn ident(self: Vc<Self>) -> Result<Vc<AssetIdent>> {
+        let this = self.await?;
+        let path = match *this.chunking_context.content_hashing().await? {
+            Some(content_hashing) => content_hashed_path(
+                self.unhashed_path(),
+                content_hashing,
+                self.own_content().code(),
+            ),
+            None => self.unhashed_path(),
+        };
+        Ok(AssetIdent::from_path(path))
     }
 
     #[turbo_tasks::function]
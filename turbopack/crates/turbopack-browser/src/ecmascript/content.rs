@@ -1,18 +1,25 @@
 use std::io::Write;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use indoc::writedoc;
 use turbo_tasks::{RcStr, Vc};
 use turbo_tasks_fs::File;
 use turbopack_core::{
     asset::AssetContent,
-    chunk::{ChunkingContext, MinifyType, ModuleId},
+    chunk::{ChunkItem, ChunkingContext, MinifyType, ModuleId},
     code_builder::{Code, CodeBuilder},
+    environment::ChunkLoading,
+    issue::{code_gen::CodeGenerationIssue, IssueExt, IssueSeverity, StyledString},
     output::OutputAsset,
     source_map::{GenerateSourceMap, OptionSourceMap},
     version::{MergeableVersionedContent, Version, VersionedContent, VersionedContentMerger},
 };
-use turbopack_ecmascript::{chunk::EcmascriptChunkContent, minify::minify, utils::StringifyJs};
+use turbopack_ecmascript::{
+    chunk::{EcmascriptChunkContent, EcmascriptChunkItem},
+    minify::minify,
+    utils::StringifyJs,
+    EcmascriptModuleAsset,
+};
 
 use super::{
     chunk::EcmascriptDevChunk, content_entry::EcmascriptDevChunkContentEntries,
@@ -38,6 +45,9 @@ impl EcmascriptDevChunkContent {
         let entries = EcmascriptDevChunkContentEntries::new(content)
             .resolve()
             .await?;
+        if *chunking_context.environment().chunk_loading().await? == ChunkLoading::DomModule {
+            warn_non_strict_chunk_items(content).await?;
+        }
         Ok(EcmascriptDevChunkContent {
             entries,
             chunking_context,
@@ -52,6 +62,45 @@ impl EcmascriptDevChunkContent {
     }
 }
 
+/// ES module chunks are strict mode code, so the module factories of modules
+/// that aren't, like most CommonJS modules, are evaluated in strict mode too.
+/// This warns about them, as they may behave differently or fail, e.g. when
+/// they assign undeclared variables. Code generated by Turbopack itself, like
+/// async loaders, is fine in strict mode.
+async fn warn_non_strict_chunk_items(content: Vc<EcmascriptChunkContent>) -> Result<()> {
+    for &(chunk_item, async_module_info) in content.await?.chunk_items.iter() {
+        if Vc::try_resolve_downcast_type::<EcmascriptModuleAsset>(chunk_item.module())
+            .await?
+            .is_none()
+        {
+            continue;
+        }
+        if chunk_item
+            .content_with_async_module_info(async_module_info)
+            .await?
+            .options
+            .strict
+        {
+            continue;
+        }
+        CodeGenerationIssue {
+            severity: IssueSeverity::Warning.cell(),
+            path: chunk_item.asset_ident().path(),
+            title: StyledString::Text("Module is evaluated in strict mode".into()).cell(),
+            message: StyledString::Text(
+                "Chunks are emitted as ES modules, which are always evaluated in strict mode. \
+                 This module isn't strict mode code, so it may behave differently or fail, e.g. \
+                 when it assigns undeclared variables or uses `with` statements."
+                    .into(),
+            )
+            .cell(),
+        }
+        .cell()
+        .emit();
+    }
+    Ok(())
+}
+
 #[turbo_tasks::value_impl]
 impl EcmascriptDevChunkContent {
     #[turbo_tasks::function]
@@ -64,10 +113,10 @@ impl EcmascriptDevChunkContent {
     }
 
     #[turbo_tasks::function]
-    async fn code(self: Vc<Self>) -> Result<Vc<Code>> {
+    pub(super) async fn code(self: Vc<Self>) -> Result<Vc<Code>> {
         let this = self.await?;
         let output_root = this.chunking_context.output_root().await?;
-        let chunk_path_vc = this.chunk.ident().path();
+        let chunk_path_vc = this.chunk.content_path();
         let chunk_path = chunk_path_vc.await?;
         let chunk_server_path = if let Some(path) = output_root.get_path_to(&chunk_path) {
             path
@@ -82,16 +131,18 @@ impl EcmascriptDevChunkContent {
 
         // When a chunk is executed, it will either register itself with the current
         // instance of the runtime, or it will push itself onto the list of pending
-        // chunks (`self.TURBOPACK`).
+        // chunks (`self.TURBOPACK`, unless configured otherwise).
         //
         // When the runtime executes (see the `evaluate` module), it will pick up and
         // register all pending chunks, and replace the list of pending chunks
         // with itself so later chunks can register directly with it.
+        let chunk_loading_global = this.chunking_context.await?.chunk_loading_global().clone();
         writedoc!(
             code,
             r#"
-                (globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([{chunk_path}, {{
+                (globalThis.{global} = globalThis.{global} || []).push([{chunk_path}, {{
             "#,
+            global = chunk_loading_global,
             chunk_path = StringifyJs(chunk_server_path)
         )?;
 
@@ -103,6 +154,12 @@ impl EcmascriptDevChunkContent {
 
         write!(code, "\n}}]);")?;
 
+        // Chunks loaded via `import()` are ES modules, which makes them fail loudly
+        // when they are loaded as classic scripts instead.
+        if *this.chunking_context.environment().chunk_loading().await? == ChunkLoading::DomModule {
+            write!(code, "\nexport {{}};")?;
+        }
+
         if code.has_source_map() {
             let filename = chunk_path.file_name();
             write!(
@@ -128,10 +185,20 @@ impl EcmascriptDevChunkContent {
 impl VersionedContent for EcmascriptDevChunkContent {
     #[turbo_tasks::function]
     async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
+        let this = self.await?;
         let code = self.code().await?;
-        Ok(AssetContent::file(
-            File::from(code.source_code().clone()).into(),
-        ))
+        let source = match *this.chunking_context.content_hashing().await? {
+            Some(content_hashing) => {
+                let output_root = this.chunking_context.output_root().await?;
+                let chunk_path = this.chunk.content_path().await?;
+                let chunk_path = output_root
+                    .get_path_to(&chunk_path)
+                    .context("chunk path must be inside the output root")?;
+                content_hashing.apply(&code, chunk_path)?
+            }
+            None => code.source_code().clone(),
+        };
+        Ok(AssetContent::file(File::from(source).into()))
     }
 
     #[turbo_tasks::function]
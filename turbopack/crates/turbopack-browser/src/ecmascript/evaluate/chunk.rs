@@ -1,15 +1,15 @@
 use std::io::Write;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use indoc::writedoc;
 use serde::Serialize;
 use turbo_tasks::{RcStr, ReadRef, TryJoinIterExt, Value, ValueToString, Vc};
-use turbo_tasks_fs::File;
+use turbo_tasks_fs::{File, FileSystemPath};
 use turbopack_core::{
     asset::{Asset, AssetContent},
     chunk::{
-        ChunkData, ChunkItemExt, ChunkableModule, ChunkingContext, ChunksData, EvaluatableAssets,
-        MinifyType, ModuleId,
+        content_hash_placeholder_path, content_hashed_path, ChunkData, ChunkItemExt,
+        ChunkableModule, ChunkingContext, ChunksData, EvaluatableAssets, MinifyType, ModuleId,
     },
     code_builder::{Code, CodeBuilder},
     ident::AssetIdent,
@@ -56,9 +56,48 @@ impl EcmascriptDevEvaluateChunk {
         .cell()
     }
 
+    /// The path of the chunk without a content hash.
+    #[turbo_tasks::function]
+    async fn unhashed_path(&self) -> Result<Vc<FileSystemPath>> {
+        let mut ident = self.ident.await?.clone_value();
+
+        ident.add_modifier(modifier());
+
+        let evaluatable_assets = self.evaluatable_assets.await?;
+        ident.modifiers.extend(
+            evaluatable_assets
+                .iter()
+                .map(|entry| entry.ident().to_string()),
+        );
+
+        for chunk in &*self.other_chunks.await? {
+            ident.add_modifier(chunk.ident().to_string());
+        }
+
+        let ident = AssetIdent::new(Value::new(ident));
+        Ok(self.chunking_context.chunk_path(ident, ".js".into()))
+    }
+
+    /// The path the chunk refers to itself by in its content. With content
+    /// hashing, this contains a placeholder for the hash.
+    #[turbo_tasks::function]
+    async fn content_path(self: Vc<Self>) -> Result<Vc<FileSystemPath>> {
+        let this = self.await?;
+        Ok(match *this.chunking_context.content_hashing().await? {
+            Some(content_hashing) => {
+                content_hash_placeholder_path(self.unhashed_path(), content_hashing)
+            }
+            None => self.unhashed_path(),
+        })
+    }
+
     #[turbo_tasks::function]
     fn chunks_data(&self) -> Vc<ChunksData> {
-        ChunkData::from_assets(self.chunking_context.output_root(), self.other_chunks)
+        ChunkData::from_assets(
+            self.chunking_context.output_root(),
+            self.other_chunks,
+            self.chunking_context.integrity_algorithm(),
+        )
     }
 
     #[turbo_tasks::function]
@@ -68,7 +107,7 @@ impl EcmascriptDevEvaluateChunk {
         let environment = this.chunking_context.environment();
 
         let output_root = this.chunking_context.output_root().await?;
-        let chunk_path_vc = self.ident().path();
+        let chunk_path_vc = self.content_path();
         let chunk_path = chunk_path_vc.await?;
         let chunk_public_path = if let Some(path) = output_root.get_path_to(&chunk_path) {
             path
@@ -125,17 +164,19 @@ impl EcmascriptDevEvaluateChunk {
         // We still use the `TURBOPACK` global variable to store the chunk here,
         // as there may be another runtime already loaded in the page.
         // This is the case in integration tests.
+        let chunk_loading_global = chunking_context.chunk_loading_global();
         writedoc!(
             code,
             r#"
-                (globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
-                    {},
+                (globalThis.{global} = globalThis.{global} || []).push([
+                    {path},
                     {{}},
-                    {}
+                    {params}
                 ]);
             "#,
-            StringifyJs(&chunk_public_path),
-            StringifyJs(&params),
+            global = chunk_loading_global,
+            path = StringifyJs(&chunk_public_path),
+            params = StringifyJs(&params),
         )?;
 
         match chunking_context.runtime_type() {
@@ -145,6 +186,9 @@ impl EcmascriptDevEvaluateChunk {
                     chunking_context.chunk_base_path(),
                     Value::new(chunking_context.runtime_type()),
                     Vc::cell(output_root.to_string().into()),
+                    chunk_loading_global.clone(),
+                    chunking_context.module_federation(),
+                    chunking_context.url_externals(),
                 );
                 code.push_code(&*runtime_code.await?);
             }
@@ -154,6 +198,9 @@ impl EcmascriptDevEvaluateChunk {
                     chunking_context.chunk_base_path(),
                     Value::new(chunking_context.runtime_type()),
                     Vc::cell(output_root.to_string().into()),
+                    chunk_loading_global.clone(),
+                    chunking_context.module_federation(),
+                    chunking_context.url_externals(),
                 );
                 code.push_code(&*runtime_code.await?);
             }
@@ -201,26 +248,15 @@ fn modifier() -> Vc<RcStr> {
 #[turbo_tasks::value_impl]
 impl OutputAsset for EcmascriptDevEvaluateChunk {
     #[turbo_tasks::function]
-    async fn ident(&self) -> Result<Vc<AssetIdent>> {
-        let mut ident = self.ident.await?.clone_value();
-
-        ident.add_modifier(modifier());
-
-        let evaluatable_assets = self.evaluatable_assets.await?;
-        ident.modifiers.extend(
-            evaluatable_assets
-                .iter()
-                .map(|entry| entry.ident().to_string()),
-        );
-
-        for chunk in &*self.other_chunks.await? {
-            ident.add_modifier(chunk.ident().to_string());
-        }
-
-        let ident = AssetIdent::new(Value::new(ident));
-        Ok(AssetIdent::from_path(
-            self.chunking_context.chunk_path(ident, ".js".into()),
-        ))
+    async fn ident(self: Vc<Self>) -> Result<Vc<AssetIdent>> {
+        let this = self.await?;
+        let path = match *this.chunking_context.content_hashing().await? {
+            Some(content_hashing) => {
+                content_hashed_path(self.unhashed_path(), content_hashing, self.code())
+            }
+            None => self.unhashed_path(),
+        };
+        Ok(AssetIdent::from_path(path))
     }
 
     #[turbo_tasks::function]
@@ -249,10 +285,20 @@ impl OutputAsset for EcmascriptDevEvaluateChunk {
 impl Asset for EcmascriptDevEvaluateChunk {
     #[turbo_tasks::function]
     async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
+        let this = self.await?;
         let code = self.code().await?;
-        Ok(AssetContent::file(
-            File::from(code.source_code().clone()).into(),
-        ))
+        let source = match *this.chunking_context.content_hashing().await? {
+            Some(content_hashing) => {
+                let output_root = this.chunking_context.output_root().await?;
+                let chunk_path = self.content_path().await?;
+                let chunk_path = output_root
+                    .get_path_to(&chunk_path)
+                    .context("chunk path must be inside the output root")?;
+                content_hashing.apply(&code, chunk_path)?
+            }
+            None => code.source_code().clone(),
+        };
+        Ok(AssetContent::file(File::from(source).into()))
     }
 }
 
@@ -3,7 +3,7 @@ use std::io::Write;
 use anyhow::{Context, Result};
 use indoc::writedoc;
 use serde::Serialize;
-use turbo_tasks::{FxIndexMap, IntoTraitRef, TryJoinIterExt, Vc};
+use turbo_tasks::{FxIndexMap, IntoTraitRef, RcStr, TryJoinIterExt, Vc};
 use turbo_tasks_fs::File;
 use turbopack_core::{
     asset::{Asset, AssetContent},
@@ -26,6 +26,7 @@ use super::{
 #[turbo_tasks::value]
 pub(super) struct EcmascriptDevChunkListContent {
     chunk_list_path: String,
+    chunk_loading_global: RcStr,
     pub(super) chunks_contents: FxIndexMap<String, Vc<Box<dyn VersionedContent>>>,
     source: EcmascriptDevChunkListSource,
 }
@@ -42,6 +43,11 @@ impl EcmascriptDevChunkListContent {
                 .get_path_to(&*chunk_list.ident().path().await?)
                 .context("chunk list path not in output root")?
                 .to_string(),
+            chunk_loading_global: chunk_list_ref
+                .chunking_context
+                .await?
+                .chunk_loading_global()
+                .clone(),
             chunks_contents: chunk_list_ref
                 .chunks
                 .await?
@@ -121,19 +127,20 @@ impl EcmascriptDevChunkListContent {
         let mut code = CodeBuilder::default();
 
         // When loaded, JS chunks must register themselves with the `TURBOPACK` global
-        // variable. Similarly, we register the chunk list with the
-        // `TURBOPACK_CHUNK_LISTS` global variable.
+        // variable (unless configured otherwise). Similarly, we register the chunk
+        // list with the `TURBOPACK_CHUNK_LISTS` global variable.
         writedoc!(
             code,
             r#"
-                (globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([
-                    {},
+                (globalThis.{global} = globalThis.{global} || []).push([
+                    {path},
                     {{}},
                 ]);
-                (globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({:#});
+                (globalThis.TURBOPACK_CHUNK_LISTS = globalThis.TURBOPACK_CHUNK_LISTS || []).push({params:#});
             "#,
-            StringifyJs(&this.chunk_list_path),
-            StringifyJs(&params),
+            global = this.chunk_loading_global,
+            path = StringifyJs(&this.chunk_list_path),
+            params = StringifyJs(&params),
         )?;
 
         Ok(Code::cell(code.build()))
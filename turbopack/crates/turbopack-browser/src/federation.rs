@@ -0,0 +1,439 @@
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{trace::TraceRawVcs, FxIndexMap, RcStr, ValueToString, Vc};
+use turbo_tasks_fs::{glob::Glob, rope::RopeBuilder, FileContent, FileSystemPath};
+use turbopack_core::{
+    asset::{Asset, AssetContent},
+    chunk::{
+        ChunkItem, ChunkType, ChunkableModule, ChunkableModuleReference, ChunkingContext,
+        EvaluatableAsset,
+    },
+    ident::AssetIdent,
+    module::Module,
+    package_json::read_package_json,
+    reference::{ModuleReference, ModuleReferences},
+    resolve::{
+        find_context_file,
+        options::{ImportMap, ImportMapping},
+        package_json, ExternalType, FindContextFileResult, ModuleResolveResult,
+    },
+};
+use turbopack_ecmascript::{
+    chunk::{
+        EcmascriptChunkItem, EcmascriptChunkItemContent, EcmascriptChunkItemOptions,
+        EcmascriptChunkPlaceable, EcmascriptChunkType, EcmascriptExports,
+    },
+    utils::StringifyJs,
+};
+
+/// How a dependency is shared with other containers.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SharedDependency {
+    /// Whether only a single version of the dependency may be loaded in the
+    /// page, e. g. for `react` and `react-dom`.
+    pub singleton: bool,
+    /// The version range this build requires. Singletons only warn when it
+    /// isn't satisfied.
+    pub required_version: Option<RcStr>,
+    /// The version this build provides. Defaults to the version in the
+    /// package's `package.json`, and is required when that has none.
+    pub version: Option<RcStr>,
+}
+
+/// Module federation options of a build, similar to the options of webpack's
+/// `ModuleFederationPlugin`.
+#[turbo_tasks::value(shared)]
+#[derive(Clone, Debug, Default)]
+pub struct ModuleFederationOptions {
+    /// The name of the container, i. e. the global variable it's assigned to
+    /// when modules are exposed.
+    pub name: RcStr,
+    /// Map from the alias remotes are requested by to the remote, given as
+    /// `name@url`.
+    pub remotes: FxIndexMap<RcStr, RcStr>,
+    /// Map from a package name to how it's shared.
+    pub shared: FxIndexMap<RcStr, SharedDependency>,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionModuleFederationOptions(Option<Vc<ModuleFederationOptions>>);
+
+#[turbo_tasks::value_impl]
+impl ModuleFederationOptions {
+    /// Returns an import map that maps requests to remotes (`<remote>` and
+    /// `<remote>/<module>`) and shared dependencies to module federation
+    /// externals, which are loaded asynchronously by the runtime.
+    ///
+    /// The shared modules passed to [module_federation_entry] must be resolved
+    /// without this import map, as they would resolve to themselves otherwise.
+    #[turbo_tasks::function]
+    pub fn import_map(&self) -> Vc<ImportMap> {
+        let mut import_map = ImportMap::empty();
+        let external = ImportMapping::External(None, ExternalType::ModuleFederation).cell();
+        for alias in self.remotes.keys() {
+            import_map.insert_exact_alias(alias.clone(), external);
+            import_map.insert_wildcard_alias(format!("{alias}/"), external);
+        }
+        for name in self.shared.keys() {
+            import_map.insert_exact_alias(name.clone(), external);
+        }
+        import_map.cell()
+    }
+}
+
+/// Map from a name to a module, e. g. from an exposed name like `./Button` to
+/// the exposed module.
+#[turbo_tasks::value(transparent)]
+pub struct FederationModules(FxIndexMap<RcStr, Vc<Box<dyn ChunkableModule>>>);
+
+/// Creates the entry module of a module federation build. It registers the
+/// remotes, provides the shared dependencies and exposes the `exposes` modules
+/// as a container. It's meant to be evaluated as a runtime entry of a
+/// chunking context with [module_federation] enabled.
+///
+/// [module_federation]: crate::BrowserChunkingContextBuilder::module_federation
+#[turbo_tasks::function]
+pub fn module_federation_entry(
+    ident: Vc<AssetIdent>,
+    options: Vc<ModuleFederationOptions>,
+    exposes: Vc<FederationModules>,
+    shared: Vc<FederationModules>,
+) -> Vc<ModuleFederationEntryModule> {
+    ModuleFederationEntryModule {
+        ident,
+        options,
+        exposes,
+        shared,
+    }
+    .cell()
+}
+
+#[turbo_tasks::function]
+fn modifier() -> Vc<RcStr> {
+    Vc::cell("module federation entry".into())
+}
+
+#[turbo_tasks::value(shared)]
+pub struct ModuleFederationEntryModule {
+    ident: Vc<AssetIdent>,
+    options: Vc<ModuleFederationOptions>,
+    exposes: Vc<FederationModules>,
+    shared: Vc<FederationModules>,
+}
+
+#[turbo_tasks::value_impl]
+impl Module for ModuleFederationEntryModule {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        self.ident.with_modifier(modifier())
+    }
+
+    #[turbo_tasks::function]
+    async fn references(&self) -> Result<Vc<ModuleReferences>> {
+        let exposes = self.exposes.await?;
+        let shared = self.shared.await?;
+        Ok(Vc::cell(
+            exposes
+                .values()
+                .chain(shared.values())
+                .map(|module| Vc::upcast(ModuleFederationEntryReference::new(Vc::upcast(*module))))
+                .collect(),
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ChunkableModule for ModuleFederationEntryModule {
+    #[turbo_tasks::function]
+    fn as_chunk_item(
+        self: Vc<Self>,
+        chunking_context: Vc<Box<dyn ChunkingContext>>,
+    ) -> Vc<Box<dyn ChunkItem>> {
+        Vc::upcast(
+            ModuleFederationEntryChunkItem {
+                module: self,
+                chunking_context,
+            }
+            .cell(),
+        )
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for ModuleFederationEntryModule {
+    #[turbo_tasks::function]
+    fn content(&self) -> Vc<AssetContent> {
+        // The entry is generated from the options, so there is no source content. This
+        // should be `NotFound` as this function gets called to detect source changes.
+        AssetContent::file(FileContent::NotFound.cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl EcmascriptChunkPlaceable for ModuleFederationEntryModule {
+    #[turbo_tasks::function]
+    fn get_exports(self: Vc<Self>) -> Vc<EcmascriptExports> {
+        EcmascriptExports::None.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn is_marked_as_side_effect_free(self: Vc<Self>, _: Vc<Glob>) -> Vc<bool> {
+        Vc::cell(false)
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl EvaluatableAsset for ModuleFederationEntryModule {}
+
+/// A reference from the entry to an exposed or shared module. The modules are
+/// placed in parallel chunks, so they can be imported by the entry.
+#[turbo_tasks::value]
+pub struct ModuleFederationEntryReference {
+    pub module: Vc<Box<dyn Module>>,
+}
+
+#[turbo_tasks::value_impl]
+impl ModuleFederationEntryReference {
+    #[turbo_tasks::function]
+    pub fn new(module: Vc<Box<dyn Module>>) -> Vc<Self> {
+        ModuleFederationEntryReference { module }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ValueToString for ModuleFederationEntryReference {
+    #[turbo_tasks::function]
+    fn to_string(&self) -> Vc<RcStr> {
+        Vc::cell("module federation".into())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ModuleReference for ModuleFederationEntryReference {
+    #[turbo_tasks::function]
+    fn resolve_reference(&self) -> Vc<ModuleResolveResult> {
+        ModuleResolveResult::module(self.module).cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ChunkableModuleReference for ModuleFederationEntryReference {}
+
+/// Returns the version of the shared `package` from the `package.json` the
+/// file at `path` belongs to. Fails when there's none, as the version is
+/// what the runtime negotiates shared dependencies by.
+async fn package_version(package: &str, path: Vc<FileSystemPath>) -> Result<RcStr> {
+    let version = match &*find_context_file(path.parent(), package_json()).await? {
+        FindContextFileResult::Found(package_json_path, _) => read_package_json(*package_json_path)
+            .await?
+            .as_ref()
+            .and_then(|package_json| package_json["version"].as_str())
+            .map(RcStr::from),
+        FindContextFileResult::NotFound(_) => None,
+    };
+    version.with_context(|| {
+        format!(
+            "Unable to determine the version of the shared module {package} from its \
+             package.json, set `version` in its shared config"
+        )
+    })
+}
+
+/// The chunk item for [`ModuleFederationEntryModule`].
+#[turbo_tasks::value]
+struct ModuleFederationEntryChunkItem {
+    module: Vc<ModuleFederationEntryModule>,
+    chunking_context: Vc<Box<dyn ChunkingContext>>,
+}
+
+#[turbo_tasks::value_impl]
+impl ChunkItem for ModuleFederationEntryChunkItem {
+    #[turbo_tasks::function]
+    fn chunking_context(&self) -> Vc<Box<dyn ChunkingContext>> {
+        Vc::upcast(self.chunking_context)
+    }
+
+    #[turbo_tasks::function]
+    fn asset_ident(&self) -> Vc<AssetIdent> {
+        self.module.ident()
+    }
+
+    #[turbo_tasks::function]
+    fn references(&self) -> Vc<ModuleReferences> {
+        self.module.references()
+    }
+
+    #[turbo_tasks::function]
+    fn ty(&self) -> Vc<Box<dyn ChunkType>> {
+        Vc::upcast(Vc::<EcmascriptChunkType>::default())
+    }
+
+    #[turbo_tasks::function]
+    fn module(&self) -> Vc<Box<dyn Module>> {
+        Vc::upcast(self.module)
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl EcmascriptChunkItem for ModuleFederationEntryChunkItem {
+    #[turbo_tasks::function]
+    fn chunking_context(&self) -> Vc<Box<dyn ChunkingContext>> {
+        self.chunking_context
+    }
+
+    #[turbo_tasks::function]
+    async fn content(&self) -> Result<Vc<EcmascriptChunkItemContent>> {
+        let this = self.module.await?;
+        let options = this.options.await?;
+        let name = &options.name;
+
+        let mut code = RopeBuilder::default();
+        if !options.remotes.is_empty() {
+            writeln!(
+                code,
+                "__turbopack_federation__.registerRemotes({});",
+                StringifyJs(&options.remotes)
+            )?;
+        }
+
+        for (package, module) in this.shared.await?.iter() {
+            let config = options.shared.get(package).cloned().unwrap_or_default();
+            let version = match config.version {
+                Some(version) => version,
+                None => package_version(package, module.ident().path()).await?,
+            };
+            let id = self
+                .chunking_context
+                .chunk_item_id(module.as_chunk_item(self.chunking_context))
+                .await?;
+            writeln!(
+                code,
+                "__turbopack_federation__.provideShared({}, {}, {}, {{ singleton: {}, \
+                 requiredVersion: {} }}, async () => __turbopack_import__({}));",
+                StringifyJs(name),
+                StringifyJs(package),
+                StringifyJs(&version),
+                config.singleton,
+                StringifyJs(&config.required_version),
+                StringifyJs(&id)
+            )?;
+        }
+
+        let exposes = this.exposes.await?;
+        if !exposes.is_empty() {
+            writeln!(
+                code,
+                "__turbopack_federation__.expose({}, {{",
+                StringifyJs(name)
+            )?;
+            for (exposed, module) in exposes.iter() {
+                let id = self
+                    .chunking_context
+                    .chunk_item_id(module.as_chunk_item(self.chunking_context))
+                    .await?;
+                writeln!(
+                    code,
+                    "    {}: async () => __turbopack_import__({}),",
+                    StringifyJs(exposed),
+                    StringifyJs(&id)
+                )?;
+            }
+            writeln!(code, "}});")?;
+        }
+
+        Ok(EcmascriptChunkItemContent {
+            inner_code: code.build(),
+            options: EcmascriptChunkItemOptions {
+                strict: true,
+                module: true,
+                federation: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .cell())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+    use turbo_tasks::{fxindexmap, Vc};
+    use turbo_tasks_fs::{FileSystem, VirtualFileSystem};
+    use turbopack_core::resolve::{
+        options::ImportMapResult, parse::Request, ExternalType, ResolveResultItem,
+    };
+
+    use super::{ModuleFederationOptions, SharedDependency};
+
+    async fn lookup(
+        options: Vc<ModuleFederationOptions>,
+        request: &str,
+    ) -> Option<ResolveResultItem> {
+        let lookup_path = VirtualFileSystem::new().root().join("src".into());
+        let result = options
+            .import_map()
+            .await
+            .unwrap()
+            .lookup(lookup_path, Request::parse_string(request.into()))
+            .await
+            .unwrap();
+        match result {
+            ImportMapResult::Result(result) => {
+                let result = result.await.unwrap();
+                assert_eq!(result.primary.len(), 1);
+                result.primary.values().next().cloned()
+            }
+            ImportMapResult::NoEntry => None,
+            _ => panic!("unexpected import map result for {request}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn import_map_maps_remotes_and_shared_to_externals() {
+        crate::register();
+
+        turbo_tasks_testing::VcStorage::with(async {
+            let options = ModuleFederationOptions {
+                name: "host".into(),
+                remotes: fxindexmap! {
+                    "shop".into() => "shop@https://shop.example.com/remoteEntry.js".into(),
+                },
+                shared: fxindexmap! {
+                    "react".into() => SharedDependency {
+                        singleton: true,
+                        ..Default::default()
+                    },
+                },
+            }
+            .cell();
+
+            for request in ["shop", "shop/Button", "react"] {
+                assert!(
+                    matches!(
+                        lookup(options, request).await,
+                        Some(ResolveResultItem::External(name, ExternalType::ModuleFederation))
+                            if name == request
+                    ),
+                    "{request} should be a module federation external"
+                );
+            }
+            // Only exact requests of shared dependencies are shared, and other remotes or
+            // packages are resolved as usual.
+            for request in ["react/jsx-runtime", "shopping", "lodash"] {
+                assert!(
+                    lookup(options, request).await.is_none(),
+                    "{request} shouldn't be mapped"
+                );
+            }
+
+            anyhow::Ok(())
+        })
+        .await
+        .unwrap()
+    }
+}
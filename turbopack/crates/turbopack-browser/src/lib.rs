@@ -5,9 +5,19 @@
 
 pub(crate) mod chunking_context;
 pub mod ecmascript;
+pub mod federation;
 pub mod react_refresh;
+pub mod url_externals;
 
 pub use chunking_context::{BrowserChunkingContext, BrowserChunkingContextBuilder};
+pub use federation::{
+    module_federation_entry, FederationModules, ModuleFederationOptions,
+    OptionModuleFederationOptions, SharedDependency,
+};
+pub use url_externals::{
+    import_map_asset, import_map_loader_source, OptionUrlExternalsOptions, UrlExternal,
+    UrlExternalsOptions,
+};
 
 pub fn register() {
     turbo_tasks::register();
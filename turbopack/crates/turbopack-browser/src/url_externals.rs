@@ -0,0 +1,167 @@
+use anyhow::{bail, Result};
+use indoc::formatdoc;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value as JsonValue};
+use turbo_tasks::{trace::TraceRawVcs, FxIndexMap, RcStr, Vc};
+use turbo_tasks_fs::{File, FileSystemPath};
+use turbopack_core::{
+    asset::AssetContent,
+    output::OutputAsset,
+    resolve::{
+        options::{ImportMap, ImportMapping},
+        ExternalType,
+    },
+    source::Source,
+    virtual_output::VirtualOutputAsset,
+    virtual_source::VirtualSource,
+};
+use turbopack_ecmascript::utils::StringifyJs;
+
+/// A package that is loaded from a URL, e. g. a CDN, instead of being bundled.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct UrlExternal {
+    /// The URL the package is loaded from, e. g. `https://esm.sh/react@18`.
+    pub url: RcStr,
+    /// The subresource integrity metadata of the URL, e. g. `sha384-...`.
+    ///
+    /// Import maps key integrity metadata by the URL of a single module, so it
+    /// isn't supported for specifiers ending with `/`, which map many URLs.
+    pub integrity: Option<RcStr>,
+}
+
+/// Packages that are externalized to URLs in a browser build.
+///
+/// The externals are loaded with a native `import()` of their specifier,
+/// which the browser resolves through the import map created by
+/// [import_map_asset].
+///
+/// This requires the chunking context to be built with
+/// [url_externals][crate::BrowserChunkingContextBuilder::url_externals] and
+/// `import_externals` to be enabled in the ecmascript options.
+///
+/// The options are deserialized from the map of externals, e. g.
+/// `{ "react": { "url": "https://esm.sh/react@18", "integrity": "sha384-..." } }`.
+#[turbo_tasks::value(shared)]
+#[derive(Clone, Debug, Default)]
+#[serde(transparent)]
+pub struct UrlExternalsOptions {
+    /// Map from a specifier to the external it's loaded from. Specifiers
+    /// ending with `/` map all subpaths, like in import maps.
+    pub externals: FxIndexMap<RcStr, UrlExternal>,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionUrlExternalsOptions(Option<Vc<UrlExternalsOptions>>);
+
+impl UrlExternalsOptions {
+    fn validate(&self) -> Result<()> {
+        for (specifier, external) in &self.externals {
+            if specifier.ends_with('/') && external.integrity.is_some() {
+                bail!(
+                    "The URL external \"{specifier}\" maps all subpaths, so it can't have \
+                     integrity metadata. Add the modules that need it as separate externals \
+                     instead."
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl UrlExternalsOptions {
+    /// Returns an import map that maps the specifiers to ESM externals.
+    #[turbo_tasks::function]
+    pub fn import_map(&self) -> Result<Vc<ImportMap>> {
+        self.validate()?;
+        let mut import_map = ImportMap::empty();
+        let external = ImportMapping::External(None, ExternalType::EcmaScriptModule).cell();
+        for specifier in self.externals.keys() {
+            if specifier.ends_with('/') {
+                import_map.insert_wildcard_alias(specifier.clone(), external);
+            } else {
+                import_map.insert_exact_alias(specifier.clone(), external);
+            }
+        }
+        Ok(import_map.cell())
+    }
+
+    /// Returns the JSON of the browser import map, including the integrity
+    /// metadata of the URLs that have it.
+    #[turbo_tasks::function]
+    pub fn import_map_json(&self) -> Result<Vc<RcStr>> {
+        self.validate()?;
+        let mut imports = Map::new();
+        let mut integrity = Map::new();
+        for (specifier, external) in &self.externals {
+            imports.insert(
+                specifier.to_string(),
+                JsonValue::String(external.url.to_string()),
+            );
+            if let Some(metadata) = &external.integrity {
+                integrity.insert(
+                    external.url.to_string(),
+                    JsonValue::String(metadata.to_string()),
+                );
+            }
+        }
+        let mut import_map = json!({ "imports": imports });
+        if !integrity.is_empty() {
+            import_map["integrity"] = JsonValue::Object(integrity);
+        }
+        Ok(Vc::cell(serde_json::to_string_pretty(&import_map)?.into()))
+    }
+
+    /// Returns the `<script type="importmap">` tag for the HTML page. It must
+    /// come before any module script of the page.
+    #[turbo_tasks::function]
+    pub async fn import_map_script(self: Vc<Self>) -> Result<Vc<RcStr>> {
+        Ok(Vc::cell(
+            format!(
+                "<script type=\"importmap\">\n{}\n</script>",
+                // `<` only occurs within strings, where escaping it keeps the JSON
+                // from closing the script tag.
+                self.import_map_json().await?.replace('<', "\\u003c")
+            )
+            .into(),
+        ))
+    }
+}
+
+/// Creates the import map of the URL externals as a JSON asset at `path`.
+#[turbo_tasks::function]
+pub async fn import_map_asset(
+    path: Vc<FileSystemPath>,
+    options: Vc<UrlExternalsOptions>,
+) -> Result<Vc<Box<dyn OutputAsset>>> {
+    Ok(Vc::upcast(VirtualOutputAsset::new(
+        path,
+        AssetContent::file(File::from(options.import_map_json().await?.as_str()).into()),
+    )))
+}
+
+/// Creates a script at `path` that adds the import map of the URL externals to
+/// the page, for apps whose HTML isn't generated by the bundler. It must be
+/// evaluated before any of the externals is imported.
+#[turbo_tasks::function]
+pub async fn import_map_loader_source(
+    path: Vc<FileSystemPath>,
+    options: Vc<UrlExternalsOptions>,
+) -> Result<Vc<Box<dyn Source>>> {
+    let code = formatdoc! {
+        r#"
+            if (typeof document !== "undefined") {{
+                const script = document.createElement("script");
+                script.type = "importmap";
+                script.textContent = {import_map};
+                document.head.appendChild(script);
+            }}
+        "#,
+        import_map = StringifyJs(&*options.import_map_json().await?),
+    };
+    Ok(Vc::upcast(VirtualSource::new(
+        path,
+        AssetContent::file(File::from(code).into()),
+    )))
+}
@@ -89,6 +89,18 @@ pub struct DevArguments {
     #[clap(long)]
     pub no_open: bool,
 
+    /// Emit chunks as ES modules, which are loaded via `import()` instead of
+    /// classic scripts.
+    #[clap(long)]
+    pub module_chunks: bool,
+
+    /// A JSON file that maps packages to the URLs they are loaded from instead
+    /// of being bundled, e. g. `{ "react": { "url": "https://esm.sh/react@18" } }`.
+    /// The page includes them in its import map. Resolved relative to the
+    /// project's directory (`--dir`).
+    #[clap(long)]
+    pub url_externals: Option<String>,
+
     // ==
     // = Inherited options from next-dev, need revisit later.
     // ==
@@ -107,4 +119,19 @@ pub struct BuildArguments {
     /// Don't minify build output.
     #[clap(long)]
     pub no_minify: bool,
+
+    /// Explain how the request is resolved from the project directory instead
+    /// of building.
+    #[clap(long)]
+    pub explain_resolve: Option<String>,
+
+    /// Print the trace of `--explain-resolve` as a JSON report to stdout
+    /// instead of reporting it as an issue.
+    #[clap(long, requires = "explain_resolve")]
+    pub explain_resolve_json: bool,
+
+    /// Check the "exports" and "imports" fields of the package.json files in
+    /// the workspace.
+    #[clap(long)]
+    pub lint_package_exports: bool,
 }
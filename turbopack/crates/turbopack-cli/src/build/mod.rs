@@ -19,14 +19,16 @@ use turbopack_core::{
         EvaluatableAsset, EvaluatableAssets, MinifyType,
     },
     environment::{BrowserEnvironment, Environment, ExecutionEnvironment},
-    issue::{handle_issues, IssueReporter, IssueSeverity},
+    issue::{handle_issues, resolve::ResolveTraceIssue, IssueExt, IssueReporter, IssueSeverity},
     module::Module,
     output::{OutputAsset, OutputAssets},
     reference::all_assets_from_entries,
-    reference_type::{EntryReferenceSubType, ReferenceType},
+    reference_type::{EcmaScriptModulesReferenceSubType, EntryReferenceSubType, ReferenceType},
     resolve::{
-        origin::{PlainResolveOrigin, ResolveOriginExt},
+        exports_lint::lint_workspace_package_exports,
+        origin::{PlainResolveOrigin, ResolveOrigin, ResolveOriginExt},
         parse::Request,
+        trace::trace_resolve,
     },
 };
 use turbopack_ecmascript_runtime::RuntimeType;
@@ -58,6 +60,9 @@ pub struct TurbopackBuildBuilder {
     show_all: bool,
     log_detail: bool,
     minify_type: MinifyType,
+    explain_resolve: Option<RcStr>,
+    explain_resolve_json: bool,
+    lint_package_exports: bool,
 }
 
 impl TurbopackBuildBuilder {
@@ -75,6 +80,9 @@ impl TurbopackBuildBuilder {
             log_level: IssueSeverity::Warning,
             show_all: false,
             log_detail: false,
+            explain_resolve: None,
+            explain_resolve_json: false,
+            lint_package_exports: false,
             minify_type: MinifyType::Minify,
         }
     }
@@ -109,6 +117,27 @@ impl TurbopackBuildBuilder {
         self
     }
 
+    /// Instead of building, reports how `request` is resolved from the project
+    /// directory.
+    pub fn explain_resolve(mut self, request: Option<RcStr>) -> Self {
+        self.explain_resolve = request;
+        self
+    }
+
+    /// Prints the trace of [Self::explain_resolve] as JSON instead of
+    /// reporting it as an issue.
+    pub fn explain_resolve_json(mut self, explain_resolve_json: bool) -> Self {
+        self.explain_resolve_json = explain_resolve_json;
+        self
+    }
+
+    /// Reports problems in the "exports" and "imports" fields of the
+    /// package.json files in the workspace.
+    pub fn lint_package_exports(mut self, lint_package_exports: bool) -> Self {
+        self.lint_package_exports = lint_package_exports;
+        self
+    }
+
     pub async fn build(self) -> Result<()> {
         let task = self.turbo_tasks.spawn_once_task::<(), _>(async move {
             let build_result = build_internal(
@@ -124,6 +153,9 @@ impl TurbopackBuildBuilder {
                 .cell(),
                 self.browserslist_query,
                 self.minify_type,
+                self.explain_resolve,
+                self.explain_resolve_json,
+                self.lint_package_exports,
             );
 
             // Await the result to propagate any errors.
@@ -165,6 +197,9 @@ async fn build_internal(
     entry_requests: Vc<EntryRequests>,
     browserslist_query: RcStr,
     minify_type: MinifyType,
+    explain_resolve: Option<RcStr>,
+    explain_resolve_json: bool,
+    lint_package_exports: bool,
 ) -> Result<Vc<()>> {
     let env = Environment::new(Value::new(ExecutionEnvironment::Browser(
         BrowserEnvironment {
@@ -172,6 +207,7 @@ async fn build_internal(
             web_worker: false,
             service_worker: false,
             browserslist_query: browserslist_query.clone(),
+            module_chunks: false,
         }
         .into(),
     )));
@@ -186,6 +222,11 @@ async fn build_internal(
     let project_path = project_fs.root().join(project_relative);
     let build_output_root = output_fs.root().join("dist".into());
 
+    if lint_package_exports {
+        // Problems are reported as issues of the build
+        lint_workspace_package_exports(project_fs.root()).await?;
+    }
+
     let node_env = NodeEnv::Production.cell();
 
     let chunking_context = Vc::upcast(
@@ -205,11 +246,16 @@ async fn build_internal(
         .build(),
     );
 
-    let compile_time_info = get_client_compile_time_info(browserslist_query, node_env);
+    let compile_time_info = get_client_compile_time_info(browserslist_query, false, node_env);
     let execution_context =
         ExecutionContext::new(project_path, chunking_context, load_env(project_path));
-    let asset_context =
-        get_client_asset_context(project_path, execution_context, compile_time_info, node_env);
+    let asset_context = get_client_asset_context(
+        project_path,
+        execution_context,
+        compile_time_info,
+        node_env,
+        Vc::cell(None),
+    );
 
     let entry_requests = (*entry_requests
         .await?
@@ -236,6 +282,30 @@ async fn build_internal(
         .to_vec();
 
     let origin = PlainResolveOrigin::new(asset_context, output_fs.root().join("_".into()));
+
+    if let Some(request) = explain_resolve {
+        let ty = Value::new(ReferenceType::EcmaScriptModules(
+            EcmaScriptModulesReferenceSubType::Undefined,
+        ));
+        let lookup_path = origin.origin_path().parent();
+        let trace = trace_resolve(
+            lookup_path,
+            ty.clone(),
+            Request::parse_string(request),
+            origin.resolve_options(ty),
+        );
+        if explain_resolve_json {
+            println!("{}", serde_json::to_string_pretty(&*trace.await?)?);
+        } else {
+            ResolveTraceIssue {
+                file_path: lookup_path,
+                trace,
+            }
+            .cell()
+            .emit();
+        }
+        return Ok(Default::default());
+    }
     let project_dir = &project_dir;
     let entries = entry_requests
         .into_iter()
@@ -343,7 +413,15 @@ pub async fn build(args: &BuildArguments) -> Result<()> {
         } else {
             MinifyType::Minify
         })
-        .show_all(args.common.show_all);
+        .show_all(args.common.show_all)
+        .explain_resolve(args.explain_resolve.clone().map(RcStr::from))
+        .explain_resolve_json(args.explain_resolve_json)
+        .lint_package_exports(args.lint_package_exports);
+
+    if args.explain_resolve.is_some() && !args.explain_resolve_json {
+        // The trace is reported as an informational issue with details
+        builder = builder.log_level(IssueSeverity::Info).log_detail(true);
+    }
 
     for entry in normalize_entries(&args.common.entries) {
         builder = builder.entry_request(EntryRequest::Relative(entry));
@@ -1,8 +1,8 @@
 use std::fmt;
 
-use anyhow::Result;
-use turbo_tasks::{RcStr, Value, Vc};
-use turbo_tasks_fs::{FileSystem, FileSystemPath};
+use anyhow::{bail, Context, Result};
+use turbo_tasks::{RcStr, Value, ValueToString, Vc};
+use turbo_tasks_fs::{FileJsonContent, FileSystem, FileSystemPath};
 use turbopack::{
     ecmascript::{EcmascriptInputTransform, TreeShakingMode},
     module_options::{
@@ -11,7 +11,9 @@ use turbopack::{
     },
     ModuleAssetContext,
 };
-use turbopack_browser::react_refresh::assert_can_resolve_react_refresh;
+use turbopack_browser::{
+    react_refresh::assert_can_resolve_react_refresh, OptionUrlExternalsOptions, UrlExternalsOptions,
+};
 use turbopack_core::{
     compile_time_defines,
     compile_time_info::{CompileTimeDefines, CompileTimeInfo},
@@ -49,8 +51,33 @@ async fn foreign_code_context_condition() -> Result<ContextCondition> {
     Ok(ContextCondition::InDirectory("node_modules".to_string()))
 }
 
+/// Reads the URL externals from the JSON file at `path`, relative to the
+/// project directory.
+#[turbo_tasks::function]
+pub async fn get_url_externals(
+    project_path: Vc<FileSystemPath>,
+    path: Option<RcStr>,
+) -> Result<Vc<OptionUrlExternalsOptions>> {
+    let Some(path) = path else {
+        return Ok(Vc::cell(None));
+    };
+    let path = project_path.join(path);
+    let FileJsonContent::Content(json) = &*path.read_json().await? else {
+        bail!(
+            "Unable to read the URL externals from {}",
+            path.to_string().await?
+        );
+    };
+    let url_externals: UrlExternalsOptions = serde_json::from_value(json.clone())
+        .with_context(|| format!("Invalid URL externals in {}", path.to_string().await?))?;
+    Ok(Vc::cell(Some(url_externals.cell())))
+}
+
 #[turbo_tasks::function]
-pub fn get_client_import_map(project_path: Vc<FileSystemPath>) -> Vc<ImportMap> {
+pub async fn get_client_import_map(
+    project_path: Vc<FileSystemPath>,
+    url_externals: Vc<OptionUrlExternalsOptions>,
+) -> Result<Vc<ImportMap>> {
     let mut import_map = ImportMap::empty();
 
     import_map.insert_singleton_alias("@swc/helpers", project_path);
@@ -67,14 +94,20 @@ pub fn get_client_import_map(project_path: Vc<FileSystemPath>) -> Vc<ImportMap>
         .cell(),
     );
 
-    import_map.cell()
+    // URL externals replace the singleton aliases of the same packages.
+    if let Some(url_externals) = *url_externals.await? {
+        import_map.extend_ref(&*url_externals.import_map().await?);
+    }
+
+    Ok(import_map.cell())
 }
 
 #[turbo_tasks::function]
 pub async fn get_client_resolve_options_context(
     project_path: Vc<FileSystemPath>,
+    url_externals: Vc<OptionUrlExternalsOptions>,
 ) -> Result<Vc<ResolveOptionsContext>> {
-    let next_client_import_map = get_client_import_map(project_path);
+    let next_client_import_map = get_client_import_map(project_path, url_externals);
     let module_options_context = ResolveOptionsContext {
         enable_node_modules: Some(project_path.root().resolve().await?),
         custom_conditions: vec!["development".into()],
@@ -101,15 +134,23 @@ async fn get_client_module_options_context(
     execution_context: Vc<ExecutionContext>,
     env: Vc<Environment>,
     node_env: Vc<NodeEnv>,
+    url_externals: Vc<OptionUrlExternalsOptions>,
 ) -> Result<Vc<ModuleOptionsContext>> {
+    // URL externals are loaded with a native `import()`, also from
+    // `node_modules`.
+    let import_externals = url_externals.await?.is_some();
     let module_options_context = ModuleOptionsContext {
+        ecmascript: EcmascriptOptionsContext {
+            import_externals,
+            ..Default::default()
+        },
         preset_env_versions: Some(env),
         execution_context: Some(execution_context),
         tree_shaking_mode: Some(TreeShakingMode::ReexportsOnly),
         ..Default::default()
     };
 
-    let resolve_options_context = get_client_resolve_options_context(project_path);
+    let resolve_options_context = get_client_resolve_options_context(project_path, url_externals);
 
     let enable_react_refresh = matches!(*node_env.await?, NodeEnv::Development)
         && assert_can_resolve_react_refresh(project_path, resolve_options_context)
@@ -157,6 +198,7 @@ async fn get_client_module_options_context(
         ecmascript: EcmascriptOptionsContext {
             enable_jsx,
             enable_typescript_transform: Some(Default::default()),
+            import_externals,
             ..Default::default()
         },
         enable_postcss_transform: Some(PostCssTransformOptions::default().cell()),
@@ -178,13 +220,15 @@ pub fn get_client_asset_context(
     execution_context: Vc<ExecutionContext>,
     compile_time_info: Vc<CompileTimeInfo>,
     node_env: Vc<NodeEnv>,
+    url_externals: Vc<OptionUrlExternalsOptions>,
 ) -> Vc<Box<dyn AssetContext>> {
-    let resolve_options_context = get_client_resolve_options_context(project_path);
+    let resolve_options_context = get_client_resolve_options_context(project_path, url_externals);
     let module_options_context = get_client_module_options_context(
         project_path,
         execution_context,
         compile_time_info.environment(),
         node_env,
+        url_externals,
     );
 
     let asset_context: Vc<Box<dyn AssetContext>> = Vc::upcast(ModuleAssetContext::new(
@@ -210,6 +254,7 @@ fn client_defines(node_env: &NodeEnv) -> Vc<CompileTimeDefines> {
 #[turbo_tasks::function]
 pub async fn get_client_compile_time_info(
     browserslist_query: RcStr,
+    module_chunks: bool,
     node_env: Vc<NodeEnv>,
 ) -> Result<Vc<CompileTimeInfo>> {
     Ok(
@@ -219,6 +264,7 @@ pub async fn get_client_compile_time_info(
                 web_worker: false,
                 service_worker: false,
                 browserslist_query,
+                module_chunks,
             }
             .into(),
         ))))
@@ -41,7 +41,7 @@ use turbopack_nodejs::NodeJsChunkingContext;
 use self::web_entry_source::create_web_entry_source;
 use crate::{
     arguments::DevArguments,
-    contexts::NodeEnv,
+    contexts::{get_url_externals, NodeEnv},
     util::{
         normalize_dirs, normalize_entries, output_fs, project_fs, EntryRequest, NormalizedDirs,
     },
@@ -59,6 +59,8 @@ pub struct TurbopackDevServerBuilder {
     issue_reporter: Option<Box<dyn IssueReporterProvider>>,
     port: Option<u16>,
     browserslist_query: RcStr,
+    module_chunks: bool,
+    url_externals: Option<RcStr>,
     log_level: IssueSeverity,
     show_all: bool,
     log_detail: bool,
@@ -83,6 +85,8 @@ impl TurbopackDevServerBuilder {
             browserslist_query: "last 1 Chrome versions, last 1 Firefox versions, last 1 Safari \
                                  versions, last 1 Edge versions"
                 .into(),
+            module_chunks: false,
+            url_externals: None,
             log_level: IssueSeverity::Warning,
             show_all: false,
             log_detail: false,
@@ -115,6 +119,20 @@ impl TurbopackDevServerBuilder {
         self
     }
 
+    /// Emits chunks as ES modules, which are loaded via `import()` instead of
+    /// classic scripts.
+    pub fn module_chunks(mut self, module_chunks: bool) -> TurbopackDevServerBuilder {
+        self.module_chunks = module_chunks;
+        self
+    }
+
+    /// Loads the packages in the JSON file at `url_externals`, relative to
+    /// the project directory, from URLs instead of bundling them.
+    pub fn url_externals(mut self, url_externals: Option<RcStr>) -> TurbopackDevServerBuilder {
+        self.url_externals = url_externals;
+        self
+    }
+
     pub fn log_level(mut self, log_level: IssueSeverity) -> TurbopackDevServerBuilder {
         self.log_level = log_level;
         self
@@ -196,6 +214,8 @@ impl TurbopackDevServerBuilder {
         let show_all = self.show_all;
         let log_detail: bool = self.log_detail;
         let browserslist_query: RcStr = self.browserslist_query;
+        let module_chunks = self.module_chunks;
+        let url_externals = self.url_externals;
         let log_args = TransientInstance::new(LogOptions {
             current_dir: current_dir().unwrap(),
             project_dir: PathBuf::from(project_dir.clone()),
@@ -217,6 +237,8 @@ impl TurbopackDevServerBuilder {
                 entry_requests.clone(),
                 eager_compile,
                 browserslist_query.clone(),
+                module_chunks,
+                url_externals.clone(),
             )
         };
 
@@ -232,6 +254,8 @@ async fn source(
     entry_requests: TransientInstance<Vec<EntryRequest>>,
     eager_compile: bool,
     browserslist_query: RcStr,
+    module_chunks: bool,
+    url_externals: Option<RcStr>,
 ) -> Vc<Box<dyn ContentSource>> {
     let project_relative = project_dir.strip_prefix(&*root_dir).unwrap();
     let project_relative: RcStr = project_relative
@@ -290,6 +314,8 @@ async fn source(
         eager_compile,
         NodeEnv::Development.cell(),
         browserslist_query,
+        module_chunks,
+        get_url_externals(project_path, url_externals),
     );
     let static_source = Vc::upcast(StaticAssetsContentSource::new(
         Default::default(),
@@ -338,6 +364,8 @@ pub async fn start_server(args: &DevArguments) -> Result<()> {
 
     let mut server = TurbopackDevServerBuilder::new(tt, project_dir, root_dir)
         .eager_compile(args.eager_compile)
+        .module_chunks(args.module_chunks)
+        .url_externals(args.url_externals.clone().map(RcStr::from))
         .hostname(args.hostname)
         .port(args.port)
         .log_detail(args.common.log_detail)
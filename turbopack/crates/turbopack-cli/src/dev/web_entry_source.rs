@@ -1,13 +1,17 @@
 use anyhow::{anyhow, Result};
-use turbo_tasks::{RcStr, TryJoinIterExt, Value, Vc};
+use turbo_tasks::{FxIndexSet, RcStr, TryJoinIterExt, Value, Vc};
 use turbo_tasks_env::ProcessEnv;
 use turbo_tasks_fs::FileSystemPath;
-use turbopack_browser::{react_refresh::assert_can_resolve_react_refresh, BrowserChunkingContext};
+use turbopack_browser::{
+    import_map_asset, react_refresh::assert_can_resolve_react_refresh, BrowserChunkingContext,
+    OptionUrlExternalsOptions,
+};
 use turbopack_cli_utils::runtime_entry::{RuntimeEntries, RuntimeEntry};
 use turbopack_core::{
     chunk::{ChunkableModule, ChunkingContext, EvaluatableAsset},
     environment::Environment,
     file_source::FileSource,
+    output::OutputAsset,
     reference_type::{EntryReferenceSubType, ReferenceType},
     resolve::{
         origin::{PlainResolveOrigin, ResolveOriginExt},
@@ -34,27 +38,30 @@ pub fn get_client_chunking_context(
     project_path: Vc<FileSystemPath>,
     server_root: Vc<FileSystemPath>,
     environment: Vc<Environment>,
+    url_externals: bool,
 ) -> Vc<Box<dyn ChunkingContext>> {
-    Vc::upcast(
-        BrowserChunkingContext::builder(
-            project_path,
-            server_root,
-            server_root,
-            server_root.join("/_chunks".into()),
-            server_root.join("/_assets".into()),
-            environment,
-            RuntimeType::Development,
-        )
-        .hot_module_replacement()
-        .build(),
+    let mut builder = BrowserChunkingContext::builder(
+        project_path,
+        server_root,
+        server_root,
+        server_root.join("/_chunks".into()),
+        server_root.join("/_assets".into()),
+        environment,
+        RuntimeType::Development,
     )
+    .hot_module_replacement();
+    if url_externals {
+        builder = builder.url_externals();
+    }
+    Vc::upcast(builder.build())
 }
 
 #[turbo_tasks::function]
 pub async fn get_client_runtime_entries(
     project_path: Vc<FileSystemPath>,
+    url_externals: Vc<OptionUrlExternalsOptions>,
 ) -> Result<Vc<RuntimeEntries>> {
-    let resolve_options_context = get_client_resolve_options_context(project_path);
+    let resolve_options_context = get_client_resolve_options_context(project_path, url_externals);
 
     let mut runtime_entries = Vec::new();
 
@@ -89,13 +96,25 @@ pub async fn create_web_entry_source(
     eager_compile: bool,
     node_env: Vc<NodeEnv>,
     browserslist_query: RcStr,
+    module_chunks: bool,
+    url_externals: Vc<OptionUrlExternalsOptions>,
 ) -> Result<Vc<Box<dyn ContentSource>>> {
-    let compile_time_info = get_client_compile_time_info(browserslist_query, node_env);
-    let asset_context =
-        get_client_asset_context(project_path, execution_context, compile_time_info, node_env);
-    let chunking_context =
-        get_client_chunking_context(project_path, server_root, compile_time_info.environment());
-    let entries = get_client_runtime_entries(project_path);
+    let compile_time_info =
+        get_client_compile_time_info(browserslist_query, module_chunks, node_env);
+    let asset_context = get_client_asset_context(
+        project_path,
+        execution_context,
+        compile_time_info,
+        node_env,
+        url_externals,
+    );
+    let chunking_context = get_client_chunking_context(
+        project_path,
+        server_root,
+        compile_time_info.environment(),
+        url_externals.await?.is_some(),
+    );
+    let entries = get_client_runtime_entries(project_path, url_externals);
 
     let runtime_entries = entries.resolve_entries(asset_context);
 
@@ -146,15 +165,22 @@ pub async fn create_web_entry_source(
         .try_join()
         .await?;
 
-    let entry_asset = Vc::upcast(DevHtmlAsset::new(
-        server_root.join("index.html".into()),
-        entries,
-    ));
+    let mut entry_asset = DevHtmlAsset::new(server_root.join("index.html".into()), entries);
+    let mut root_assets = FxIndexSet::default();
+    if let Some(url_externals) = *url_externals.await? {
+        // The import map must come before the chunks, which load the externals.
+        entry_asset = entry_asset.with_head(url_externals.import_map_script().await?.clone_value());
+        root_assets.insert(import_map_asset(
+            server_root.join("importmap.json".into()),
+            url_externals,
+        ));
+    }
+    root_assets.insert(Vc::upcast::<Box<dyn OutputAsset>>(entry_asset));
 
     let graph = Vc::upcast(if eager_compile {
-        AssetGraphContentSource::new_eager(server_root, entry_asset)
+        AssetGraphContentSource::new_eager_multiple(server_root, Vc::cell(root_assets))
     } else {
-        AssetGraphContentSource::new_lazy(server_root, entry_asset)
+        AssetGraphContentSource::new_lazy_multiple(server_root, Vc::cell(root_assets))
     });
     Ok(graph)
 }
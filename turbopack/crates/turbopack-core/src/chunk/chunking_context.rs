@@ -4,7 +4,10 @@ use turbo_tasks::{trace::TraceRawVcs, RcStr, TaskInput, Upcast, Value, Vc};
 use turbo_tasks_fs::FileSystemPath;
 use turbo_tasks_hash::DeterministicHash;
 
-use super::{availability_info::AvailabilityInfo, ChunkableModule, EvaluatableAssets};
+use super::{
+    availability_info::AvailabilityInfo, content_hash::OptionContentHashing,
+    integrity::OptionIntegrityAlgorithm, ChunkableModule, EvaluatableAssets,
+};
 use crate::{
     chunk::{ChunkItem, ModuleId},
     environment::Environment,
@@ -82,6 +85,18 @@ pub trait ChunkingContext {
         Vc::cell(false)
     }
 
+    /// Whether chunk filenames include a hash of the chunk content. See
+    /// [ContentHashing][super::ContentHashing].
+    fn content_hashing(self: Vc<Self>) -> Vc<OptionContentHashing> {
+        Vc::cell(None)
+    }
+
+    /// The algorithm used to compute the integrity metadata of chunks loaded
+    /// by the runtime, if any. See [IntegrityAlgorithm][super::IntegrityAlgorithm].
+    fn integrity_algorithm(self: Vc<Self>) -> Vc<OptionIntegrityAlgorithm> {
+        Vc::cell(None)
+    }
+
     fn async_loader_chunk_item(
         &self,
         module: Vc<Box<dyn ChunkableModule>>,
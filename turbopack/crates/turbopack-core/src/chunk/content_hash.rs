@@ -0,0 +1,205 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{trace::TraceRawVcs, TaskInput, Vc};
+use turbo_tasks_fs::{rope::Rope, FileSystemPath};
+use turbo_tasks_hash::{hash_xxh3_hash64, DeterministicHash};
+
+use crate::code_builder::Code;
+
+/// How the filenames of emitted chunks include a hash of their content, so
+/// they can be cached immutably.
+#[derive(
+    Debug,
+    TaskInput,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    TraceRawVcs,
+    DeterministicHash,
+)]
+pub enum ContentHashing {
+    /// Inserts the first `length` hex digits of the hash of the chunk content
+    /// (after minification) before the extension, e. g. `chunk.1a2b3c4d.js`.
+    /// The content is hashed as rendered with the placeholder path, before the
+    /// hash is filled into the chunk path and the `sourceMappingURL` comment,
+    /// so the emitted bytes differ from the hashed ones in these places.
+    /// `length` is capped at 16.
+    Direct { length: u8 },
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionContentHashing(Option<ContentHashing>);
+
+impl ContentHashing {
+    fn length(self) -> usize {
+        match self {
+            ContentHashing::Direct { length } => (length as usize).clamp(1, 16),
+        }
+    }
+
+    /// Chunks reference their own path in their content, e. g. to register
+    /// themselves with the runtime. As the hash can only be computed from the
+    /// content, chunks are rendered with this placeholder in place of the
+    /// hash. It has the same length as the hash, so source maps stay valid
+    /// when it is replaced.
+    fn placeholder(self) -> String {
+        "_".repeat(self.length())
+    }
+
+    fn hash(self, content: &Rope) -> String {
+        let mut hash = format!("{:016x}", hash_xxh3_hash64(content));
+        hash.truncate(self.length());
+        hash
+    }
+
+    /// Replaces the placeholder in the places a chunk refers to itself by the
+    /// hash of `code`: the path the chunk registers itself with, which is
+    /// the first occurrence of `chunk_path`, and the `sourceMappingURL`
+    /// comment. `code` has to be rendered with `chunk_path`, the output root
+    /// relative path returned by [content_hash_placeholder_path]. Everything
+    /// else in the chunk is left untouched, even if it happens to contain the
+    /// placeholder.
+    pub fn apply(self, code: &Code, chunk_path: &str) -> Result<Rope> {
+        let source = code.source_code();
+        let hash = self.hash(source);
+        let mut content = source
+            .to_str()
+            .context("content hashed chunks must be valid UTF-8")?
+            .into_owned();
+
+        let start = content.find(chunk_path).with_context(|| {
+            format!("content hashed chunk doesn't contain its path {chunk_path}")
+        })?;
+        let hashed_path = self.replace_placeholder(chunk_path, &hash)?;
+        content.replace_range(start..start + chunk_path.len(), &hashed_path);
+
+        if let Some(start) = content.rfind(SOURCE_MAPPING_URL) {
+            let end = content[start..]
+                .find('\n')
+                .map_or(content.len(), |end| start + end);
+            let comment = self.replace_placeholder(&content[start..end], &hash)?;
+            content.replace_range(start..end, &comment);
+        }
+
+        Ok(Rope::from(content))
+    }
+
+    /// Replaces the last placeholder segment in `path`, which is the one
+    /// inserted by [content_hash_placeholder_path], by `hash`.
+    fn replace_placeholder(self, path: &str, hash: &str) -> Result<String> {
+        let segment = format!(".{}.", self.placeholder());
+        let start = path
+            .rfind(&segment)
+            .with_context(|| format!("{path} doesn't contain a content hash placeholder"))?;
+        Ok(format!(
+            "{}.{hash}.{}",
+            &path[..start],
+            &path[start + segment.len()..]
+        ))
+    }
+}
+
+const SOURCE_MAPPING_URL: &str = "//# sourceMappingURL=";
+
+/// Returns `path` with the content hash placeholder inserted before its
+/// extension. Chunks render their content with this path.
+#[turbo_tasks::function]
+pub async fn content_hash_placeholder_path(
+    path: Vc<FileSystemPath>,
+    content_hashing: ContentHashing,
+) -> Result<Vc<FileSystemPath>> {
+    insert_before_extension(path, &content_hashing.placeholder()).await
+}
+
+/// Returns `path` with the hash of `code` inserted before its extension.
+/// `code` has to be rendered with [content_hash_placeholder_path].
+#[turbo_tasks::function]
+pub async fn content_hashed_path(
+    path: Vc<FileSystemPath>,
+    content_hashing: ContentHashing,
+    code: Vc<Code>,
+) -> Result<Vc<FileSystemPath>> {
+    let hash = content_hashing.hash(code.await?.source_code());
+    insert_before_extension(path, &hash).await
+}
+
+async fn insert_before_extension(
+    path: Vc<FileSystemPath>,
+    segment: &str,
+) -> Result<Vc<FileSystemPath>> {
+    let extension = extension_with_segment(path.await?.extension_ref(), segment);
+    Ok(path.with_extension(extension.into()))
+}
+
+/// The extension of a path with `segment` inserted before `extension`.
+fn extension_with_segment(extension: Option<&str>, segment: &str) -> String {
+    match extension {
+        Some(extension) => format!("{segment}.{extension}"),
+        None => segment.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use turbo_tasks_fs::{rope::Rope, FileSystem, VirtualFileSystem};
+
+    use super::{content_hash_placeholder_path, content_hashed_path, ContentHashing};
+    use crate::code_builder::CodeBuilder;
+
+    #[test]
+    fn hash_matches_placeholder_length() {
+        let content_hashing = ContentHashing::Direct { length: 8 };
+        let hash = content_hashing.hash(&Rope::from("console.log(1)".to_string()));
+        assert_eq!(hash.len(), content_hashing.placeholder().len());
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+
+        let long = ContentHashing::Direct { length: 40 };
+        assert_eq!(long.placeholder().len(), 16);
+    }
+
+    #[allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+    #[tokio::test]
+    async fn apply_matches_hashed_path() {
+        crate::register();
+
+        turbo_tasks_testing::VcStorage::with(async {
+            let content_hashing = ContentHashing::Direct { length: 8 };
+            let path = VirtualFileSystem::new()
+                .root()
+                .join("static/chunks/a.js".into());
+
+            let placeholder_path = content_hash_placeholder_path(path, content_hashing).await?;
+            let placeholder = content_hashing.placeholder();
+            let mut builder = CodeBuilder::default();
+            builder.push_source(
+                &Rope::from(format!(
+                    "(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push([\"{}\", \
+                     {{}}]);\nconst unrelated = \"x.{placeholder}.js\";\n\n//# \
+                     sourceMappingURL={}.map",
+                    placeholder_path.path,
+                    placeholder_path.file_name()
+                )),
+                None,
+            );
+            let code = builder.build();
+
+            let content = content_hashing.apply(&code, &placeholder_path.path)?;
+            let content = content.to_str()?;
+            let hashed_path = content_hashed_path(path, content_hashing, code.cell()).await?;
+            assert!(content.contains(&format!(".push([\"{}\"", hashed_path.path)));
+            assert!(content.ends_with(&format!(
+                "//# sourceMappingURL={}.map",
+                hashed_path.file_name()
+            )));
+            assert!(content.contains(&format!("const unrelated = \"x.{placeholder}.js\";")));
+
+            anyhow::Ok(())
+        })
+        .await
+        .unwrap()
+    }
+}
@@ -3,7 +3,10 @@ use turbo_tasks::{RcStr, ReadRef, TryJoinIterExt, Vc};
 use turbo_tasks_fs::FileSystemPath;
 
 use crate::{
-    chunk::{ModuleId, OutputChunk, OutputChunkRuntimeInfo},
+    chunk::{
+        integrity::{output_asset_integrity, OptionIntegrityAlgorithm},
+        ModuleId, OutputChunk, OutputChunkRuntimeInfo,
+    },
     output::{OutputAsset, OutputAssets},
 };
 
@@ -14,6 +17,9 @@ pub struct ChunkData {
     pub excluded: Vec<ReadRef<ModuleId>>,
     pub module_chunks: Vec<String>,
     pub references: Vc<OutputAssets>,
+    /// The subresource integrity metadata of the chunk, when an
+    /// [IntegrityAlgorithm][crate::chunk::IntegrityAlgorithm] is configured.
+    pub integrity: Option<RcStr>,
 }
 
 #[turbo_tasks::value(transparent)]
@@ -37,6 +43,7 @@ impl ChunkData {
     pub async fn from_asset(
         output_root: Vc<FileSystemPath>,
         chunk: Vc<Box<dyn OutputAsset>>,
+        integrity_algorithm: Vc<OptionIntegrityAlgorithm>,
     ) -> Result<Vc<ChunkDataOption>> {
         let output_root = output_root.await?;
         let path = chunk.ident().path().await?;
@@ -48,6 +55,13 @@ impl ChunkData {
         };
         let path = path.to_string();
 
+        let integrity = match *integrity_algorithm.await? {
+            Some(algorithm) => output_asset_integrity(chunk, algorithm)
+                .await?
+                .clone_value(),
+            None => None,
+        };
+
         let Some(output_chunk) = Vc::try_resolve_sidecast::<Box<dyn OutputChunk>>(chunk).await?
         else {
             return Ok(Vc::cell(Some(
@@ -57,6 +71,7 @@ impl ChunkData {
                     excluded: Vec::new(),
                     module_chunks: Vec::new(),
                     references: OutputAssets::empty(),
+                    integrity,
                 }
                 .cell(),
             )));
@@ -112,6 +127,7 @@ impl ChunkData {
                 excluded,
                 module_chunks,
                 references: Vc::cell(module_chunks_references),
+                integrity,
             }
             .cell(),
         )))
@@ -121,12 +137,13 @@ impl ChunkData {
     pub async fn from_assets(
         output_root: Vc<FileSystemPath>,
         chunks: Vc<OutputAssets>,
+        integrity_algorithm: Vc<OptionIntegrityAlgorithm>,
     ) -> Result<Vc<ChunksData>> {
         Ok(Vc::cell(
             chunks
                 .await?
                 .iter()
-                .map(|&chunk| ChunkData::from_asset(output_root, chunk))
+                .map(|&chunk| ChunkData::from_asset(output_root, chunk, integrity_algorithm))
                 .try_join()
                 .await?
                 .into_iter()
@@ -0,0 +1,112 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use turbo_tasks::{trace::TraceRawVcs, RcStr, TaskInput, Vc};
+use turbo_tasks_fs::{rope::Rope, FileContent};
+
+use crate::{
+    asset::{Asset, AssetContent},
+    output::OutputAsset,
+};
+
+/// The hash algorithm used for [subresource integrity] metadata of emitted
+/// chunks.
+///
+/// [subresource integrity]: https://developer.mozilla.org/en-US/docs/Web/Security/Subresource_Integrity
+#[derive(
+    Debug, TaskInput, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TraceRawVcs,
+)]
+pub enum IntegrityAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionIntegrityAlgorithm(Option<IntegrityAlgorithm>);
+
+impl IntegrityAlgorithm {
+    fn prefix(self) -> &'static str {
+        match self {
+            IntegrityAlgorithm::Sha256 => "sha256",
+            IntegrityAlgorithm::Sha384 => "sha384",
+            IntegrityAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    fn digest<D: Digest>(content: &Rope) -> Vec<u8> {
+        let mut hasher = D::new();
+        for bytes in content.read() {
+            hasher.update(bytes);
+        }
+        hasher.finalize().to_vec()
+    }
+
+    /// Returns the integrity metadata of `content`, e. g. `sha384-<base64>`,
+    /// as used in the `integrity` attribute of script and link tags.
+    pub fn integrity(self, content: &Rope) -> String {
+        let digest = match self {
+            IntegrityAlgorithm::Sha256 => Self::digest::<Sha256>(content),
+            IntegrityAlgorithm::Sha384 => Self::digest::<Sha384>(content),
+            IntegrityAlgorithm::Sha512 => Self::digest::<Sha512>(content),
+        };
+        format!("{}-{}", self.prefix(), STANDARD.encode(digest))
+    }
+}
+
+impl FromStr for IntegrityAlgorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "sha256" => IntegrityAlgorithm::Sha256,
+            "sha384" => IntegrityAlgorithm::Sha384,
+            "sha512" => IntegrityAlgorithm::Sha512,
+            _ => bail!(
+                "unsupported subresource integrity algorithm \"{s}\", expected one of \"sha256\", \
+                 \"sha384\" or \"sha512\""
+            ),
+        })
+    }
+}
+
+/// Computes the integrity metadata of the emitted content of `asset`. Returns
+/// `None` for assets that aren't emitted as a file.
+#[turbo_tasks::function]
+pub async fn output_asset_integrity(
+    asset: Vc<Box<dyn OutputAsset>>,
+    algorithm: IntegrityAlgorithm,
+) -> Result<Vc<Option<RcStr>>> {
+    let AssetContent::File(file) = &*asset.content().await? else {
+        return Ok(Vc::cell(None));
+    };
+    let FileContent::Content(file) = &*file.await? else {
+        return Ok(Vc::cell(None));
+    };
+    Ok(Vc::cell(Some(algorithm.integrity(file.content()).into())))
+}
+
+#[cfg(test)]
+mod tests {
+    use turbo_tasks_fs::rope::Rope;
+
+    use super::IntegrityAlgorithm;
+
+    #[test]
+    fn integrity_of_known_content() {
+        // echo -n "alert('Hello, world.');" | openssl dgst -sha384 -binary | openssl base64 -A
+        let content = Rope::from("alert('Hello, world.');".to_string());
+        assert_eq!(
+            IntegrityAlgorithm::Sha384.integrity(&content),
+            "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO"
+        );
+        assert_eq!(
+            "sha512".parse::<IntegrityAlgorithm>().unwrap(),
+            IntegrityAlgorithm::Sha512
+        );
+        assert!("md5".parse::<IntegrityAlgorithm>().is_err());
+    }
+}
@@ -4,8 +4,10 @@ pub mod chunk_group;
 pub mod chunking;
 pub(crate) mod chunking_context;
 pub(crate) mod containment_tree;
+pub(crate) mod content_hash;
 pub(crate) mod data;
 pub(crate) mod evaluate;
+pub(crate) mod integrity;
 pub mod module_id_strategies;
 pub mod optimize;
 
@@ -35,8 +37,12 @@ pub use self::{
     chunking_context::{
         ChunkGroupResult, ChunkingContext, ChunkingContextExt, EntryChunkGroupResult, MinifyType,
     },
+    content_hash::{
+        content_hash_placeholder_path, content_hashed_path, ContentHashing, OptionContentHashing,
+    },
     data::{ChunkData, ChunkDataOption, ChunksData},
     evaluate::{EvaluatableAsset, EvaluatableAssetExt, EvaluatableAssets},
+    integrity::{output_asset_integrity, IntegrityAlgorithm, OptionIntegrityAlgorithm},
 };
 use crate::{
     asset::Asset,
@@ -34,6 +34,9 @@ pub enum ChunkLoading {
     NodeJs,
     /// <script> and <link> tags in the browser
     Dom,
+    /// ES modules loaded via `import()` and `<link rel="modulepreload">` in
+    /// the browser
+    DomModule,
 }
 
 #[turbo_tasks::value]
@@ -207,16 +210,22 @@ impl Environment {
     }
 
     #[turbo_tasks::function]
-    pub fn chunk_loading(&self) -> Vc<ChunkLoading> {
+    pub async fn chunk_loading(&self) -> Result<Vc<ChunkLoading>> {
         let env = self;
-        match env.execution {
+        Ok(match env.execution {
             ExecutionEnvironment::NodeJsBuildTime(_) | ExecutionEnvironment::NodeJsLambda(_) => {
                 ChunkLoading::NodeJs.cell()
             }
             ExecutionEnvironment::EdgeWorker(_) => ChunkLoading::Edge.cell(),
-            ExecutionEnvironment::Browser(_) => ChunkLoading::Dom.cell(),
+            ExecutionEnvironment::Browser(browser_env) => {
+                if browser_env.await?.module_chunks {
+                    ChunkLoading::DomModule.cell()
+                } else {
+                    ChunkLoading::Dom.cell()
+                }
+            }
             ExecutionEnvironment::Custom(_) => todo!(),
-        }
+        })
     }
 }
 
@@ -288,6 +297,9 @@ pub struct BrowserEnvironment {
     pub web_worker: bool,
     pub service_worker: bool,
     pub browserslist_query: RcStr,
+    /// Whether chunks are emitted as ES modules, which are loaded via
+    /// `import()` instead of classic scripts.
+    pub module_chunks: bool,
 }
 
 #[turbo_tasks::value(shared)]
@@ -11,6 +11,7 @@ use crate::{
     resolve::{
         options::{ImportMap, ImportMapResult, ResolveOptions},
         parse::Request,
+        trace::ResolveTrace,
     },
 };
 
@@ -126,6 +127,77 @@ impl Issue for ResolvingIssue {
     // TODO add source link
 }
 
+/// An informational issue listing the steps taken to resolve a request, see
+/// [crate::resolve::trace::trace_resolve].
+#[turbo_tasks::value(shared)]
+pub struct ResolveTraceIssue {
+    pub file_path: Vc<FileSystemPath>,
+    pub trace: Vc<ResolveTrace>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for ResolveTraceIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Info.cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn title(&self) -> Result<Vc<StyledString>> {
+        Ok(StyledString::Line(vec![
+            StyledString::Text("Resolving '".into()),
+            StyledString::Code(self.trace.await?.request.clone()),
+            StyledString::Text("'".into()),
+        ])
+        .cell())
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Resolve.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.file_path
+    }
+
+    #[turbo_tasks::function]
+    async fn description(&self) -> Result<Vc<OptionStyledString>> {
+        let trace = self.trace.await?;
+        let mut description = String::new();
+        if trace.steps.is_empty() {
+            writeln!(description, "No steps were recorded.")?;
+        }
+        for step in trace.steps.iter() {
+            writeln!(description, "{step}")?;
+        }
+        Ok(Vc::cell(Some(
+            StyledString::Text(description.into()).cell(),
+        )))
+    }
+
+    #[turbo_tasks::function]
+    async fn detail(&self) -> Result<Vc<OptionStyledString>> {
+        let trace = self.trace.await?;
+        let mut detail = String::new();
+        writeln!(
+            detail,
+            "Path where resolving has started: {}",
+            trace.lookup_path
+        )?;
+        if trace.results.is_empty() {
+            writeln!(detail, "The request could not be resolved.")?;
+        } else {
+            writeln!(detail, "Results:")?;
+            for result in trace.results.iter() {
+                writeln!(detail, "  {result}")?;
+            }
+        }
+        Ok(Vc::cell(Some(StyledString::Text(detail.into()).cell())))
+    }
+}
+
 async fn lookup_import_map(
     import_map: Vc<ImportMap>,
     file_path: Vc<FileSystemPath>,
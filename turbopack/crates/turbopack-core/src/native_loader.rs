@@ -0,0 +1,176 @@
+use anyhow::Result;
+use serde_json::Value as JsonValue;
+use turbo_tasks::{FxIndexMap, RcStr, TryJoinIterExt, Vc};
+use turbo_tasks_fs::FileSystemPath;
+
+use crate::{
+    asset::{Asset, AssetContent},
+    ident::AssetIdent,
+    source::Source,
+    source_map::{GenerateSourceMap, OptionSourceMap, SourceMap},
+    source_transform::SourceTransform,
+};
+
+/// A loader implemented in Rust. It can be used in place of a webpack loader
+/// in loader rules and avoids the overhead of running loaders in Node.js.
+#[turbo_tasks::value_trait]
+pub trait NativeLoader {
+    /// Processes the content of `source`. `options` are the options of the
+    /// loader in the rule, or an empty object.
+    fn process(
+        self: Vc<Self>,
+        source: Vc<Box<dyn Source>>,
+        options: Vc<JsonValue>,
+    ) -> Vc<NativeLoaderResult>;
+}
+
+#[turbo_tasks::value(shared)]
+pub struct NativeLoaderResult {
+    pub content: Vc<AssetContent>,
+    /// The source map from the processed content to the content of the
+    /// source. It's chained with the source map of the source, if any.
+    pub source_map: Option<Vc<SourceMap>>,
+    /// Files the result depends on. Files read through turbo-tasks are tracked
+    /// anyway, but loaders that read files by other means, e. g. loaders in a
+    /// dynamic library, need to report them.
+    pub file_dependencies: Vec<Vc<FileSystemPath>>,
+}
+
+/// Native loaders by the name rules reference them with.
+#[turbo_tasks::value(transparent)]
+pub struct NativeLoaders(FxIndexMap<RcStr, Vc<Box<dyn NativeLoader>>>);
+
+#[turbo_tasks::value_impl]
+impl NativeLoaders {
+    #[turbo_tasks::function]
+    pub fn empty() -> Vc<Self> {
+        Vc::cell(FxIndexMap::default())
+    }
+}
+
+/// A [SourceTransform] that runs a [NativeLoader].
+#[turbo_tasks::value]
+pub struct NativeLoaderTransform {
+    loader: Vc<Box<dyn NativeLoader>>,
+    options: Vc<JsonValue>,
+    rename_as: Option<RcStr>,
+}
+
+#[turbo_tasks::value_impl]
+impl NativeLoaderTransform {
+    #[turbo_tasks::function]
+    pub fn new(
+        loader: Vc<Box<dyn NativeLoader>>,
+        options: Vc<JsonValue>,
+        rename_as: Option<RcStr>,
+    ) -> Vc<Self> {
+        NativeLoaderTransform {
+            loader,
+            options,
+            rename_as,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl SourceTransform for NativeLoaderTransform {
+    #[turbo_tasks::function]
+    fn transform(self: Vc<Self>, source: Vc<Box<dyn Source>>) -> Vc<Box<dyn Source>> {
+        Vc::upcast(
+            NativeLoaderProcessedSource {
+                transform: self,
+                source,
+            }
+            .cell(),
+        )
+    }
+}
+
+#[turbo_tasks::value]
+struct NativeLoaderProcessedSource {
+    transform: Vc<NativeLoaderTransform>,
+    source: Vc<Box<dyn Source>>,
+}
+
+#[turbo_tasks::value_impl]
+impl NativeLoaderProcessedSource {
+    #[turbo_tasks::function]
+    async fn process(&self) -> Result<Vc<NativeLoaderResult>> {
+        let transform = self.transform.await?;
+        let result = transform.loader.process(self.source, transform.options);
+        // Read reported dependencies to make them dependencies of this task.
+        // This task will execute again when they change.
+        result
+            .await?
+            .file_dependencies
+            .iter()
+            .map(|path| path.read())
+            .try_join()
+            .await?;
+        Ok(result)
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Source for NativeLoaderProcessedSource {
+    #[turbo_tasks::function]
+    async fn ident(&self) -> Result<Vc<AssetIdent>> {
+        Ok(
+            if let Some(rename_as) = self.transform.await?.rename_as.as_deref() {
+                self.source.ident().rename_as(rename_as.into())
+            } else {
+                self.source.ident()
+            },
+        )
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for NativeLoaderProcessedSource {
+    #[turbo_tasks::function]
+    async fn content(self: Vc<Self>) -> Result<Vc<AssetContent>> {
+        Ok(self.process().await?.content)
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GenerateSourceMap for NativeLoaderProcessedSource {
+    #[turbo_tasks::function]
+    async fn generate_source_map(self: Vc<Self>) -> Result<Vc<OptionSourceMap>> {
+        let Some(source_map) = self.process().await?.source_map else {
+            return Ok(Vc::cell(None));
+        };
+        let source = self.await?.source;
+        if let Some(generate_source_map) =
+            Vc::try_resolve_sidecast::<Box<dyn GenerateSourceMap>>(source).await?
+        {
+            if let Some(input_source_map) = *generate_source_map.generate_source_map().await? {
+                if let Some(source_map) = chain_source_maps(input_source_map, source_map).await? {
+                    return Ok(Vc::cell(Some(source_map)));
+                }
+            }
+        }
+        Ok(Vc::cell(Some(source_map)))
+    }
+}
+
+/// Chains the source map of the processed content with the source map of the
+/// source, so it maps to the original sources. Returns `None` when either map
+/// can't be flattened into a regular map.
+async fn chain_source_maps(
+    input: Vc<SourceMap>,
+    output: Vc<SourceMap>,
+) -> Result<Option<Vc<SourceMap>>> {
+    let input = input.await?.to_source_map().await?;
+    let output = output.await?.to_source_map().await?;
+    let (Some(input), Some(output)) = (
+        input.as_regular_source_map(),
+        output.as_regular_source_map(),
+    ) else {
+        return Ok(None);
+    };
+    let mut source_map = input.into_owned();
+    source_map.adjust_mappings(&output);
+    Ok(Some(SourceMap::new_regular(source_map).cell()))
+}
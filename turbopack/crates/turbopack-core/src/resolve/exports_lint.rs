@@ -0,0 +1,513 @@
+//! Validation of the `exports` and `imports` fields of package.json files.
+//!
+//! Resolving only looks at the parts of these fields a request hits. This
+//! checks the whole fields, e. g. of the packages in a workspace, and reports
+//! problems as issues on the package.json.
+
+use std::{future::Future, pin::Pin};
+
+use anyhow::Result;
+use serde_json::Value;
+use turbo_tasks::{Completion, RcStr, TryJoinIterExt, Vc};
+use turbo_tasks_fs::{
+    glob::Glob, DirectoryEntry, FileSystemEntryType, FileSystemPath, ReadGlobResult,
+};
+
+use super::{
+    pattern::{read_matches, Pattern, PatternMatch},
+    remap::{ExportsField, ImportsField},
+};
+use crate::{
+    issue::{Issue, IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString},
+    package_json::read_package_json,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Field {
+    Exports,
+    Imports,
+}
+
+impl Field {
+    fn name(self) -> &'static str {
+        match self {
+            Field::Exports => "exports",
+            Field::Imports => "imports",
+        }
+    }
+}
+
+/// A relative target of a subpath, which needs to exist in the package.
+#[derive(Debug, PartialEq, Eq)]
+struct Target {
+    subpath: String,
+    /// The target without the leading `./`.
+    path: String,
+}
+
+/// The problems found in a field that can be found without looking at the
+/// files of the package, and the targets to check.
+#[derive(Default, Debug)]
+struct FieldLint {
+    messages: Vec<String>,
+    targets: Vec<Target>,
+}
+
+impl FieldLint {
+    /// Checks the `value` of `field`. `typed` is whether the package has type
+    /// declarations.
+    fn new(field: Field, value: &Value, typed: bool) -> Self {
+        let mut lint = FieldLint::default();
+        match (field, value) {
+            (Field::Exports, Value::Object(object)) => {
+                let (subpaths, conditions): (Vec<_>, Vec<_>) =
+                    object.iter().partition(|(key, _)| key.starts_with('.'));
+                if !subpaths.is_empty() && !conditions.is_empty() {
+                    lint.messages.push(format!(
+                        "The \"exports\" field mixes subpaths and conditions ({}). Node.js \
+                         rejects such packages.",
+                        conditions
+                            .iter()
+                            .map(|(key, _)| format!("\"{key}\""))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                }
+                if subpaths.is_empty() {
+                    lint.check_subpath(field, ".", value, typed);
+                }
+                for (subpath, value) in subpaths {
+                    lint.check_subpath(field, subpath, value, typed);
+                }
+            }
+            (Field::Exports, _) => lint.check_subpath(field, ".", value, typed),
+            (Field::Imports, Value::Object(object)) => {
+                for (subpath, value) in object {
+                    lint.check_subpath(field, subpath, value, typed);
+                }
+            }
+            (Field::Imports, _) => lint
+                .messages
+                .push("The \"imports\" field must be an object.".to_string()),
+        }
+        lint
+    }
+
+    fn check_subpath(&mut self, field: Field, subpath: &str, value: &Value, typed: bool) {
+        match field {
+            Field::Exports if subpath != "." && !subpath.starts_with("./") => {
+                self.messages.push(format!(
+                    "The subpath \"{subpath}\" must start with \"./\", it can never be imported."
+                ));
+                return;
+            }
+            Field::Imports if !subpath.starts_with('#') => {
+                self.messages.push(format!(
+                    "The subpath \"{subpath}\" must start with \"#\", it can never be imported."
+                ));
+                return;
+            }
+            Field::Imports if subpath == "#" || subpath.starts_with("#/") => {
+                self.messages.push(format!(
+                    "The subpath \"{subpath}\" is reserved by Node.js, it can never be imported."
+                ));
+                return;
+            }
+            _ => {}
+        }
+        let wildcards = subpath.matches('*').count();
+        if wildcards > 1 {
+            self.messages.push(format!(
+                "The subpath pattern \"{subpath}\" contains more than one \"*\", it can never \
+                 match."
+            ));
+            return;
+        }
+
+        let mut has_types = false;
+        let mut has_js_target = false;
+        self.check_value(
+            field,
+            subpath,
+            wildcards == 1,
+            value,
+            &mut has_types,
+            &mut has_js_target,
+        );
+        if field == Field::Exports && typed && has_js_target && !has_types {
+            self.messages.push(format!(
+                "The subpath \"{subpath}\" has no \"types\" condition. TypeScript ignores the \
+                 top-level \"types\" field of packages with an \"exports\" field."
+            ));
+        }
+    }
+
+    fn check_value(
+        &mut self,
+        field: Field,
+        subpath: &str,
+        is_pattern: bool,
+        value: &Value,
+        has_types: &mut bool,
+        has_js_target: &mut bool,
+    ) {
+        match value {
+            Value::Null => {}
+            Value::String(target) => {
+                if !target.starts_with("./") {
+                    // Imports can map to packages
+                    if field == Field::Exports || target.starts_with('.') || target.starts_with('/')
+                    {
+                        self.messages.push(format!(
+                            "The target \"{target}\" of \"{subpath}\" must start with \"./\"."
+                        ));
+                    }
+                    return;
+                }
+                if target.split('/').any(|segment| segment == "..") {
+                    self.messages.push(format!(
+                        "The target \"{target}\" of \"{subpath}\" points outside of the package."
+                    ));
+                    return;
+                }
+                if !is_pattern && target.contains('*') {
+                    self.messages.push(format!(
+                        "The target \"{target}\" of \"{subpath}\" contains \"*\", but the subpath \
+                         is not a pattern."
+                    ));
+                    return;
+                }
+                if [".js", ".mjs", ".cjs"]
+                    .iter()
+                    .any(|extension| target.ends_with(extension))
+                {
+                    *has_js_target = true;
+                }
+                self.targets.push(Target {
+                    subpath: subpath.to_string(),
+                    path: target[2..].to_string(),
+                });
+            }
+            Value::Array(alternatives) => {
+                for value in alternatives {
+                    self.check_value(field, subpath, is_pattern, value, has_types, has_js_target);
+                }
+            }
+            Value::Object(conditions) => {
+                let mut default_seen = false;
+                for (index, (condition, value)) in conditions.iter().enumerate() {
+                    if condition.starts_with('.') {
+                        self.messages.push(format!(
+                            "The conditions of \"{subpath}\" contain the subpath \"{condition}\", \
+                             it can never be imported."
+                        ));
+                        continue;
+                    }
+                    if default_seen {
+                        self.messages.push(format!(
+                            "The condition \"{condition}\" of \"{subpath}\" comes after \
+                             \"default\", it is never used."
+                        ));
+                    }
+                    if condition == "default" {
+                        default_seen = true;
+                    }
+                    if condition == "types" {
+                        *has_types = true;
+                        if index > 0 {
+                            self.messages.push(format!(
+                                "The \"types\" condition of \"{subpath}\" should come first. \
+                                 TypeScript uses the first matching condition."
+                            ));
+                        }
+                    }
+                    self.check_value(field, subpath, is_pattern, value, has_types, has_js_target);
+                }
+            }
+            Value::Bool(_) | Value::Number(_) => {
+                self.messages.push(format!(
+                    "The value of \"{subpath}\" must be a string, an object, an array or null."
+                ));
+            }
+        }
+    }
+}
+
+/// Checks the `exports` and `imports` fields of a package.json and emits a
+/// [PackageExportsIssue] for each problem.
+#[turbo_tasks::function]
+pub async fn lint_package_exports(package_json_path: Vc<FileSystemPath>) -> Result<Vc<Completion>> {
+    let read = read_package_json(package_json_path).await?;
+    let Some(package_json) = &*read else {
+        return Ok(Completion::new());
+    };
+    let package_dir = package_json_path.parent();
+    let typed = package_json.get("types").is_some() || package_json.get("typings").is_some();
+    for field in [Field::Exports, Field::Imports] {
+        let Some(value) = package_json.get(field.name()) else {
+            continue;
+        };
+        let parsed = match field {
+            Field::Exports => ExportsField::try_from(value).map(|_| ()),
+            Field::Imports => ImportsField::try_from(value).map(|_| ()),
+        };
+        let lint = FieldLint::new(field, value, typed);
+        let mut messages = lint.messages;
+        if let Err(err) = parsed {
+            messages.push(format!("The field can't be parsed: {err}"));
+        }
+        let missing = lint
+            .targets
+            .iter()
+            .map(|target| async move {
+                anyhow::Ok((!target_exists(package_dir, target).await?).then_some(target))
+            })
+            .try_join()
+            .await?;
+        for target in missing.into_iter().flatten() {
+            messages.push(format!(
+                "The target \"./{}\" of \"{}\" doesn't exist.",
+                target.path, target.subpath
+            ));
+        }
+        for message in messages {
+            PackageExportsIssue {
+                path: package_json_path,
+                field: field.name().into(),
+                message: message.into(),
+            }
+            .cell()
+            .emit();
+        }
+    }
+    Ok(Completion::new())
+}
+
+async fn target_exists(package_dir: Vc<FileSystemPath>, target: &Target) -> Result<bool> {
+    if target.path.contains('*') {
+        let mut parts = Vec::new();
+        for (index, part) in target.path.split('*').enumerate() {
+            if index > 0 {
+                parts.push(Pattern::Dynamic);
+            }
+            parts.push(Pattern::Constant(part.into()));
+        }
+        let mut pattern = Pattern::Concatenation(parts);
+        pattern.normalize();
+        let matches = read_matches(package_dir, "".into(), true, Pattern::new(pattern)).await?;
+        return Ok(matches.iter().any(|m| matches!(m, PatternMatch::File(..))));
+    }
+    // Folder mappings, e. g. `"./features/": "./src/features/"`
+    let (path, ty) = match target.path.strip_suffix('/') {
+        Some(path) => (path, FileSystemEntryType::Directory),
+        None => (target.path.as_str(), FileSystemEntryType::File),
+    };
+    Ok(*package_dir.join(path.into()).get_type().await? == ty)
+}
+
+/// Checks the package.json of the workspace at `root` and of all packages of
+/// the workspace, see [lint_package_exports]. Packages are found with the
+/// `workspaces` field of the root package.json.
+#[turbo_tasks::function]
+pub async fn lint_workspace_package_exports(root: Vc<FileSystemPath>) -> Result<Vc<Completion>> {
+    let root_package_json = root.join("package.json".into());
+    let mut package_jsons = vec![root_package_json];
+    if let Some(package_json) = &*read_package_json(root_package_json).await? {
+        let workspaces = match package_json.get("workspaces") {
+            Some(Value::Object(workspaces)) => workspaces.get("packages"),
+            workspaces => workspaces,
+        };
+        let patterns = workspaces
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            // Exclusions are not supported
+            .filter(|pattern| !pattern.starts_with('!'));
+        for pattern in patterns {
+            let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+            let glob = Glob::new(format!("{pattern}/package.json").into());
+            add_glob_files(root.read_glob(glob, false), &mut package_jsons).await?;
+        }
+    }
+    package_jsons
+        .into_iter()
+        .map(lint_package_exports)
+        .try_join()
+        .await?;
+    Ok(Completion::new())
+}
+
+fn add_glob_files<'a>(
+    result: Vc<ReadGlobResult>,
+    files: &'a mut Vec<Vc<FileSystemPath>>,
+) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+    // Boxing for async recursion
+    Box::pin(async move {
+        let result = result.await?;
+        for entry in result.results.values() {
+            if let DirectoryEntry::File(path) = entry {
+                files.push(**path);
+            }
+        }
+        for (name, inner) in result.inner.iter() {
+            if name != "node_modules" {
+                add_glob_files(**inner, files).await?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// A problem with the `exports` or `imports` field of a package.json.
+#[turbo_tasks::value(shared)]
+pub struct PackageExportsIssue {
+    pub path: Vc<FileSystemPath>,
+    pub field: RcStr,
+    pub message: RcStr,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for PackageExportsIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Line(vec![
+            StyledString::Text("Invalid ".into()),
+            StyledString::Code(format!("\"{}\"", self.field).into()),
+            StyledString::Text(" field in package.json".into()),
+        ])
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Resolve.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        Vc::cell(Some(StyledString::Text(self.message.clone()).cell()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Field, FieldLint, Target};
+
+    fn messages(field: Field, value: serde_json::Value, typed: bool) -> Vec<String> {
+        FieldLint::new(field, &value, typed).messages
+    }
+
+    #[test]
+    fn valid_exports() {
+        let lint = FieldLint::new(
+            Field::Exports,
+            &json!({
+                ".": {
+                    "types": "./dist/index.d.ts",
+                    "import": "./dist/index.mjs",
+                    "default": "./dist/index.js"
+                },
+                "./utils/*": {
+                    "types": "./dist/utils/*.d.ts",
+                    "default": "./dist/utils/*.js"
+                },
+                "./internal/*": null,
+                "./package.json": "./package.json"
+            }),
+            true,
+        );
+        assert_eq!(lint.messages, Vec::<String>::new());
+        assert_eq!(lint.targets.len(), 6);
+        assert_eq!(
+            lint.targets[0],
+            Target {
+                subpath: ".".to_string(),
+                path: "dist/index.d.ts".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn condition_order() {
+        assert_eq!(
+            messages(
+                Field::Exports,
+                json!({
+                    "default": "./index.js",
+                    "types": "./index.d.ts",
+                }),
+                true
+            ),
+            vec![
+                "The condition \"types\" of \".\" comes after \"default\", it is never used.",
+                "The \"types\" condition of \".\" should come first. TypeScript uses the first \
+                 matching condition.",
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_types() {
+        let value = json!({ "./a": "./a.js", "./b.css": "./b.css" });
+        assert_eq!(
+            messages(Field::Exports, value.clone(), true),
+            vec![
+                "The subpath \"./a\" has no \"types\" condition. TypeScript ignores the top-level \
+                 \"types\" field of packages with an \"exports\" field."
+            ]
+        );
+        assert_eq!(messages(Field::Exports, value, false), Vec::<String>::new());
+    }
+
+    #[test]
+    fn subpaths_that_never_match() {
+        assert_eq!(
+            messages(
+                Field::Exports,
+                json!({
+                    "./a/*/b/*": "./a/*.js",
+                    "./c": "./c/*.js",
+                    ".d": "./d.js",
+                    "./e": "e.js",
+                }),
+                false
+            ),
+            vec![
+                "The subpath pattern \"./a/*/b/*\" contains more than one \"*\", it can never \
+                 match.",
+                "The target \"./c/*.js\" of \"./c\" contains \"*\", but the subpath is not a \
+                 pattern.",
+                "The subpath \".d\" must start with \"./\", it can never be imported.",
+                "The target \"e.js\" of \"./e\" must start with \"./\".",
+            ]
+        );
+        assert_eq!(
+            messages(
+                Field::Imports,
+                json!({
+                    "#/a": "./a.js",
+                    "b": "./b.js",
+                    "#c": "c-package",
+                }),
+                false
+            ),
+            vec![
+                "The subpath \"#/a\" is reserved by Node.js, it can never be imported.",
+                "The subpath \"b\" must start with \"#\", it can never be imported.",
+            ]
+        );
+    }
+}
@@ -14,7 +14,7 @@ use turbo_tasks::{
     Value, ValueToString, Vc,
 };
 use turbo_tasks_fs::{
-    util::normalize_request, FileSystemEntryType, FileSystemPath, RealPathResult,
+    util::normalize_request, File, FileSystemEntryType, FileSystemPath, RealPathResult,
 };
 
 use self::{
@@ -26,12 +26,18 @@ use self::{
     parse::Request,
     pattern::Pattern,
     plugin::BeforeResolvePlugin,
+    pnp::pnp_find_package,
     remap::{ExportsField, ImportsField},
+    trace::{trace_resolve, trace_step, ResolveTraceStepKind},
 };
 use crate::{
+    asset::AssetContent,
     context::AssetContext,
     file_source::FileSource,
-    issue::{resolve::ResolvingIssue, IssueExt, IssueSource},
+    issue::{
+        resolve::{ResolveTraceIssue, ResolvingIssue},
+        IssueExt, IssueSource,
+    },
     module::{Module, Modules, OptionModule},
     output::{OutputAsset, OutputAssets},
     package_json::{read_package_json, PackageJsonIssue},
@@ -42,16 +48,20 @@ use crate::{
         plugin::AfterResolvePlugin,
     },
     source::{OptionSource, Source, Sources},
+    virtual_source::VirtualSource,
 };
 
 mod alias_map;
+pub mod exports_lint;
 pub mod node;
 pub mod options;
 pub mod origin;
 pub mod parse;
 pub mod pattern;
 pub mod plugin;
+pub mod pnp;
 pub(crate) mod remap;
+pub mod trace;
 
 pub use alias_map::{
     AliasMap, AliasMapIntoIter, AliasMapLookupIterator, AliasMatch, AliasPattern, AliasTemplate,
@@ -376,6 +386,10 @@ pub enum ExternalType {
     Url,
     CommonJs,
     EcmaScriptModule,
+    /// A module provided at runtime through module federation: either a
+    /// module exposed by a remote container, requested as `<remote>/<module>`,
+    /// or a shared dependency, requested by its package name.
+    ModuleFederation,
 }
 
 impl Display for ExternalType {
@@ -384,6 +398,7 @@ impl Display for ExternalType {
             ExternalType::CommonJs => write!(f, "commonjs"),
             ExternalType::EcmaScriptModule => write!(f, "esm"),
             ExternalType::Url => write!(f, "url"),
+            ExternalType::ModuleFederation => write!(f, "module-federation"),
         }
     }
 }
@@ -1248,6 +1263,19 @@ async fn find_package(
                     lookup_path_value = new_context_value;
                 }
             }
+            ResolveModules::Pnp(dir) => {
+                let result = pnp_find_package(*dir, lookup_path, package_name.clone()).await?;
+                if let Some(manifest) = result.manifest {
+                    affecting_sources.push(Vc::upcast(FileSource::new(manifest)));
+                }
+                if let Some(package_dir) = result.package {
+                    if let Some(package_dir) =
+                        dir_exists(package_dir, &mut affecting_sources).await?
+                    {
+                        packages.push(FindPackageItem::PackageDirectory(package_dir));
+                    }
+                }
+            }
             ResolveModules::Path {
                 dir,
                 excluded_extensions,
@@ -1387,6 +1415,20 @@ pub async fn resolve(
     request: Vc<Request>,
     options: Vc<ResolveOptions>,
 ) -> Result<Vc<ResolveResult>> {
+    let options_value = options.await?;
+    // Requests resolved while tracing are not traced again.
+    if !options_value.trace && !options_value.trace_requests.is_empty() {
+        if let Some(request_str) = request.await?.request() {
+            if options_value.trace_requests.contains(&request_str) {
+                ResolveTraceIssue {
+                    file_path: lookup_path,
+                    trace: trace_resolve(lookup_path, reference_type.clone(), request, options),
+                }
+                .cell()
+                .emit();
+            }
+        }
+    }
     resolve_inline(lookup_path, reference_type.into_value(), request, options).await
 }
 
@@ -1479,7 +1521,8 @@ async fn handle_before_resolve_plugins(
     request: Vc<Request>,
     options: Vc<ResolveOptions>,
 ) -> Result<Option<Vc<ResolveResult>>> {
-    for plugin in &options.await?.before_resolve_plugins {
+    let options_value = options.await?;
+    for plugin in &options_value.before_resolve_plugins {
         let condition = plugin.before_resolve_condition().resolve().await?;
         if !condition.await?.matches(request).await? {
             continue;
@@ -1489,6 +1532,10 @@ async fn handle_before_resolve_plugins(
             .before_resolve(lookup_path, reference_type.clone(), request)
             .await?
         {
+            trace_step(&options_value, lookup_path, Some(request), async {
+                Ok(ResolveTraceStepKind::BeforeResolvePlugin)
+            })
+            .await?;
             return Ok(Some(result));
         }
     }
@@ -1536,6 +1583,12 @@ async fn handle_after_resolve_plugins(
                 apply_plugins_to_path(path, lookup_path, reference_type.clone(), request, options)
                     .await?
             {
+                trace_step(&*options.await?, lookup_path, Some(request), async {
+                    Ok(ResolveTraceStepKind::AfterResolvePlugin {
+                        path: path.to_string().await?.clone_value(),
+                    })
+                })
+                .await?;
                 let new_result = new_result.await?;
                 changed = true;
                 new_primary.extend(
@@ -1606,6 +1659,32 @@ async fn resolve_internal_inline(
                 let result = import_map.await?.lookup(lookup_path, *request).await?;
                 if !matches!(result, ImportMapResult::NoEntry) {
                     has_alias = true;
+                    trace_step(options_value, lookup_path, Some(*request), async {
+                        let result = result.clone().cell().to_string().await?.clone_value();
+                        // tsconfig `paths` are merged into the import map, so
+                        // they are told apart by their result.
+                        let map = match options_value.tsconfig_import_map {
+                            Some(tsconfig_import_map) => {
+                                let tsconfig_result = tsconfig_import_map
+                                    .await?
+                                    .lookup(lookup_path, *request)
+                                    .await?;
+                                if !matches!(tsconfig_result, ImportMapResult::NoEntry)
+                                    && *tsconfig_result.cell().to_string().await? == result
+                                {
+                                    "tsconfigPaths"
+                                } else {
+                                    "importMap"
+                                }
+                            }
+                            None => "importMap",
+                        };
+                        Ok(ResolveTraceStepKind::ImportMap {
+                            map: map.into(),
+                            result,
+                        })
+                    })
+                    .await?;
                     let resolved_result = resolve_import_map_result(
                         &result,
                         lookup_path,
@@ -1851,6 +1930,15 @@ async fn resolve_internal_inline(
         if let Some(import_map) = &options_value.fallback_import_map {
             if *result.is_unresolvable().await? {
                 let result = import_map.await?.lookup(lookup_path, request).await?;
+                if !matches!(result, ImportMapResult::NoEntry) {
+                    trace_step(options_value, lookup_path, Some(request), async {
+                        Ok(ResolveTraceStepKind::ImportMap {
+                            map: "fallbackImportMap".into(),
+                            result: result.clone().cell().to_string().await?.clone_value(),
+                        })
+                    })
+                    .await?;
+                }
                 let resolved_result = resolve_import_map_result(
                     &result,
                     lookup_path,
@@ -1908,6 +1996,17 @@ async fn resolve_into_folder(
                         // we are not that strict when a main field fails to resolve
                         // we continue to try other alternatives
                         if !result.is_unresolvable_ref() {
+                            trace_step(&options_value, package_path, None, async {
+                                Ok(ResolveTraceStepKind::MainField {
+                                    field: name.clone(),
+                                    package_json: package_json_path
+                                        .to_string()
+                                        .await?
+                                        .clone_value(),
+                                    value: field_value.into(),
+                                })
+                            })
+                            .await?;
                             let mut result = result.with_request_ref(".".into());
                             result.add_affecting_source_ref(Vc::upcast(FileSource::new(
                                 package_json_path,
@@ -2046,12 +2145,21 @@ async fn resolve_relative_request(
     }
 
     let mut results = Vec::new();
-    let matches = read_matches(
-        lookup_path,
-        "".into(),
-        force_in_lookup_dir,
-        Pattern::new(new_path).resolve().await?,
-    )
+    let new_path = Pattern::new(new_path).resolve().await?;
+    let matches = read_matches(lookup_path, "".into(), force_in_lookup_dir, new_path).await?;
+    trace_step(options_value, lookup_path, Some(request), async {
+        Ok(ResolveTraceStepKind::Files {
+            pattern: new_path.await?.to_string().into(),
+            matches: matches
+                .iter()
+                .map(|m| match m {
+                    PatternMatch::File(matched, _) | PatternMatch::Directory(matched, _) => {
+                        matched.clone()
+                    }
+                })
+                .collect(),
+        })
+    })
     .await?;
 
     for m in matches.iter() {
@@ -2207,14 +2315,26 @@ async fn apply_in_package(
         let refs = refs.clone();
         let request_key = RequestKey::new(request.clone());
 
+        trace_step(options_value, lookup_path, None, async {
+            Ok(ResolveTraceStepKind::AliasField {
+                field: field.clone(),
+                package_json: package_json_path.to_string().await?.clone_value(),
+                value: value.to_string().into(),
+            })
+        })
+        .await?;
+
         if value.as_bool() == Some(false) {
+            // Browser code often reads properties of modules that are replaced
+            // by `false` in the `browser` field, so they resolve to an empty
+            // module there.
+            let item = if options_value.browser && field.as_str() == "browser" {
+                ResolveResultItem::Source(alias_field_ignored_source(package_path))
+            } else {
+                ResolveResultItem::Ignore
+            };
             return Ok(Some(
-                ResolveResult::primary_with_affecting_sources(
-                    request_key,
-                    ResolveResultItem::Ignore,
-                    refs,
-                )
-                .cell(),
+                ResolveResult::primary_with_affecting_sources(request_key, item, refs).cell(),
             ));
         }
 
@@ -2255,6 +2375,17 @@ async fn apply_in_package(
     Ok(None)
 }
 
+/// The module a `false` entry of the `browser` field (e.g. `"fs": false`)
+/// resolves to when resolving for a browser. Like in webpack, it's an empty
+/// CommonJS module, so both `require` and `import` of it get an empty object.
+#[turbo_tasks::function]
+fn alias_field_ignored_source(package_path: Vc<FileSystemPath>) -> Vc<Box<dyn Source>> {
+    Vc::upcast(VirtualSource::new(
+        package_path.join("__alias_field_ignored__.cjs".into()),
+        AssetContent::file(File::from("module.exports = {};").into()),
+    ))
+}
+
 #[turbo_tasks::value]
 enum FindSelfReferencePackageResult {
     Found {
@@ -2343,6 +2474,26 @@ async fn resolve_module_request(
     )
     .await?;
 
+    trace_step(options_value, lookup_path, Some(request), async {
+        Ok(ResolveTraceStepKind::FindPackage {
+            module: module.into(),
+            packages: result
+                .packages
+                .iter()
+                .map(|item| async move {
+                    match *item {
+                        FindPackageItem::PackageDirectory(path)
+                        | FindPackageItem::PackageFile(path) => {
+                            anyhow::Ok(path.to_string().await?.clone_value())
+                        }
+                    }
+                })
+                .try_join()
+                .await?,
+        })
+    })
+    .await?;
+
     if result.packages.is_empty() {
         return Ok(ResolveResult::unresolvable_with_affecting_sources(
             result.affecting_sources.clone(),
@@ -2572,11 +2723,56 @@ async fn resolved(
         return Ok(result);
     }
 
+    // Alias field keys may omit the extension when the request did too, e.g.
+    // `"./lib/server": "./lib/client"` for a request to `package/lib/server`.
+    if let Some(extension) = options_value
+        .extensions
+        .iter()
+        .find(|extension| path_ref.path.ends_with(extension.as_str()))
+    {
+        let request_has_extension = original_request
+            .await?
+            .request()
+            .is_some_and(|request| request.ends_with(extension.as_str()));
+        if !request_has_extension {
+            if let Some(result) = apply_in_package(
+                path.parent().resolve().await?,
+                options,
+                options_value,
+                |package_path| {
+                    let request = package_path.get_relative_path_to(path_ref)?;
+                    Some(request.strip_suffix(extension.as_str())?.into())
+                },
+                query,
+                fragment,
+            )
+            .await?
+            {
+                return Ok(result);
+            }
+        }
+    }
+
     if let Some(resolved_map) = options_value.resolved_map {
         let result = resolved_map
             .lookup(**path, original_context, original_request)
             .await?;
 
+        if !matches!(*result, ImportMapResult::NoEntry) {
+            trace_step(
+                options_value,
+                original_context,
+                Some(original_request),
+                async {
+                    Ok(ResolveTraceStepKind::ImportMap {
+                        map: "resolvedMap".into(),
+                        result: (*result).clone().cell().to_string().await?.clone_value(),
+                    })
+                },
+            )
+            .await?;
+        }
+
         let resolved_result = resolve_import_map_result(
             &result,
             path.parent(),
@@ -2637,6 +2833,46 @@ async fn handle_exports_imports_field(
         }
     }
 
+    trace_step(&*options.await?, package_path, None, async {
+        Ok(ResolveTraceStepKind::PackageField {
+            field: if path.starts_with('#') {
+                "imports".into()
+            } else {
+                "exports".into()
+            },
+            package_json: package_json_path.to_string().await?.clone_value(),
+            subpath: path.into(),
+            conditions: conditions
+                .iter()
+                .filter(|(_, value)| matches!(value, ConditionValue::Set))
+                .map(|(condition, _)| condition.clone())
+                .collect(),
+            targets: results
+                .iter()
+                .map(|(result_path, conditions)| {
+                    let mut target = result_path.to_string();
+                    if !conditions.is_empty() {
+                        write!(
+                            target,
+                            " ({})",
+                            conditions
+                                .iter()
+                                .map(|(condition, value)| if *value {
+                                    condition.to_string()
+                                } else {
+                                    format!("!{condition}")
+                                })
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )?;
+                    }
+                    Ok(target.into())
+                })
+                .collect::<Result<_>>()?,
+        })
+    })
+    .await?;
+
     let mut resolved_results = Vec::new();
     for (result_path, conditions) in results {
         if let Some(result_path) = result_path.with_normalized_path() {
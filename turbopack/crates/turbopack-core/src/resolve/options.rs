@@ -32,6 +32,9 @@ pub enum ResolveModules {
     /// when inside of path, use the list of directories to
     /// resolve inside these
     Nested(Vc<FileSystemPath>, Vec<RcStr>),
+    /// use the Yarn Plug'n'Play manifest in that directory to find the
+    /// dependencies of packages
+    Pnp(Vc<FileSystemPath>),
     /// look into that directory, unless the request has an excluded extension
     Path {
         dir: Vc<FileSystemPath>,
@@ -494,6 +497,24 @@ pub struct ResolveOptions {
     pub enable_typescript_with_output_extension: bool,
     /// Warn instead of error for resolve errors
     pub loose_errors: bool,
+    /// Whether requests are resolved for a browser. Then `false` entries of the
+    /// `browser` alias field resolve to an empty module instead of being
+    /// ignored.
+    pub browser: bool,
+    /// Emit a [ResolveTraceStep] for each decision made while resolving. See
+    /// [trace_resolve].
+    ///
+    /// [ResolveTraceStep]: super::trace::ResolveTraceStep
+    /// [trace_resolve]: super::trace::trace_resolve
+    pub trace: bool,
+    /// Requests that emit a [ResolveTraceIssue] with their trace when they are
+    /// resolved.
+    ///
+    /// [ResolveTraceIssue]: crate::issue::resolve::ResolveTraceIssue
+    pub trace_requests: Vec<RcStr>,
+    /// The import map created from the `paths` of a tsconfig.json. It's already
+    /// part of `import_map` and is only used to label trace steps.
+    pub tsconfig_import_map: Option<Vc<ImportMap>>,
 
     pub placeholder_for_future_extensions: (),
 }
@@ -553,6 +574,18 @@ impl ResolveOptions {
         resolve_options.fully_specified = fully_specified;
         Ok(resolve_options.cell())
     }
+
+    /// Overrides the trace flag for resolving
+    #[turbo_tasks::function]
+    pub async fn with_trace(self: Vc<Self>, trace: bool) -> Result<Vc<Self>> {
+        let resolve_options = self.await?;
+        if resolve_options.trace == trace {
+            return Ok(self);
+        }
+        let mut resolve_options = resolve_options.clone_value();
+        resolve_options.trace = trace;
+        Ok(resolve_options.cell())
+    }
 }
 
 #[turbo_tasks::value(shared)]
@@ -0,0 +1,553 @@
+//! Resolving of packages with Yarn Plug'n'Play.
+//!
+//! Instead of `node_modules` directories, Plug'n'Play uses a manifest
+//! (`.pnp.cjs` or `.pnp.data.json`) that lists the location and the
+//! dependencies of each package. Packages are usually located in zip
+//! archives in the Yarn cache, which are read with a [ZipFileSystem].
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use turbo_tasks::{FxIndexMap, FxIndexSet, RcStr, Vc};
+use turbo_tasks_fs::{FileContent, FileSystem, FileSystemPath, ZipFileSystem};
+
+/// The manifest files, in order of preference.
+const MANIFEST_FILES: [&str; 2] = [".pnp.data.json", ".pnp.cjs"];
+
+/// A package in the Plug'n'Play manifest. The top-level package has neither
+/// name nor reference.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct PnpLocator {
+    name: Option<RcStr>,
+    reference: Option<RcStr>,
+}
+
+#[derive(Clone, Debug)]
+struct PnpPackage {
+    /// The location relative to the manifest directory, without leading `./`
+    /// and trailing `/`.
+    location: RcStr,
+    /// The dependencies by name. `None` is a missing peer dependency.
+    dependencies: FxIndexMap<RcStr, Option<PnpLocator>>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct PnpData {
+    packages: FxIndexMap<PnpLocator, PnpPackage>,
+    /// The locators by location, longest location first.
+    locations: Vec<(RcStr, PnpLocator)>,
+    enable_top_level_fallback: bool,
+    fallback_pool: FxIndexMap<RcStr, Option<PnpLocator>>,
+    fallback_exclusion_list: FxIndexSet<PnpLocator>,
+    /// Issuers matching this pattern aren't managed by Plug'n'Play. Patterns
+    /// use JavaScript syntax, those that aren't supported by the regex crate,
+    /// e. g. with look-arounds, fail to load the manifest.
+    ignore_pattern: Option<Regex>,
+}
+
+/// A parsed Plug'n'Play manifest.
+#[turbo_tasks::value(serialization = "none", eq = "manual", cell = "new")]
+pub struct PnpManifest {
+    /// The manifest file.
+    pub path: Vc<FileSystemPath>,
+    #[turbo_tasks(debug_ignore, trace_ignore)]
+    data: PnpData,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionPnpManifest(Option<Vc<PnpManifest>>);
+
+/// Reads the Plug'n'Play manifest in `dir`, if any.
+#[turbo_tasks::function]
+pub async fn read_pnp_manifest(dir: Vc<FileSystemPath>) -> Result<Vc<OptionPnpManifest>> {
+    for name in MANIFEST_FILES {
+        let path = dir.join(name.into());
+        let FileContent::Content(file) = &*path.read().await? else {
+            continue;
+        };
+        let content = file.content().to_str()?;
+        let json = if name.ends_with(".cjs") {
+            extract_runtime_state(&content).with_context(|| {
+                format!("{name} doesn't contain the Plug'n'Play data, please run `yarn install`")
+            })?
+        } else {
+            content.into_owned()
+        };
+        let raw: RawPnpData = serde_json::from_str(&json)
+            .with_context(|| format!("failed to parse the Plug'n'Play data in {name}"))?;
+        let data = PnpData::try_from(raw)
+            .with_context(|| format!("failed to load the Plug'n'Play data in {name}"))?;
+        return Ok(Vc::cell(Some(PnpManifest { path, data }.cell())));
+    }
+    Ok(Vc::cell(None))
+}
+
+/// Extracts the JSON data from the `RAW_RUNTIME_STATE` string literal in a
+/// `.pnp.cjs` file.
+fn extract_runtime_state(content: &str) -> Option<String> {
+    let start = content.find("RAW_RUNTIME_STATE")?;
+    let rest = &content[start..];
+    let rest = rest[rest.find('=')? + 1..].trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let mut result = String::new();
+    let mut chars = rest[1..].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                // Line continuation
+                '\n' => {}
+                'n' => result.push('\n'),
+                'r' => result.push('\r'),
+                't' => result.push('\t'),
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    result.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                }
+                c => result.push(c),
+            },
+            c if c == quote => return Some(result),
+            c => result.push(c),
+        }
+    }
+    None
+}
+
+/// The result of looking up a dependency in the manifest.
+enum PnpResolution {
+    /// The issuer is not managed by Plug'n'Play.
+    NotManaged,
+    /// The location of the dependency, relative to the manifest directory.
+    Found(RcStr),
+    /// The dependency is a peer dependency that is not provided.
+    MissingPeerDependency,
+    /// The issuer doesn't declare the dependency.
+    Undeclared,
+}
+
+impl PnpData {
+    fn find_locator(&self, path: &str) -> Option<&PnpLocator> {
+        self.locations
+            .iter()
+            .find(|(location, _)| {
+                location.is_empty()
+                    || path
+                        .strip_prefix(&**location)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .map(|(_, locator)| locator)
+    }
+
+    /// Looks up the dependency `name` of the package containing `issuer`, a
+    /// path relative to the manifest directory.
+    fn resolve(&self, issuer: &str, name: &str) -> PnpResolution {
+        if let Some(ignore_pattern) = &self.ignore_pattern {
+            if ignore_pattern.is_match(issuer) {
+                return PnpResolution::NotManaged;
+            }
+        }
+        let Some(issuer) = self.find_locator(issuer) else {
+            return PnpResolution::NotManaged;
+        };
+        let dependency = self
+            .packages
+            .get(issuer)
+            .and_then(|package| package.dependencies.get(name));
+        let dependency = match dependency {
+            Some(Some(locator)) => locator,
+            Some(None) => return PnpResolution::MissingPeerDependency,
+            None => {
+                if !self.enable_top_level_fallback || self.fallback_exclusion_list.contains(issuer)
+                {
+                    return PnpResolution::Undeclared;
+                }
+                let top_level = PnpLocator {
+                    name: None,
+                    reference: None,
+                };
+                let fallback = self
+                    .packages
+                    .get(&top_level)
+                    .and_then(|package| package.dependencies.get(name))
+                    .or_else(|| self.fallback_pool.get(name));
+                match fallback {
+                    Some(Some(locator)) => locator,
+                    _ => return PnpResolution::Undeclared,
+                }
+            }
+        };
+        match self.packages.get(dependency) {
+            Some(package) => PnpResolution::Found(package.location.clone()),
+            None => PnpResolution::Undeclared,
+        }
+    }
+}
+
+/// Finds the directory of the package `package_name` as dependency of the
+/// package containing `lookup_path`, using the manifest in `dir`.
+#[turbo_tasks::function]
+pub async fn pnp_find_package(
+    dir: Vc<FileSystemPath>,
+    lookup_path: Vc<FileSystemPath>,
+    package_name: RcStr,
+) -> Result<Vc<PnpFindPackageResult>> {
+    let Some(manifest) = *read_pnp_manifest(dir).await? else {
+        return Ok(PnpFindPackageResult::cell(PnpFindPackageResult {
+            package: None,
+            manifest: None,
+        }));
+    };
+    let package = match pnp_path(dir, lookup_path).await? {
+        Some(issuer) => match manifest.await?.data.resolve(&issuer, &package_name) {
+            PnpResolution::Found(location) => Some(location_to_path(dir, &location)),
+            PnpResolution::NotManaged
+            | PnpResolution::MissingPeerDependency
+            | PnpResolution::Undeclared => None,
+        },
+        None => None,
+    };
+    Ok(PnpFindPackageResult::cell(PnpFindPackageResult {
+        package,
+        manifest: Some(manifest.await?.path),
+    }))
+}
+
+#[turbo_tasks::value(shared)]
+pub struct PnpFindPackageResult {
+    /// The directory of the package.
+    pub package: Option<Vc<FileSystemPath>>,
+    /// The manifest the package was looked up in.
+    pub manifest: Option<Vc<FileSystemPath>>,
+}
+
+/// Returns `path` relative to the manifest directory `dir`. Paths inside of
+/// zip archives are relative to the path the archive is mounted at.
+async fn pnp_path(dir: Vc<FileSystemPath>, path: Vc<FileSystemPath>) -> Result<Option<RcStr>> {
+    let dir = dir.await?;
+    let (path, inner) = match Vc::try_resolve_downcast_type::<ZipFileSystem>(path.fs()).await? {
+        Some(zip_fs) => (zip_fs.mount_path().await?, Some(path.await?.path.clone())),
+        None => (path.await?, None),
+    };
+    let path = if *dir == *path {
+        ""
+    } else {
+        let Some(path) = dir.get_path_to(&path) else {
+            return Ok(None);
+        };
+        path
+    };
+    Ok(Some(match inner {
+        Some(inner) if !inner.is_empty() => {
+            if path.is_empty() {
+                inner
+            } else {
+                format!("{path}/{inner}").into()
+            }
+        }
+        _ => path.into(),
+    }))
+}
+
+/// Converts a package location to a path. Locations inside of zip archives are
+/// mapped to a [ZipFileSystem] mounted at the location of the archive.
+fn location_to_path(dir: Vc<FileSystemPath>, location: &str) -> Vc<FileSystemPath> {
+    let archive_end = location
+        .match_indices(".zip")
+        .map(|(i, _)| i + ".zip".len())
+        .find(|&end| location[end..].is_empty() || location[end..].starts_with('/'));
+    match archive_end {
+        Some(end) => {
+            let mount_path = dir.join(location[..end].into());
+            let archive = dir.join(devirtualize(&location[..end]).into());
+            let inner = location[end..].trim_start_matches('/');
+            ZipFileSystem::new_mounted(archive, mount_path)
+                .root()
+                .join(inner.into())
+        }
+        None => dir.join(devirtualize(location).into()),
+    }
+}
+
+/// Maps a path inside of a virtual folder to the physical path. Virtual paths
+/// have the form `<base>/__virtual__/<hash>/<depth>/<rest>` and map to
+/// `<rest>` relative to `<depth>` levels above `<base>`.
+fn devirtualize(path: &str) -> String {
+    let segments: Vec<&str> = path.split('/').collect();
+    let Some(index) = segments
+        .iter()
+        .position(|s| *s == "__virtual__" || *s == "$$virtual")
+    else {
+        return path.to_string();
+    };
+    let Some(depth) = segments
+        .get(index + 2)
+        .and_then(|depth| depth.parse::<usize>().ok())
+    else {
+        return path.to_string();
+    };
+    let mut result = segments[..index].to_vec();
+    for _ in 0..depth {
+        result.pop();
+    }
+    result.extend(&segments[(index + 3).min(segments.len())..]);
+    result.join("/")
+}
+
+/// The format of the data in the manifest.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPnpData {
+    #[serde(default)]
+    enable_top_level_fallback: bool,
+    #[serde(default)]
+    fallback_exclusion_list: Vec<(RcStr, Vec<RcStr>)>,
+    #[serde(default)]
+    fallback_pool: Vec<(RcStr, Option<RawDependencyTarget>)>,
+    #[serde(default)]
+    ignore_pattern_data: Option<RcStr>,
+    package_registry_data: Vec<(Option<RcStr>, Vec<RawPackageReference>)>,
+}
+
+/// A reference of a package and the information about it.
+type RawPackageReference = (Option<RcStr>, RawPackageInformation);
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPackageInformation {
+    package_location: RcStr,
+    #[serde(default)]
+    package_dependencies: Vec<(RcStr, Option<RawDependencyTarget>)>,
+}
+
+/// A dependency is either a reference of the package with the same name, or
+/// a name and a reference for aliased dependencies.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawDependencyTarget {
+    Reference(RcStr),
+    Alias(RcStr, RcStr),
+}
+
+impl RawDependencyTarget {
+    fn into_locator(self, name: &RcStr) -> PnpLocator {
+        match self {
+            RawDependencyTarget::Reference(reference) => PnpLocator {
+                name: Some(name.clone()),
+                reference: Some(reference),
+            },
+            RawDependencyTarget::Alias(name, reference) => PnpLocator {
+                name: Some(name),
+                reference: Some(reference),
+            },
+        }
+    }
+}
+
+impl TryFrom<RawPnpData> for PnpData {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawPnpData) -> Result<Self> {
+        let mut packages = FxIndexMap::default();
+        let mut locations = Vec::new();
+        for (name, references) in raw.package_registry_data {
+            for (reference, information) in references {
+                let locator = PnpLocator {
+                    name: name.clone(),
+                    reference,
+                };
+                let location = information.package_location;
+                let location: RcStr = location
+                    .strip_prefix("./")
+                    .unwrap_or(&location)
+                    .trim_end_matches('/')
+                    .into();
+                let dependencies = information
+                    .package_dependencies
+                    .into_iter()
+                    .map(|(name, target)| {
+                        let locator = target.map(|target| target.into_locator(&name));
+                        (name, locator)
+                    })
+                    .collect();
+                locations.push((location.clone(), locator.clone()));
+                packages.insert(
+                    locator,
+                    PnpPackage {
+                        location,
+                        dependencies,
+                    },
+                );
+            }
+        }
+        // Longer locations are more specific, e. g. nested workspaces
+        locations.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+        let ignore_pattern = raw
+            .ignore_pattern_data
+            .map(|pattern| {
+                Regex::new(&pattern)
+                    .with_context(|| format!("unsupported ignorePatternData {pattern:?}"))
+            })
+            .transpose()?;
+        Ok(PnpData {
+            packages,
+            locations,
+            enable_top_level_fallback: raw.enable_top_level_fallback,
+            fallback_pool: raw
+                .fallback_pool
+                .into_iter()
+                .map(|(name, target)| {
+                    let locator = target.map(|target| target.into_locator(&name));
+                    (name, locator)
+                })
+                .collect(),
+            fallback_exclusion_list: raw
+                .fallback_exclusion_list
+                .into_iter()
+                .flat_map(|(name, references)| {
+                    references.into_iter().map(move |reference| PnpLocator {
+                        name: Some(name.clone()),
+                        reference: Some(reference),
+                    })
+                })
+                .collect(),
+            ignore_pattern,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{devirtualize, extract_runtime_state, PnpData, PnpResolution, RawPnpData};
+
+    const DATA: &str = r#"{
+        "enableTopLevelFallback": true,
+        "fallbackExclusionList": [["strict", ["npm:1.0.0"]]],
+        "fallbackPool": [],
+        "ignorePatternData": null,
+        "packageRegistryData": [
+            [null, [[null, {
+                "packageLocation": "./",
+                "packageDependencies": [["react", "npm:18.2.0"], ["strict", "npm:1.0.0"]]
+            }]]],
+            ["react", [["npm:18.2.0", {
+                "packageLocation": "./.yarn/cache/react-npm-18.2.0-1eae08fee2-88e38092da.zip/node_modules/react/",
+                "packageDependencies": [["loose-envify", "npm:1.4.0"], ["peer", null]]
+            }]]],
+            ["loose-envify", [["npm:1.4.0", {
+                "packageLocation": "./.yarn/unplugged/loose-envify-npm-1.4.0-6307b72ccf/node_modules/loose-envify/",
+                "packageDependencies": [["tokens", ["js-tokens", "npm:4.0.0"]]]
+            }]]],
+            ["js-tokens", [["npm:4.0.0", {
+                "packageLocation": "./.yarn/cache/js-tokens-npm-4.0.0-0ac852e9e2-8a95213a5a.zip/node_modules/js-tokens/",
+                "packageDependencies": []
+            }]]],
+            ["strict", [["npm:1.0.0", {
+                "packageLocation": "./.yarn/cache/strict.zip/node_modules/strict/",
+                "packageDependencies": []
+            }]]]
+        ]
+    }"#;
+
+    fn data() -> PnpData {
+        serde_json::from_str::<RawPnpData>(DATA)
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    fn found(resolution: PnpResolution) -> Option<String> {
+        match resolution {
+            PnpResolution::Found(location) => Some(location.to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn resolve_dependencies() {
+        let data = data();
+        assert_eq!(
+            found(data.resolve("src", "react")).as_deref(),
+            Some(".yarn/cache/react-npm-18.2.0-1eae08fee2-88e38092da.zip/node_modules/react")
+        );
+        assert_eq!(
+            found(data.resolve(
+                ".yarn/cache/react-npm-18.2.0-1eae08fee2-88e38092da.zip/node_modules/react/cjs",
+                "loose-envify"
+            ))
+            .as_deref(),
+            Some(".yarn/unplugged/loose-envify-npm-1.4.0-6307b72ccf/node_modules/loose-envify")
+        );
+        // Aliased dependency
+        assert_eq!(
+            found(data.resolve(
+                ".yarn/unplugged/loose-envify-npm-1.4.0-6307b72ccf/node_modules/loose-envify",
+                "tokens"
+            ))
+            .as_deref(),
+            Some(
+                ".yarn/cache/js-tokens-npm-4.0.0-0ac852e9e2-8a95213a5a.zip/node_modules/js-tokens"
+            )
+        );
+    }
+
+    #[test]
+    fn resolve_failures() {
+        let data = data();
+        let react = ".yarn/cache/react-npm-18.2.0-1eae08fee2-88e38092da.zip/node_modules/react";
+        assert!(matches!(
+            data.resolve(react, "peer"),
+            PnpResolution::MissingPeerDependency
+        ));
+        // Falls back to the dependencies of the top-level package
+        assert!(found(data.resolve(react, "strict")).is_some());
+        // Excluded from the fallback
+        assert!(matches!(
+            data.resolve(".yarn/cache/strict.zip/node_modules/strict", "react"),
+            PnpResolution::Undeclared
+        ));
+    }
+
+    #[test]
+    fn resolve_ignored_issuers() {
+        let mut raw = serde_json::from_str::<RawPnpData>(DATA).unwrap();
+        raw.ignore_pattern_data = Some("^(?:src/generated(?:/|$))".into());
+        let data = PnpData::try_from(raw).unwrap();
+        assert!(matches!(
+            data.resolve("src/generated/icons", "react"),
+            PnpResolution::NotManaged
+        ));
+        assert!(found(data.resolve("src/icons", "react")).is_some());
+
+        // Look-arounds aren't supported
+        let mut raw = serde_json::from_str::<RawPnpData>(DATA).unwrap();
+        raw.ignore_pattern_data = Some("^(?!src)".into());
+        let err = PnpData::try_from(raw).unwrap_err();
+        assert!(
+            err.to_string().contains("unsupported ignorePatternData"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn devirtualize_paths() {
+        assert_eq!(
+            devirtualize(".yarn/__virtual__/react-dom-virtual-6cd2b5aba5/0/cache/react-dom.zip"),
+            ".yarn/cache/react-dom.zip"
+        );
+        assert_eq!(
+            devirtualize(".yarn/__virtual__/pkg-virtual-6cd2b5aba5/2/packages/pkg"),
+            "packages/pkg"
+        );
+        assert_eq!(devirtualize("packages/pkg"), "packages/pkg");
+    }
+
+    #[test]
+    fn extract_runtime_state_from_cjs() {
+        let content = "#!/usr/bin/env node\n/* eslint-disable */\n\"use strict\";\n\nconst \
+                       RAW_RUNTIME_STATE =\n'{\\\n  \"__info\": [\"It\\'s generated\"],\\\n  \
+                       \"packageRegistryData\": []\\\n}';\n";
+        assert_eq!(
+            extract_runtime_state(content).as_deref(),
+            Some("{  \"__info\": [\"It's generated\"],  \"packageRegistryData\": []}")
+        );
+    }
+}
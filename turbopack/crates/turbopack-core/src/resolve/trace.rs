@@ -0,0 +1,376 @@
+use std::{
+    fmt::{Display, Formatter},
+    future::Future,
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{
+    emit, trace::TraceRawVcs, CollectiblesSource, RcStr, TryJoinIterExt, Upcast, Value,
+    ValueToString, Vc,
+};
+use turbo_tasks_fs::FileSystemPath;
+
+use super::{options::ResolveOptions, parse::Request, resolve, ResolveResultItem};
+use crate::{reference_type::ReferenceType, source::Source};
+
+/// A decision made while resolving a request.
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, TraceRawVcs,
+)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ResolveTraceStepKind {
+    /// A before resolve plugin resolved the request.
+    BeforeResolvePlugin,
+    /// An import map has an entry for the request. `map` is one of
+    /// `importMap`, `fallbackImportMap` or `resolvedMap`.
+    #[serde(rename_all = "camelCase")]
+    ImportMap { map: RcStr, result: RcStr },
+    /// An alias field in a package.json, e. g. `browser`, has an entry for the
+    /// request.
+    #[serde(rename_all = "camelCase")]
+    AliasField {
+        field: RcStr,
+        package_json: RcStr,
+        value: RcStr,
+    },
+    /// A main field in a package.json, e. g. `module` or `main`, resolved.
+    #[serde(rename_all = "camelCase")]
+    MainField {
+        field: RcStr,
+        package_json: RcStr,
+        value: RcStr,
+    },
+    /// The `exports` or `imports` field of a package.json matched the subpath.
+    /// `conditions` are the conditions that are set, `targets` the matched
+    /// targets with their remaining conditions.
+    #[serde(rename_all = "camelCase")]
+    PackageField {
+        field: RcStr,
+        package_json: RcStr,
+        subpath: RcStr,
+        conditions: Vec<RcStr>,
+        targets: Vec<RcStr>,
+    },
+    /// The locations of a module were looked up.
+    #[serde(rename_all = "camelCase")]
+    FindPackage { module: RcStr, packages: Vec<RcStr> },
+    /// Files matching a pattern were looked up. The pattern contains the
+    /// extensions that were tried.
+    #[serde(rename_all = "camelCase")]
+    Files { pattern: RcStr, matches: Vec<RcStr> },
+    /// An after resolve plugin replaced a resolved file.
+    #[serde(rename_all = "camelCase")]
+    AfterResolvePlugin { path: RcStr },
+}
+
+impl ResolveTraceStepKind {
+    /// The position of the decision in the resolve algorithm: before resolve
+    /// plugins run first, then the import map and alias fields are applied,
+    /// the package is looked up and its fields are read, files are matched,
+    /// and the resolved and fallback import maps and after resolve plugins are
+    /// applied to the result.
+    fn phase(&self) -> u8 {
+        match self {
+            ResolveTraceStepKind::BeforeResolvePlugin => 0,
+            ResolveTraceStepKind::ImportMap { map, .. } => match map.as_str() {
+                "resolvedMap" => 7,
+                "fallbackImportMap" => 8,
+                _ => 1,
+            },
+            ResolveTraceStepKind::AliasField { .. } => 2,
+            ResolveTraceStepKind::FindPackage { .. } => 3,
+            ResolveTraceStepKind::PackageField { .. } => 4,
+            ResolveTraceStepKind::MainField { .. } => 5,
+            ResolveTraceStepKind::Files { .. } => 6,
+            ResolveTraceStepKind::AfterResolvePlugin { .. } => 9,
+        }
+    }
+}
+
+/// A step of resolving a request, emitted as a collectible when
+/// [ResolveOptions::trace] is enabled.
+#[turbo_tasks::value(shared)]
+#[derive(Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveTraceStep {
+    pub lookup_path: RcStr,
+    pub request: Option<RcStr>,
+    pub kind: ResolveTraceStepKind,
+}
+
+impl Display for ResolveTraceStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}]", self.lookup_path)?;
+        if let Some(request) = &self.request {
+            write!(f, " {request}")?;
+        }
+        write!(f, ": ")?;
+        match &self.kind {
+            ResolveTraceStepKind::BeforeResolvePlugin => {
+                write!(f, "resolved by a before resolve plugin")
+            }
+            ResolveTraceStepKind::ImportMap { map, result } => {
+                write!(f, "{map} entry -> {result}")
+            }
+            ResolveTraceStepKind::AliasField {
+                field,
+                package_json,
+                value,
+            } => write!(f, "\"{field}\" field in {package_json} -> {value}"),
+            ResolveTraceStepKind::MainField {
+                field,
+                package_json,
+                value,
+            } => write!(f, "\"{field}\" field in {package_json} -> {value}"),
+            ResolveTraceStepKind::PackageField {
+                field,
+                package_json,
+                subpath,
+                conditions,
+                targets,
+            } => write!(
+                f,
+                "\"{field}\" field in {package_json} for {subpath} with conditions [{}] -> [{}]",
+                conditions.join(", "),
+                targets.join(", ")
+            ),
+            ResolveTraceStepKind::FindPackage { module, packages } => {
+                write!(f, "package {module} found in [{}]", packages.join(", "))
+            }
+            ResolveTraceStepKind::Files { pattern, matches } => {
+                write!(f, "files matching {pattern}: [{}]", matches.join(", "))
+            }
+            ResolveTraceStepKind::AfterResolvePlugin { path } => {
+                write!(f, "{path} replaced by an after resolve plugin")
+            }
+        }
+    }
+}
+
+#[turbo_tasks::value_trait]
+pub trait ResolveTraceEvent {
+    fn step(self: Vc<Self>) -> Vc<ResolveTraceStep>;
+}
+
+#[turbo_tasks::value_impl]
+impl ResolveTraceEvent for ResolveTraceStep {
+    #[turbo_tasks::function]
+    fn step(self: Vc<Self>) -> Vc<ResolveTraceStep> {
+        self
+    }
+}
+
+pub trait ResolveTraceEventExt {
+    fn emit(self);
+}
+
+impl<T> ResolveTraceEventExt for Vc<T>
+where
+    T: Upcast<Box<dyn ResolveTraceEvent>>,
+{
+    fn emit(self) {
+        let event = Vc::upcast::<Box<dyn ResolveTraceEvent>>(self);
+        emit(event);
+    }
+}
+
+/// Emits a step when tracing is enabled in `options`. `kind` is only awaited
+/// in that case.
+pub(super) async fn trace_step(
+    options: &ResolveOptions,
+    lookup_path: Vc<FileSystemPath>,
+    request: Option<Vc<Request>>,
+    kind: impl Future<Output = Result<ResolveTraceStepKind>>,
+) -> Result<()> {
+    if !options.trace {
+        return Ok(());
+    }
+    let request = match request {
+        Some(request) => Some(request.to_string().await?.clone_value()),
+        None => None,
+    };
+    ResolveTraceStep {
+        lookup_path: lookup_path.to_string().await?.clone_value(),
+        request,
+        kind: kind.await?,
+    }
+    .cell()
+    .emit();
+    Ok(())
+}
+
+/// The steps taken to resolve a request and the results.
+#[turbo_tasks::value(shared)]
+#[derive(Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveTrace {
+    pub lookup_path: RcStr,
+    pub request: RcStr,
+    /// The steps in the order of the resolve algorithm. Steps of nested
+    /// requests, e. g. of an alias, have the lookup path and request of the
+    /// nested request.
+    pub steps: Vec<ResolveTraceStep>,
+    pub results: Vec<RcStr>,
+}
+
+/// Resolves a request with tracing enabled and returns the steps taken to
+/// resolve it.
+#[turbo_tasks::function]
+pub async fn trace_resolve(
+    lookup_path: Vc<FileSystemPath>,
+    reference_type: Value<ReferenceType>,
+    request: Vc<Request>,
+    options: Vc<ResolveOptions>,
+) -> Result<Vc<ResolveTrace>> {
+    let result = resolve(
+        lookup_path,
+        reference_type,
+        request,
+        options.with_trace(true),
+    );
+    let results = result
+        .await?
+        .primary
+        .iter()
+        .map(|(key, item)| async move {
+            let item: RcStr = match item {
+                ResolveResultItem::Source(source) => {
+                    source.ident().to_string().await?.clone_value()
+                }
+                ResolveResultItem::External(name, ty) => format!("external {ty} {name}").into(),
+                ResolveResultItem::Ignore => "ignored".into(),
+                ResolveResultItem::Error(error) => format!("error: {}", error.await?).into(),
+                ResolveResultItem::Empty => "empty".into(),
+                ResolveResultItem::Custom(_) => "custom".into(),
+            };
+            anyhow::Ok(RcStr::from(format!("{key} -> {item}")))
+        })
+        .try_join()
+        .await?;
+    let steps = result
+        .peek_collectibles::<Box<dyn ResolveTraceEvent>>()
+        .into_iter()
+        .map(|event| async move { anyhow::Ok(event.step().await?.clone_value()) })
+        .try_join()
+        .await?;
+    let steps = order_steps(steps);
+    Ok(ResolveTrace {
+        lookup_path: lookup_path.to_string().await?.clone_value(),
+        request: request.to_string().await?.clone_value(),
+        steps,
+        results,
+    }
+    .cell())
+}
+
+/// Orders the steps by the phase of the resolve algorithm they belong to.
+/// Collectibles are unordered, so steps of the same phase are ordered by their
+/// content to keep the trace stable between runs. The same decision can be
+/// made in several tasks, so equal steps are only kept once.
+fn order_steps(mut steps: Vec<ResolveTraceStep>) -> Vec<ResolveTraceStep> {
+    steps.sort_by(|a, b| {
+        (a.kind.phase(), &a.lookup_path, &a.request, &a.kind).cmp(&(
+            b.kind.phase(),
+            &b.lookup_path,
+            &b.request,
+            &b.kind,
+        ))
+    });
+    steps.dedup();
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{order_steps, ResolveTrace, ResolveTraceStep, ResolveTraceStepKind};
+
+    fn step(kind: ResolveTraceStepKind) -> ResolveTraceStep {
+        ResolveTraceStep {
+            lookup_path: "[project]/src".into(),
+            request: Some("pkg".into()),
+            kind,
+        }
+    }
+
+    fn files(pattern: &str) -> ResolveTraceStepKind {
+        ResolveTraceStepKind::Files {
+            pattern: pattern.into(),
+            matches: vec![],
+        }
+    }
+
+    fn find_package() -> ResolveTraceStepKind {
+        ResolveTraceStepKind::FindPackage {
+            module: "pkg".into(),
+            packages: vec![],
+        }
+    }
+
+    fn import_map(map: &str) -> ResolveTraceStepKind {
+        ResolveTraceStepKind::ImportMap {
+            map: map.into(),
+            result: "pkg -> ./src/pkg".into(),
+        }
+    }
+
+    #[test]
+    fn orders_by_resolve_phase() {
+        let steps = order_steps(vec![
+            step(files("b")),
+            step(import_map("fallbackImportMap")),
+            step(find_package()),
+            step(files("a")),
+            step(import_map("importMap")),
+        ]);
+        assert_eq!(
+            steps.into_iter().map(|step| step.kind).collect::<Vec<_>>(),
+            vec![
+                import_map("importMap"),
+                find_package(),
+                files("a"),
+                files("b"),
+                import_map("fallbackImportMap"),
+            ]
+        );
+    }
+
+    #[test]
+    fn removes_repeated_steps() {
+        let steps = order_steps(vec![step(files("a")), step(files("b")), step(files("a"))]);
+        assert_eq!(
+            steps.into_iter().map(|step| step.kind).collect::<Vec<_>>(),
+            vec![files("a"), files("b")]
+        );
+    }
+
+    #[test]
+    fn serializes_json_report() {
+        let trace = ResolveTrace {
+            lookup_path: "[project]/src".into(),
+            request: "pkg".into(),
+            steps: vec![step(ResolveTraceStepKind::ImportMap {
+                map: "tsconfig paths".into(),
+                result: "pkg -> ./src/pkg".into(),
+            })],
+            results: vec!["pkg -> [project]/src/pkg.ts".into()],
+        };
+        assert_eq!(
+            serde_json::to_value(&trace).unwrap(),
+            serde_json::json!({
+                "lookupPath": "[project]/src",
+                "request": "pkg",
+                "steps": [{
+                    "lookupPath": "[project]/src",
+                    "request": "pkg",
+                    "kind": {
+                        "type": "importMap",
+                        "map": "tsconfig paths",
+                        "result": "pkg -> ./src/pkg",
+                    },
+                }],
+                "results": ["pkg -> [project]/src/pkg.ts"],
+            })
+        );
+    }
+}
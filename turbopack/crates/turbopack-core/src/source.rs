@@ -1,6 +1,14 @@
-use turbo_tasks::Vc;
+use anyhow::Result;
+use turbo_tasks::{FxIndexMap, RcStr, Vc};
 
-use crate::{asset::Asset, ident::AssetIdent};
+use crate::{
+    asset::{Asset, AssetContent},
+    chunk::ChunkingContext,
+    ident::AssetIdent,
+    issue::{code_gen::CodeGenerationIssue, IssueExt, IssueSeverity, StyledString},
+    reference::{ModuleReferences, SingleOutputAssetReference},
+    virtual_output::VirtualOutputAsset,
+};
 
 /// (Unparsed) Source Code. Source Code is processed into [Module]s by the
 /// [AssetContext]. All [Source]s have content and an identifier.
@@ -11,6 +19,69 @@ pub trait Source: Asset {
     fn ident(&self) -> Vc<AssetIdent>;
 }
 
+/// Files emitted while producing a [Source], by their path relative to the
+/// output root.
+#[turbo_tasks::value(transparent)]
+pub struct EmittedFiles(FxIndexMap<RcStr, Vc<AssetContent>>);
+
+/// A [Source] that emits additional files, e. g. a source processed by webpack
+/// loaders calling `this.emitFile`. The files are written to the output root
+/// of the chunks containing the [Module] created from the [Source].
+#[turbo_tasks::value_trait]
+pub trait EmitFiles {
+    fn emitted_files(self: Vc<Self>) -> Vc<EmittedFiles>;
+}
+
+#[turbo_tasks::function]
+fn emitted_file_description() -> Vc<RcStr> {
+    Vc::cell("emitted file".into())
+}
+
+/// References to the files emitted by `source`, if it implements
+/// [EmitFiles]. Chunk items add them to their references, so the files are
+/// written to the output root of `chunking_context`, whatever the type of the
+/// module created from `source`.
+#[turbo_tasks::function]
+pub async fn emitted_file_references(
+    source: Vc<Box<dyn Source>>,
+    chunking_context: Vc<Box<dyn ChunkingContext>>,
+    module_ident: Vc<AssetIdent>,
+) -> Result<Vc<ModuleReferences>> {
+    let Some(source) = Vc::try_resolve_sidecast::<Box<dyn EmitFiles>>(source).await? else {
+        return Ok(Vc::cell(vec![]));
+    };
+
+    let output_root = chunking_context.output_root();
+    let mut references = Vec::new();
+    for (path, &content) in source.emitted_files().await?.iter() {
+        // Paths containing `..` are normalized. Files that would be written
+        // outside of the output directory are reported and skipped.
+        let Some(emitted_path) = *output_root.try_join(path.clone()).await? else {
+            CodeGenerationIssue {
+                severity: IssueSeverity::Error.into(),
+                title: StyledString::Text("Emitted file leaves the output directory".into()).cell(),
+                message: StyledString::Text(
+                    format!(
+                        "The file \"{path}\" emitted while processing this module would be \
+                         written outside of the output directory, so it's not emitted."
+                    )
+                    .into(),
+                )
+                .cell(),
+                path: module_ident.path(),
+            }
+            .cell()
+            .emit();
+            continue;
+        };
+        references.push(Vc::upcast(SingleOutputAssetReference::new(
+            Vc::upcast(VirtualOutputAsset::new(*emitted_path, content)),
+            emitted_file_description(),
+        )));
+    }
+    Ok(Vc::cell(references))
+}
+
 #[turbo_tasks::value(transparent)]
 pub struct OptionSource(Option<Vc<Box<dyn Source>>>);
 
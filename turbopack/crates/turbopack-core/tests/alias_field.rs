@@ -0,0 +1,72 @@
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+use std::path::PathBuf;
+
+use turbo_tasks::{RcStr, TurboTasks, Value, ValueToString};
+use turbo_tasks_fs::{DiskFileSystem, FileSystem};
+use turbo_tasks_memory::MemoryBackend;
+use turbopack_core::{
+    reference_type::ReferenceType,
+    resolve::{
+        options::{ResolveInPackage, ResolveOptions},
+        parse::Request,
+        resolve, ResolveResultItem,
+    },
+    source::Source,
+};
+
+#[tokio::test]
+async fn false_entries_of_alias_fields() {
+    turbopack_core::register();
+
+    let root: RcStr = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/alias-field")
+        .to_str()
+        .unwrap()
+        .into();
+    let tt = TurboTasks::new(MemoryBackend::default());
+    let results = tt
+        .run_once(async move {
+            let fs = DiskFileSystem::new("fixture".into(), root, vec![]);
+            let mut results = vec![];
+            for (field, browser) in [("browser", true), ("browser", false), ("custom", true)] {
+                let options = ResolveOptions {
+                    extensions: vec![".js".into()],
+                    in_package: vec![ResolveInPackage::AliasField(field.into())],
+                    default_files: vec!["index".into()],
+                    browser,
+                    ..Default::default()
+                }
+                .cell();
+                let result = resolve(
+                    fs.root().join("pkg".into()),
+                    Value::new(ReferenceType::Undefined),
+                    Request::parse_string("./ignored.js".into()),
+                    options,
+                )
+                .await?;
+                let item = match result.primary.values().next() {
+                    Some(ResolveResultItem::Source(source)) => {
+                        source.ident().path().to_string().await?.to_string()
+                    }
+                    Some(ResolveResultItem::Ignore) => "ignored".to_string(),
+                    item => panic!("unexpected result {item:?}"),
+                };
+                results.push(format!("{field} (browser: {browser}): {item}"));
+            }
+            Ok(results)
+        })
+        .await
+        .unwrap();
+
+    // Only the `browser` field resolves `false` entries to an empty module,
+    // and only when resolving for a browser.
+    assert_eq!(
+        results,
+        [
+            "browser (browser: true): [fixture]/pkg/__alias_field_ignored__.cjs",
+            "browser (browser: false): ignored",
+            "custom (browser: true): ignored",
+        ]
+    );
+}
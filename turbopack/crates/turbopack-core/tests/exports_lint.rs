@@ -0,0 +1,59 @@
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+use std::path::PathBuf;
+
+use turbo_tasks::{RcStr, TurboTasks};
+use turbo_tasks_fs::{DiskFileSystem, FileSystem};
+use turbo_tasks_memory::MemoryBackend;
+use turbopack_core::{
+    issue::{IssueDescriptionExt, StyledString},
+    resolve::exports_lint::lint_workspace_package_exports,
+};
+
+#[tokio::test]
+async fn lints_workspace_packages() {
+    turbopack_core::register();
+
+    let root: RcStr = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/exports-lint")
+        .to_str()
+        .unwrap()
+        .into();
+    let tt = TurboTasks::new(MemoryBackend::default());
+    let mut messages = tt
+        .run_once(async move {
+            let fs = DiskFileSystem::new("workspace".into(), root, vec![]);
+            let completion = lint_workspace_package_exports(fs.root());
+            completion.await?;
+            let issues = completion.peek_issues_with_path().await?;
+            let messages = issues
+                .get_plain_issues()
+                .await?
+                .iter()
+                .map(|issue| {
+                    let Some(StyledString::Text(description)) = &issue.description else {
+                        panic!("unexpected description {:?}", issue.description);
+                    };
+                    format!("{}: {description}", issue.file_path)
+                })
+                .collect::<Vec<_>>();
+            Ok(messages)
+        })
+        .await
+        .unwrap();
+    messages.sort();
+
+    // `other` isn't part of the workspace and the pattern target of
+    // `./features/*` matches a file.
+    assert_eq!(
+        messages,
+        [
+            "[workspace]/packages/a/package.json: The target \"./missing.js\" of \"./missing\" \
+             doesn't exist.",
+            "[workspace]/packages/a/package.json: The target \"./src/unmatched/*.js\" of \
+             \"./unmatched/*\" doesn't exist.",
+            "[workspace]/packages/b/package.json: The target \"./internal.js\" of \"#internal\" \
+             doesn't exist.",
+        ]
+    );
+}
@@ -10,7 +10,7 @@ use turbopack_core::{
     reference::{ModuleReference, ModuleReferences},
     reference_type::ImportContext,
     resolve::origin::ResolveOrigin,
-    source::Source,
+    source::{emitted_file_references, Source},
 };
 
 use crate::{
@@ -191,8 +191,21 @@ impl ChunkItem for CssModuleChunkItem {
     }
 
     #[turbo_tasks::function]
-    fn references(&self) -> Vc<ModuleReferences> {
-        self.module.references()
+    async fn references(&self) -> Result<Vc<ModuleReferences>> {
+        // Files emitted by the source, e. g. by a webpack loader producing
+        // CSS, are written next to the chunks.
+        let mut references = self.module.references().await?.clone_value();
+        references.extend(
+            emitted_file_references(
+                self.module.await?.source,
+                self.chunking_context,
+                self.module.ident(),
+            )
+            .await?
+            .iter()
+            .copied(),
+        );
+        Ok(Vc::cell(references))
     }
 
     #[turbo_tasks::function]
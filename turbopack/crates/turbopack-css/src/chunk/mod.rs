@@ -5,12 +5,13 @@ use std::fmt::Write;
 
 use anyhow::{bail, Result};
 use turbo_tasks::{FxIndexSet, RcStr, TryJoinIterExt, Value, ValueDefault, ValueToString, Vc};
-use turbo_tasks_fs::{rope::Rope, File, FileSystem};
+use turbo_tasks_fs::{rope::Rope, File, FileSystem, FileSystemPath};
 use turbopack_core::{
     asset::{Asset, AssetContent},
     chunk::{
-        round_chunk_item_size, AsyncModuleInfo, Chunk, ChunkItem, ChunkItemWithAsyncModuleInfo,
-        ChunkType, ChunkableModule, ChunkingContext, ModuleId, OutputChunk, OutputChunkRuntimeInfo,
+        content_hashed_path, round_chunk_item_size, AsyncModuleInfo, Chunk, ChunkItem,
+        ChunkItemWithAsyncModuleInfo, ChunkType, ChunkableModule, ChunkingContext, ModuleId,
+        OutputChunk, OutputChunkRuntimeInfo,
     },
     code_builder::{Code, CodeBuilder},
     ident::AssetIdent,
@@ -54,8 +55,10 @@ impl CssChunk {
         self.content
     }
 
+    /// The code of the chunk without the source map comment. Content hashes
+    /// are computed from it, as the comment contains the hashed path.
     #[turbo_tasks::function]
-    async fn code(self: Vc<Self>) -> Result<Vc<Code>> {
+    async fn code_without_source_map_url(self: Vc<Self>) -> Result<Vc<Code>> {
         use std::io::Write;
 
         let this = self.await?;
@@ -89,22 +92,90 @@ impl CssChunk {
         let built = &body.build();
         code.push_code(built);
 
-        if *this
-            .chunking_context
-            .reference_chunk_source_maps(Vc::upcast(self))
-            .await?
-            && code.has_source_map()
+        Ok(code.build().cell())
+    }
+
+    #[turbo_tasks::function]
+    async fn code(self: Vc<Self>) -> Result<Vc<Code>> {
+        use std::io::Write;
+
+        let this = self.await?;
+        let code_without_source_map_url = self.code_without_source_map_url();
+        let built = code_without_source_map_url.await?;
+        if !built.has_source_map()
+            || !*this
+                .chunking_context
+                .reference_chunk_source_maps(Vc::upcast(self))
+                .await?
         {
-            let chunk_path = self.path().await?;
-            writeln!(
-                code,
-                "/*# sourceMappingURL={}.map*/",
-                urlencoding::encode(chunk_path.file_name())
-            )?;
+            return Ok(code_without_source_map_url);
         }
 
-        let c = code.build().cell();
-        Ok(c)
+        let mut code = CodeBuilder::default();
+        code.push_code(&built);
+        let chunk_path = self.path().await?;
+        writeln!(
+            code,
+            "/*# sourceMappingURL={}.map*/",
+            urlencoding::encode(chunk_path.file_name())
+        )?;
+        Ok(code.build().cell())
+    }
+
+    /// The path of the chunk without a content hash.
+    #[turbo_tasks::function]
+    async fn unhashed_path(&self) -> Result<Vc<FileSystemPath>> {
+        let mut assets = Vec::new();
+
+        let CssChunkContent { chunk_items, .. } = &*self.content.await?;
+        let mut common_path = if let Some(chunk_item) = chunk_items.first() {
+            let path = chunk_item.asset_ident().path().resolve().await?;
+            Some((path, path.await?))
+        } else {
+            None
+        };
+
+        // The included chunk items and the availability info describe the chunk
+        // uniquely
+        let chunk_item_key = chunk_item_key();
+        for &chunk_item in chunk_items.iter() {
+            if let Some((common_path_vc, common_path_ref)) = common_path.as_mut() {
+                let path = chunk_item.asset_ident().path().await?;
+                while !path.is_inside_or_equal_ref(common_path_ref) {
+                    let parent = common_path_vc.parent().resolve().await?;
+                    if parent == *common_path_vc {
+                        common_path = None;
+                        break;
+                    }
+                    *common_path_vc = parent;
+                    *common_path_ref = (*common_path_vc).await?;
+                }
+            }
+            assets.push((chunk_item_key, chunk_item.content_ident()));
+        }
+
+        // Make sure the idents are resolved
+        for (_, ident) in assets.iter_mut() {
+            *ident = ident.resolve().await?;
+        }
+
+        let ident = AssetIdent {
+            path: if let Some((common_path, _)) = common_path {
+                common_path
+            } else {
+                ServerFileSystem::new().root()
+            },
+            query: Vc::<RcStr>::default(),
+            fragment: None,
+            assets,
+            modifiers: Vec::new(),
+            part: None,
+            layer: None,
+        };
+
+        Ok(self
+            .chunking_context
+            .chunk_path(AssetIdent::new(Value::new(ident)), ".css".into()))
     }
 
     #[turbo_tasks::function]
@@ -228,59 +299,17 @@ fn chunk_item_key() -> Vc<RcStr> {
 #[turbo_tasks::value_impl]
 impl OutputAsset for CssChunk {
     #[turbo_tasks::function]
-    async fn ident(&self) -> Result<Vc<AssetIdent>> {
-        let mut assets = Vec::new();
-
-        let CssChunkContent { chunk_items, .. } = &*self.content.await?;
-        let mut common_path = if let Some(chunk_item) = chunk_items.first() {
-            let path = chunk_item.asset_ident().path().resolve().await?;
-            Some((path, path.await?))
-        } else {
-            None
-        };
-
-        // The included chunk items and the availability info describe the chunk
-        // uniquely
-        let chunk_item_key = chunk_item_key();
-        for &chunk_item in chunk_items.iter() {
-            if let Some((common_path_vc, common_path_ref)) = common_path.as_mut() {
-                let path = chunk_item.asset_ident().path().await?;
-                while !path.is_inside_or_equal_ref(common_path_ref) {
-                    let parent = common_path_vc.parent().resolve().await?;
-                    if parent == *common_path_vc {
-                        common_path = None;
-                        break;
-                    }
-                    *common_path_vc = parent;
-                    *common_path_ref = (*common_path_vc).await?;
-                }
-            }
-            assets.push((chunk_item_key, chunk_item.content_ident()));
-        }
-
-        // Make sure the idents are resolved
-        for (_, ident) in assets.iter_mut() {
-            *ident = ident.resolve().await?;
-        }
-
-        let ident = AssetIdent {
-            path: if let Some((common_path, _)) = common_path {
-                common_path
-            } else {
-                ServerFileSystem::new().root()
-            },
-            query: Vc::<RcStr>::default(),
-            fragment: None,
-            assets,
-            modifiers: Vec::new(),
-            part: None,
-            layer: None,
+    async fn ident(self: Vc<Self>) -> Result<Vc<AssetIdent>> {
+        let this = self.await?;
+        let path = match *this.chunking_context.content_hashing().await? {
+            Some(content_hashing) => content_hashed_path(
+                self.unhashed_path(),
+                content_hashing,
+                self.code_without_source_map_url(),
+            ),
+            None => self.unhashed_path(),
         };
-
-        Ok(AssetIdent::from_path(self.chunking_context.chunk_path(
-            AssetIdent::new(Value::new(ident)),
-            ".css".into(),
-        )))
+        Ok(AssetIdent::from_path(path))
     }
 
     #[turbo_tasks::function]
@@ -9,6 +9,7 @@ use turbopack_core::{
         availability_info::AvailabilityInfo, ChunkableModule, ChunkingContext, ChunkingContextExt,
         EvaluatableAssets,
     },
+    environment::ChunkLoading,
     ident::AssetIdent,
     module::Module,
     output::{OutputAsset, OutputAssets},
@@ -32,6 +33,9 @@ pub struct DevHtmlAsset {
     path: Vc<FileSystemPath>,
     entries: Vec<DevHtmlEntry>,
     body: Option<RcStr>,
+    /// HTML that comes first in the head, before any chunk is loaded, e. g.
+    /// an import map.
+    head: Option<RcStr>,
 }
 
 #[turbo_tasks::function]
@@ -72,6 +76,7 @@ impl DevHtmlAsset {
             path,
             entries,
             body: None,
+            head: None,
         }
         .cell()
     }
@@ -86,6 +91,7 @@ impl DevHtmlAsset {
             path,
             entries,
             body: Some(body),
+            head: None,
         }
         .cell()
     }
@@ -106,6 +112,13 @@ impl DevHtmlAsset {
         html.body = Some(body);
         Ok(html.cell())
     }
+
+    #[turbo_tasks::function]
+    pub async fn with_head(self: Vc<Self>, head: RcStr) -> Result<Vc<Self>> {
+        let mut html: DevHtmlAsset = self.await?.clone_value();
+        html.head = Some(head);
+        Ok(html.cell())
+    }
 }
 
 #[turbo_tasks::value_impl]
@@ -122,7 +135,24 @@ impl DevHtmlAsset {
             }
         }
 
-        Ok(DevHtmlAssetContent::new(chunk_paths, this.body.clone()))
+        let module_chunks = this
+            .entries
+            .iter()
+            .map(|&(_, chunking_context, _)| async move {
+                Ok(*chunking_context.environment().chunk_loading().await?
+                    == ChunkLoading::DomModule)
+            })
+            .try_join()
+            .await?
+            .into_iter()
+            .any(|module_chunks| module_chunks);
+
+        Ok(DevHtmlAssetContent::new(
+            chunk_paths,
+            this.body.clone(),
+            this.head.clone(),
+            module_chunks,
+        ))
     }
 
     #[turbo_tasks::function]
@@ -167,11 +197,26 @@ impl DevHtmlAsset {
 struct DevHtmlAssetContent {
     chunk_paths: Vec<RcStr>,
     body: Option<RcStr>,
+    head: Option<RcStr>,
+    /// Whether the chunks are ES modules, which need to be loaded as module
+    /// scripts.
+    module_chunks: bool,
 }
 
 impl DevHtmlAssetContent {
-    fn new(chunk_paths: Vec<RcStr>, body: Option<RcStr>) -> Vc<Self> {
-        DevHtmlAssetContent { chunk_paths, body }.cell()
+    fn new(
+        chunk_paths: Vec<RcStr>,
+        body: Option<RcStr>,
+        head: Option<RcStr>,
+        module_chunks: bool,
+    ) -> Vc<Self> {
+        DevHtmlAssetContent {
+            chunk_paths,
+            body,
+            head,
+            module_chunks,
+        }
+        .cell()
     }
 }
 
@@ -181,9 +226,19 @@ impl DevHtmlAssetContent {
     async fn content(&self) -> Result<Vc<AssetContent>> {
         let mut scripts = Vec::new();
         let mut stylesheets = Vec::new();
+        let mut preloads = Vec::new();
 
         for relative_path in &*self.chunk_paths {
-            if relative_path.ends_with(".js") {
+            if relative_path.ends_with(".js") && self.module_chunks {
+                preloads.push(format!(
+                    "<link rel=\"modulepreload\" href=\"{}\">",
+                    relative_path
+                ));
+                scripts.push(format!(
+                    "<script type=\"module\" src=\"{}\"></script>",
+                    relative_path
+                ));
+            } else if relative_path.ends_with(".js") {
                 scripts.push(format!("<script src=\"{}\"></script>", relative_path));
             } else if relative_path.ends_with(".css") {
                 stylesheets.push(format!(
@@ -202,7 +257,13 @@ impl DevHtmlAssetContent {
 
         let html: RcStr = format!(
             "<!DOCTYPE html>\n<html>\n<head>\n{}\n</head>\n<body>\n{}\n{}\n</body>\n</html>",
-            stylesheets.join("\n"),
+            self.head
+                .iter()
+                .map(|head| head.to_string())
+                .chain(preloads)
+                .chain(stylesheets)
+                .collect::<Vec<_>>()
+                .join("\n"),
             body,
             scripts.join("\n"),
         )
@@ -249,6 +310,10 @@ impl Version for DevHtmlAssetVersion {
         if let Some(body) = &self.content.body {
             hasher.write_ref(body);
         }
+        if let Some(head) = &self.content.head {
+            hasher.write_ref(head);
+        }
+        hasher.write_value(self.content.module_chunks);
         let hash = hasher.finish();
         let hex_hash = encode_hex(hash);
         Vc::cell(hex_hash.into())
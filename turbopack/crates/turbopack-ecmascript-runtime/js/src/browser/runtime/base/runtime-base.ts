@@ -110,6 +110,12 @@ const availableModules: Map<ModuleId, Promise<any> | true> = new Map();
 
 const availableModuleChunks: Map<ChunkPath, Promise<any> | true> = new Map();
 
+/**
+ * Map from a chunk path to its subresource integrity metadata. The backend
+ * sets it as `integrity` attribute when loading the chunk.
+ */
+const chunkIntegrity: Map<ChunkPath, string> = new Map();
+
 async function loadChunk(
   source: SourceInfo,
   chunkData: ChunkData
@@ -118,6 +124,10 @@ async function loadChunk(
     return loadChunkPath(source, chunkData);
   }
 
+  if (chunkData.integrity) {
+    chunkIntegrity.set(chunkData.path, chunkData.integrity);
+  }
+
   const includedList = chunkData.included || [];
   const modulesPromises = includedList.map((included) => {
     if (moduleFactories[included]) return true;
@@ -0,0 +1,29 @@
+/**
+ * This file contains the runtime code that loads ESM externals in the
+ * Turbopack DOM runtime, e. g. packages that are externalized to a CDN URL.
+ *
+ * Externals are loaded with a native `import()` of their specifier, which the
+ * browser resolves through the import map of the page.
+ *
+ * It will be appended to the DOM backend runtime code when URL externals are
+ * enabled.
+ */
+
+/* eslint-disable @typescript-eslint/no-unused-vars */
+
+/// <reference path="./runtime-backend-dom.ts" />
+
+importExternal = async (id) => {
+  let raw;
+  try {
+    raw = await import(/* webpackIgnore: true */ id);
+  } catch (err) {
+    throw new Error(`Failed to load external module ${id}: ${err}`);
+  }
+
+  if (raw && raw.__esModule && raw.default && "default" in raw.default) {
+    return interopEsm(raw.default, createNS(raw), true);
+  }
+
+  return raw;
+};
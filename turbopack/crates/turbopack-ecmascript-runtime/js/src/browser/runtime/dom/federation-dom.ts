@@ -0,0 +1,437 @@
+/**
+ * This file contains the module federation runtime of the Turbopack DOM
+ * runtime. It loads modules from remote containers, exposes modules of this
+ * build as a container and negotiates shared dependencies.
+ *
+ * Containers follow webpack's container interface (`init` and `get`), so
+ * remotes built with webpack and Turbopack can be mixed.
+ *
+ * It will be appended to the DOM backend runtime code when module federation
+ * is enabled.
+ */
+
+/* eslint-disable @typescript-eslint/no-unused-vars */
+
+/// <reference path="./runtime-backend-dom.ts" />
+
+type ModuleFactoryPromise = Promise<() => Exports>;
+
+interface FederationContainer {
+  init(shareScope: ShareScope): void | Promise<void>;
+  get(module: string): ModuleFactoryPromise;
+}
+
+type SharedEntry = {
+  get: () => ModuleFactoryPromise;
+  from: string;
+  eager?: boolean;
+  loaded?: 1;
+};
+
+/**
+ * Map from a package name to the versions provided for it, in the same shape
+ * as webpack's share scopes.
+ */
+type ShareScope = Record<string, Record<string, SharedEntry>>;
+
+type SharedConfig = {
+  singleton: boolean;
+  requiredVersion?: string;
+};
+
+type Remote = {
+  /**
+   * The name of the global variable the container is assigned to.
+   */
+  name: string;
+  url: string;
+};
+
+type ContainerResolver = {
+  promise: Promise<FederationContainer>;
+  resolve: (container: FederationContainer) => void;
+  reject: (error?: Error) => void;
+};
+
+interface ModuleFederation {
+  load(request: string): Promise<Exports>;
+  registerRemotes(remotes: Record<string, string>): void;
+  provideShared(
+    from: string,
+    name: string,
+    version: string,
+    config: SharedConfig,
+    load: () => Promise<Exports>
+  ): void;
+  expose(name: string, modules: Record<string, () => Promise<Exports>>): void;
+}
+
+declare var TURBOPACK_FEDERATION_SHARE_SCOPE: ShareScope | undefined;
+declare var TURBOPACK_FEDERATION_CONTAINERS:
+  | Record<string, ContainerResolver>
+  | undefined;
+
+/**
+ * Map from a remote alias, i.e. the first segment of a request, to the remote.
+ */
+const federationRemotes: Map<string, Remote> = new Map();
+/**
+ * Map from a shared package name to the requirements of this build.
+ */
+const federationSharedConfigs: Map<string, SharedConfig> = new Map();
+const federationSharedModules: Map<string, Promise<Exports>> = new Map();
+const initializedContainers: Set<FederationContainer> = new Set();
+
+moduleFederation = {
+  load: federationLoad,
+  registerRemotes,
+  provideShared,
+  expose,
+};
+
+/**
+ * The share scope is global, so all Turbopack runtimes in the page negotiate
+ * shared dependencies with each other.
+ */
+function getShareScope(): ShareScope {
+  return (globalThis.TURBOPACK_FEDERATION_SHARE_SCOPE ??= {});
+}
+
+/**
+ * Containers of Turbopack builds are registered asynchronously, once their
+ * entry chunks have been loaded, so they are awaited through a registry that
+ * is shared by all runtimes in the page.
+ */
+function getContainerResolver(name: string): ContainerResolver {
+  const containers = (globalThis.TURBOPACK_FEDERATION_CONTAINERS ??= {});
+  let resolver = containers[name];
+  if (!resolver) {
+    let resolve: (container: FederationContainer) => void;
+    let reject: (error?: Error) => void;
+    const promise = new Promise<FederationContainer>((innerResolve, innerReject) => {
+      resolve = innerResolve;
+      reject = innerReject;
+    });
+    resolver = { promise, resolve: resolve!, reject: reject! };
+    containers[name] = resolver;
+  }
+  return resolver;
+}
+
+/**
+ * Registers remotes, given as `name@url` or just `url` by their alias.
+ */
+function registerRemotes(remotes: Record<string, string>) {
+  for (const [alias, remote] of Object.entries(remotes)) {
+    const match = /^([^@/:]+)@(.+)$/.exec(remote);
+    federationRemotes.set(
+      alias,
+      match != null ? { name: match[1], url: match[2] } : { name: alias, url: remote }
+    );
+  }
+}
+
+/**
+ * Loads a federated module, which is either a module exposed by a remote,
+ * requested as `<remote>/<module>`, or a shared dependency.
+ */
+async function federationLoad(request: string): Promise<Exports> {
+  const separator = request.indexOf("/");
+  const alias = separator === -1 ? request : request.slice(0, separator);
+  const remote = federationRemotes.get(alias);
+  if (remote == null) {
+    return loadShared(request);
+  }
+
+  const container = await loadContainer(remote);
+  const module =
+    separator === -1 ? "." : `./${request.slice(separator + 1)}`;
+  const factory = await container.get(module);
+  return factory();
+}
+
+async function loadContainer(remote: Remote): Promise<FederationContainer> {
+  const resolver = getContainerResolver(remote.name);
+  const existing = (globalThis as any)[remote.name] as
+    | FederationContainer
+    | undefined;
+  if (existing != null) {
+    resolver.resolve(existing);
+  } else if (
+    document.querySelector(`script[data-turbopack-remote="${remote.name}"]`) ==
+    null
+  ) {
+    const script = document.createElement("script");
+    script.src = remote.url;
+    script.dataset.turbopackRemote = remote.name;
+    script.onload = () => {
+      // Containers built with webpack are assigned synchronously.
+      const container = (globalThis as any)[remote.name];
+      if (container != null) {
+        resolver.resolve(container);
+      }
+    };
+    script.onerror = () => {
+      resolver.reject(
+        new Error(`Failed to load remote ${remote.name} from ${remote.url}`)
+      );
+    };
+    document.head.appendChild(script);
+  }
+
+  const container = await resolver.promise;
+  if (!initializedContainers.has(container)) {
+    initializedContainers.add(container);
+    await container.init(getShareScope());
+  }
+  return container;
+}
+
+/**
+ * Provides a version of a shared dependency to the share scope.
+ */
+function provideShared(
+  from: string,
+  name: string,
+  version: string,
+  config: SharedConfig,
+  load: () => Promise<Exports>
+) {
+  federationSharedConfigs.set(name, config);
+  const versions = (getShareScope()[name] ??= {});
+  if (versions[version] == null) {
+    versions[version] = {
+      get: () => load().then((exports) => () => exports),
+      from,
+    };
+  }
+}
+
+function loadShared(name: string): Promise<Exports> {
+  let promise = federationSharedModules.get(name);
+  if (promise == null) {
+    promise = resolveShared(name);
+    federationSharedModules.set(name, promise);
+  }
+  return promise;
+}
+
+async function resolveShared(name: string): Promise<Exports> {
+  const versions = getShareScope()[name];
+  if (versions == null || Object.keys(versions).length === 0) {
+    throw new Error(`Shared module ${name} is not provided by any container`);
+  }
+
+  const version = selectSharedVersion(
+    name,
+    versions,
+    federationSharedConfigs.get(name)
+  );
+  const entry = versions[version];
+  entry.loaded = 1;
+  const factory = await entry.get();
+  return factory();
+}
+
+/**
+ * Selects the version of a shared dependency to use. Singletons use the
+ * version that was already loaded, or else the highest version, and only warn
+ * when it doesn't satisfy the required version. Other dependencies use the
+ * highest version that satisfies the required version.
+ */
+function selectSharedVersion(
+  name: string,
+  versions: Record<string, SharedEntry>,
+  config: SharedConfig | undefined
+): string {
+  const available = Object.keys(versions).sort(
+    (a, b) => compareVersions(b, a)
+  );
+  const requiredVersion = config?.requiredVersion;
+
+  if (config?.singleton) {
+    const version =
+      available.find((version) => versions[version].loaded) ?? available[0];
+    if (requiredVersion != null && !satisfiesVersion(version, requiredVersion)) {
+      console.warn(
+        `Unsatisfied version ${version} of shared singleton module ${name} (required ${requiredVersion})`
+      );
+    }
+    return version;
+  }
+
+  if (requiredVersion != null) {
+    const version = available.find((version) =>
+      satisfiesVersion(version, requiredVersion)
+    );
+    if (version != null) {
+      return version;
+    }
+    console.warn(
+      `No version of shared module ${name} satisfies ${requiredVersion}, using ${available[0]}`
+    );
+  }
+  return available[0];
+}
+
+function parseVersion(version: string): [number, number, number] {
+  const [major, minor, patch] = version
+    .replace(/^v/, "")
+    .split(/[-+]/)[0]
+    .split(".")
+    .map((part) => parseInt(part, 10) || 0);
+  return [major ?? 0, minor ?? 0, patch ?? 0];
+}
+
+function compareVersions(a: string, b: string): number {
+  return compareParsedVersions(parseVersion(a), parseVersion(b));
+}
+
+function compareParsedVersions(a: number[], b: number[]): number {
+  for (let i = 0; i < 3; i++) {
+    if (a[i] !== b[i]) {
+      return a[i] - b[i];
+    }
+  }
+  return 0;
+}
+
+/**
+ * Parses a possibly partial version of a range, like `18`, `18.2` or `18.x`,
+ * into its specified parts. Parts after a wildcard are ignored.
+ */
+function parsePartialVersion(version: string): number[] {
+  const parts: number[] = [];
+  for (const part of version.replace(/^v/, "").split(/[-+]/)[0].split(".")) {
+    if (part === "" || part === "*" || part === "x" || part === "X") {
+      break;
+    }
+    parts.push(parseInt(part, 10) || 0);
+  }
+  return parts.slice(0, 3);
+}
+
+/**
+ * Returns the version after all versions that start with the `length` first
+ * parts of `parts`, e.g. `1.3.0` for the first two parts of `1.2.3`.
+ */
+function bumpVersion(parts: number[], length: number): number[] {
+  const bumped = [0, 0, 0];
+  for (let i = 0; i < length; i++) {
+    bumped[i] = parts[i];
+  }
+  bumped[length - 1]++;
+  return bumped;
+}
+
+/**
+ * Checks a version against a single comparator like `^18.2.0`, `>=1.x` or
+ * `18`. Partial versions are handled like x-ranges.
+ */
+function satisfiesComparator(actual: number[], comparator: string): boolean {
+  const [, operator, version] = /^(\^|~|>=|<=|>|<|=)?(.*)$/.exec(comparator)!;
+  const parts = parsePartialVersion(version);
+  if (parts.length === 0) {
+    // `*`, `x` and `>=*` match everything, `<*` and `>*` match nothing.
+    return operator !== "<" && operator !== ">";
+  }
+  const lower = [parts[0], parts[1] ?? 0, parts[2] ?? 0];
+  const comparison = compareParsedVersions(actual, lower);
+  switch (operator) {
+    case "^": {
+      // The upper bound bumps the first non-zero part that's specified.
+      const nonZero = parts.findIndex((part) => part !== 0);
+      const length = nonZero === -1 ? parts.length : nonZero + 1;
+      return (
+        comparison >= 0 &&
+        compareParsedVersions(actual, bumpVersion(parts, length)) < 0
+      );
+    }
+    case "~":
+      return (
+        comparison >= 0 &&
+        compareParsedVersions(
+          actual,
+          bumpVersion(parts, Math.min(parts.length, 2))
+        ) < 0
+      );
+    case ">=":
+      return comparison >= 0;
+    case "<":
+      return comparison < 0;
+    case ">":
+      return parts.length === 3
+        ? comparison > 0
+        : compareParsedVersions(actual, bumpVersion(parts, parts.length)) >= 0;
+    case "<=":
+      return parts.length === 3
+        ? comparison <= 0
+        : compareParsedVersions(actual, bumpVersion(parts, parts.length)) < 0;
+    default:
+      // Exact versions and x-ranges like `18.x`.
+      return parts.length === 3
+        ? comparison === 0
+        : comparison >= 0 &&
+            compareParsedVersions(actual, bumpVersion(parts, parts.length)) < 0;
+  }
+}
+
+/**
+ * Checks a version against a range. Supports `||`, space-separated comparator
+ * sets that must all match, hyphen ranges, x-ranges like `18.x`, `^`, `~` and
+ * comparison operators. Prerelease tags are ignored.
+ */
+function satisfiesVersion(version: string, range: string): boolean {
+  const actual = parseVersion(version);
+  return range.split("||").some((set) => {
+    const hyphen = /^\s*(\S+)\s+-\s+(\S+)\s*$/.exec(set);
+    const comparators = hyphen
+      ? [`>=${hyphen[1]}`, `<=${hyphen[2]}`]
+      : set
+          // Operators may be separated from their version, as in `>= 1.2`.
+          .replace(/(\^|~|>=|<=|>|<|=)\s+/g, "$1")
+          .trim()
+          .split(/\s+/);
+    return comparators.every((comparator) =>
+      satisfiesComparator(actual, comparator)
+    );
+  });
+}
+
+/**
+ * Exposes modules of this build as a container, which is assigned to the
+ * global variable `name`.
+ */
+function expose(
+  name: string,
+  modules: Record<string, () => Promise<Exports>>
+) {
+  const container: FederationContainer = {
+    get(module) {
+      const load = modules[module];
+      if (load == null) {
+        return Promise.reject(
+          new Error(`Module ${module} is not exposed by container ${name}`)
+        );
+      }
+      return load().then((exports) => () => exports);
+    },
+    init(shareScope) {
+      // Adopt the share scope of the host, so shared dependencies are
+      // negotiated across all containers.
+      const ownShareScope = getShareScope();
+      if (shareScope === ownShareScope) {
+        return;
+      }
+      for (const [packageName, versions] of Object.entries(ownShareScope)) {
+        const target = (shareScope[packageName] ??= {});
+        for (const [version, entry] of Object.entries(versions)) {
+          target[version] ??= entry;
+        }
+      }
+      globalThis.TURBOPACK_FEDERATION_SHARE_SCOPE = shareScope;
+    },
+  };
+  (globalThis as any)[name] = container;
+  getContainerResolver(name).resolve(container);
+}
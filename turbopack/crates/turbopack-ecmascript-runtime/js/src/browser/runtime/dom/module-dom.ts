@@ -0,0 +1,69 @@
+/**
+ * This file contains the runtime code specific to the Turbopack ECMAScript
+ * DOM runtime when chunks are emitted as ES modules.
+ *
+ * Chunks are loaded via `import()` instead of `<script>` tags, and a
+ * `<link rel="modulepreload">` hint carrying the subresource integrity
+ * metadata is added for each of them, as `import()` can't check integrity by
+ * itself. This keeps chunk loading compatible with strict Content Security
+ * Policies and import maps.
+ *
+ * It will be appended to the DOM backend runtime code.
+ */
+
+/* eslint-disable @typescript-eslint/no-unused-vars */
+
+/// <reference path="./runtime-backend-dom.ts" />
+
+/**
+ * Maps chunk paths to the number of times they have been imported. Browsers
+ * evaluate a module only once per URL, so a chunk that is imported again, e.g.
+ * after it has been unloaded during HMR, is imported from a fresh URL to
+ * register itself again.
+ */
+const chunkImportCounts: Map<ChunkPath, number> = new Map();
+
+importChunk = async (chunkPath, chunkUrl) => {
+  // `import()` resolves relative URLs against the runtime's own URL, while
+  // chunk URLs are relative to the document, like the `src` of scripts.
+  const url = new URL(chunkUrl, document.baseURI);
+  const importCount = chunkImportCounts.get(chunkPath) ?? 0;
+  chunkImportCounts.set(chunkPath, importCount + 1);
+  if (importCount > 0) {
+    url.searchParams.set("turbopack-import", String(importCount));
+  }
+  preloadModuleChunk(chunkPath, url.href);
+  await import(/* webpackIgnore: true */ url.href);
+
+  // Chunks register themselves synchronously when they're evaluated. If the
+  // module was already evaluated from this URL, e.g. by a `<script>` added
+  // outside of the runtime, loading the chunk would never finish otherwise.
+  if (!chunkResolvers.get(chunkPath)?.resolved) {
+    throw new Error(
+      `Chunk ${chunkPath} was imported from ${url.href}, but didn't register itself`
+    );
+  }
+};
+
+/**
+ * Adds a `<link rel="modulepreload">` hint for the chunk, unless there is one
+ * already, e. g. rendered into the HTML for the chunks of the entry.
+ */
+function preloadModuleChunk(chunkPath: ChunkPath, url: string) {
+  const previousLinks = document.querySelectorAll(
+    `link[rel=modulepreload][href="${url}"]`
+  );
+  if (previousLinks.length > 0) {
+    return;
+  }
+
+  const link = document.createElement("link");
+  link.rel = "modulepreload";
+  link.href = url;
+  const integrity = chunkIntegrity.get(chunkPath);
+  if (integrity != null) {
+    link.integrity = integrity;
+    link.crossOrigin = "anonymous";
+  }
+  document.head.appendChild(link);
+}
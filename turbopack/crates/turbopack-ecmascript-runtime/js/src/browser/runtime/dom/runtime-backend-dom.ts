@@ -19,7 +19,34 @@ type ChunkResolver = {
 
 let BACKEND: RuntimeBackend;
 
+/**
+ * The module federation runtime, when it's included (see `federation-dom.ts`).
+ */
+let moduleFederation: ModuleFederation | undefined;
+
+/**
+ * Loads an ESM external, when URL externals are enabled (see
+ * `externals-dom.ts`).
+ */
+let importExternal:
+  | ((id: ModuleId) => Promise<Exports | EsmNamespaceObject>)
+  | undefined;
+
+/**
+ * Loads a JS chunk as an ES module, when chunks are emitted as ES modules (see
+ * `module-dom.ts`). Chunks are loaded as classic scripts otherwise.
+ */
+let importChunk:
+  | ((chunkPath: ChunkPath, chunkUrl: string) => Promise<void>)
+  | undefined;
+
 function augmentContext(context: unknown): unknown {
+  if (moduleFederation != null) {
+    (context as any).F = moduleFederation;
+  }
+  if (importExternal != null) {
+    (context as any).y = importExternal;
+  }
   return context;
 }
 
@@ -88,6 +115,22 @@ const chunkResolvers: Map<ChunkPath, ChunkResolver> = new Map();
     },
   };
 
+  /**
+   * Sets the subresource integrity metadata of the chunk, if any, on the
+   * element loading it.
+   */
+  function setIntegrity(
+    element: HTMLScriptElement | HTMLLinkElement,
+    chunkPath: ChunkPath
+  ) {
+    const integrity = chunkIntegrity.get(chunkPath);
+    if (integrity != null) {
+      element.integrity = integrity;
+      // Integrity checks of cross-origin resources require CORS.
+      element.crossOrigin = "anonymous";
+    }
+  }
+
   function getOrCreateResolver(chunkPath: ChunkPath): ChunkResolver {
     let resolver = chunkResolvers.get(chunkPath);
     if (!resolver) {
@@ -163,6 +206,7 @@ const chunkResolvers: Map<ChunkPath, ChunkResolver> = new Map();
           const link = document.createElement("link");
           link.rel = "stylesheet";
           link.href = chunkUrl;
+          setIntegrity(link, chunkPath);
           link.onerror = () => {
             resolver.reject();
           };
@@ -173,6 +217,12 @@ const chunkResolvers: Map<ChunkPath, ChunkResolver> = new Map();
           };
           document.body.appendChild(link);
         }
+      } else if (chunkPath.endsWith(".js") && importChunk != null) {
+        // Like scripts, ES module chunks register themselves once they've
+        // been evaluated.
+        importChunk(chunkPath, chunkUrl).catch((error) => {
+          resolver.reject(error);
+        });
       } else if (chunkPath.endsWith(".js")) {
         const previousScripts = document.querySelectorAll(
           `script[src="${chunkUrl}"],script[src^="${chunkUrl}?"],script[src="${decodedChunkUrl}"],script[src^="${decodedChunkUrl}?"]`
@@ -188,6 +238,7 @@ const chunkResolvers: Map<ChunkPath, ChunkResolver> = new Map();
         } else {
           const script = document.createElement("script");
           script.src = chunkUrl;
+          setIntegrity(script, chunkPath);
           // We'll only mark the chunk as loaded once the script has been executed,
           // which happens in `registerChunk`. Hence the absence of `resolve()` in
           // this branch.
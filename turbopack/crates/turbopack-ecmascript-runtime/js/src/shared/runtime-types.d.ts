@@ -23,6 +23,7 @@ type ChunkData =
       included: ModuleId[];
       excluded: ModuleId[];
       moduleChunks: ChunkPath[];
+      integrity?: string;
     };
 
 type CommonJsRequire = (moduleId: ModuleId) => Exports;
@@ -1,6 +1,6 @@
 use std::io::Write;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use indoc::writedoc;
 use turbo_tasks::{RcStr, Value, Vc};
 use turbopack_core::{
@@ -19,6 +19,9 @@ pub async fn get_browser_runtime_code(
     chunk_base_path: Vc<Option<RcStr>>,
     runtime_type: Value<RuntimeType>,
     output_root: Vc<RcStr>,
+    chunk_loading_global: RcStr,
+    module_federation: bool,
+    url_externals: bool,
 ) -> Result<Vc<Code>> {
     let asset_context = get_runtime_asset_context(environment);
 
@@ -43,6 +46,13 @@ pub async fn get_browser_runtime_code(
         .chunk_loading()
         .await?;
 
+    if module_federation && !matches!(chunk_loading, ChunkLoading::Dom | ChunkLoading::DomModule) {
+        bail!("Module federation is only supported with DOM chunk loading");
+    }
+    if url_externals && !matches!(chunk_loading, ChunkLoading::Dom | ChunkLoading::DomModule) {
+        bail!("URL externals are only supported with DOM chunk loading");
+    }
+
     let mut runtime_backend_code = vec![];
     match (chunk_loading, *runtime_type) {
         (ChunkLoading::Edge, RuntimeType::Development) => {
@@ -56,11 +66,11 @@ pub async fn get_browser_runtime_code(
         (ChunkLoading::NodeJs, _) => {
             panic!("Node.js runtime is not supported in the browser runtime!")
         }
-        (ChunkLoading::Dom, RuntimeType::Development) => {
+        (ChunkLoading::Dom | ChunkLoading::DomModule, RuntimeType::Development) => {
             runtime_backend_code.push("browser/runtime/dom/runtime-backend-dom.ts");
             runtime_backend_code.push("browser/runtime/dom/dev-backend-dom.ts");
         }
-        (ChunkLoading::Dom, RuntimeType::Production) => {
+        (ChunkLoading::Dom | ChunkLoading::DomModule, RuntimeType::Production) => {
             // TODO
             runtime_backend_code.push("browser/runtime/dom/runtime-backend-dom.ts");
         }
@@ -70,6 +80,9 @@ pub async fn get_browser_runtime_code(
             panic!("This configuration is not supported in the browser runtime")
         }
     };
+    if *chunk_loading == ChunkLoading::DomModule {
+        runtime_backend_code.push("browser/runtime/dom/module-dom.ts");
+    }
 
     let mut code: CodeBuilder = CodeBuilder::default();
     let output_root = output_root.await?.to_string();
@@ -80,17 +93,17 @@ pub async fn get_browser_runtime_code(
         code,
         r#"
             (() => {{
-            if (!Array.isArray(globalThis.TURBOPACK)) {{
+            if (!Array.isArray(globalThis.{global})) {{
                 return;
             }}
 
-            const CHUNK_BASE_PATH = {};
-            const RUNTIME_PUBLIC_PATH = {};
-            const OUTPUT_ROOT = {};
+            const CHUNK_BASE_PATH = {chunk_base_path};
+            const RUNTIME_PUBLIC_PATH = {chunk_base_path};
+            const OUTPUT_ROOT = {output_root};
         "#,
-        StringifyJs(chunk_base_path),
-        StringifyJs(chunk_base_path),
-        StringifyJs(output_root.as_str()),
+        global = chunk_loading_global,
+        chunk_base_path = StringifyJs(chunk_base_path),
+        output_root = StringifyJs(output_root.as_str()),
     )?;
 
     code.push_code(&*shared_runtime_utils_code.await?);
@@ -120,16 +133,33 @@ pub async fn get_browser_runtime_code(
         code.push_code(&*embed_static_code(asset_context, backend_code.into()).await?);
     }
 
+    if module_federation {
+        code.push_code(
+            &*embed_static_code(
+                asset_context,
+                "browser/runtime/dom/federation-dom.ts".into(),
+            )
+            .await?,
+        );
+    }
+    if url_externals {
+        code.push_code(
+            &*embed_static_code(asset_context, "browser/runtime/dom/externals-dom.ts".into())
+                .await?,
+        );
+    }
+
     // Registering chunks depends on the BACKEND variable, which is set by the
     // specific runtime code, hence it must be appended after it.
     writedoc!(
         code,
         r#"
-            const chunksToRegister = globalThis.TURBOPACK;
-            globalThis.TURBOPACK = {{ push: registerChunk }};
+            const chunksToRegister = globalThis.{global};
+            globalThis.{global} = {{ push: registerChunk }};
             chunksToRegister.forEach(registerChunk);
             }})();
-        "#
+        "#,
+        global = chunk_loading_global,
     )?;
 
     Ok(Code::cell(code.build()))
@@ -59,6 +59,7 @@ impl AsyncLoaderChunkItem {
         Ok(ChunkData::from_assets(
             this.chunking_context.output_root(),
             self.chunks(),
+            this.chunking_context.integrity_algorithm(),
         ))
     }
 }
@@ -15,6 +15,8 @@ pub enum EcmascriptChunkData<'a> {
         excluded: &'a [ReadRef<ModuleId>],
         #[serde(skip_serializing_if = "<[_]>::is_empty", default)]
         module_chunks: &'a [String],
+        #[serde(skip_serializing_if = "Option::is_none")]
+        integrity: Option<&'a str>,
     },
 }
 
@@ -26,8 +28,13 @@ impl EcmascriptChunkData<'_> {
             excluded,
             module_chunks,
             references: _,
+            integrity,
         } = chunk_data;
-        if included.is_empty() && excluded.is_empty() && module_chunks.is_empty() {
+        if included.is_empty()
+            && excluded.is_empty()
+            && module_chunks.is_empty()
+            && integrity.is_none()
+        {
             return EcmascriptChunkData::Simple(path);
         }
         EcmascriptChunkData::WithRuntimeInfo {
@@ -35,6 +42,7 @@ impl EcmascriptChunkData<'_> {
             included,
             excluded,
             module_chunks,
+            integrity: integrity.as_deref(),
         }
     }
 }
@@ -105,6 +105,9 @@ impl EcmascriptChunkItemContent {
             args.push("x: __turbopack_external_require__");
             args.push("y: __turbopack_external_import__");
         }
+        if self.options.federation {
+            args.push("F: __turbopack_federation__");
+        }
         if self.options.refresh {
             args.push("k: __turbopack_refresh__");
         }
@@ -188,6 +191,9 @@ pub struct EcmascriptChunkItemOptions {
     /// Whether this chunk item's module factory should include
     /// `__turbopack_wasm__` to load WebAssembly.
     pub wasm: bool,
+    /// Whether this chunk item's module factory should include
+    /// `__turbopack_federation__` to load federated modules.
+    pub federation: bool,
     pub placeholder_for_future_extensions: (),
 }
 
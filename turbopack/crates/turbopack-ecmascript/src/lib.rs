@@ -78,7 +78,7 @@ use turbopack_core::{
         find_context_file, origin::ResolveOrigin, package_json, parse::Request,
         FindContextFileResult,
     },
-    source::Source,
+    source::{emitted_file_references, Source},
     source_map::{GenerateSourceMap, OptionSourceMap},
 };
 // TODO remove this
@@ -623,8 +623,20 @@ impl ChunkItem for ModuleChunkItem {
     }
 
     #[turbo_tasks::function]
-    fn references(&self) -> Vc<ModuleReferences> {
-        self.module.references()
+    async fn references(&self) -> Result<Vc<ModuleReferences>> {
+        // Files emitted by the source are written next to the chunks.
+        let mut references = self.module.references().await?.clone_value();
+        references.extend(
+            emitted_file_references(
+                self.module.await?.source,
+                self.chunking_context,
+                self.module.ident(),
+            )
+            .await?
+            .iter()
+            .copied(),
+        );
+        Ok(Vc::cell(references))
     }
 
     #[turbo_tasks::function]
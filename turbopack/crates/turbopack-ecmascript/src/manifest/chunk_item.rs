@@ -30,7 +30,11 @@ pub(super) struct ManifestChunkItem {
 impl ManifestChunkItem {
     #[turbo_tasks::function]
     fn chunks_data(&self) -> Vc<ChunksData> {
-        ChunkData::from_assets(self.chunking_context.output_root(), self.manifest.chunks())
+        ChunkData::from_assets(
+            self.chunking_context.output_root(),
+            self.manifest.chunks(),
+            self.chunking_context.integrity_algorithm(),
+        )
     }
 }
 
@@ -62,7 +62,11 @@ impl ManifestLoaderChunkItem {
     #[turbo_tasks::function]
     pub fn chunks_data(&self) -> Vc<ChunksData> {
         let chunks = self.manifest.manifest_chunks();
-        ChunkData::from_assets(self.chunking_context.output_root(), chunks)
+        ChunkData::from_assets(
+            self.chunking_context.output_root(),
+            chunks,
+            self.chunking_context.integrity_algorithm(),
+        )
     }
 
     #[turbo_tasks::function]
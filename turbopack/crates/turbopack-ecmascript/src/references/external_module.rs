@@ -14,8 +14,8 @@ use turbopack_core::{
 
 use crate::{
     chunk::{
-        EcmascriptChunkItem, EcmascriptChunkItemContent, EcmascriptChunkPlaceable,
-        EcmascriptChunkType, EcmascriptExports,
+        EcmascriptChunkItem, EcmascriptChunkItemContent, EcmascriptChunkItemOptions,
+        EcmascriptChunkPlaceable, EcmascriptChunkType, EcmascriptExports,
     },
     references::async_module::{AsyncModule, OptionAsyncModule},
     utils::StringifyJs,
@@ -34,6 +34,20 @@ pub enum CachedExternalType {
     CommonJs,
     EcmaScriptViaRequire,
     EcmaScriptViaImport,
+    /// Loaded through the module federation runtime. See
+    /// [ExternalType::ModuleFederation][turbopack_core::resolve::ExternalType::ModuleFederation].
+    ModuleFederation,
+}
+
+impl CachedExternalType {
+    /// Whether the external is loaded asynchronously, which makes the module
+    /// an async module.
+    fn is_async(self) -> bool {
+        matches!(
+            self,
+            CachedExternalType::EcmaScriptViaImport | CachedExternalType::ModuleFederation
+        )
+    }
 }
 
 impl Display for CachedExternalType {
@@ -42,6 +56,7 @@ impl Display for CachedExternalType {
             CachedExternalType::CommonJs => write!(f, "cjs"),
             CachedExternalType::EcmaScriptViaRequire => write!(f, "esm_require"),
             CachedExternalType::EcmaScriptViaImport => write!(f, "esm_import"),
+            CachedExternalType::ModuleFederation => write!(f, "module_federation"),
         }
     }
 }
@@ -72,6 +87,12 @@ impl CachedExternalModule {
                 "const mod = await __turbopack_external_import__({});",
                 StringifyJs(&self.request)
             )?;
+        } else if self.external_type == CachedExternalType::ModuleFederation {
+            writeln!(
+                code,
+                "const mod = await __turbopack_federation__.load({});",
+                StringifyJs(&self.request)
+            )?;
         } else {
             writeln!(
                 code,
@@ -149,19 +170,17 @@ impl EcmascriptChunkPlaceable for CachedExternalModule {
 
     #[turbo_tasks::function]
     fn get_async_module(&self) -> Vc<OptionAsyncModule> {
-        Vc::cell(
-            if self.external_type == CachedExternalType::EcmaScriptViaImport {
-                Some(
-                    AsyncModule {
-                        has_top_level_await: true,
-                        import_externals: true,
-                    }
-                    .cell(),
-                )
-            } else {
-                None
-            },
-        )
+        Vc::cell(if self.external_type.is_async() {
+            Some(
+                AsyncModule {
+                    has_top_level_await: true,
+                    import_externals: true,
+                }
+                .cell(),
+            )
+        } else {
+            None
+        })
     }
 
     #[turbo_tasks::function]
@@ -208,9 +227,7 @@ impl ChunkItem for CachedExternalModuleChunkItem {
 
     #[turbo_tasks::function]
     async fn is_self_async(&self) -> Result<Vc<bool>> {
-        Ok(Vc::cell(
-            self.module.await?.external_type == CachedExternalType::EcmaScriptViaImport,
-        ))
+        Ok(Vc::cell(self.module.await?.external_type.is_async()))
     }
 }
 
@@ -227,21 +244,33 @@ impl EcmascriptChunkItem for CachedExternalModuleChunkItem {
     }
 
     #[turbo_tasks::function]
-    fn content_with_async_module_info(
+    async fn content_with_async_module_info(
         &self,
         async_module_info: Option<Vc<AsyncModuleInfo>>,
-    ) -> Vc<EcmascriptChunkItemContent> {
+    ) -> Result<Vc<EcmascriptChunkItemContent>> {
         let async_module_options = self
             .module
             .get_async_module()
             .module_options(async_module_info);
 
-        EcmascriptChunkItemContent::new(
+        let content = EcmascriptChunkItemContent::new(
             self.module.content(),
             self.chunking_context,
             EcmascriptOptions::default().cell(),
             async_module_options,
-        )
+        );
+        if self.module.await?.external_type != CachedExternalType::ModuleFederation {
+            return Ok(content);
+        }
+        let content = content.await?;
+        Ok(EcmascriptChunkItemContent {
+            options: EcmascriptChunkItemOptions {
+                federation: true,
+                ..content.options.clone()
+            },
+            ..content.clone_value()
+        }
+        .cell())
     }
 }
 
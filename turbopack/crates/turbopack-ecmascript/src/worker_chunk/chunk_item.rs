@@ -64,6 +64,7 @@ impl WorkerLoaderChunkItem {
         Ok(ChunkData::from_assets(
             this.chunking_context.output_root(),
             self.chunks(),
+            this.chunking_context.integrity_algorithm(),
         ))
     }
 }
@@ -2,8 +2,27 @@
 #![feature(arbitrary_self_types)]
 #![feature(arbitrary_self_types_pointers)]
 
+pub mod plugin;
+
 use anyhow::Result;
-use mdxjs::{compile, MdxParseOptions, Options};
+use markdown::{
+    message::{Message, Place},
+    Location,
+};
+use mdxjs::{MdxParseOptions, Options};
+use swc_core::{
+    common::{
+        comments::{Comments, SingleThreadedComments},
+        sync::Lrc,
+        SourceMap,
+    },
+    ecma::{
+        ast::Module,
+        codegen::{text_writer::JsWriter, Emitter},
+        transforms::base::fixer::fixer,
+        visit::VisitMutWith,
+    },
+};
 use turbo_tasks::{RcStr, ValueDefault, Vc};
 use turbo_tasks_fs::{rope::Rope, File, FileContent, FileSystemPath};
 use turbopack_core::{
@@ -18,6 +37,11 @@ use turbopack_core::{
     source_transform::SourceTransform,
 };
 
+use self::plugin::plugin_message;
+pub use self::plugin::{
+    MdxBuiltinPlugin, MdxPlugin, MdxPluginContext, MdxTransformPlugin, MdxTransformPlugins,
+};
+
 #[turbo_tasks::function]
 fn modifier() -> Vc<RcStr> {
     Vc::cell("mdx".into())
@@ -48,6 +72,8 @@ pub struct MdxTransformOptions {
     pub provider_import_source: Option<RcStr>,
     /// Determines how to parse mdx contents.
     pub mdx_type: Option<MdxParseConstructs>,
+    /// Built-in plugins to run while compiling the document, see [MdxPlugin].
+    pub plugins: Option<Vec<MdxBuiltinPlugin>>,
 }
 
 impl Default for MdxTransformOptions {
@@ -59,6 +85,7 @@ impl Default for MdxTransformOptions {
             jsx_import_source: None,
             provider_import_source: None,
             mdx_type: Some(MdxParseConstructs::Commonmark),
+            plugins: None,
         }
     }
 }
@@ -80,13 +107,24 @@ impl ValueDefault for MdxTransformOptions {
 #[turbo_tasks::value]
 pub struct MdxTransform {
     options: Vc<MdxTransformOptions>,
+    plugins: Vc<MdxTransformPlugins>,
 }
 
 #[turbo_tasks::value_impl]
 impl MdxTransform {
     #[turbo_tasks::function]
     pub fn new(options: Vc<MdxTransformOptions>) -> Vc<Self> {
-        MdxTransform { options }.cell()
+        Self::new_with_plugins(options, MdxTransformPlugins::empty())
+    }
+
+    /// Creates a transform which additionally runs custom [MdxPlugin]s after
+    /// the built-in plugins configured in `options`.
+    #[turbo_tasks::function]
+    pub fn new_with_plugins(
+        options: Vc<MdxTransformOptions>,
+        plugins: Vc<MdxTransformPlugins>,
+    ) -> Vc<Self> {
+        MdxTransform { options, plugins }.cell()
     }
 }
 
@@ -97,6 +135,7 @@ impl SourceTransform for MdxTransform {
         Vc::upcast(
             MdxTransformedAsset {
                 options: self.options,
+                plugins: self.plugins,
                 source,
             }
             .cell(),
@@ -107,6 +146,7 @@ impl SourceTransform for MdxTransform {
 #[turbo_tasks::value]
 struct MdxTransformedAsset {
     options: Vc<MdxTransformOptions>,
+    plugins: Vc<MdxTransformPlugins>,
     source: Vc<Box<dyn Source>>,
 }
 
@@ -157,11 +197,31 @@ impl MdxTransformedAsset {
             None
         };
 
-        let parse_options = match transform_options.mdx_type {
+        let mut parse_options = match transform_options.mdx_type {
             Some(MdxParseConstructs::Gfm) => MdxParseOptions::gfm(),
             _ => MdxParseOptions::default(),
         };
 
+        let builtin_plugins = transform_options.plugins.as_deref().unwrap_or_default();
+        if builtin_plugins.iter().any(|p| p.needs_frontmatter()) {
+            parse_options.constructs.frontmatter = true;
+        }
+
+        let builtin_plugins = builtin_plugins
+            .iter()
+            .map(|p| p.create())
+            .collect::<Vec<_>>();
+        let mut custom_plugins = Vec::new();
+        for plugin in self.plugins.await?.iter() {
+            custom_plugins.push(plugin.await?);
+        }
+        let plugins = builtin_plugins
+            .iter()
+            .map(|p| &**p)
+            .chain(custom_plugins.iter().map(|p| &***p))
+            .collect::<Vec<_>>();
+
+        let file_path = self.source.ident().path().await?.to_string();
         let options = Options {
             parse: parse_options,
             development: transform_options.development.unwrap_or(false),
@@ -175,11 +235,12 @@ impl MdxTransformedAsset {
                 .jsx_import_source
                 .clone()
                 .map(RcStr::into_owned),
-            filepath: Some(self.source.ident().path().await?.to_string()),
+            filepath: Some(file_path),
             ..Default::default()
         };
 
-        let result = compile(&file.content().to_str()?, &options);
+        let value = file.content().to_str()?;
+        let result = compile_with_plugins(&value, &options, &plugins);
 
         match result {
             Ok(mdx_jsx_component) => Ok(MdxTransformResult {
@@ -187,29 +248,8 @@ impl MdxTransformedAsset {
             }
             .cell()),
             Err(err) => {
-                let loc = err.place.map(|p| {
-                    let (start, end) = match *p {
-                        // markdown's positions are 1-indexed, SourcePos is 0-indexed.
-                        // Both end positions point to the first character after the range
-                        markdown::message::Place::Position(p) => (
-                            SourcePos {
-                                line: p.start.line - 1,
-                                column: p.start.column - 1,
-                            },
-                            SourcePos {
-                                line: p.end.line - 1,
-                                column: p.end.column - 1,
-                            },
-                        ),
-                        markdown::message::Place::Point(p) => {
-                            let p = SourcePos {
-                                line: p.line - 1,
-                                column: p.column - 1,
-                            };
-                            (p, p)
-                        }
-                    };
-
+                let loc = err.place.map(|place| {
+                    let (start, end) = place_to_source_pos(&place);
                     IssueSource::from_line_col(self.source, start, end)
                 });
 
@@ -232,6 +272,93 @@ impl MdxTransformedAsset {
     }
 }
 
+/// Converts the place of a markdown message to the range of an issue source.
+fn place_to_source_pos(place: &Place) -> (SourcePos, SourcePos) {
+    match place {
+        // markdown's positions are 1-indexed, SourcePos is 0-indexed.
+        // Both end positions point to the first character after the range
+        Place::Position(p) => (
+            SourcePos {
+                line: p.start.line - 1,
+                column: p.start.column - 1,
+            },
+            SourcePos {
+                line: p.end.line - 1,
+                column: p.end.column - 1,
+            },
+        ),
+        Place::Point(p) => {
+            let p = SourcePos {
+                line: p.line - 1,
+                column: p.column - 1,
+            };
+            (p, p)
+        }
+    }
+}
+
+/// Compiles a document like `mdxjs::compile` and runs the hooks of `plugins`
+/// between its stages. Without plugins, `mdxjs::compile` is used as is.
+fn compile_with_plugins(
+    value: &str,
+    options: &Options,
+    plugins: &[&(dyn MdxPlugin + Send + Sync)],
+) -> Result<String, Message> {
+    if plugins.is_empty() {
+        return mdxjs::compile(value, options);
+    }
+
+    let ctx = MdxPluginContext {
+        file_path: options.filepath.as_deref().unwrap_or_default(),
+        source: value,
+    };
+
+    let mut mdast = mdxjs::mdast_util_from_mdx(value, options)?;
+    for plugin in plugins {
+        plugin.transform_mdast(&mut mdast, &ctx)?;
+    }
+
+    let mut hast = mdxjs::mdast_util_to_hast(&mdast);
+    for plugin in plugins {
+        plugin.transform_hast(&mut hast, &ctx)?;
+    }
+
+    let location = Location::new(value.as_bytes());
+    let mut explicit_jsxs = Default::default();
+    let mut program = mdxjs::hast_util_to_swc(&hast, options, Some(&location), &mut explicit_jsxs)?;
+    mdxjs::mdx_plugin_recma_document(&mut program, options, Some(&location))?;
+    mdxjs::mdx_plugin_recma_jsx_rewrite(&mut program, options, Some(&location), &explicit_jsxs)?;
+    for plugin in plugins {
+        plugin.transform_module(&mut program.module, &mdast, &ctx)?;
+    }
+
+    // The JSX is kept and compiled like in any other module afterwards. The
+    // pragma comments added by `mdxjs` keep the configured JSX runtime.
+    let comments = SingleThreadedComments::default();
+    for comment in &program.comments {
+        comments.add_leading(comment.span.lo, comment.clone());
+    }
+    serialize(&mut program.module, &comments)
+}
+
+/// Emits a module generated from a document, like `mdxjs` does.
+fn serialize(module: &mut Module, comments: &SingleThreadedComments) -> Result<String, Message> {
+    module.visit_mut_with(&mut fixer(Some(comments)));
+
+    let cm: Lrc<SourceMap> = Default::default();
+    let mut code = Vec::new();
+    let mut emitter = Emitter {
+        cfg: Default::default(),
+        cm: cm.clone(),
+        comments: Some(comments),
+        wr: JsWriter::new(cm, "\n", &mut code, None),
+    };
+    emitter
+        .emit_module(module)
+        .map_err(|err| plugin_message(err.to_string(), None, "plugins", "turbopack-mdx"))?;
+    Ok(String::from_utf8_lossy(&code).into_owned())
+}
+
 #[turbo_tasks::value]
 struct MdxTransformResult {
     content: Vc<AssetContent>,
@@ -285,3 +412,218 @@ pub fn register() {
     turbopack_ecmascript::register();
     include!(concat!(env!("OUT_DIR"), "/register.rs"));
 }
+
+#[cfg(test)]
+mod tests {
+    use markdown::{mdast, message::Message};
+    use mdxjs::{hast, MdxParseOptions, Options};
+    use swc_core::ecma::ast::Module;
+
+    use super::{
+        compile_with_plugins, place_to_source_pos,
+        plugin::{
+            export_const, set_jsx_attr, visit_markdown_elements_mut, FrontmatterPlugin,
+            HeadingSlugsPlugin, SyntaxHighlightClassesPlugin, TocPlugin,
+        },
+        MdxPlugin, MdxPluginContext,
+    };
+
+    #[derive(Debug)]
+    struct CountHeadings;
+
+    impl MdxPlugin for CountHeadings {
+        fn transform_module(
+            &self,
+            module: &mut Module,
+            mdast: &mdast::Node,
+            _ctx: &MdxPluginContext<'_>,
+        ) -> Result<(), Message> {
+            fn count(node: &mdast::Node) -> usize {
+                let headings = usize::from(matches!(node, mdast::Node::Heading(_)));
+                headings + node.children().map_or(0, |c| c.iter().map(count).sum())
+            }
+            export_const(module, "headingCount", &count(mdast).into());
+            Ok(())
+        }
+    }
+
+    /// Demotes every heading by one level.
+    #[derive(Debug)]
+    struct DemoteHeadings;
+
+    impl MdxPlugin for DemoteHeadings {
+        fn transform_mdast(
+            &self,
+            tree: &mut mdast::Node,
+            ctx: &MdxPluginContext<'_>,
+        ) -> Result<(), Message> {
+            if let mdast::Node::Heading(heading) = tree {
+                heading.depth = (heading.depth + 1).min(6);
+            }
+            for child in tree.children_mut().into_iter().flatten() {
+                self.transform_mdast(child, ctx)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Renders blockquotes as asides.
+    #[derive(Debug)]
+    struct Asides;
+
+    impl MdxPlugin for Asides {
+        fn transform_hast(
+            &self,
+            tree: &mut hast::Node,
+            ctx: &MdxPluginContext<'_>,
+        ) -> Result<(), Message> {
+            let children = match tree {
+                hast::Node::Root(root) => &mut root.children,
+                hast::Node::Element(element) => {
+                    if element.tag_name == "blockquote" {
+                        element.tag_name = "aside".into();
+                    }
+                    &mut element.children
+                }
+                _ => return Ok(()),
+            };
+            for child in children {
+                self.transform_hast(child, ctx)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct TagParagraphs;
+
+    impl MdxPlugin for TagParagraphs {
+        fn transform_module(
+            &self,
+            module: &mut Module,
+            _mdast: &mdast::Node,
+            ctx: &MdxPluginContext<'_>,
+        ) -> Result<(), Message> {
+            visit_markdown_elements_mut(module, &mut |tag_name, element| {
+                if tag_name == "p" {
+                    set_jsx_attr(element, "className", ctx.file_path);
+                }
+            });
+            Ok(())
+        }
+    }
+
+    fn compile(value: &str, plugins: &[&(dyn MdxPlugin + Send + Sync)]) -> Result<String, Message> {
+        let options = Options {
+            filepath: Some("tagged".into()),
+            jsx_runtime: Some(mdxjs::JsxRuntime::Automatic),
+            ..Default::default()
+        };
+        compile_with_plugins(value, &options, plugins)
+    }
+
+    #[test]
+    fn runs_plugins() {
+        let output = compile(
+            "# Title\n\nsome text\n\n## Subtitle\n",
+            &[&CountHeadings, &TagParagraphs],
+        )
+        .unwrap();
+        assert!(
+            output.contains("export const headingCount = 2;"),
+            "{output}"
+        );
+        assert!(
+            output.contains("<_components.p className=\"tagged\">"),
+            "{output}"
+        );
+        assert!(
+            output.starts_with("/*@jsxRuntime automatic @jsxImportSource react*/"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn plugins_change_the_trees() {
+        let output = compile(
+            "# Title\n\n> quote\n",
+            &[&DemoteHeadings, &Asides, &CountHeadings],
+        )
+        .unwrap();
+        assert!(output.contains("<_components.h2>"), "{output}");
+        assert!(!output.contains("<_components.h1>"), "{output}");
+        assert!(output.contains("<_components.aside>"), "{output}");
+        assert!(!output.contains("blockquote"), "{output}");
+        // Later stages see the changed mdast.
+        assert!(
+            output.contains("export const headingCount = 1;"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn runs_builtin_plugins() {
+        let output = compile(
+            "# Hello World\n\n## Hello World\n\n```js\nx\n```\n",
+            &[
+                &HeadingSlugsPlugin,
+                &SyntaxHighlightClassesPlugin::new("hl-"),
+            ],
+        )
+        .unwrap();
+        assert!(output.contains("id=\"hello-world\""), "{output}");
+        assert!(output.contains("id=\"hello-world-1\""), "{output}");
+        assert!(
+            output.contains("<_components.pre className=\"hl-js\">"),
+            "{output}"
+        );
+        assert!(
+            output.contains("className=\"language-js hl-js\""),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn toc_ids_match_heading_ids() {
+        let value = "# Hello `World`\n\n## Hello World\n\n<h2 id=\"custom\">x</h2>\n\n##                      Hello *World*\n";
+        // Run the TOC first, so it can't rely on the ids added by `HeadingSlugsPlugin`.
+        let output = compile(value, &[&TocPlugin, &HeadingSlugsPlugin]).unwrap();
+        for id in ["hello-world", "hello-world-1", "hello-world-2"] {
+            assert_eq!(output.matches(&format!("\"{id}\"")).count(), 2, "{output}");
+        }
+    }
+
+    #[test]
+    fn exports_frontmatter() {
+        let value = "---\ntitle: Hello\ntags: [a, b]\n---\n\n# Title\n";
+        let mut options = Options {
+            parse: MdxParseOptions::default(),
+            ..Default::default()
+        };
+        options.parse.constructs.frontmatter = true;
+        let output = compile_with_plugins(value, &options, &[&FrontmatterPlugin]).unwrap();
+        assert!(output.contains("export const frontmatter = {"), "{output}");
+        assert!(output.contains("\"title\": \"Hello\""), "{output}");
+        assert!(!output.contains("tags: [a, b]"), "{output}");
+    }
+
+    #[test]
+    fn frontmatter_errors_are_positioned() {
+        let value = "+++\ntitle = \"a\"\nbad = \n+++\n\n# Title\n";
+        let mut options = Options {
+            parse: MdxParseOptions::default(),
+            ..Default::default()
+        };
+        options.parse.constructs.frontmatter = true;
+        let err = compile_with_plugins(value, &options, &[&FrontmatterPlugin]).unwrap_err();
+
+        assert_eq!(*err.rule_id, "frontmatter");
+        let (start, _) = place_to_source_pos(&err.place.unwrap());
+        assert_eq!(start.line, 2);
+        assert!(start.column >= "bad =".len(), "{start:?}");
+
+        // Errors of mdxjs itself carry a place as well.
+        let err = compile("# Title\n\n<div>\n", &[&HeadingSlugsPlugin]).unwrap_err();
+        assert!(err.place.is_some(), "{err}");
+    }
+}
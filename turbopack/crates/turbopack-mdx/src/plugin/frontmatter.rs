@@ -0,0 +1,82 @@
+use std::ops::Range;
+
+use markdown::{mdast, message::Message, unist::Position, Location};
+use swc_core::ecma::ast::Module;
+
+use super::{export_const, plugin_message, MdxPlugin, MdxPluginContext};
+
+/// Maps a byte range inside of the frontmatter value to a position in the
+/// whole document. The value starts on the line after the opening fence.
+fn value_position(
+    source: &str,
+    node_position: Option<&Position>,
+    range: Range<usize>,
+) -> Option<Position> {
+    let node_start = node_position?.start.offset;
+    let value_start = node_start + source.get(node_start..)?.find('\n')? + 1;
+    let location = Location::new(source.as_bytes());
+    let start = location.to_point(value_start + range.start)?;
+    let end = location
+        .to_point(value_start + range.end)
+        .unwrap_or_else(|| start.clone());
+    Some(Position { start, end })
+}
+
+/// Parses a yaml or toml frontmatter node into a json value. Errors point to
+/// the offending place inside of the frontmatter when the parser reports one.
+pub(crate) fn parse_frontmatter(
+    node: &mdast::Node,
+    source: &str,
+) -> Option<Result<serde_json::Value, Message>> {
+    let (result, node_position) = match node {
+        mdast::Node::Yaml(yaml) => (
+            serde_yaml::from_str::<serde_json::Value>(&yaml.value).map_err(|e| {
+                let range = e.location().map(|l| l.index()..l.index());
+                (e.to_string(), range)
+            }),
+            yaml.position.as_ref(),
+        ),
+        mdast::Node::Toml(toml) => (
+            toml::from_str::<serde_json::Value>(&toml.value)
+                .map_err(|e| (e.message().to_string(), e.span())),
+            toml.position.as_ref(),
+        ),
+        _ => return None,
+    };
+    Some(result.map_err(|(reason, range)| {
+        let position = range
+            .and_then(|range| value_position(source, node_position, range))
+            .or_else(|| node_position.cloned());
+        plugin_message(
+            format!("Unable to parse frontmatter: {reason}"),
+            position.as_ref(),
+            "frontmatter",
+            "turbopack-mdx",
+        )
+    }))
+}
+
+/// Exports the yaml or toml frontmatter of a document as
+/// `export const frontmatter = { ... }`, like `remark-mdx-frontmatter`.
+#[derive(Debug)]
+pub struct FrontmatterPlugin;
+
+impl MdxPlugin for FrontmatterPlugin {
+    fn transform_module(
+        &self,
+        module: &mut Module,
+        mdast: &mdast::Node,
+        ctx: &MdxPluginContext<'_>,
+    ) -> Result<(), Message> {
+        let Some(children) = mdast.children() else {
+            return Ok(());
+        };
+        if let Some(value) = children
+            .iter()
+            .find_map(|child| parse_frontmatter(child, ctx.source))
+        {
+            export_const(module, "frontmatter", &value?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use markdown::{mdast, message::Message};
+use swc_core::ecma::ast::{JSXElement, Module};
+
+use super::{
+    jsx_attr, jsx_text, set_jsx_attr, visit_markdown_elements_mut, MdxPlugin, MdxPluginContext,
+};
+
+/// Generates unique, GitHub-compatible slugs, mirroring `github-slugger`.
+#[derive(Default)]
+pub(crate) struct Slugger {
+    occurrences: HashMap<String, usize>,
+}
+
+impl Slugger {
+    pub fn slug(&mut self, value: &str) -> String {
+        let original: String = value
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter_map(|c| match c {
+                c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+                c if c.is_whitespace() => Some('-'),
+                _ => None,
+            })
+            .collect();
+
+        let mut slug = original.clone();
+        while self.occurrences.contains_key(&slug) {
+            let count = self.occurrences.entry(original.clone()).or_default();
+            *count += 1;
+            slug = format!("{original}-{count}");
+        }
+        self.occurrences.insert(slug.clone(), 0);
+        slug
+    }
+}
+
+/// Returns the depth of a heading tag name, e. g. `2` for `h2`.
+pub(crate) fn heading_depth(tag_name: &str) -> Option<u8> {
+    match tag_name.as_bytes() {
+        [b'h', depth @ b'1'..=b'6'] => Some(depth - b'0'),
+        _ => None,
+    }
+}
+
+/// Calls `visitor` with every heading created from markdown, its depth and
+/// its id, which is either its `id` attribute or, when it has none, a slug of
+/// its text. The last argument tells whether the id was generated.
+///
+/// Both [HeadingSlugsPlugin] and [super::TocPlugin] use this single pass, so
+/// the ids in the table of contents always match the ones in the document.
+pub(crate) fn visit_headings_mut(
+    module: &mut Module,
+    visitor: &mut impl FnMut(&mut JSXElement, u8, String, bool),
+) {
+    let mut slugger = Slugger::default();
+    visit_markdown_elements_mut(module, &mut |tag_name, element| {
+        let Some(depth) = heading_depth(tag_name) else {
+            return;
+        };
+        match jsx_attr(element, "id") {
+            Some(id) => visitor(element, depth, id, false),
+            None => {
+                let id = slugger.slug(&jsx_text(&element.children));
+                visitor(element, depth, id, true)
+            }
+        }
+    });
+}
+
+/// Adds an `id` to every heading, like `rehype-slug`.
+#[derive(Debug)]
+pub struct HeadingSlugsPlugin;
+
+impl MdxPlugin for HeadingSlugsPlugin {
+    fn transform_module(
+        &self,
+        module: &mut Module,
+        _mdast: &mdast::Node,
+        _ctx: &MdxPluginContext<'_>,
+    ) -> Result<(), Message> {
+        visit_headings_mut(module, &mut |element, _, id, generated| {
+            if generated {
+                set_jsx_attr(element, "id", &id);
+            }
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{heading_depth, Slugger};
+
+    #[test]
+    fn slugs_are_unique() {
+        let mut slugger = Slugger::default();
+        assert_eq!(slugger.slug("Hello, World!"), "hello-world");
+        assert_eq!(slugger.slug("Hello World"), "hello-world-1");
+        assert_eq!(slugger.slug("hello-world-1"), "hello-world-1-1");
+        assert_eq!(slugger.slug("Ünïcödé  _x_"), "ünïcödé--_x_");
+    }
+
+    #[test]
+    fn heading_depths() {
+        assert_eq!(heading_depth("h1"), Some(1));
+        assert_eq!(heading_depth("h6"), Some(6));
+        assert_eq!(heading_depth("h7"), None);
+        assert_eq!(heading_depth("hr"), None);
+    }
+}
@@ -0,0 +1,342 @@
+mod frontmatter;
+mod heading_slugs;
+mod syntax_highlight;
+mod toc;
+
+use std::fmt::Debug;
+
+use markdown::{
+    mdast,
+    message::{Message, Place},
+    unist::Position,
+};
+use mdxjs::hast;
+use swc_core::{
+    common::{SyntaxContext, DUMMY_SP},
+    ecma::{
+        ast::{
+            ArrayLit, BindingIdent, Bool, Decl, ExportDecl, Expr, ExprOrSpread, Ident, IdentName,
+            JSXAttr, JSXAttrName, JSXAttrOrSpread, JSXAttrValue, JSXElement, JSXElementChild,
+            JSXElementName, JSXExpr, JSXObject, KeyValueProp, Lit, Module, ModuleDecl, ModuleItem,
+            Null, Number, ObjectLit, Pat, Prop, PropName, PropOrSpread, Str, VarDecl, VarDeclKind,
+            VarDeclarator,
+        },
+        visit::{VisitMut, VisitMutWith},
+    },
+};
+use turbo_tasks::{RcStr, Vc};
+
+pub use self::{
+    frontmatter::FrontmatterPlugin, heading_slugs::HeadingSlugsPlugin,
+    syntax_highlight::SyntaxHighlightClassesPlugin, toc::TocPlugin,
+};
+
+/// Information about the document being compiled, passed to every
+/// [MdxPlugin] hook.
+pub struct MdxPluginContext<'a> {
+    /// The path of the mdx source file.
+    pub file_path: &'a str,
+    /// The original source text of the document.
+    pub source: &'a str,
+}
+
+/// A Rust-side equivalent of remark (mdast), rehype (hast) and recma plugins.
+///
+/// The document is parsed once and each hook can change the tree of its
+/// stage: all `transform_mdast` hooks run in order first, then the mdast is
+/// turned into hast and all `transform_hast` hooks run, and finally the hast is
+/// turned into an ecmascript module and all `transform_module` hooks run.
+/// Errors are reported as mdx issues.
+pub trait MdxPlugin: Debug {
+    fn transform_mdast(
+        &self,
+        _tree: &mut mdast::Node,
+        _ctx: &MdxPluginContext<'_>,
+    ) -> Result<(), Message> {
+        Ok(())
+    }
+
+    fn transform_hast(
+        &self,
+        _tree: &mut hast::Node,
+        _ctx: &MdxPluginContext<'_>,
+    ) -> Result<(), Message> {
+        Ok(())
+    }
+
+    /// The module keeps its JSX, elements created from markdown are named
+    /// `_components.<tag name>`, e. g. `<_components.h1>`. The JSX is compiled
+    /// like in any other module afterwards. `mdast` is the tree after all
+    /// `transform_mdast` hooks ran, e. g. to read the frontmatter, which isn't
+    /// part of the hast.
+    fn transform_module(
+        &self,
+        _module: &mut Module,
+        _mdast: &mdast::Node,
+        _ctx: &MdxPluginContext<'_>,
+    ) -> Result<(), Message> {
+        Ok(())
+    }
+}
+
+/// A wrapper around an [MdxPlugin] instance, allowing it to operate with the
+/// turbo_task caching requirements.
+#[turbo_tasks::value(
+    transparent,
+    serialization = "none",
+    eq = "manual",
+    into = "new",
+    cell = "new"
+)]
+#[derive(Debug)]
+pub struct MdxTransformPlugin(#[turbo_tasks(trace_ignore)] Box<dyn MdxPlugin + Send + Sync>);
+
+impl MdxPlugin for MdxTransformPlugin {
+    fn transform_mdast(
+        &self,
+        tree: &mut mdast::Node,
+        ctx: &MdxPluginContext<'_>,
+    ) -> Result<(), Message> {
+        self.0.transform_mdast(tree, ctx)
+    }
+
+    fn transform_hast(
+        &self,
+        tree: &mut hast::Node,
+        ctx: &MdxPluginContext<'_>,
+    ) -> Result<(), Message> {
+        self.0.transform_hast(tree, ctx)
+    }
+
+    fn transform_module(
+        &self,
+        module: &mut Module,
+        mdast: &mdast::Node,
+        ctx: &MdxPluginContext<'_>,
+    ) -> Result<(), Message> {
+        self.0.transform_module(module, mdast, ctx)
+    }
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct MdxTransformPlugins(Vec<Vc<MdxTransformPlugin>>);
+
+#[turbo_tasks::value_impl]
+impl MdxTransformPlugins {
+    #[turbo_tasks::function]
+    pub fn empty() -> Vc<Self> {
+        Vc::cell(Vec::new())
+    }
+}
+
+/// Built-in plugins which can be enabled through `MdxTransformOptions`, and
+/// therefore from `experimental.mdxRs` in next.config.js.
+#[turbo_tasks::value(shared)]
+#[derive(Hash, Debug, Clone)]
+#[serde(tag = "name", rename_all = "camelCase")]
+pub enum MdxBuiltinPlugin {
+    /// Adds a GitHub-style slug `id` to every heading which has none.
+    HeadingSlugs,
+    /// Exports yaml (`---`) or toml (`+++`) frontmatter as
+    /// `export const frontmatter`.
+    Frontmatter,
+    /// Exports the heading tree of the document as `export const toc`.
+    Toc,
+    /// Adds the language class of fenced code blocks to the wrapping `<pre>`,
+    /// so syntax highlighters can style the whole block.
+    #[serde(rename_all = "camelCase")]
+    SyntaxHighlightClasses { class_prefix: Option<RcStr> },
+}
+
+impl MdxBuiltinPlugin {
+    pub fn create(&self) -> Box<dyn MdxPlugin + Send + Sync> {
+        match self {
+            MdxBuiltinPlugin::HeadingSlugs => Box::new(HeadingSlugsPlugin),
+            MdxBuiltinPlugin::Frontmatter => Box::new(FrontmatterPlugin),
+            MdxBuiltinPlugin::Toc => Box::new(TocPlugin),
+            MdxBuiltinPlugin::SyntaxHighlightClasses { class_prefix } => Box::new(
+                SyntaxHighlightClassesPlugin::new(class_prefix.as_deref().unwrap_or("language-")),
+            ),
+        }
+    }
+
+    /// Whether the plugin requires additional markdown constructs to be
+    /// enabled while parsing.
+    pub fn needs_frontmatter(&self) -> bool {
+        matches!(self, MdxBuiltinPlugin::Frontmatter)
+    }
+}
+
+/// Creates a [Message] in the same shape as the ones reported by `mdxjs`.
+pub fn plugin_message(
+    reason: String,
+    position: Option<&Position>,
+    rule_id: &str,
+    source: &str,
+) -> Message {
+    Message {
+        place: position.map(|p| Box::new(Place::Position(p.clone()))),
+        reason,
+        rule_id: Box::new(rule_id.into()),
+        source: Box::new(source.into()),
+    }
+}
+
+/// Returns the tag name of a JSX element created from markdown, e. g. `h1` for
+/// `<_components.h1>`.
+pub fn markdown_tag_name(element: &JSXElement) -> Option<&str> {
+    let JSXElementName::JSXMemberExpr(member) = &element.opening.name else {
+        return None;
+    };
+    match &member.obj {
+        JSXObject::Ident(obj) if &*obj.sym == "_components" => Some(&member.prop.sym),
+        _ => None,
+    }
+}
+
+/// Calls `visitor` with every JSX element created from markdown and its tag
+/// name, parents before children.
+pub fn visit_markdown_elements_mut(
+    module: &mut Module,
+    visitor: &mut impl FnMut(&str, &mut JSXElement),
+) {
+    struct Visitor<'a, F>(&'a mut F);
+
+    impl<F: FnMut(&str, &mut JSXElement)> VisitMut for Visitor<'_, F> {
+        fn visit_mut_jsx_element(&mut self, element: &mut JSXElement) {
+            if let Some(tag_name) = markdown_tag_name(element).map(|t| t.to_string()) {
+                (self.0)(&tag_name, element);
+            }
+            element.visit_mut_children_with(self);
+        }
+    }
+
+    module.visit_mut_with(&mut Visitor(visitor));
+}
+
+/// Collects the text content of JSX children, similar to `hast-util-to-string`.
+pub fn jsx_text(children: &[JSXElementChild]) -> String {
+    children
+        .iter()
+        .map(|child| match child {
+            JSXElementChild::JSXText(text) => text.value.to_string(),
+            JSXElementChild::JSXExprContainer(container) => match &container.expr {
+                JSXExpr::Expr(expr) => match &**expr {
+                    Expr::Lit(Lit::Str(str)) => str.value.to_string(),
+                    _ => String::new(),
+                },
+                JSXExpr::JSXEmptyExpr(_) => String::new(),
+            },
+            JSXElementChild::JSXElement(element) => jsx_text(&element.children),
+            JSXElementChild::JSXFragment(fragment) => jsx_text(&fragment.children),
+            JSXElementChild::JSXSpreadChild(_) => String::new(),
+        })
+        .collect()
+}
+
+/// Returns the value of the string attribute `name` of a JSX element.
+pub fn jsx_attr(element: &JSXElement, name: &str) -> Option<String> {
+    element.opening.attrs.iter().find_map(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(JSXAttr {
+            name: JSXAttrName::Ident(attr_name),
+            value: Some(JSXAttrValue::Lit(Lit::Str(value))),
+            ..
+        }) if &*attr_name.sym == name => Some(value.value.to_string()),
+        _ => None,
+    })
+}
+
+/// Sets the string attribute `name` of a JSX element, replacing any previous
+/// value.
+pub fn set_jsx_attr(element: &mut JSXElement, name: &str, value: &str) {
+    element.opening.attrs.retain(|attr| {
+        !matches!(
+            attr,
+            JSXAttrOrSpread::JSXAttr(JSXAttr {
+                name: JSXAttrName::Ident(attr_name),
+                ..
+            }) if &*attr_name.sym == name
+        )
+    });
+    element
+        .opening
+        .attrs
+        .push(JSXAttrOrSpread::JSXAttr(JSXAttr {
+            span: DUMMY_SP,
+            name: JSXAttrName::Ident(IdentName::new(name.into(), DUMMY_SP)),
+            value: Some(JSXAttrValue::Lit(Lit::Str(str_lit(value)))),
+        }));
+}
+
+fn str_lit(value: &str) -> Str {
+    Str {
+        span: DUMMY_SP,
+        value: value.into(),
+        raw: None,
+    }
+}
+
+/// Converts a json value to the equivalent ecmascript expression.
+fn json_to_expr(value: &serde_json::Value) -> Expr {
+    match value {
+        serde_json::Value::Null => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+        serde_json::Value::Bool(value) => Expr::Lit(Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value: *value,
+        })),
+        serde_json::Value::Number(value) => Expr::Lit(Lit::Num(Number {
+            span: DUMMY_SP,
+            value: value.as_f64().unwrap_or(f64::NAN),
+            raw: Some(value.to_string().into()),
+        })),
+        serde_json::Value::String(value) => Expr::Lit(Lit::Str(str_lit(value))),
+        serde_json::Value::Array(values) => Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: values
+                .iter()
+                .map(|value| {
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(json_to_expr(value)),
+                    })
+                })
+                .collect(),
+        }),
+        serde_json::Value::Object(values) => Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: values
+                .iter()
+                .map(|(key, value)| {
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: PropName::Str(str_lit(key)),
+                        value: Box::new(json_to_expr(value)),
+                    })))
+                })
+                .collect(),
+        }),
+    }
+}
+
+/// Appends `export const <name> = <value>;` to the module.
+pub fn export_const(module: &mut Module, name: &str, value: &serde_json::Value) {
+    module
+        .body
+        .push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            span: DUMMY_SP,
+            decl: Decl::Var(Box::new(VarDecl {
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                kind: VarDeclKind::Const,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(BindingIdent::from(Ident::new_no_ctxt(
+                        name.into(),
+                        DUMMY_SP,
+                    ))),
+                    init: Some(Box::new(json_to_expr(value))),
+                    definite: false,
+                }],
+            })),
+        })));
+}
@@ -0,0 +1,72 @@
+use markdown::{mdast, message::Message};
+use swc_core::ecma::ast::{JSXElement, JSXElementChild, Module};
+
+use super::{
+    jsx_attr, markdown_tag_name, set_jsx_attr, visit_markdown_elements_mut, MdxPlugin,
+    MdxPluginContext,
+};
+
+/// Annotates `<pre><code class="language-*">` blocks (as produced for fenced
+/// code) with a language class on both elements, like `rehype-prism` does
+/// before highlighting.
+#[derive(Debug)]
+pub struct SyntaxHighlightClassesPlugin {
+    class_prefix: String,
+}
+
+impl SyntaxHighlightClassesPlugin {
+    pub fn new(class_prefix: &str) -> Self {
+        Self {
+            class_prefix: class_prefix.to_string(),
+        }
+    }
+}
+
+fn add_class_name(element: &mut JSXElement, class_name: &str) {
+    let classes = jsx_attr(element, "className").unwrap_or_default();
+    if classes.split_whitespace().any(|c| c == class_name) {
+        return;
+    }
+    let classes = classes
+        .split_whitespace()
+        .chain([class_name])
+        .collect::<Vec<_>>()
+        .join(" ");
+    set_jsx_attr(element, "className", &classes);
+}
+
+impl MdxPlugin for SyntaxHighlightClassesPlugin {
+    fn transform_module(
+        &self,
+        module: &mut Module,
+        _mdast: &mdast::Node,
+        _ctx: &MdxPluginContext<'_>,
+    ) -> Result<(), Message> {
+        visit_markdown_elements_mut(module, &mut |tag_name, pre| {
+            if tag_name != "pre" {
+                return;
+            }
+            let Some(language) = pre.children.iter().find_map(|child| match child {
+                JSXElementChild::JSXElement(code) if markdown_tag_name(code) == Some("code") => {
+                    jsx_attr(code, "className")?
+                        .split_whitespace()
+                        .find_map(|c| c.strip_prefix("language-").map(|l| l.to_string()))
+                }
+                _ => None,
+            }) else {
+                return;
+            };
+
+            let class_name = format!("{}{}", self.class_prefix, language);
+            for child in pre.children.iter_mut() {
+                if let JSXElementChild::JSXElement(code) = child {
+                    if markdown_tag_name(code) == Some("code") {
+                        add_class_name(code, &class_name);
+                    }
+                }
+            }
+            add_class_name(pre, &class_name);
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use markdown::{mdast, message::Message};
+use serde::Serialize;
+use swc_core::ecma::ast::Module;
+
+use super::{
+    export_const, heading_slugs::visit_headings_mut, jsx_text, MdxPlugin, MdxPluginContext,
+};
+
+#[derive(Serialize)]
+struct TocEntry {
+    depth: u8,
+    value: String,
+    id: String,
+    children: Vec<TocEntry>,
+}
+
+/// Nests entries below the closest preceding entry with a lower depth.
+fn insert_entry(entries: &mut Vec<TocEntry>, entry: TocEntry) {
+    match entries.last_mut() {
+        Some(parent) if parent.depth < entry.depth => insert_entry(&mut parent.children, entry),
+        _ => entries.push(entry),
+    }
+}
+
+/// Appends `export const toc = [...]` to the document, a tree of all headings
+/// with their depth, text and id. The ids match the ones added by
+/// [super::HeadingSlugsPlugin].
+#[derive(Debug)]
+pub struct TocPlugin;
+
+impl MdxPlugin for TocPlugin {
+    fn transform_module(
+        &self,
+        module: &mut Module,
+        _mdast: &mdast::Node,
+        _ctx: &MdxPluginContext<'_>,
+    ) -> Result<(), Message> {
+        let mut toc = Vec::new();
+        visit_headings_mut(module, &mut |element, depth, id, _| {
+            let entry = TocEntry {
+                depth,
+                value: jsx_text(&element.children),
+                id,
+                children: Vec::new(),
+            };
+            insert_entry(&mut toc, entry);
+        });
+
+        let toc = serde_json::to_value(toc)
+            .map_err(|err| super::plugin_message(err.to_string(), None, "toc", "turbopack-mdx"))?;
+        export_const(module, "toc", &toc);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{insert_entry, TocEntry};
+
+    fn entry(depth: u8, value: &str) -> TocEntry {
+        TocEntry {
+            depth,
+            value: value.into(),
+            id: value.into(),
+            children: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn nests_entries_by_depth() {
+        let mut toc = Vec::new();
+        for (depth, value) in [(1, "a"), (2, "b"), (3, "c"), (2, "d"), (1, "e"), (3, "f")] {
+            insert_entry(&mut toc, entry(depth, value));
+        }
+
+        let toc = serde_json::to_value(toc).unwrap();
+        assert_eq!(
+            toc,
+            serde_json::json!([
+                { "depth": 1, "value": "a", "id": "a", "children": [
+                    { "depth": 2, "value": "b", "id": "b", "children": [
+                        { "depth": 3, "value": "c", "id": "c", "children": [] },
+                    ] },
+                    { "depth": 2, "value": "d", "id": "d", "children": [] },
+                ] },
+                { "depth": 1, "value": "e", "id": "e", "children": [
+                    { "depth": 3, "value": "f", "id": "f", "children": [] },
+                ] },
+            ])
+        );
+    }
+}
@@ -11,6 +11,7 @@ import {
   dirname,
   resolve as pathResolve,
 } from "path";
+import { statSync } from "fs";
 import {
   StackFrame,
   parse as parseStackTrace,
@@ -44,11 +45,48 @@ export type IpcInfoMessage =
     trace?: StackFrame[];
   };
 
-export type IpcRequestMessage = {
-  type: "resolve";
-  options: any;
-  lookupPath: string;
-  request: string;
+export type IpcRequestMessage =
+  | {
+    type: "resolve";
+    options: any;
+    lookupPath: string;
+    request: string;
+  }
+  | {
+    type: "loadModule";
+    lookupPath: string;
+    request: string;
+  }
+  | {
+    type: "importModule";
+    lookupPath: string;
+    request: string;
+  };
+
+/**
+ * Contents that aren't valid UTF-8 are sent base64 encoded.
+ */
+type Content = string | { binary: string };
+
+type EmittedAsset = {
+  file: string;
+  content: Content;
+  sourceMap?: unknown;
+};
+
+const contentToBuffer = (content: Content) =>
+  typeof content === "string"
+    ? Buffer.from(content, "utf-8")
+    : Buffer.from(content.binary, "base64");
+
+/**
+ * The dependencies reported by the loaders. They are part of the result, so
+ * the result can be cached and invalidated when any of them changes.
+ */
+type LoaderDependencies = {
+  fileDependencies: string[];
+  dirDependencies: { path: string; glob: string }[];
+  buildDependencies: string[];
 };
 
 type LoaderConfig =
@@ -164,13 +202,69 @@ type ResolveOptions = {
   importFields?: string[];
 };
 
+const getMtime = (file: string) => {
+  try {
+    return statSync(file).mtimeMs;
+  } catch {
+    return undefined;
+  }
+};
+
+/**
+ * The modification times of the files the loaders consist of, at the time
+ * they were required. Loaders stay in the require cache across transforms, so
+ * they need to be evicted when any of these files changes.
+ */
+const loaderFileMtimes = new Map<string, number | undefined>();
+
+const evictChangedLoaders = () => {
+  for (const [file, mtime] of loaderFileMtimes) {
+    if (getMtime(file) !== mtime) {
+      // Modules keep references to the modules they required, so all of them
+      // need to be required again.
+      const requireCache = __turbopack_external_require__("module")._cache;
+      for (const file of loaderFileMtimes.keys()) {
+        delete requireCache[file];
+      }
+      loaderFileMtimes.clear();
+      return;
+    }
+  }
+};
+
+/**
+ * Returns the files of the loaders and of all modules they require, as far as
+ * they are in the require cache. ESM loaders are not covered beyond their
+ * entry file.
+ */
+const collectLoaderFiles = (loaders: string[]) => {
+  const requireCache = __turbopack_external_require__("module")._cache;
+  const files = new Set<string>();
+  const queue = [...loaders];
+  while (queue.length > 0) {
+    const file = queue.pop()!;
+    if (files.has(file)) continue;
+    files.add(file);
+    for (const child of requireCache[file]?.children ?? []) {
+      queue.push(child.filename);
+    }
+  }
+  for (const file of files) {
+    if (!loaderFileMtimes.has(file)) {
+      loaderFileMtimes.set(file, getMtime(file));
+    }
+  }
+  return files;
+};
+
 const transform = (
   ipc: Ipc<IpcInfoMessage, IpcRequestMessage>,
-  content: string,
+  content: Content,
   name: string,
   query: string,
   loaders: LoaderConfig[]
 ) => {
+  evictChangedLoaders();
   return new Promise((resolve, reject) => {
     const resource = pathResolve(contextDir, name);
     const resourceDir = dirname(resource);
@@ -179,6 +273,40 @@ const transform = (
       typeof loader === "string" ? { loader, options: {} } : loader
     );
 
+    const resolvedLoaders = loadersWithOptions.map((loader) => ({
+      loader: __turbopack_external_require__.resolve(loader.loader, {
+        paths: [resourceDir],
+      }),
+      options: loader.options,
+    }));
+
+    const assets: EmittedAsset[] = [];
+    const addedBuildDependencies = new Set<string>();
+
+    const loadModule = async (request: string) => {
+      return (await ipc.sendRequest({
+        type: "loadModule",
+        lookupPath: toPath(resourceDir),
+        request,
+      })) as { path: string; source: Content; map: string | null };
+    };
+
+    const importModule = async (request: string) => {
+      const { entry, chunks } = (await ipc.sendRequest({
+        type: "importModule",
+        lookupPath: toPath(resourceDir),
+        request,
+      })) as { path: string; entry: string; chunks: string[] };
+      // The chunks are rebuilt when the module changes, but the loader
+      // process is kept alive, so they must not be served from the require
+      // cache.
+      const requireCache = __turbopack_external_require__("module")._cache;
+      for (const chunk of chunks) {
+        delete requireCache[chunk];
+      }
+      return __turbopack_external_require__(entry);
+    };
+
     runLoaders(
       {
         resource: resource + query,
@@ -190,6 +318,9 @@ const transform = (
           },
           currentTraceSpan: new DummySpan(),
           rootContext: contextDir,
+          addBuildDependency(file: string) {
+            addedBuildDependencies.add(pathResolve(contextDir, file));
+          },
           getOptions() {
             const entry = this.loaders[this.loaderIndex];
             return entry.options && typeof entry.options === "object"
@@ -328,6 +459,57 @@ const transform = (
           },
           emitWarning: makeErrorEmitter("warning", ipc),
           emitError: makeErrorEmitter("error", ipc),
+          emitFile(
+            name: string,
+            content: string | Buffer,
+            sourceMap?: unknown
+          ) {
+            assets.push({
+              file: name,
+              content: Buffer.isBuffer(content)
+                ? { binary: content.toString("base64") }
+                : content,
+              sourceMap,
+            });
+          },
+          loadModule(
+            request: string,
+            callback: (
+              err: Error | null,
+              source?: string | Buffer,
+              sourceMap?: unknown,
+              module?: unknown
+            ) => void
+          ) {
+            loadModule(request).then(
+              ({ path, source, map }) => {
+                // The module is passed in the shape of a webpack module, as
+                // far as loaders commonly access it.
+                callback(
+                  null,
+                  typeof source === "string" ? source : contentToBuffer(source),
+                  map ? JSON.parse(map) : null,
+                  { resource: fromPath(path) }
+                );
+              },
+              (err) => callback(err)
+            );
+          },
+          importModule(
+            request: string,
+            _options?: unknown,
+            callback?: (err: Error | null, exports?: unknown) => void
+          ) {
+            const promise = importModule(request);
+            if (callback) {
+              promise.then(
+                (exports) => callback(null, exports),
+                (err) => callback(err)
+              );
+            } else {
+              return promise;
+            }
+          },
           getLogger(name: unknown) {
             const logFn = (logType: string, ...args: unknown[]) => {
               let trace;
@@ -440,15 +622,10 @@ const transform = (
           },
         },
 
-        loaders: loadersWithOptions.map((loader) => ({
-          loader: __turbopack_external_require__.resolve(loader.loader, {
-            paths: [resourceDir],
-          }),
-          options: loader.options,
-        })),
+        loaders: resolvedLoaders,
         readResource: (_filename, callback) => {
           // TODO assuming the filename === resource, but loaders might change that
-          callback(null, Buffer.from(content, "utf-8"));
+          callback(null, contentToBuffer(content));
         },
       },
       (err, result) => {
@@ -466,6 +643,25 @@ const transform = (
             path: toPath(dep),
           });
         }
+        const dependencies: LoaderDependencies = {
+          fileDependencies: result.fileDependencies.map(toPath),
+          dirDependencies: result.contextDependencies.map((dep) => ({
+            path: toPath(dep),
+            glob: "**",
+          })),
+          // The loaders, the modules they require and the files they added
+          // are build dependencies, so changing any of them invalidates the
+          // results. Paths are relative to the project directory and may
+          // point to parent directories (e. g. hoisted packages in a
+          // monorepo), only paths on other drives can't be tracked.
+          buildDependencies: [
+            ...collectLoaderFiles(resolvedLoaders.map(({ loader }) => loader)),
+            ...addedBuildDependencies,
+          ]
+            .map((path) => relative(contextDir, path))
+            .filter((path) => !isAbsolute(path))
+            .map((path) => (sep !== "/" ? path.replaceAll(sep, "/") : path)),
+        };
         if (!result.result) return reject(new Error("No result from loaders"));
         const [source, map] = result.result;
         resolve({
@@ -476,6 +672,8 @@ const transform = (
               : typeof map === "object"
                 ? JSON.stringify(map)
                 : undefined,
+          assets,
+          dependencies,
         });
       }
     );
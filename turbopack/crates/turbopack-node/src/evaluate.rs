@@ -343,6 +343,7 @@ pub async fn compute(
                         args: args.iter().map(|v| &**v).collect(),
                     })
                     .await?;
+                operation.set_in_progress(true);
                 Ok(operation)
             },
             PoolErrorHandler,
@@ -374,6 +375,7 @@ pub async fn compute(
                 }
             }
         }
+        operation.set_in_progress(false);
 
         evaluate_context.finish(state, &pool).await?;
 
@@ -383,6 +385,9 @@ pub async fn compute(
     };
 
     let mut sender = (sender.get)();
+    // When this task is cancelled or times out, the stream would end without a
+    // value. Report that as error instead.
+    let mut aborted = AbortedEvaluationGuard(Some(sender.clone()));
     pin_mut!(stream);
     while let Some(value) = stream.next().await {
         if sender.send(value).await.is_err() {
@@ -392,10 +397,23 @@ pub async fn compute(
             return Ok(Default::default());
         }
     }
+    aborted.0 = None;
 
     Ok(Default::default())
 }
 
+struct AbortedEvaluationGuard(Option<UnboundedSender<EvaluationItem>>);
+
+impl Drop for AbortedEvaluationGuard {
+    fn drop(&mut self) {
+        if let Some(sender) = self.0.take() {
+            let _ = sender.unbounded_send(Err(SharedError::new(anyhow!(
+                "Node.js evaluation was aborted before it finished"
+            ))));
+        }
+    }
+}
+
 /// Repeatedly pulls from the NodeJsOperation until we receive a
 /// value/error/end.
 async fn pull_operation<T: EvaluateContext>(
@@ -816,6 +816,7 @@ impl NodeJsPool {
             start: Instant::now(),
             stats: self.stats.clone(),
             allow_process_reuse: true,
+            in_progress: false,
         })
     }
 }
@@ -830,6 +831,9 @@ pub struct NodeJsOperation {
     start: Instant,
     stats: Arc<Mutex<NodeJsPoolStats>>,
     allow_process_reuse: bool,
+    /// The process is in the middle of a job and can't be reused when the
+    /// operation is dropped, e. g. because the task driving it was cancelled.
+    in_progress: bool,
 }
 
 impl NodeJsOperation {
@@ -907,6 +911,13 @@ impl NodeJsOperation {
         Ok(status)
     }
 
+    /// Marks the start (`true`) or the end (`false`) of a job. When the
+    /// operation is dropped while a job is in progress, the process is not
+    /// returned to the pool.
+    pub fn set_in_progress(&mut self, in_progress: bool) {
+        self.in_progress = in_progress;
+    }
+
     pub fn disallow_reuse(&mut self) {
         if self.allow_process_reuse {
             self.stats.lock().remove_worker();
@@ -929,6 +940,9 @@ impl NodeJsOperation {
 
 impl Drop for NodeJsOperation {
     fn drop(&mut self) {
+        if self.in_progress {
+            self.disallow_reuse();
+        }
         if let Some(process) = self.process.take() {
             let elapsed = self.start.elapsed();
             {
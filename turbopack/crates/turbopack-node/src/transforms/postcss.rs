@@ -495,6 +495,7 @@ impl PostCssTransformedAsset {
             resolve_options_context: None,
             args: vec![Vc::cell(content.into()), Vc::cell(css_path.into())],
             additional_invalidation: config_changed,
+            timeout_ms: None,
         })
         .await?;
 
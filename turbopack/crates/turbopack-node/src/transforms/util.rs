@@ -1,21 +1,53 @@
 use std::collections::BTreeMap;
 
+use anyhow::Result;
+use either::Either;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use turbo_tasks::{RcStr, Vc};
-use turbo_tasks_fs::{File, FileContent, FileSystem};
+use serde_with::serde_as;
+use turbo_tasks::{FxIndexMap, RcStr, Vc};
+use turbo_tasks_fs::{rope::Rope, File, FileContent, FileSystem};
 use turbopack_core::{
-    asset::AssetContent, server_fs::ServerFileSystem, virtual_source::VirtualSource,
+    asset::AssetContent, server_fs::ServerFileSystem, source::EmittedFiles,
+    virtual_source::VirtualSource,
 };
 
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BytesBase64 {
+    #[serde_as(as = "serde_with::base64::Base64")]
+    pub binary: Vec<u8>,
+}
+
+/// Returns the content as a string, or base64 encoded when it isn't valid
+/// UTF-8 (e. g. images), in the shape the loader runner expects.
+pub fn rope_to_string_or_bytes(rope: &Rope) -> Result<Either<RcStr, BytesBase64>> {
+    let bytes = rope.to_bytes()?;
+    Ok(match std::str::from_utf8(&bytes) {
+        Ok(str) => Either::Left(str.into()),
+        Err(_) => Either::Right(BytesBase64 {
+            binary: bytes.into_owned(),
+        }),
+    })
+}
+
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct EmittedAsset {
     file: RcStr,
-    content: RcStr,
+    #[serde(with = "either::serde_untagged")]
+    content: Either<RcStr, BytesBase64>,
     source_map: Option<JsonValue>,
 }
 
+fn emitted_content(content: Either<RcStr, BytesBase64>) -> Vc<AssetContent> {
+    let file = match content {
+        Either::Left(str) => File::from(str),
+        Either::Right(bytes) => File::from(bytes.binary),
+    };
+    AssetContent::File(FileContent::Content(file).cell()).cell()
+}
+
 pub fn emitted_assets_to_virtual_sources(
     assets: Option<Vec<EmittedAsset>>,
 ) -> Vec<Vc<VirtualSource>> {
@@ -36,8 +68,23 @@ pub fn emitted_assets_to_virtual_sources(
             // TODO handle SourceMap
             VirtualSource::new(
                 ServerFileSystem::new().root().join(file),
-                AssetContent::File(FileContent::Content(File::from(content)).cell()).cell(),
+                emitted_content(content),
             )
         })
         .collect()
 }
+
+/// Converts emitted assets into files that are written to the output root,
+/// see [turbopack_core::source::EmitFiles].
+pub fn emitted_assets_to_emitted_files(assets: Option<Vec<EmittedAsset>>) -> Vc<EmittedFiles> {
+    let files = assets
+        .into_iter()
+        .flatten()
+        .map(|EmittedAsset { file, content, .. }| (file, content))
+        // Sort it to make it determinstic
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .map(|(file, content)| (file, emitted_content(content)))
+        .collect::<FxIndexMap<_, _>>();
+    Vc::cell(files)
+}
@@ -1,28 +1,29 @@
-use std::mem::take;
+use std::{mem::take, time::Duration};
 
 use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
 use either::Either;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value as JsonValue};
-use serde_with::serde_as;
 use turbo_tasks::{
-    trace::TraceRawVcs, Completion, RcStr, TaskInput, TryJoinIterExt, Value, ValueToString, Vc,
+    debug::ValueDebugFormat, set_task_timeout, trace::TraceRawVcs, Completion, FxIndexMap, RcStr,
+    TaskInput, TryJoinIterExt, Value, ValueToString, Vc,
 };
 use turbo_tasks_bytes::stream::SingleValue;
 use turbo_tasks_env::ProcessEnv;
 use turbo_tasks_fs::{
-    glob::Glob, json::parse_json_with_source_context, DirectoryEntry, File, FileContent,
-    FileSystemPath, ReadGlobResult,
+    glob::Glob, json::parse_json_with_source_context, to_sys_path, DirectoryEntry, File,
+    FileContent, FileSystemPath, ReadGlobResult,
 };
 use turbopack_core::{
     asset::{Asset, AssetContent},
-    chunk::ChunkingContext,
+    chunk::{ChunkingContext, EvaluatableAssets},
     context::{AssetContext, ProcessResult},
     file_source::FileSource,
     ident::AssetIdent,
     issue::{Issue, IssueExt, IssueSeverity, IssueStage, OptionStyledString, StyledString},
     module::Module,
+    output::OutputAsset,
     reference_type::{InnerAssets, ReferenceType},
     resolve::{
         options::{ConditionValue, ResolveInPackage, ResolveIntoPackage, ResolveOptions},
@@ -30,47 +31,64 @@ use turbopack_core::{
         pattern::Pattern,
         resolve,
     },
-    source::Source,
+    source::{EmitFiles, EmittedFiles, Source},
     source_map::{GenerateSourceMap, OptionSourceMap, SourceMap},
     source_transform::SourceTransform,
-    virtual_source::VirtualSource,
 };
 use turbopack_resolve::{
     ecmascript::get_condition_maps, resolve::resolve_options,
     resolve_options_context::ResolveOptionsContext,
 };
 
-use super::util::{emitted_assets_to_virtual_sources, EmittedAsset};
+use super::util::{
+    emitted_assets_to_emitted_files, rope_to_string_or_bytes, BytesBase64, EmittedAsset,
+};
 use crate::{
     debug::should_debug,
     embed_js::embed_file_path,
+    emit,
     evaluate::{
         compute, custom_evaluate, get_evaluate_pool, EvaluateContext, EvaluationIssue,
         JavaScriptEvaluation, JavaScriptStreamSender,
     },
     execution_context::ExecutionContext,
+    get_intermediate_asset, internal_assets,
     pool::{FormattingMode, NodeJsPool},
     source_map::{StackFrame, StructuredError},
     AssetsForSourceMapping,
 };
 
-#[serde_as]
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-struct BytesBase64 {
-    #[serde_as(as = "serde_with::base64::Base64")]
-    binary: Vec<u8>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TraceRawVcs, ValueDebugFormat)]
 #[serde(rename_all = "camelCase")]
-#[turbo_tasks::value(serialization = "custom")]
 struct WebpackLoadersProcessingResult {
     #[serde(with = "either::serde_untagged")]
     #[turbo_tasks(debug_ignore, trace_ignore)]
     source: Either<RcStr, BytesBase64>,
     map: Option<RcStr>,
-    #[turbo_tasks(trace_ignore)]
+    #[turbo_tasks(debug_ignore, trace_ignore)]
     assets: Option<Vec<EmittedAsset>>,
+    #[serde(default)]
+    dependencies: WebpackLoaderDependencies,
+}
+
+#[turbo_tasks::value(transparent)]
+struct OptionWebpackLoadersProcessingResult(Option<WebpackLoadersProcessingResult>);
+
+/// The dependencies reported by the loaders, relative to the project path.
+#[derive(
+    Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, TraceRawVcs, ValueDebugFormat,
+)]
+#[serde(rename_all = "camelCase")]
+struct WebpackLoaderDependencies {
+    file_dependencies: Vec<RcStr>,
+    dir_dependencies: Vec<WebpackLoaderDirDependency>,
+    build_dependencies: Vec<RcStr>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, TraceRawVcs, ValueDebugFormat)]
+struct WebpackLoaderDirDependency {
+    path: RcStr,
+    glob: RcStr,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, TraceRawVcs, Serialize, Deserialize)]
@@ -91,10 +109,13 @@ pub struct WebpackLoaders {
     loaders: Vc<WebpackLoaderItems>,
     rename_as: Option<RcStr>,
     resolve_options_context: Vc<ResolveOptionsContext>,
+    timeout_ms: Option<u64>,
 }
 
 #[turbo_tasks::value_impl]
 impl WebpackLoaders {
+    /// `timeout_ms` overrides how long running the loaders of a single module
+    /// may take, see [compute_webpack_loader_evaluation].
     #[turbo_tasks::function]
     pub fn new(
         evaluate_context: Vc<Box<dyn AssetContext>>,
@@ -102,6 +123,7 @@ impl WebpackLoaders {
         loaders: Vc<WebpackLoaderItems>,
         rename_as: Option<RcStr>,
         resolve_options_context: Vc<ResolveOptionsContext>,
+        timeout_ms: Option<u64>,
     ) -> Vc<Self> {
         WebpackLoaders {
             evaluate_context,
@@ -109,6 +131,7 @@ impl WebpackLoaders {
             loaders,
             rename_as,
             resolve_options_context,
+            timeout_ms,
         }
         .cell()
     }
@@ -164,11 +187,20 @@ impl GenerateSourceMap for WebpackLoadersProcessedAsset {
     }
 }
 
+#[turbo_tasks::value_impl]
+impl EmitFiles for WebpackLoadersProcessedAsset {
+    #[turbo_tasks::function]
+    async fn emitted_files(self: Vc<Self>) -> Result<Vc<EmittedFiles>> {
+        Ok(self.process().await?.emitted_files)
+    }
+}
+
 #[turbo_tasks::value]
 struct ProcessWebpackLoadersResult {
     content: Vc<AssetContent>,
     source_map: Option<Vc<SourceMap>>,
-    assets: Vec<Vc<VirtualSource>>,
+    /// Files emitted by loaders via `this.emitFile`.
+    emitted_files: Vc<EmittedFiles>,
 }
 
 #[turbo_tasks::function]
@@ -186,98 +218,178 @@ impl WebpackLoadersProcessedAsset {
     #[turbo_tasks::function]
     async fn process(self: Vc<Self>) -> Result<Vc<ProcessWebpackLoadersResult>> {
         let this = self.await?;
-        let transform = this.transform.await?;
 
-        let ExecutionContext {
-            project_path,
-            chunking_context,
-            env,
-        } = *transform.execution_context.await?;
         let source_content = this.source.content();
         let AssetContent::File(file) = *source_content.await? else {
             bail!("Webpack Loaders transform only support transforming files");
         };
-        let FileContent::Content(content) = &*file.await? else {
+        let FileContent::Content(_) = &*file.await? else {
             return Ok(ProcessWebpackLoadersResult {
                 content: AssetContent::File(FileContent::NotFound.cell()).cell(),
-                assets: Vec::new(),
                 source_map: None,
+                emitted_files: Vc::cell(FxIndexMap::default()),
             }
             .cell());
         };
-        let content = content.content().to_str()?;
-        let evaluate_context = transform.evaluate_context;
-
-        let webpack_loaders_executor = webpack_loaders_executor(evaluate_context).module();
-        let resource_fs_path = this.source.ident().path();
-        let resource_fs_path_ref = resource_fs_path.await?;
-        let Some(resource_path) = project_path
-            .await?
-            .get_relative_path_to(&resource_fs_path_ref)
-        else {
-            bail!(format!(
-                "Resource path \"{}\" need to be on project filesystem \"{}\"",
-                resource_fs_path_ref,
-                project_path.await?
-            ));
-        };
-        let loaders = transform.loaders.await?;
-        let config_value = evaluate_webpack_loader(WebpackLoaderContext {
-            module_asset: webpack_loaders_executor,
-            cwd: project_path,
-            env,
-            context_ident_for_issue: this.source.ident(),
-            asset_context: evaluate_context,
-            chunking_context,
-            resolve_options_context: Some(transform.resolve_options_context),
-            args: vec![
-                Vc::cell(content.into()),
-                // We need to pass the query string to the loader
-                Vc::cell(resource_path.to_string().into()),
-                Vc::cell(this.source.ident().query().await?.to_string().into()),
-                Vc::cell(json!(*loaders)),
-            ],
-            additional_invalidation: Completion::immutable(),
-        })
-        .await?;
-
-        let SingleValue::Single(val) = config_value.try_into_single().await? else {
+        let processed = webpack_loaders_processing_result(this.transform, this.source).await?;
+        let Some(processed) = &*processed else {
             // An error happened, which has already been converted into an issue.
             return Ok(ProcessWebpackLoadersResult {
                 content: AssetContent::File(FileContent::NotFound.cell()).cell(),
-                assets: Vec::new(),
                 source_map: None,
+                emitted_files: Vc::cell(FxIndexMap::default()),
             }
             .cell());
         };
-        let processed: WebpackLoadersProcessingResult = parse_json_with_source_context(
-            val.to_str()?,
-        )
-        .context("Unable to deserializate response from webpack loaders transform operation")?;
 
         // handle SourceMap
-        let source_map = if let Some(source_map) = processed.map {
-            SourceMap::new_from_file_content(FileContent::Content(File::from(source_map)).cell())
-                .await?
-                .map(|source_map| source_map.cell())
+        let source_map = if let Some(source_map) = &processed.map {
+            SourceMap::new_from_file_content(
+                FileContent::Content(File::from(source_map.clone())).cell(),
+            )
+            .await?
+            .map(|source_map| source_map.cell())
         } else {
             None
         };
-        let file = match processed.source {
-            Either::Left(str) => File::from(str),
-            Either::Right(bytes) => File::from(bytes.binary),
+        let file = match &processed.source {
+            Either::Left(str) => File::from(str.clone()),
+            Either::Right(bytes) => File::from(bytes.binary.clone()),
         };
-        let assets = emitted_assets_to_virtual_sources(processed.assets);
+        let emitted_files = emitted_assets_to_emitted_files(processed.assets.clone());
         let content = AssetContent::File(FileContent::Content(file).cell()).cell();
         Ok(ProcessWebpackLoadersResult {
             content,
-            assets,
             source_map,
+            emitted_files,
         }
         .cell())
     }
 }
 
+/// Runs the loaders of `transform` on `source`.
+///
+/// The result is serializable, so it's stored in the persistent cache and
+/// loaders don't need to run again after a restart. It depends on the source
+/// content, the loaders and their options and on all dependencies reported by
+/// the loaders, so it's invalidated when any of them changes.
+#[turbo_tasks::function]
+async fn webpack_loaders_processing_result(
+    transform: Vc<WebpackLoaders>,
+    source: Vc<Box<dyn Source>>,
+) -> Result<Vc<OptionWebpackLoadersProcessingResult>> {
+    let transform = transform.await?;
+
+    let ExecutionContext {
+        project_path,
+        chunking_context,
+        env,
+    } = *transform.execution_context.await?;
+    let AssetContent::File(file) = *source.content().await? else {
+        bail!("Webpack Loaders transform only support transforming files");
+    };
+    let FileContent::Content(content) = &*file.await? else {
+        return Ok(Vc::cell(None));
+    };
+    let content = match rope_to_string_or_bytes(content.content())? {
+        Either::Left(str) => JsonValue::String(str.to_string()),
+        Either::Right(bytes) => serde_json::to_value(bytes)?,
+    };
+    let evaluate_context = transform.evaluate_context;
+
+    let webpack_loaders_executor = webpack_loaders_executor(evaluate_context).module();
+    let resource_fs_path = source.ident().path();
+    let resource_fs_path_ref = resource_fs_path.await?;
+    let Some(resource_path) = project_path
+        .await?
+        .get_relative_path_to(&resource_fs_path_ref)
+    else {
+        bail!(format!(
+            "Resource path \"{}\" need to be on project filesystem \"{}\"",
+            resource_fs_path_ref,
+            project_path.await?
+        ));
+    };
+    let loaders = transform.loaders.await?;
+    let config_value = evaluate_webpack_loader(WebpackLoaderContext {
+        module_asset: webpack_loaders_executor,
+        cwd: project_path,
+        env,
+        context_ident_for_issue: source.ident(),
+        asset_context: evaluate_context,
+        chunking_context,
+        resolve_options_context: Some(transform.resolve_options_context),
+        args: vec![
+            Vc::cell(content),
+            // We need to pass the query string to the loader
+            Vc::cell(resource_path.to_string().into()),
+            Vc::cell(source.ident().query().await?.to_string().into()),
+            Vc::cell(json!(*loaders)),
+        ],
+        additional_invalidation: Completion::immutable(),
+        timeout_ms: transform.timeout_ms,
+    })
+    .await?;
+
+    let SingleValue::Single(val) = config_value.try_into_single().await? else {
+        return Ok(Vc::cell(None));
+    };
+    let processed: WebpackLoadersProcessingResult =
+        parse_json_with_source_context(val.to_str()?)
+            .context("Unable to deserializate response from webpack loaders transform operation")?;
+
+    // The evaluation itself isn't persisted, so the dependencies are read again
+    // to make them dependencies of this task. This keeps the cached result
+    // valid across restarts until one of them changes.
+    track_loader_dependencies(project_path, &processed.dependencies).await?;
+
+    Ok(Vc::cell(Some(processed)))
+}
+
+/// Reads all dependencies reported by loaders to make them dependencies of the
+/// current task.
+async fn track_loader_dependencies(
+    cwd: Vc<FileSystemPath>,
+    dependencies: &WebpackLoaderDependencies,
+) -> Result<()> {
+    let WebpackLoaderDependencies {
+        file_dependencies,
+        dir_dependencies,
+        build_dependencies,
+    } = dependencies;
+    file_dependencies
+        .iter()
+        .map(|path| async move {
+            cwd.join(path.clone()).read().await?;
+            anyhow::Ok(())
+        })
+        .try_join()
+        .await?;
+    // Loaders might be installed outside of the project filesystem (e. g. in a
+    // global cache), which can't be watched.
+    build_dependencies
+        .iter()
+        .map(|path| async move {
+            if let Some(path) = *cwd.try_join(path.clone()).await? {
+                path.read().await?;
+            }
+            anyhow::Ok(())
+        })
+        .try_join()
+        .await?;
+    dir_dependencies
+        .iter()
+        .map(|WebpackLoaderDirDependency { path, glob }| {
+            dir_dependency(
+                cwd.join(path.clone())
+                    .read_glob(Glob::new(glob.clone()), false),
+            )
+        })
+        .try_join()
+        .await?;
+    Ok(())
+}
+
 #[turbo_tasks::function]
 pub(crate) fn evaluate_webpack_loader(
     webpack_loader_context: WebpackLoaderContext,
@@ -285,11 +397,17 @@ pub(crate) fn evaluate_webpack_loader(
     custom_evaluate(webpack_loader_context)
 }
 
-#[turbo_tasks::function]
+/// Loaders run arbitrary user code, so a hanging loader must not block the
+/// compilation forever. The default timeout can be changed with
+/// [WebpackLoaderContext::timeout_ms].
+#[turbo_tasks::function(timeout_ms = 300_000)]
 async fn compute_webpack_loader_evaluation(
     webpack_loader_context: WebpackLoaderContext,
     sender: Vc<JavaScriptStreamSender>,
 ) -> Result<Vc<()>> {
+    if let Some(timeout_ms) = webpack_loader_context.timeout_ms {
+        set_task_timeout(Duration::from_millis(timeout_ms));
+    }
     compute(webpack_loader_context, sender).await
 }
 
@@ -364,12 +482,36 @@ pub enum RequestMessage {
         lookup_path: RcStr,
         request: RcStr,
     },
+    /// `this.loadModule`: Returns the content of the module after it has been
+    /// processed by the loaders.
+    #[serde(rename_all = "camelCase")]
+    LoadModule { lookup_path: RcStr, request: RcStr },
+    /// `this.importModule`: Builds the module into an entry chunk the loader
+    /// can `require`.
+    #[serde(rename_all = "camelCase")]
+    ImportModule { lookup_path: RcStr, request: RcStr },
 }
 
 #[derive(Serialize, Debug)]
 #[serde(untagged)]
 pub enum ResponseMessage {
-    Resolve { path: RcStr },
+    Resolve {
+        path: RcStr,
+    },
+    LoadModule {
+        path: RcStr,
+        #[serde(with = "either::serde_untagged")]
+        source: Either<RcStr, BytesBase64>,
+        map: Option<RcStr>,
+    },
+    ImportModule {
+        path: RcStr,
+        /// The absolute path of the entry chunk.
+        entry: RcStr,
+        /// The absolute paths of all chunks, which need to be evicted from the
+        /// require cache, as they might have changed since the last import.
+        chunks: Vec<RcStr>,
+    },
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, TaskInput, Serialize, Deserialize, Debug)]
@@ -383,6 +525,53 @@ pub struct WebpackLoaderContext {
     pub resolve_options_context: Option<Vc<ResolveOptionsContext>>,
     pub args: Vec<Vc<JsonValue>>,
     pub additional_invalidation: Vc<Completion>,
+    pub timeout_ms: Option<u64>,
+}
+
+impl WebpackLoaderContext {
+    /// Resolves a request made by a loader, relative to `lookup_path`.
+    async fn resolve_source(
+        &self,
+        webpack_options: Option<WebpackResolveOptions>,
+        lookup_path: RcStr,
+        request: RcStr,
+    ) -> Result<Vc<Box<dyn Source>>> {
+        let Some(resolve_options_context) = self.resolve_options_context else {
+            bail!("Resolve options are not available in this context");
+        };
+        let lookup_path = self.cwd.join(lookup_path);
+        let request = Request::parse(Value::new(Pattern::Constant(request)));
+        let mut options = resolve_options(lookup_path, resolve_options_context);
+        if let Some(webpack_options) = webpack_options {
+            options = apply_webpack_resolve_options(options, webpack_options);
+        }
+
+        let resolved = resolve(
+            lookup_path,
+            Value::new(ReferenceType::Undefined),
+            request,
+            options,
+        );
+
+        if let Some(source) = *resolved.first_source().await? {
+            Ok(source)
+        } else {
+            bail!(
+                "Unable to resolve {} in {}",
+                request.to_string().await?,
+                lookup_path.to_string().await?
+            );
+        }
+    }
+
+    /// Returns `path` relative to the working directory of the loader.
+    async fn relative_path(&self, path: Vc<FileSystemPath>) -> Result<RcStr> {
+        let path = path.await?;
+        let Some(relative_path) = self.cwd.await?.get_relative_path_to(&path) else {
+            bail!("{} is on a different filesystem than the loader", path);
+        };
+        Ok(relative_path)
+    }
 }
 
 #[async_trait]
@@ -449,12 +638,18 @@ impl EvaluateContext for WebpackLoaderContext {
             }
             InfoMessage::BuildDependency { path } => {
                 // TODO We might miss some changes that happened during execution
-                BuildDependencyIssue {
-                    context_ident: self.context_ident_for_issue,
-                    path: self.cwd.join(path),
+                // Build dependencies are tracked like file dependencies, unless they are
+                // outside of the project filesystem.
+                if let Some(path) = *self.cwd.try_join(path.clone()).await? {
+                    path.read().await?;
+                } else {
+                    BuildDependencyIssue {
+                        context_ident: self.context_ident_for_issue,
+                        path,
+                    }
+                    .cell()
+                    .emit();
                 }
-                .cell()
-                .emit();
             }
             InfoMessage::DirDependency { path, glob } => {
                 // TODO We might miss some changes that happened during execution
@@ -493,41 +688,80 @@ impl EvaluateContext for WebpackLoaderContext {
                 lookup_path,
                 request,
             } => {
-                let Some(resolve_options_context) = self.resolve_options_context else {
-                    bail!("Resolve options are not available in this context");
+                let source = self
+                    .resolve_source(Some(webpack_options), lookup_path, request)
+                    .await?;
+                let path = self.relative_path(source.ident().path()).await?;
+                Ok(ResponseMessage::Resolve { path })
+            }
+            RequestMessage::LoadModule {
+                lookup_path,
+                request,
+            } => {
+                let source = self.resolve_source(None, lookup_path, request).await?;
+                let module = self
+                    .asset_context
+                    .process(source, Value::new(ReferenceType::Undefined))
+                    .module();
+                let path = self.relative_path(module.ident().path()).await?;
+                // Reading the content makes the loader depend on it, so it's
+                // executed again when the module changes.
+                let AssetContent::File(file) = *module.content().await? else {
+                    bail!("Module {} has no content that can be loaded", path);
                 };
-                let lookup_path = self.cwd.join(lookup_path);
-                let request = Request::parse(Value::new(Pattern::Constant(request)));
-                let options = resolve_options(lookup_path, resolve_options_context);
-
-                let options = apply_webpack_resolve_options(options, webpack_options);
-
-                let resolved = resolve(
-                    lookup_path,
-                    Value::new(ReferenceType::Undefined),
-                    request,
-                    options,
-                );
-
-                let request_str = request.to_string().await?;
-                let lookup_path_str = lookup_path.to_string().await?;
-                if let Some(source) = *resolved.first_source().await? {
-                    if let Some(path) = self
-                        .cwd
-                        .await?
-                        .get_relative_path_to(&*source.ident().path().await?)
-                    {
-                        Ok(ResponseMessage::Resolve { path })
-                    } else {
-                        bail!(
-                            "Resolving {} in {} ends up on a different filesystem",
-                            request_str,
-                            lookup_path_str
-                        );
+                let FileContent::Content(content) = &*file.await? else {
+                    bail!("Module {} has no content that can be loaded", path);
+                };
+                let source = rope_to_string_or_bytes(content.content())?;
+                let map = if let Some(generate_source_map) =
+                    Vc::try_resolve_sidecast::<Box<dyn GenerateSourceMap>>(module).await?
+                {
+                    match *generate_source_map.generate_source_map().await? {
+                        Some(source_map) => {
+                            Some(source_map.to_rope().await?.to_str()?.as_ref().into())
+                        }
+                        None => None,
                     }
                 } else {
-                    bail!("Unable to resolve {} in {}", request_str, lookup_path_str);
+                    None
+                };
+                Ok(ResponseMessage::LoadModule { path, source, map })
+            }
+            RequestMessage::ImportModule {
+                lookup_path,
+                request,
+            } => {
+                let source = self.resolve_source(None, lookup_path, request).await?;
+                let module = self
+                    .asset_context
+                    .process(source, Value::new(ReferenceType::Undefined))
+                    .module();
+                let path = self.relative_path(module.ident().path()).await?;
+                let entry = get_intermediate_asset(
+                    self.chunking_context,
+                    module,
+                    EvaluatableAssets::empty(),
+                );
+                let output_root = self.chunking_context.output_root();
+                // Writing the chunks makes the loader depend on their content,
+                // so it's executed again when the module or its dependencies
+                // change.
+                emit(entry, output_root).await?;
+                let mut chunks = Vec::new();
+                for chunk in internal_assets(entry, output_root).await?.iter() {
+                    if let Some(chunk) = to_sys_path(chunk.ident().path()).await? {
+                        chunks.push(chunk.to_string_lossy().into());
+                    }
                 }
+                let Some(entry) = to_sys_path(entry.ident().path()).await? else {
+                    bail!("Modules can only be imported from a disk filesystem");
+                };
+                let entry = entry.to_string_lossy().into();
+                Ok(ResponseMessage::ImportModule {
+                    path,
+                    entry,
+                    chunks,
+                })
             }
         }
     }
@@ -648,11 +882,12 @@ async fn apply_webpack_resolve_options(
     Ok(resolve_options.cell())
 }
 
-/// An issue that occurred while evaluating node code.
+/// A build dependency outside of the project filesystem, which can't be
+/// tracked.
 #[turbo_tasks::value(shared)]
 pub struct BuildDependencyIssue {
     pub context_ident: Vc<AssetIdent>,
-    pub path: Vc<FileSystemPath>,
+    pub path: RcStr,
 }
 
 #[turbo_tasks::value_impl]
@@ -664,7 +899,7 @@ impl Issue for BuildDependencyIssue {
 
     #[turbo_tasks::function]
     fn title(&self) -> Vc<StyledString> {
-        StyledString::Text("Build dependencies are not yet supported".into()).cell()
+        StyledString::Text("Build dependency outside of the project can't be tracked".into()).cell()
     }
 
     #[turbo_tasks::function]
@@ -678,20 +913,19 @@ impl Issue for BuildDependencyIssue {
     }
 
     #[turbo_tasks::function]
-    async fn description(&self) -> Result<Vc<OptionStyledString>> {
-        Ok(Vc::cell(Some(
+    fn description(&self) -> Vc<OptionStyledString> {
+        Vc::cell(Some(
             StyledString::Line(vec![
                 StyledString::Text("The file at ".into()),
-                StyledString::Code(self.path.await?.to_string().into()),
+                StyledString::Code(self.path.clone()),
                 StyledString::Text(
-                    " is a build dependency, which is not yet implemented.
-    Changing this file or any dependency will not be recognized and might require restarting the \
-                     server"
+                    " is a build dependency outside of the project filesystem. Changing it will \
+                     not be recognized and might require restarting the server"
                         .into(),
                 ),
             ])
             .cell(),
-        )))
+        ))
     }
 }
 
@@ -12,7 +12,9 @@ use turbopack_core::resolve::{
 
 use crate::{
     resolve_options_context::ResolveOptionsContext,
-    typescript::{apply_tsconfig_resolve_options, tsconfig, tsconfig_resolve_options},
+    typescript::{
+        apply_tsconfig_resolve_options, tsconfig, tsconfig_for_path, tsconfig_resolve_options,
+    },
 };
 
 const NODE_EXTERNALS: [&str; 63] = [
@@ -211,15 +213,16 @@ async fn base_resolve_options(
     };
     Ok(ResolveOptions {
         extensions,
-        modules: if let Some(environment) = emulating {
-            if *environment.resolve_node_modules().await? {
-                vec![ResolveModules::Nested(root, vec!["node_modules".into()])]
-            } else {
-                Vec::new()
-            }
-        } else {
+        modules: {
             let mut mods = Vec::new();
-            if let Some(dir) = opt.enable_node_modules {
+            if let Some(dir) = opt.enable_pnp {
+                mods.push(ResolveModules::Pnp(dir));
+            }
+            if let Some(environment) = emulating {
+                if *environment.resolve_node_modules().await? {
+                    mods.push(ResolveModules::Nested(root, vec!["node_modules".into()]));
+                }
+            } else if let Some(dir) = opt.enable_node_modules {
                 mods.push(ResolveModules::Nested(dir, vec!["node_modules".into()]));
             }
             mods
@@ -260,6 +263,8 @@ async fn base_resolve_options(
         plugins,
         before_resolve_plugins: opt.before_resolve_plugins.clone(),
         loose_errors: opt.loose_errors,
+        browser: opt.browser,
+        trace_requests: opt.trace_requests.clone(),
         ..Default::default()
     }
     .into())
@@ -285,9 +290,10 @@ pub async fn resolve_options(
     let resolve_options = if options_context_value.enable_typescript {
         let tsconfig = find_context_file(resolve_path, tsconfig()).await?;
         match *tsconfig {
-            FindContextFileResult::Found(path, _) => {
-                apply_tsconfig_resolve_options(resolve_options, tsconfig_resolve_options(path))
-            }
+            FindContextFileResult::Found(path, _) => apply_tsconfig_resolve_options(
+                resolve_options,
+                tsconfig_resolve_options(tsconfig_for_path(path, resolve_path)),
+            ),
             FindContextFileResult::NotFound(_) => resolve_options,
         }
     } else {
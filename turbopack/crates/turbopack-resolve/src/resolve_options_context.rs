@@ -31,6 +31,10 @@ pub struct ResolveOptionsContext {
     /// directory
     pub enable_node_modules: Option<Vc<FileSystemPath>>,
     #[serde(default)]
+    /// Enable resolving of dependencies with Yarn Plug'n'Play, using the
+    /// `.pnp.cjs` or `.pnp.data.json` manifest in the provided directory
+    pub enable_pnp: Option<Vc<FileSystemPath>>,
+    #[serde(default)]
     /// Mark well-known Node.js modules as external imports and load them using
     /// native `require`. e.g. url, querystring, os
     pub enable_node_externals: bool,
@@ -74,6 +78,10 @@ pub struct ResolveOptionsContext {
     pub before_resolve_plugins: Vec<Vc<Box<dyn BeforeResolvePlugin>>>,
     /// Warn instead of error for resolve errors
     pub loose_errors: bool,
+    #[serde(default)]
+    /// Requests that report how they are resolved, see
+    /// [turbopack_core::resolve::options::ResolveOptions::trace_requests].
+    pub trace_requests: Vec<RcStr>,
 
     #[serde(default)]
     pub placeholder_for_future_extensions: (),
@@ -1,9 +1,15 @@
-use std::{collections::HashMap, fmt::Write, mem::take};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    mem::take,
+};
 
 use anyhow::Result;
 use serde_json::Value as JsonValue;
-use turbo_tasks::{fxindexset, RcStr, Value, ValueDefault, Vc};
-use turbo_tasks_fs::{FileContent, FileJsonContent, FileSystemPath};
+use turbo_tasks::{fxindexset, RcStr, ReadRef, Value, ValueDefault, Vc};
+use turbo_tasks_fs::{
+    FileContent, FileJsonContent, FileSystemEntryType, FileSystemPath, FileSystemPathOption,
+};
 use turbopack_core::{
     asset::Asset,
     context::AssetContext,
@@ -46,17 +52,27 @@ type TsConfigs = Vec<(Vc<FileJsonContent>, Vc<Box<dyn Source>>)>;
 
 #[tracing::instrument(skip_all)]
 pub async fn read_tsconfigs(
-    mut data: Vc<FileContent>,
-    mut tsconfig: Vc<Box<dyn Source>>,
+    data: Vc<FileContent>,
+    tsconfig: Vc<Box<dyn Source>>,
     resolve_options: Vc<ResolveOptions>,
 ) -> Result<TsConfigs> {
     let mut configs = Vec::new();
     let resolve_options = json_only(resolve_options);
-    loop {
+    // Configs are visited depth-first, so the result is ordered by priority: a
+    // config comes before everything it extends, and later entries of an
+    // `extends` array come before earlier ones.
+    let mut stack = vec![(data, tsconfig)];
+    let mut visited = HashSet::new();
+    while let Some((data, tsconfig)) = stack.pop() {
+        let tsconfig = tsconfig.resolve().await?;
+        if !visited.insert(tsconfig) {
+            continue;
+        }
+
         // tsc ignores empty config files.
         if let FileContent::Content(file) = &*data.await? {
             if file.content().is_empty() {
-                break;
+                continue;
             }
         }
 
@@ -89,12 +105,19 @@ pub async fn read_tsconfigs(
             }
             FileJsonContent::Content(json) => {
                 configs.push((parsed_data, tsconfig));
-                if let Some(extends) = json["extends"].as_str() {
+                // TypeScript 5 allows `extends` to be an array of configs.
+                let extends_list = match &json["extends"] {
+                    JsonValue::String(extends) => vec![extends.as_str()],
+                    JsonValue::Array(extends) => extends
+                        .iter()
+                        .filter_map(|extends| extends.as_str())
+                        .collect(),
+                    _ => vec![],
+                };
+                for extends in extends_list {
                     let resolved = resolve_extends(tsconfig, extends, resolve_options).await?;
                     if let Some(source) = *resolved.await? {
-                        data = source.content().file_content();
-                        tsconfig = source;
-                        continue;
+                        stack.push((source.content().file_content(), source));
                     } else {
                         TsConfigIssue {
                             severity: IssueSeverity::Error.into(),
@@ -108,7 +131,6 @@ pub async fn read_tsconfigs(
                 }
             }
         }
-        break;
     }
     Ok(configs)
 }
@@ -216,6 +238,24 @@ pub async fn read_from_tsconfigs<T>(
     Ok(None)
 }
 
+/// The `${configDir}` template variable that can be used in paths of a
+/// tsconfig.
+const CONFIG_DIR: &str = "${configDir}";
+
+/// Joins a path from a tsconfig to the directory of that tsconfig, or to
+/// `config_dir` when it starts with `${configDir}`.
+fn join_config_path(
+    dir: Vc<FileSystemPath>,
+    config_dir: Vc<FileSystemPath>,
+    path: &str,
+) -> Vc<FileSystemPathOption> {
+    if let Some(path) = path.strip_prefix(CONFIG_DIR) {
+        config_dir.try_join(path.trim_start_matches('/').into())
+    } else {
+        dir.try_join(path.into())
+    }
+}
+
 /// Resolve options specific to tsconfig.json.
 #[turbo_tasks::value]
 #[derive(Default)]
@@ -249,10 +289,14 @@ pub async fn tsconfig_resolve_options(
         return Ok(Default::default());
     }
 
+    // `${configDir}` refers to the directory of the config that is used, not the one
+    // it's written in.
+    let config_dir = configs[0].1.ident().path().parent();
+
     let base_url = if let Some(base_url) = read_from_tsconfigs(&configs, |json, source| {
         json["compilerOptions"]["baseUrl"]
             .as_str()
-            .map(|base_url| source.ident().path().parent().try_join(base_url.into()))
+            .map(|base_url| join_config_path(source.ident().path().parent(), config_dir, base_url))
     })
     .await?
     {
@@ -267,10 +311,16 @@ pub async fn tsconfig_resolve_options(
             if let JsonValue::Object(paths) = &json["compilerOptions"]["paths"] {
                 let mut context_dir = source.ident().path().parent();
                 if let Some(base_url) = json["compilerOptions"]["baseUrl"].as_str() {
-                    if let Some(new_context) = *context_dir.try_join(base_url.into()).await? {
+                    if let Some(new_context) =
+                        *join_config_path(context_dir, config_dir, base_url).await?
+                    {
                         context_dir = *new_context;
                     }
                 };
+                let config_dir_from_context = context_dir
+                    .await?
+                    .get_relative_path_to(&*config_dir.await?)
+                    .unwrap_or_else(|| ".".into());
                 for (key, value) in paths.iter() {
                     if let JsonValue::Array(vec) = value {
                         let entries = vec
@@ -283,6 +333,9 @@ pub async fn tsconfig_resolve_options(
                                 }
 
                                 entry.map(|s| {
+                                    if let Some(s) = s.strip_prefix(CONFIG_DIR) {
+                                        return format!("{config_dir_from_context}{s}").into();
+                                    }
                                     // tsconfig paths are always relative requests
                                     if s.starts_with("./") || s.starts_with("../") {
                                         s.into()
@@ -342,6 +395,63 @@ pub async fn tsconfig_resolve_options(
     .cell())
 }
 
+/// Follows the `references` of `tsconfig` to the project that `lookup_path`
+/// belongs to. Files inside a referenced project are resolved with that
+/// project's tsconfig instead of the referencing one.
+#[turbo_tasks::function]
+pub async fn tsconfig_for_path(
+    tsconfig: Vc<FileSystemPath>,
+    lookup_path: Vc<FileSystemPath>,
+) -> Result<Vc<FileSystemPath>> {
+    let FileJsonContent::Content(json) = &*tsconfig.read().parse_json_with_comments().await? else {
+        return Ok(tsconfig);
+    };
+    let JsonValue::Array(references) = &json["references"] else {
+        return Ok(tsconfig);
+    };
+    let tsconfig_dir = tsconfig.parent();
+    let tsconfig_dir_ref = tsconfig_dir.await?;
+    let lookup_path_ref = lookup_path.await?;
+
+    let mut closest: Option<(Vc<FileSystemPath>, ReadRef<FileSystemPath>)> = None;
+    for reference in references {
+        let Some(path) = reference["path"].as_str() else {
+            continue;
+        };
+        let Some(reference_path) = *tsconfig_dir.try_join(path.into()).await? else {
+            continue;
+        };
+        // A reference can point to a directory containing a tsconfig.json or to a
+        // config file directly.
+        let reference_path = match *reference_path.get_type().await? {
+            FileSystemEntryType::Directory => reference_path.join("tsconfig.json".into()),
+            _ => *reference_path,
+        };
+        if !matches!(*reference_path.get_type().await?, FileSystemEntryType::File) {
+            continue;
+        }
+        let project_dir = reference_path.parent().await?;
+        // Only descend into projects that contain the lookup path. Projects that
+        // contain the referencing config are skipped, which also prevents cycles.
+        if !lookup_path_ref.is_inside_or_equal_ref(&project_dir)
+            || tsconfig_dir_ref.is_inside_or_equal_ref(&project_dir)
+        {
+            continue;
+        }
+        if closest
+            .as_ref()
+            .map_or(true, |(_, dir)| project_dir.path.len() > dir.path.len())
+        {
+            closest = Some((reference_path, project_dir));
+        }
+    }
+
+    Ok(match closest {
+        Some((reference_path, _)) => tsconfig_for_path(reference_path, lookup_path),
+        None => tsconfig,
+    })
+}
+
 #[turbo_tasks::function]
 pub fn tsconfig() -> Vc<Vec<RcStr>> {
     Vc::cell(vec!["tsconfig.json".into(), "jsconfig.json".into()])
@@ -373,6 +483,7 @@ pub async fn apply_tsconfig_resolve_options(
                 .map(|import_map| import_map.extend(tsconfig_import_map))
                 .unwrap_or(tsconfig_import_map),
         );
+        resolve_options.tsconfig_import_map = Some(tsconfig_import_map);
     }
     resolve_options.enable_typescript_with_output_extension =
         tsconfig_resolve_options.is_module_resolution_nodenext;
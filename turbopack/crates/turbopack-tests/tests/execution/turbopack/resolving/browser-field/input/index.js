@@ -0,0 +1,34 @@
+import {
+  serverOnlyLib,
+  serverOnlyLibRequired,
+  nodeOnly,
+  env,
+  other,
+  dep,
+} from "pkg";
+import noext from "pkg/lib/noext";
+
+it("should replace a bare specifier mapped to false with an empty module", () => {
+  expect(serverOnlyLib).toEqual({});
+  expect(serverOnlyLibRequired).toEqual({});
+});
+
+it("should replace a package-relative file mapped to false with an empty module", () => {
+  expect(nodeOnly).toEqual({});
+});
+
+it("should remap a package-relative file", () => {
+  expect(env).toBe("client");
+});
+
+it("should remap a bare specifier to another package", () => {
+  expect(other).toBe("other-browser");
+});
+
+it("should apply the browser field of the package that contains the file", () => {
+  expect(dep).toBe("dep-browser");
+});
+
+it("should match keys without extension for deep imports", () => {
+  expect(noext).toBe("noext-browser");
+});
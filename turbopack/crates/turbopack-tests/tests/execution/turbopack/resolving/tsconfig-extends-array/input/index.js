@@ -0,0 +1,10 @@
+import baseFoo from "@base/foo";
+import shared from "@shared";
+
+it("should resolve `${configDir}` relative to the used tsconfig", () => {
+  expect(baseFoo).toBe("base/foo");
+});
+
+it("should prefer later entries of an `extends` array", () => {
+  expect(shared).toBe("from-override");
+});
@@ -0,0 +1,10 @@
+import appName from "@/name";
+import uiName from "./packages/ui";
+
+it("should resolve paths with the app's tsconfig", () => {
+  expect(appName).toBe("app");
+});
+
+it("should resolve paths inside a referenced project with its tsconfig", () => {
+  expect(uiName).toBe("ui");
+});
@@ -0,0 +1,154 @@
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+#![cfg(test)]
+
+use std::{
+    collections::{HashSet, VecDeque},
+    env, fs,
+    path::Path,
+};
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use turbo_tasks::{RcStr, TurboTasks, Value, ValueToString, Vc};
+use turbo_tasks_fs::{FileSystem, MemoryFileSystem, MemoryFiles};
+use turbo_tasks_memory::MemoryBackend;
+use turbo_tasks_testing::IncrementalTest;
+use turbopack::{module_options::ModuleOptionsContext, ModuleAssetContext};
+use turbopack_core::{
+    compile_time_info::CompileTimeInfo,
+    context::AssetContext,
+    environment::{Environment, ExecutionEnvironment, NodeJsEnvironment},
+    file_source::FileSource,
+    module::Module,
+    reference::primary_referenced_modules,
+    reference_type::{EntryReferenceSubType, ReferenceType},
+};
+use turbopack_resolve::resolve_options_context::ResolveOptionsContext;
+
+fn register() {
+    turbo_tasks::register();
+    turbo_tasks_fs::register();
+    turbopack::register();
+    turbopack_resolve::register();
+    include!(concat!(env!("OUT_DIR"), "/register_test_incremental.rs"));
+}
+
+/// The files of the test project. Edits to them invalidate the build.
+static FILES: Lazy<MemoryFiles> = Lazy::new(MemoryFiles::new);
+
+/// Creates the filesystem once, so every execution of the build reads the
+/// same filesystem.
+#[turbo_tasks::function]
+fn project_fs() -> Vc<MemoryFileSystem> {
+    MemoryFileSystem::new("project".into(), FILES.clone())
+}
+
+/// Returns the idents of all modules reachable from `index.js`.
+#[turbo_tasks::function]
+async fn module_graph() -> Result<Vc<Vec<RcStr>>> {
+    let root = project_fs().root();
+    let env = Environment::new(Value::new(ExecutionEnvironment::NodeJsBuildTime(
+        NodeJsEnvironment::default().into(),
+    )));
+    let asset_context: Vc<Box<dyn AssetContext>> = Vc::upcast(ModuleAssetContext::new(
+        Default::default(),
+        CompileTimeInfo::new(env),
+        ModuleOptionsContext::default().cell(),
+        ResolveOptionsContext::default().cell(),
+        Vc::cell("test".into()),
+    ));
+    let entry = asset_context
+        .process(
+            Vc::upcast(FileSource::new(root.join("index.js".into()))),
+            Value::new(ReferenceType::Entry(EntryReferenceSubType::Undefined)),
+        )
+        .module();
+
+    let mut seen = HashSet::from([entry]);
+    let mut queue = VecDeque::from([entry]);
+    let mut idents = Vec::new();
+    while let Some(module) = queue.pop_front() {
+        idents.push(module.ident().to_string().await?.clone_value());
+        for &referenced in primary_referenced_modules(module).await?.iter() {
+            if seen.insert(referenced) {
+                queue.push_back(referenced);
+            }
+        }
+    }
+    idents.sort();
+    Ok(Vc::cell(idents))
+}
+
+#[tokio::test]
+async fn edits_only_recompute_affected_modules() -> Result<()> {
+    register();
+
+    FILES.write(
+        "index.js",
+        "import { b } from './b.js';\nimport { c } from './c.js';\nconsole.log(b, c);\n",
+    );
+    FILES.write("b.js", "export const b = 1;\n");
+    FILES.write("c.js", "export const c = 2;\n");
+
+    let mut test = IncrementalTest::new(TurboTasks::new(MemoryBackend::default()));
+    test.build(|| async { Ok(module_graph()) }).await?;
+
+    // The set of modules doesn't change, so the graph must not be recomputed.
+    let step = test
+        .step("change an export of b.js", || {
+            FILES.write("b.js", "export const b = 42;\n");
+        })
+        .await?;
+    step.assert_executed(&["read"]);
+    step.assert_not_executed(&["module_graph"]);
+
+    let step = test
+        .step("import a new file from c.js", || {
+            FILES.write("d.js", "export const d = 3;\n");
+            FILES.write(
+                "c.js",
+                "import { d } from './d.js';\nexport const c = 2 + d;\n",
+            );
+        })
+        .await?;
+    step.assert_executed(&["module_graph"]);
+
+    let step = test
+        .step("write identical content", || {
+            FILES.write("b.js", "export const b = 42;\n");
+        })
+        .await?;
+    assert_eq!(
+        step.to_string(),
+        "## write identical content\n(nothing re-executed)\n"
+    );
+
+    assert_report_snapshot("edits_only_recompute_affected_modules", test.report());
+    Ok(())
+}
+
+/// Compares `report` with the snapshot in `tests/incremental/{name}.md`.
+/// `UPDATE=1` updates the snapshot instead.
+fn assert_report_snapshot(name: &str, report: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/incremental")
+        .join(format!("{name}.md"));
+    if env::var("UPDATE").unwrap_or_default() == "1" {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, report).unwrap();
+        println!("updated contents of {}", path.display());
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "new snapshot {} detected, run with UPDATE=1 to create it:\n{report}",
+            path.display()
+        )
+    });
+    assert_eq!(
+        report,
+        expected,
+        "contents of {} did not match, run with UPDATE=1 to update it",
+        path.display()
+    );
+}
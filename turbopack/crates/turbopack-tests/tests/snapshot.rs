@@ -30,7 +30,7 @@ use turbopack::{
     },
     ModuleAssetContext,
 };
-use turbopack_browser::BrowserChunkingContext;
+use turbopack_browser::{import_map_asset, BrowserChunkingContext, UrlExternalsOptions};
 use turbopack_core::{
     asset::Asset,
     chunk::{
@@ -95,6 +95,10 @@ struct SnapshotOptions {
     use_swc_css: bool,
     #[serde(default)]
     tree_shaking_mode: Option<TreeShakingMode>,
+    #[serde(default)]
+    module_chunks: bool,
+    #[serde(default)]
+    url_externals: Option<UrlExternalsOptions>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -122,6 +126,8 @@ impl Default for SnapshotOptions {
             environment: Default::default(),
             use_swc_css: Default::default(),
             tree_shaking_mode: Default::default(),
+            module_chunks: Default::default(),
+            url_externals: Default::default(),
         }
     }
 }
@@ -204,6 +210,9 @@ async fn run_test(resource: RcStr) -> Result<Vc<FileSystemPath>> {
     let project_path = project_root.join(relative_path.clone());
 
     let entry_asset = project_path.join(options.entry.into());
+    let url_externals = options
+        .url_externals
+        .map(|url_externals| url_externals.cell());
 
     let env = Environment::new(Value::new(match options.environment {
         SnapshotEnvironment::Browser => {
@@ -214,6 +223,7 @@ async fn run_test(resource: RcStr) -> Result<Vc<FileSystemPath>> {
                     web_worker: false,
                     service_worker: false,
                     browserslist_query: options.browserslist.into(),
+                    module_chunks: options.module_chunks,
                 }
                 .into(),
             )
@@ -272,6 +282,7 @@ async fn run_test(resource: RcStr) -> Result<Vc<FileSystemPath>> {
                     ..Default::default()
                 })),
                 ignore_dynamic_requests: true,
+                import_externals: url_externals.is_some(),
                 ..Default::default()
             },
             css: CssOptionsContext {
@@ -282,6 +293,10 @@ async fn run_test(resource: RcStr) -> Result<Vc<FileSystemPath>> {
             rules: vec![(
                 ContextCondition::InDirectory("node_modules".into()),
                 ModuleOptionsContext {
+                    ecmascript: EcmascriptOptionsContext {
+                        import_externals: url_externals.is_some(),
+                        ..Default::default()
+                    },
                     css: CssOptionsContext {
                         use_swc_css: options.use_swc_css,
                         ..Default::default()
@@ -300,11 +315,13 @@ async fn run_test(resource: RcStr) -> Result<Vc<FileSystemPath>> {
             enable_react: true,
             enable_node_modules: Some(project_root),
             custom_conditions: vec!["development".into()],
+            import_map: url_externals.map(|url_externals| url_externals.import_map()),
             rules: vec![(
                 ContextCondition::InDirectory("node_modules".into()),
                 ResolveOptionsContext {
                     enable_node_modules: Some(project_root),
                     custom_conditions: vec!["development".into()],
+                    import_map: url_externals.map(|url_externals| url_externals.import_map()),
                     ..Default::default()
                 }
                 .cell(),
@@ -323,8 +340,8 @@ async fn run_test(resource: RcStr) -> Result<Vc<FileSystemPath>> {
     let static_root_path = path.join("static".into());
 
     let chunking_context: Vc<Box<dyn ChunkingContext>> = match options.runtime {
-        Runtime::Browser => Vc::upcast(
-            BrowserChunkingContext::builder(
+        Runtime::Browser => {
+            let mut builder = BrowserChunkingContext::builder(
                 project_root,
                 path,
                 path,
@@ -332,9 +349,12 @@ async fn run_test(resource: RcStr) -> Result<Vc<FileSystemPath>> {
                 static_root_path,
                 env,
                 options.runtime_type,
-            )
-            .build(),
-        ),
+            );
+            if url_externals.is_some() {
+                builder = builder.url_externals();
+            }
+            Vc::upcast(builder.build())
+        }
         Runtime::NodeJs => Vc::upcast(
             NodeJsChunkingContext::builder(
                 project_root,
@@ -417,6 +437,12 @@ async fn run_test(resource: RcStr) -> Result<Vc<FileSystemPath>> {
 
     let mut seen = HashSet::new();
     let mut queue: VecDeque<_> = chunks.await?.iter().copied().collect();
+    if let Some(url_externals) = url_externals {
+        queue.push_back(import_map_asset(
+            chunk_root_path.join("importmap.json".into()),
+            url_externals,
+        ));
+    }
 
     let output_path = path.await?;
     while let Some(asset) = queue.pop_front() {
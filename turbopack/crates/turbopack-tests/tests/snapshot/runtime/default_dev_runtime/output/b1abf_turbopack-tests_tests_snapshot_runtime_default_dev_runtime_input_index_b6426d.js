@@ -395,10 +395,17 @@ const moduleFactories = Object.create(null);
  */ const chunkChunkListsMap = new Map();
 const availableModules = new Map();
 const availableModuleChunks = new Map();
+/**
+ * Map from a chunk path to its subresource integrity metadata. The backend
+ * sets it as `integrity` attribute when loading the chunk.
+ */ const chunkIntegrity = new Map();
 async function loadChunk(source, chunkData) {
     if (typeof chunkData === "string") {
         return loadChunkPath(source, chunkData);
     }
+    if (chunkData.integrity) {
+        chunkIntegrity.set(chunkData.path, chunkData.integrity);
+    }
     const includedList = chunkData.included || [];
     const modulesPromises = includedList.map((included)=>{
         if (moduleFactories[included]) return true;
@@ -1398,7 +1405,24 @@ globalThis.TURBOPACK_CHUNK_LISTS = {
  */ /* eslint-disable @typescript-eslint/no-unused-vars */ /// <reference path="../../../browser/runtime/base/runtime-base.ts" />
 /// <reference path="../../../shared/runtime-types.d.ts" />
 let BACKEND;
+/**
+ * The module federation runtime, when it's included (see `federation-dom.ts`).
+ */ let moduleFederation;
+/**
+ * Loads an ESM external, when URL externals are enabled (see
+ * `externals-dom.ts`).
+ */ let importExternal;
+/**
+ * Loads a JS chunk as an ES module, when chunks are emitted as ES modules (see
+ * `module-dom.ts`). Chunks are loaded as classic scripts otherwise.
+ */ let importChunk;
 function augmentContext(context) {
+    if (moduleFederation != null) {
+        context.F = moduleFederation;
+    }
+    if (importExternal != null) {
+        context.y = importExternal;
+    }
     return context;
 }
 function fetchWebAssembly(wasmChunkPath) {
@@ -1444,6 +1468,17 @@ async function loadWebAssemblyModule(_source, wasmChunkPath) {
             return doLoadChunk(chunkPath, source);
         }
     };
+    /**
+   * Sets the subresource integrity metadata of the chunk, if any, on the
+   * element loading it.
+   */ function setIntegrity(element, chunkPath) {
+        const integrity = chunkIntegrity.get(chunkPath);
+        if (integrity != null) {
+            element.integrity = integrity;
+            // Integrity checks of cross-origin resources require CORS.
+            element.crossOrigin = "anonymous";
+        }
+    }
     function getOrCreateResolver(chunkPath) {
         let resolver = chunkResolvers.get(chunkPath);
         if (!resolver) {
@@ -1509,6 +1544,7 @@ async function loadWebAssemblyModule(_source, wasmChunkPath) {
                     const link = document.createElement("link");
                     link.rel = "stylesheet";
                     link.href = chunkUrl;
+                    setIntegrity(link, chunkPath);
                     link.onerror = ()=>{
                         resolver.reject();
                     };
@@ -1519,6 +1555,12 @@ async function loadWebAssemblyModule(_source, wasmChunkPath) {
                     };
                     document.body.appendChild(link);
                 }
+            } else if (chunkPath.endsWith(".js") && importChunk != null) {
+                // Like scripts, ES module chunks register themselves once they've
+                // been evaluated.
+                importChunk(chunkPath, chunkUrl).catch((error)=>{
+                    resolver.reject(error);
+                });
             } else if (chunkPath.endsWith(".js")) {
                 const previousScripts = document.querySelectorAll(`script[src="${chunkUrl}"],script[src^="${chunkUrl}?"],script[src="${decodedChunkUrl}"],script[src^="${decodedChunkUrl}?"]`);
                 if (previousScripts.length > 0) {
@@ -1532,6 +1574,7 @@ async function loadWebAssemblyModule(_source, wasmChunkPath) {
                 } else {
                     const script = document.createElement("script");
                     script.src = chunkUrl;
+                    setIntegrity(script, chunkPath);
                     // We'll only mark the chunk as loaded once the script has been executed,
                     // which happens in `registerChunk`. Hence the absence of `resolve()` in
                     // this branch.
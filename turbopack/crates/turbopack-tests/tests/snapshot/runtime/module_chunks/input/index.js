@@ -0,0 +1 @@
+import("./lazy.js").then(({ greeting }) => console.log(greeting));
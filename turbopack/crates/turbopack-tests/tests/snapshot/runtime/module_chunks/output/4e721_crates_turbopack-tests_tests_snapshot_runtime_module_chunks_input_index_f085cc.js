@@ -0,0 +1,12 @@
+(globalThis.TURBOPACK = globalThis.TURBOPACK || []).push(["output/4e721_crates_turbopack-tests_tests_snapshot_runtime_module_chunks_input_index_f085cc.js", {
+
+"[project]/turbopack/crates/turbopack-tests/tests/snapshot/runtime/module_chunks/input/index.js [test] (ecmascript)": (function(__turbopack_context__) {
+
+var { r: __turbopack_require__, f: __turbopack_module_context__, i: __turbopack_import__, s: __turbopack_esm__, v: __turbopack_export_value__, n: __turbopack_export_namespace__, c: __turbopack_cache__, M: __turbopack_modules__, l: __turbopack_load__, j: __turbopack_dynamic__, P: __turbopack_resolve_absolute_path__, U: __turbopack_relative_url__, R: __turbopack_resolve_module_id_path__, b: __turbopack_worker_blob_url__, g: global, __dirname, m: module, e: exports, t: require } = __turbopack_context__;
+{
+__turbopack_require__("[project]/turbopack/crates/turbopack-tests/tests/snapshot/runtime/module_chunks/input/lazy.js [test] (ecmascript, async loader)")(__turbopack_import__).then(({ greeting })=>console.log(greeting));
+}}),
+}]);
+export {};
+
+//# sourceMappingURL=4e721_crates_turbopack-tests_tests_snapshot_runtime_module_chunks_input_index_f085cc.js.map
\ No newline at end of file
@@ -0,0 +1,4 @@
+import { createElement } from "react";
+import debounce from "lodash-es/debounce";
+
+console.log(createElement, debounce);
@@ -0,0 +1,188 @@
+#![cfg(test)]
+#![allow(clippy::needless_return)] // tokio macro-generated code doesn't respect this
+
+mod util;
+
+use std::{fs, path::Path, sync::Arc};
+
+use anyhow::{bail, Result};
+use turbo_tasks::{RcStr, TurboTasks, Vc};
+use turbo_tasks_backend::{default_backing_storage, DefaultBackingStorage, TurboTasksBackend};
+use turbo_tasks_env::{CommandLineProcessEnv, ProcessEnv};
+use turbo_tasks_fs::{DiskFileSystem, FileContent, FileSystem};
+use turbopack::evaluate_context::{node_build_environment, node_evaluate_asset_context};
+use turbopack_core::{
+    asset::{Asset, AssetContent},
+    file_source::FileSource,
+    resolve::{
+        options::{ImportMap, ImportMapping},
+        ExternalType,
+    },
+    source_transform::SourceTransform,
+};
+use turbopack_ecmascript_runtime::RuntimeType;
+use turbopack_node::{
+    execution_context::ExecutionContext,
+    transforms::webpack::{WebpackLoaderItem, WebpackLoaders},
+};
+use turbopack_nodejs::NodeJsChunkingContext;
+use turbopack_resolve::resolve_options_context::ResolveOptionsContext;
+
+use crate::util::REPO_ROOT;
+
+fn register() {
+    turbo_tasks::register();
+    turbo_tasks_env::register();
+    turbo_tasks_fs::register();
+    turbopack::register();
+    turbopack_nodejs::register();
+    turbopack_resolve::register();
+    include!(concat!(
+        env!("OUT_DIR"),
+        "/register_test_webpack_loaders.rs"
+    ));
+}
+
+/// A loader prepending the contents of `prefix.txt`, which it adds as a build
+/// dependency. Every run is recorded in `runs.txt`, which is not tracked.
+const LOADER: &str = r#"
+const fs = require("fs");
+const path = require("path");
+
+module.exports = function (content) {
+  const prefix = path.join(this.rootContext, "prefix.txt");
+  this.addBuildDependency(prefix);
+  fs.appendFileSync(path.join(this.rootContext, "runs.txt"), "run\n");
+  return fs.readFileSync(prefix, "utf8") + content;
+};
+"#;
+
+/// Runs the loader on `input.txt` of the project at `project`.
+#[turbo_tasks::function]
+async fn loader_output(project: RcStr) -> Result<Vc<RcStr>> {
+    let project_path = DiskFileSystem::new("project".into(), project, vec![]).root();
+    let output_root = project_path.join(".output".into());
+    let chunking_context = NodeJsChunkingContext::builder(
+        project_path,
+        output_root,
+        output_root,
+        output_root,
+        output_root,
+        node_build_environment(),
+        RuntimeType::Development,
+    )
+    .build();
+    let env: Vc<Box<dyn ProcessEnv>> = Vc::upcast(CommandLineProcessEnv::new());
+    let execution_context = ExecutionContext::new(project_path, Vc::upcast(chunking_context), env);
+
+    let mut import_map = ImportMap::default();
+    import_map.insert_exact_alias(
+        "@vercel/turbopack/loader-runner",
+        ImportMapping::External(Some("loader-runner".into()), ExternalType::CommonJs).cell(),
+    );
+    let transform = WebpackLoaders::new(
+        node_evaluate_asset_context(
+            execution_context,
+            Some(import_map.cell()),
+            None,
+            "webpack_loaders".into(),
+            false,
+        ),
+        execution_context,
+        Vc::cell(vec![WebpackLoaderItem {
+            loader: "prefix-loader".into(),
+            options: Default::default(),
+        }]),
+        None,
+        ResolveOptionsContext::default().cell(),
+        None,
+    );
+
+    let source = transform.transform(Vc::upcast(FileSource::new(
+        project_path.join("input.txt".into()),
+    )));
+    let AssetContent::File(file) = *source.content().await? else {
+        bail!("expected the loader to produce a file");
+    };
+    let FileContent::Content(file) = &*file.await? else {
+        bail!("the loader didn't produce any content");
+    };
+    Ok(Vc::cell(file.content().to_str()?.into()))
+}
+
+fn persistent_turbo_tasks(
+    cache: &Path,
+) -> Arc<TurboTasks<TurboTasksBackend<DefaultBackingStorage>>> {
+    // The test needs the persistent cache, even when it's run from a dirty
+    // checkout.
+    std::env::set_var("TURBO_ENGINE_IGNORE_DIRTY", "1");
+    fs::create_dir_all(cache).unwrap();
+    TurboTasks::new(TurboTasksBackend::new(
+        default_backing_storage(cache).unwrap(),
+    ))
+}
+
+async fn run_loader(cache: &Path, project: &Path) -> Result<String> {
+    let project: RcStr = project.to_str().unwrap().into();
+    let tt = persistent_turbo_tasks(cache);
+    let output = tt
+        .run_once(async move {
+            Ok(loader_output(project)
+                .strongly_consistent()
+                .await?
+                .to_string())
+        })
+        .await;
+    tt.stop_and_wait().await;
+    output
+}
+
+fn loader_runs(project: &Path) -> usize {
+    fs::read_to_string(project.join("runs.txt"))
+        .unwrap_or_default()
+        .lines()
+        .count()
+}
+
+#[tokio::test]
+async fn build_dependencies_invalidate_restored_results() -> Result<()> {
+    register();
+
+    let root = Path::new(env!("OUT_DIR")).join("webpack_loaders");
+    let _ = fs::remove_dir_all(&root);
+    let cache = root.join("cache");
+    let project = root.join("project");
+    let loader_runner = Path::new(&*REPO_ROOT).join("packages/next/src/compiled/loader-runner");
+    for (path, content) in [
+        ("input.txt", "input".to_string()),
+        ("prefix.txt", "a: ".to_string()),
+        ("node_modules/prefix-loader/index.js", LOADER.to_string()),
+        (
+            "node_modules/loader-runner/package.json",
+            fs::read_to_string(loader_runner.join("package.json"))?,
+        ),
+        (
+            "node_modules/loader-runner/LoaderRunner.js",
+            fs::read_to_string(loader_runner.join("LoaderRunner.js"))?,
+        ),
+    ] {
+        let path = project.join(path);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, content)?;
+    }
+
+    assert_eq!(run_loader(&cache, &project).await?, "a: input");
+    assert_eq!(loader_runs(&project), 1);
+
+    // The restored result is still valid, so the loader doesn't run again.
+    assert_eq!(run_loader(&cache, &project).await?, "a: input");
+    assert_eq!(loader_runs(&project), 1);
+
+    // Editing the build dependency while the server was stopped invalidates
+    // the restored result.
+    fs::write(project.join("prefix.txt"), "b: ")?;
+    assert_eq!(run_loader(&cache, &project).await?, "b: input");
+    assert_eq!(loader_runs(&project), 2);
+
+    Ok(())
+}
@@ -0,0 +1,516 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use anyhow::{bail, Result};
+use flate2::{write::GzEncoder, Compression};
+use serde::Serialize;
+
+use crate::{span_bottom_up_ref::SpanBottomUpRef, span_ref::SpanRef, store::Store};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// The Trace Event Format, as read by `chrome://tracing`, Perfetto and
+    /// speedscope.
+    ChromeTrace,
+    /// One `frame;frame;frame self_time` line per stack, as read by
+    /// `flamegraph.pl`, inferno and speedscope, and convertible to pprof.
+    CollapsedStacks,
+    /// Like [ExportFormat::CollapsedStacks], but from the bottom up
+    /// aggregation, so each stack starts with the span that spent the time,
+    /// followed by its callers.
+    CollapsedStacksBottomUp,
+    /// A gzipped pprof protobuf profile of the self time of each stack, as
+    /// read by `go tool pprof`, speedscope and most profiling services.
+    Pprof,
+}
+
+impl ExportFormat {
+    pub fn parse(value: &str) -> Result<Self> {
+        Ok(match value {
+            "chrome-trace" => ExportFormat::ChromeTrace,
+            "collapsed-stacks" => ExportFormat::CollapsedStacks,
+            "collapsed-stacks-bottom-up" => ExportFormat::CollapsedStacksBottomUp,
+            "pprof" => ExportFormat::Pprof,
+            _ => bail!("unknown export format: {}", value),
+        })
+    }
+
+    /// Whether the exported data is binary instead of text.
+    pub fn is_binary(self) -> bool {
+        matches!(self, ExportFormat::Pprof)
+    }
+}
+
+pub fn export(store: &Store, format: ExportFormat, writer: impl Write) -> Result<()> {
+    match format {
+        ExportFormat::ChromeTrace => export_chrome_trace(store, writer),
+        ExportFormat::CollapsedStacks => export_collapsed_stacks(store, writer),
+        ExportFormat::CollapsedStacksBottomUp => export_collapsed_stacks_bottom_up(store, writer),
+        ExportFormat::Pprof => export_pprof(store, writer),
+    }
+}
+
+#[derive(Serialize)]
+struct TraceEvent<'a> {
+    name: &'a str,
+    cat: &'a str,
+    ph: &'static str,
+    /// In microseconds, like the trace itself.
+    ts: u64,
+    dur: u64,
+    pid: u32,
+    tid: usize,
+    args: HashMap<&'a str, &'a str>,
+}
+
+/// Assigns spans to "threads" of the exported trace. The trace viewers
+/// require events on the same thread to be properly nested, while spans in
+/// the store are often concurrent.
+#[derive(Default)]
+struct Lanes {
+    /// For every lane, the end times and ids of the events which are still
+    /// open.
+    open: Vec<Vec<(u64, usize)>>,
+}
+
+impl Lanes {
+    /// Places the span `id` on a lane, nested into its parent if possible.
+    /// Spans must be placed in the order of their start time.
+    fn place(&mut self, parent: Option<(usize, usize)>, id: usize, start: u64, end: u64) -> usize {
+        fn close_before(open: &mut Vec<(u64, usize)>, start: u64) {
+            while open.last().is_some_and(|&(open_end, _)| open_end <= start) {
+                open.pop();
+            }
+        }
+
+        if let Some((lane, parent_id)) = parent {
+            let open = &mut self.open[lane];
+            close_before(open, start);
+            if open
+                .last()
+                .is_some_and(|&(open_end, open_id)| open_id == parent_id && open_end >= end)
+            {
+                open.push((end, id));
+                return lane;
+            }
+        }
+        // Don't nest below unrelated spans, only use lanes which are idle.
+        for (lane, open) in self.open.iter_mut().enumerate() {
+            close_before(open, start);
+            if open.is_empty() {
+                open.push((end, id));
+                return lane;
+            }
+        }
+        self.open.push(vec![(end, id)]);
+        self.open.len() - 1
+    }
+}
+
+fn all_spans(store: &Store) -> Vec<SpanRef<'_>> {
+    let mut spans = Vec::new();
+    let mut stack = store.root_span().children().collect::<Vec<_>>();
+    while let Some(span) = stack.pop() {
+        stack.extend(span.children());
+        spans.push(span);
+    }
+    spans
+}
+
+pub fn export_chrome_trace(store: &Store, mut writer: impl Write) -> Result<()> {
+    let root_start = store.root_span().start();
+    let mut spans = all_spans(store);
+    spans.sort_by_key(|span| (span.start(), span.span.depth));
+
+    let mut lanes = Lanes::default();
+    let mut span_lanes = HashMap::new();
+    writer.write_all(b"{\"displayTimeUnit\":\"ms\",\"traceEvents\":[")?;
+    for (i, span) in spans.iter().enumerate() {
+        let start = span.start();
+        let end = span.end().max(start);
+        let parent = span.parent().and_then(|parent| {
+            let parent = parent.index().get();
+            Some((*span_lanes.get(&parent)?, parent))
+        });
+        let lane = lanes.place(parent, span.index().get(), start, end);
+        span_lanes.insert(span.index().get(), lane);
+
+        let (category, name) = span.nice_name();
+        if i > 0 {
+            writer.write_all(b",\n")?;
+        }
+        serde_json::to_writer(
+            &mut writer,
+            &TraceEvent {
+                name,
+                cat: category,
+                ph: "X",
+                ts: start - root_start,
+                dur: end - start,
+                pid: 1,
+                tid: lane,
+                args: span.args().collect(),
+            },
+        )?;
+    }
+    writer.write_all(b"]}\n")?;
+    Ok(())
+}
+
+/// Frame names must not contain the separator or line breaks.
+fn frame_name(name: &str) -> String {
+    name.replace([';', '\n', '\r'], " ")
+}
+
+fn write_stacks(stacks: HashMap<String, u64>, mut writer: impl Write) -> io::Result<()> {
+    let mut stacks = stacks
+        .into_iter()
+        .filter(|(_, value)| *value > 0)
+        .collect::<Vec<_>>();
+    stacks.sort();
+    for (stack, value) in stacks {
+        writeln!(writer, "{stack} {value}")?;
+    }
+    Ok(())
+}
+
+/// The self time of every stack of span names from the root.
+fn top_down_stacks(store: &Store) -> HashMap<String, u64> {
+    let mut stacks = HashMap::new();
+    let mut queue = store
+        .root_span()
+        .children()
+        .map(|span| (frame_name(span.group_name()), span))
+        .collect::<Vec<_>>();
+    while let Some((stack, span)) = queue.pop() {
+        for child in span.children() {
+            queue.push((format!("{stack};{}", frame_name(child.group_name())), child));
+        }
+        *stacks.entry(stack).or_default() += span.self_time();
+    }
+    stacks
+}
+
+pub fn export_collapsed_stacks(store: &Store, writer: impl Write) -> Result<()> {
+    write_stacks(top_down_stacks(store), writer)?;
+    Ok(())
+}
+
+pub fn export_collapsed_stacks_bottom_up(store: &Store, writer: impl Write) -> Result<()> {
+    fn visit(stack: String, bottom_up: SpanBottomUpRef<'_>, stacks: &mut HashMap<String, u64>) {
+        let mut children_time = 0;
+        for child in bottom_up.children() {
+            children_time += child.self_time();
+            let stack = format!("{stack};{}", frame_name(child.group_name()));
+            visit(stack, child, stacks);
+        }
+        // The time of stacks which end here, i.e. have no further callers in
+        // the aggregation.
+        *stacks.entry(stack).or_default() += bottom_up.self_time().saturating_sub(children_time);
+    }
+
+    let mut stacks = HashMap::new();
+    for bottom_up in store.root_span().bottom_up() {
+        let stack = frame_name(bottom_up.group_name());
+        visit(stack, bottom_up, &mut stacks);
+    }
+    write_stacks(stacks, writer)?;
+    Ok(())
+}
+
+/// Writes the protobuf wire format, just enough of it for
+/// [pprof's profile.proto](https://github.com/google/pprof/blob/main/proto/profile.proto).
+#[derive(Default)]
+struct ProtoWriter {
+    buf: Vec<u8>,
+}
+
+impl ProtoWriter {
+    const VARINT: u64 = 0;
+    const LEN: u64 = 2;
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+
+    fn uint(&mut self, field: u64, value: u64) {
+        // Default values are omitted, like protobuf encoders do.
+        if value != 0 {
+            self.varint((field << 3) | Self::VARINT);
+            self.varint(value);
+        }
+    }
+
+    fn bytes(&mut self, field: u64, bytes: &[u8]) {
+        self.varint((field << 3) | Self::LEN);
+        self.varint(bytes.len() as u64);
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn message(&mut self, field: u64, write: impl FnOnce(&mut ProtoWriter)) {
+        let mut message = ProtoWriter::default();
+        write(&mut message);
+        self.bytes(field, &message.buf);
+    }
+
+    fn packed(&mut self, field: u64, values: impl IntoIterator<Item = u64>) {
+        let mut packed = ProtoWriter::default();
+        for value in values {
+            packed.varint(value);
+        }
+        self.bytes(field, &packed.buf);
+    }
+}
+
+/// Interns the strings of a pprof profile. The first string must be empty.
+struct StringTable {
+    indices: HashMap<String, u64>,
+    strings: Vec<String>,
+}
+
+impl StringTable {
+    fn new() -> Self {
+        StringTable {
+            indices: HashMap::from([(String::new(), 0)]),
+            strings: vec![String::new()],
+        }
+    }
+
+    fn index(&mut self, value: &str) -> u64 {
+        if let Some(&index) = self.indices.get(value) {
+            return index;
+        }
+        let index = self.strings.len() as u64;
+        self.indices.insert(value.to_string(), index);
+        self.strings.push(value.to_string());
+        index
+    }
+}
+
+pub fn export_pprof(store: &Store, writer: impl Write) -> Result<()> {
+    // Profile fields
+    const SAMPLE_TYPE: u64 = 1;
+    const SAMPLE: u64 = 2;
+    const LOCATION: u64 = 4;
+    const FUNCTION: u64 = 5;
+    const STRING_TABLE: u64 = 6;
+    const DURATION_NANOS: u64 = 10;
+
+    let mut stacks = top_down_stacks(store)
+        .into_iter()
+        .filter(|(_, value)| *value > 0)
+        .collect::<Vec<_>>();
+    stacks.sort();
+
+    let mut strings = StringTable::new();
+    let mut profile = ProtoWriter::default();
+    let time = strings.index("time");
+    let microseconds = strings.index("microseconds");
+    profile.message(SAMPLE_TYPE, |value_type| {
+        value_type.uint(1, time);
+        value_type.uint(2, microseconds);
+    });
+
+    // Every frame name becomes a function with a single location of the same
+    // id. Ids start at 1.
+    let mut functions = Vec::new();
+    let mut function_ids = HashMap::new();
+    for (stack, value) in &stacks {
+        // Locations are listed from the leaf to the root.
+        let location_ids = stack
+            .rsplit(';')
+            .map(|frame| {
+                let name = strings.index(frame);
+                *function_ids.entry(name).or_insert_with(|| {
+                    functions.push(name);
+                    functions.len() as u64
+                })
+            })
+            .collect::<Vec<_>>();
+        profile.message(SAMPLE, |sample| {
+            sample.packed(1, location_ids);
+            sample.packed(2, [*value]);
+        });
+    }
+    for (index, &name) in functions.iter().enumerate() {
+        let id = index as u64 + 1;
+        profile.message(LOCATION, |location| {
+            location.uint(1, id);
+            location.message(4, |line| line.uint(1, id));
+        });
+        profile.message(FUNCTION, |function| {
+            function.uint(1, id);
+            function.uint(2, name);
+            function.uint(3, name);
+        });
+    }
+    for string in &strings.strings {
+        profile.bytes(STRING_TABLE, string.as_bytes());
+    }
+    let root = store.root_span();
+    profile.uint(
+        DURATION_NANOS,
+        root.end().saturating_sub(root.start()) * 1000,
+    );
+
+    let mut writer = GzEncoder::new(writer, Compression::default());
+    writer.write_all(&profile.buf)?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, io::Read};
+
+    use flate2::read::GzDecoder;
+
+    use super::{export, ExportFormat, Lanes};
+    use crate::store::Store;
+
+    /// `a` (0..35) runs `b` (10..30) in between its own work.
+    fn store() -> Store {
+        let mut store = Store::new();
+        let mut outdated = HashSet::new();
+        let a = store.add_span(None, 0, "cat".into(), "a".into(), vec![], &mut outdated);
+        let b = store.add_span(Some(a), 10, "cat".into(), "b".into(), vec![], &mut outdated);
+        store.add_self_time(a, 0, 10, &mut outdated);
+        store.add_self_time(b, 10, 30, &mut outdated);
+        store.add_self_time(a, 30, 35, &mut outdated);
+        store
+    }
+
+    fn export_to_vec(format: ExportFormat) -> Vec<u8> {
+        let mut data = Vec::new();
+        export(&store(), format, &mut data).unwrap();
+        data
+    }
+
+    /// Decodes the top level fields of a protobuf message as
+    /// `(field, varint or bytes)`.
+    fn decode_proto(mut data: &[u8]) -> Vec<(u64, Result<u64, Vec<u8>>)> {
+        fn varint(data: &mut &[u8]) -> u64 {
+            let mut value = 0;
+            let mut shift = 0;
+            loop {
+                let byte = data[0];
+                *data = &data[1..];
+                value |= ((byte & 0x7f) as u64) << shift;
+                if byte < 0x80 {
+                    return value;
+                }
+                shift += 7;
+            }
+        }
+
+        let mut fields = Vec::new();
+        while !data.is_empty() {
+            let key = varint(&mut data);
+            let value = match key & 7 {
+                0 => Ok(varint(&mut data)),
+                2 => {
+                    let len = varint(&mut data) as usize;
+                    let (bytes, rest) = data.split_at(len);
+                    data = rest;
+                    Err(bytes.to_vec())
+                }
+                wire_type => panic!("unexpected wire type {wire_type}"),
+            };
+            fields.push((key >> 3, value));
+        }
+        fields
+    }
+
+    #[test]
+    fn collapsed_stacks() {
+        let data = String::from_utf8(export_to_vec(ExportFormat::CollapsedStacks)).unwrap();
+        assert_eq!(data, "a 15\na;b 20\n");
+
+        let data = String::from_utf8(export_to_vec(ExportFormat::CollapsedStacksBottomUp)).unwrap();
+        assert!(data.lines().any(|line| line == "a 15"), "{data}");
+        assert!(
+            data.lines()
+                .any(|line| line.starts_with('b') && line.ends_with(" 20")),
+            "{data}"
+        );
+    }
+
+    #[test]
+    fn chrome_trace() {
+        let data: serde_json::Value =
+            serde_json::from_slice(&export_to_vec(ExportFormat::ChromeTrace)).unwrap();
+        assert_eq!(
+            data["traceEvents"],
+            serde_json::json!([
+                { "name": "a", "cat": "cat", "ph": "X", "ts": 0, "dur": 35, "pid": 1, "tid": 0, "args": {} },
+                { "name": "b", "cat": "cat", "ph": "X", "ts": 10, "dur": 20, "pid": 1, "tid": 0, "args": {} },
+            ])
+        );
+    }
+
+    #[test]
+    fn pprof() {
+        let mut data = Vec::new();
+        GzDecoder::new(&export_to_vec(ExportFormat::Pprof)[..])
+            .read_to_end(&mut data)
+            .unwrap();
+        let profile = decode_proto(&data);
+
+        let strings = profile
+            .iter()
+            .filter(|(field, _)| *field == 6)
+            .map(|(_, value)| String::from_utf8(value.clone().unwrap_err()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(strings, ["", "time", "microseconds", "a", "b"]);
+
+        // Samples list their locations from the leaf to the root, the location
+        // ids match the function ids, which are assigned in order of
+        // appearance.
+        let samples = profile
+            .iter()
+            .filter(|(field, _)| *field == 2)
+            .map(|(_, value)| {
+                let sample = decode_proto(value.as_ref().unwrap_err());
+                let packed = |field| {
+                    let (_, value) = sample.iter().find(|(f, _)| *f == field).unwrap();
+                    let mut data = &value.as_ref().unwrap_err()[..];
+                    let mut values = Vec::new();
+                    while !data.is_empty() {
+                        values.push(data[0] as u64);
+                        data = &data[1..];
+                    }
+                    values
+                };
+                (packed(1), packed(2))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(samples, [(vec![1], vec![15]), (vec![2, 1], vec![20])]);
+
+        let functions = profile.iter().filter(|(field, _)| *field == 5).count();
+        let locations = profile.iter().filter(|(field, _)| *field == 4).count();
+        assert_eq!((functions, locations), (2, 2));
+        assert!(profile.contains(&(10, Ok(35_000))));
+    }
+
+    #[test]
+    fn lanes_nest_children_and_split_concurrent_spans() {
+        let mut lanes = Lanes::default();
+        // parent
+        assert_eq!(lanes.place(None, 1, 0, 100), 0);
+        // two sequential children fit into the parent lane
+        assert_eq!(lanes.place(Some((0, 1)), 2, 10, 20), 0);
+        assert_eq!(lanes.place(Some((0, 1)), 3, 20, 30), 0);
+        // a concurrent child must not be nested into its sibling
+        assert_eq!(lanes.place(Some((0, 1)), 4, 25, 28), 1);
+        // a child exceeding its parent can't be nested, but the second lane
+        // is idle again
+        assert_eq!(lanes.place(Some((0, 1)), 5, 50, 150), 1);
+        assert_eq!(lanes.place(None, 6, 60, 70), 2);
+    }
+}
@@ -2,13 +2,26 @@
 #![feature(hash_raw_entry)]
 #![feature(box_patterns)]
 
-use std::{hash::BuildHasherDefault, path::PathBuf, sync::Arc};
+use std::{
+    fs::File,
+    hash::BuildHasherDefault,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    sync::Arc,
+};
 
+use anyhow::Result;
 use rustc_hash::FxHasher;
 
-use self::{reader::TraceReader, server::serve, store_container::StoreContainer};
+use self::{
+    export::{export, ExportFormat},
+    reader::TraceReader,
+    server::serve,
+    store_container::StoreContainer,
+};
 
 mod bottom_up;
+mod export;
 mod reader;
 mod self_time_tree;
 mod server;
@@ -29,10 +42,109 @@ mod viewer;
 type FxIndexMap<K, V> = indexmap::IndexMap<K, V, BuildHasherDefault<FxHasher>>;
 
 pub fn start_turbopack_trace_server(path: PathBuf) {
+    serve_turbopack_trace(path, 5747);
+}
+
+/// Reads the trace file at `path`, following it as it grows, and serves it to
+/// the trace viewer on `port`.
+pub fn serve_turbopack_trace(path: PathBuf, port: u16) {
     let store = Arc::new(StoreContainer::new());
     let reader = TraceReader::spawn(store.clone(), path);
 
-    serve(store, 5747);
+    serve(store, port);
 
     reader.join().unwrap();
 }
+
+/// Reads the trace file at `path` and writes it to `output` in the given
+/// export `format` (`chrome-trace`, `collapsed-stacks`,
+/// `collapsed-stacks-bottom-up` or `pprof`).
+pub fn export_turbopack_trace(path: PathBuf, format: &str, output: PathBuf) -> Result<()> {
+    let format = ExportFormat::parse(format)?;
+    let store = Arc::new(StoreContainer::new());
+    TraceReader::read_to_end(store.clone(), path)?;
+    let mut writer = BufWriter::new(File::create(output)?);
+    export(&store.read(), format, &mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, fs};
+
+    use turbopack_trace_utils::tracing::TraceRow;
+
+    use super::export_turbopack_trace;
+
+    /// A turbopack trace with a single span `a` running from 0 to 10.
+    fn trace() -> Vec<u8> {
+        let rows = [
+            TraceRow::Start {
+                ts: 0,
+                id: 1,
+                parent: None,
+                name: Cow::Borrowed("a"),
+                target: Cow::Borrowed("test"),
+                values: vec![],
+            },
+            TraceRow::Enter {
+                ts: 0,
+                id: 1,
+                thread_id: 0,
+            },
+            TraceRow::Exit {
+                ts: 10,
+                id: 1,
+                thread_id: 0,
+            },
+            TraceRow::End { ts: 10, id: 1 },
+        ];
+        let mut data = b"TRACEv0".to_vec();
+        for row in rows {
+            data.extend(postcard::to_allocvec(&row).unwrap());
+        }
+        data
+    }
+
+    fn export(data: &[u8]) -> anyhow::Result<String> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.log");
+        let output = dir.path().join("out.txt");
+        fs::write(&path, data).unwrap();
+        let result = export_turbopack_trace(path, "collapsed-stacks", output.clone());
+        if result.is_err() {
+            assert!(!output.exists(), "no partial export is written");
+        }
+        result?;
+        Ok(fs::read_to_string(output).unwrap())
+    }
+
+    #[test]
+    fn export_complete_trace() {
+        assert_eq!(export(&trace()).unwrap(), "a 10\n");
+    }
+
+    #[test]
+    fn export_truncated_trace() {
+        let data = trace();
+        let err = export(&data[..data.len() - 1]).unwrap_err();
+        assert!(
+            err.to_string().contains("incomplete entry"),
+            "unexpected error: {err:#}"
+        );
+    }
+
+    #[test]
+    fn export_corrupt_trace() {
+        let mut data = trace();
+        // An unknown row variant
+        data.push(0x7f);
+        data.extend([0; 16]);
+        let err = export(&data).unwrap_err();
+        assert!(
+            err.to_string().contains("Trace file error"),
+            "unexpected error: {err:#}"
+        );
+    }
+}
@@ -12,7 +12,7 @@ use std::{
     time::Duration,
 };
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use flate2::bufread::GzDecoder;
 
 use crate::{
@@ -79,18 +79,44 @@ impl TraceFile {
 pub struct TraceReader {
     store: Arc<StoreContainer>,
     path: PathBuf,
+    /// Whether to keep waiting for more data at the end of the file.
+    follow: bool,
 }
 
 impl TraceReader {
     pub fn spawn(store: Arc<StoreContainer>, path: PathBuf) -> JoinHandle<()> {
-        let mut reader = Self { store, path };
+        let mut reader = Self {
+            store,
+            path,
+            follow: true,
+        };
         std::thread::spawn(move || reader.run())
     }
 
+    /// Reads the trace file once until its current end. Fails when the file
+    /// can't be read, is corrupt or ends in the middle of an entry.
+    pub fn read_to_end(store: Arc<StoreContainer>, path: PathBuf) -> Result<()> {
+        let mut reader = Self {
+            store,
+            path,
+            follow: false,
+        };
+        if !reader.try_read()? {
+            bail!("Unable to read trace file at {:?}", reader.path);
+        }
+        Ok(())
+    }
+
     pub fn run(&mut self) {
         let mut file_warning_printed = false;
         loop {
-            let read_success = self.try_read();
+            let read_success = match self.try_read() {
+                Ok(read_success) => read_success,
+                Err(err) => {
+                    println!("{err:#}");
+                    true
+                }
+            };
             if !file_warning_printed && !read_success {
                 println!("Unable to read trace file at {:?}, waiting...", self.path);
                 file_warning_printed = true;
@@ -110,9 +136,11 @@ impl TraceReader {
         })
     }
 
-    fn try_read(&mut self) -> bool {
+    /// Reads the trace file into the store. Returns `Ok(false)` when the file
+    /// can't be opened and an error when its content can't be parsed.
+    fn try_read(&mut self) -> Result<bool> {
         let Ok(mut file) = File::open(&self.path) else {
-            return false;
+            return Ok(false);
         };
         println!("Trace file opened");
         let stop_at = env::var("STOP_AT")
@@ -133,13 +161,13 @@ impl TraceReader {
         let mut current_read = 0;
         let mut initial_read = { file.seek(SeekFrom::End(0)).ok() };
         if file.seek(SeekFrom::Start(0)).is_err() {
-            return false;
+            return Ok(false);
         }
         let mut file = match self.trace_file_from_file(file) {
             Ok(f) => f,
             Err(err) => {
                 println!("Error creating zstd decoder: {err}");
-                return false;
+                return Ok(false);
             }
         };
 
@@ -154,7 +182,7 @@ impl TraceReader {
                         if let Some(value) =
                             self.wait_for_more_data(&mut file, &mut initial_read, format.as_deref())
                         {
-                            return value;
+                            return self.finish_read(value, &buffer[index..]);
                         }
                     } else {
                         // If we have partially consumed some data, and we are at buffer capacity,
@@ -184,8 +212,7 @@ impl TraceReader {
                                     index += bytes_read;
                                 }
                                 Err(err) => {
-                                    println!("Trace file error: {err}");
-                                    return true;
+                                    return Err(err.context("Trace file error"));
                                 }
                             }
                             if self.store.want_to_read() {
@@ -224,7 +251,7 @@ impl TraceReader {
                                      Waiting for new file..."
                                 );
                                 self.wait_for_new_file(&mut file);
-                                return true;
+                                return Ok(true);
                             }
                         }
                     }
@@ -234,18 +261,29 @@ impl TraceReader {
                         if let Some(value) =
                             self.wait_for_more_data(&mut file, &mut initial_read, format.as_deref())
                         {
-                            return value;
+                            return self.finish_read(value, &buffer[index..]);
                         }
                     } else {
                         // Error reading file, maybe it was removed
-                        println!("Error reading trace file: {err:?}");
-                        return true;
+                        return Err(err).context("Error reading trace file");
                     }
                 }
             }
         }
     }
 
+    /// Checks that a read which doesn't follow the file consumed the whole
+    /// file, so that a truncated trace isn't mistaken for a complete one.
+    fn finish_read(&self, value: bool, remaining: &[u8]) -> Result<bool> {
+        if !self.follow && !remaining.is_empty() {
+            bail!(
+                "Trace file ends with an incomplete entry ({} bytes left)",
+                remaining.len()
+            );
+        }
+        Ok(value)
+    }
+
     fn wait_for_more_data(
         &mut self,
         file: &mut TraceFile,
@@ -264,6 +302,9 @@ impl TraceReader {
                 println!("Initial read completed ({} MB)", total / (1024 * 1024));
             }
         }
+        if !self.follow {
+            return Some(true);
+        }
         loop {
             // No more data to read, sleep for a while to wait for more data
             thread::sleep(Duration::from_millis(100));
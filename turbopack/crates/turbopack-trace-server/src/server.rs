@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use tungstenite::{accept, Message};
 
 use crate::{
+    export::{export, ExportFormat},
     store::SpanId,
     store_container::StoreContainer,
     u64_string,
@@ -43,6 +44,14 @@ pub enum ServerToClientMessage {
         args: Vec<(String, String)>,
         path: Vec<String>,
     },
+    Export {
+        format: String,
+        data: String,
+    },
+    ExportError {
+        format: String,
+        message: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -69,6 +78,9 @@ pub enum ClientToServerMessage {
     },
     Ack,
     CheckForMoreData,
+    Export {
+        format: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -337,6 +349,26 @@ fn handle_connection(
 
                         continue;
                     }
+                    ClientToServerMessage::Export { format } => {
+                        let data = ExportFormat::parse(&format).and_then(|export_format| {
+                            if export_format.is_binary() {
+                                bail!("{format} can only be exported with --export");
+                            }
+                            let mut data = Vec::new();
+                            export(&state.store.read(), export_format, &mut data)?;
+                            Ok(String::from_utf8(data)?)
+                        });
+                        // A failed export must not end the session.
+                        let message = match data {
+                            Ok(data) => ServerToClientMessage::Export { format, data },
+                            Err(err) => ServerToClientMessage::ExportError {
+                                format,
+                                message: format!("{err:#}"),
+                            },
+                        };
+                        let message = serde_json::to_string(&message).unwrap();
+                        websocket.send(Message::Text(message))?;
+                    }
                     ClientToServerMessage::Ack => {
                         ready_for_update = true;
                         if update_skipped {
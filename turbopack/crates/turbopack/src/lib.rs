@@ -926,6 +926,7 @@ pub async fn replace_externals(
                     CachedExternalType::EcmaScriptViaRequire
                 }
             }
+            ExternalType::ModuleFederation => CachedExternalType::ModuleFederation,
             ExternalType::Url => {
                 // we don't want to wrap url externals.
                 continue;
@@ -10,16 +10,22 @@ pub use custom_module_type::CustomModuleType;
 pub use module_options_context::*;
 pub use module_rule::*;
 pub use rule_condition::*;
+use serde_json::Value as JsonValue;
 use turbo_tasks::{RcStr, Vc};
 use turbo_tasks_fs::{glob::Glob, FileSystemPath};
 use turbopack_core::{
+    native_loader::{NativeLoaderTransform, NativeLoaders},
     reference_type::{CssReferenceSubType, ReferenceType, UrlReferenceSubType},
     resolve::options::{ImportMap, ImportMapping},
+    source_transform::{SourceTransform, SourceTransforms},
 };
 use turbopack_css::CssModuleAssetType;
 use turbopack_ecmascript::{EcmascriptInputTransform, EcmascriptOptions, SpecifiedModuleType};
 use turbopack_mdx::MdxTransform;
-use turbopack_node::transforms::{postcss::PostCssTransform, webpack::WebpackLoaders};
+use turbopack_node::transforms::{
+    postcss::PostCssTransform,
+    webpack::{WebpackLoaderItem, WebpackLoaderItems, WebpackLoaders},
+};
 use turbopack_wasm::source::WebAssemblySourceType;
 
 use crate::{
@@ -559,21 +565,29 @@ impl ModuleOptions {
                         },
                         RuleCondition::not(RuleCondition::ResourceIsVirtualSource),
                     ]),
-                    vec![ModuleRuleEffect::SourceTransforms(Vc::cell(vec![
-                        Vc::upcast(WebpackLoaders::new(
-                            node_evaluate_asset_context(
-                                execution_context,
-                                Some(import_map),
-                                None,
-                                "webpack_loaders".into(),
-                                false,
-                            ),
-                            execution_context,
-                            rule.loaders,
-                            rule.rename_as.clone(),
-                            resolve_options_context,
-                        )),
-                    ]))],
+                    vec![ModuleRuleEffect::SourceTransforms(
+                        loader_rule_transforms(
+                            rule,
+                            webpack_loaders_options.native_loaders,
+                            |loaders, rename_as| {
+                                Vc::upcast(WebpackLoaders::new(
+                                    node_evaluate_asset_context(
+                                        execution_context,
+                                        Some(import_map),
+                                        None,
+                                        "webpack_loaders".into(),
+                                        false,
+                                    ),
+                                    execution_context,
+                                    loaders,
+                                    rename_as,
+                                    resolve_options_context,
+                                    webpack_loaders_options.loader_timeout_ms,
+                                ))
+                            },
+                        )
+                        .await?,
+                    )],
                 ));
             }
         }
@@ -583,3 +597,131 @@ impl ModuleOptions {
         Ok(ModuleOptions::cell(ModuleOptions { rules }))
     }
 }
+
+/// Consecutive loaders of a rule that run together.
+enum LoaderGroup<'a> {
+    /// A loader that runs natively.
+    Native(&'a WebpackLoaderItem),
+    /// Webpack loaders that run together in Node.js, in webpack's order.
+    Webpack(Vec<WebpackLoaderItem>),
+}
+
+/// Groups the loaders of a rule in the order they run, which is from last to
+/// first, like in webpack. Native loaders run on their own, consecutive other
+/// loaders run together in Node.js.
+fn group_loaders(
+    loaders: &[WebpackLoaderItem],
+    is_native: impl Fn(&WebpackLoaderItem) -> bool,
+) -> Vec<LoaderGroup<'_>> {
+    let mut groups = Vec::new();
+    for loader in loaders.iter().rev() {
+        if is_native(loader) {
+            groups.push(LoaderGroup::Native(loader));
+            continue;
+        }
+        match groups.last_mut() {
+            Some(LoaderGroup::Webpack(group)) => group.insert(0, loader.clone()),
+            _ => groups.push(LoaderGroup::Webpack(vec![loader.clone()])),
+        }
+    }
+    groups
+}
+
+/// Creates the source transforms of a loader rule. Loaders that are available
+/// as [NativeLoaders] run natively, see [group_loaders].
+async fn loader_rule_transforms(
+    rule: &LoaderRuleItem,
+    native_loaders: Option<Vc<NativeLoaders>>,
+    webpack_loaders: impl Fn(Vc<WebpackLoaderItems>, Option<RcStr>) -> Vc<Box<dyn SourceTransform>>,
+) -> Result<Vc<SourceTransforms>> {
+    let native_loaders = match native_loaders {
+        Some(native_loaders) => native_loaders.await?.clone_value(),
+        None => Default::default(),
+    };
+    let loaders = rule.loaders.await?;
+    let groups = group_loaders(&loaders, |loader| {
+        native_loaders.contains_key(&loader.loader)
+    });
+    if let [] | [LoaderGroup::Webpack(_)] = &groups[..] {
+        return Ok(Vc::cell(vec![webpack_loaders(
+            rule.loaders,
+            rule.rename_as.clone(),
+        )]));
+    }
+
+    let last = groups.len() - 1;
+    Ok(Vc::cell(
+        groups
+            .into_iter()
+            .enumerate()
+            .map(|(i, group)| {
+                // The result of the first loader is renamed.
+                let rename_as = if i == last {
+                    rule.rename_as.clone()
+                } else {
+                    None
+                };
+                match group {
+                    LoaderGroup::Native(loader) => Vc::upcast(NativeLoaderTransform::new(
+                        native_loaders[&loader.loader],
+                        Vc::cell(JsonValue::Object(loader.options.clone())),
+                        rename_as,
+                    )),
+                    LoaderGroup::Webpack(loaders) => webpack_loaders(Vc::cell(loaders), rename_as),
+                }
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loader(name: &str) -> WebpackLoaderItem {
+        WebpackLoaderItem {
+            loader: name.into(),
+            options: Default::default(),
+        }
+    }
+
+    fn group(loaders: &[&str], native: &[&str]) -> Vec<Vec<String>> {
+        let loaders = loaders.iter().map(|name| loader(name)).collect::<Vec<_>>();
+        group_loaders(&loaders, |loader| native.contains(&loader.loader.as_str()))
+            .into_iter()
+            .map(|group| match group {
+                LoaderGroup::Native(loader) => vec![format!("native:{}", loader.loader)],
+                LoaderGroup::Webpack(loaders) => loaders
+                    .into_iter()
+                    .map(|loader| loader.loader.to_string())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_group_loaders_without_native_loaders() {
+        assert_eq!(group(&["a", "b", "c"], &[]), vec![vec!["a", "b", "c"]]);
+    }
+
+    #[test]
+    fn test_group_loaders_runs_from_last_to_first() {
+        assert_eq!(
+            group(&["a", "b", "native", "c", "d"], &["native"]),
+            vec![vec!["c", "d"], vec!["native:native"], vec!["a", "b"]]
+        );
+    }
+
+    #[test]
+    fn test_group_loaders_consecutive_native_loaders() {
+        assert_eq!(
+            group(&["x", "y", "a"], &["x", "y"]),
+            vec![vec!["a"], vec!["native:y"], vec!["native:x"]]
+        );
+    }
+
+    #[test]
+    fn test_group_loaders_only_native_loader() {
+        assert_eq!(group(&["x"], &["x"]), vec![vec!["native:x"]]);
+    }
+}
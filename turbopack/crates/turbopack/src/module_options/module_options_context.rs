@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use turbo_tasks::{trace::TraceRawVcs, FxIndexMap, RcStr, ValueDefault, Vc};
 use turbopack_core::{
     chunk::MinifyType, condition::ContextCondition, environment::Environment,
-    resolve::options::ImportMapping,
+    native_loader::NativeLoaders, resolve::options::ImportMapping,
 };
 use turbopack_ecmascript::{references::esm::UrlRewriteBehavior, TreeShakingMode};
 pub use turbopack_mdx::MdxTransformOptions;
@@ -32,6 +32,12 @@ pub struct OptionWebpackRules(Option<Vc<WebpackRules>>);
 pub struct WebpackLoadersOptions {
     pub rules: Vc<WebpackRules>,
     pub loader_runner_package: Option<Vc<ImportMapping>>,
+    /// Loaders implemented in Rust. Rules referencing a loader by one of these
+    /// names run it natively instead of in Node.js.
+    pub native_loaders: Option<Vc<NativeLoaders>>,
+    /// How long running the loaders of a single module may take before it
+    /// fails. `None` uses the default of 5 minutes.
+    pub loader_timeout_ms: Option<u64>,
 }
 
 /// The kind of decorators transform to use.